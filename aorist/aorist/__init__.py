@@ -109,20 +109,50 @@ def aorist_presto(programs, constraint, entrypoint, args):
         args_str,
     )
 
-def aorist_bash(programs, constraint, entrypoint, args):
+def aorist_duckdb(programs, constraint, entrypoint, args):
     args_str = {
         k : (
             list(inspect.signature(v).parameters.keys()),
-            dill.dumps(lambda x: v(*x)).decode('latin-1')
+            get_code(v)
+        ) for k, v in args.items()
+    }
+    programs[constraint] = constraint.register_duckdb_program(
+        "",
+        entrypoint,
+        [],
+        args_str,
+    )
+
+def aorist_spark(programs, constraint, entrypoint, args):
+    args_str = {
+        k : (
+            list(inspect.signature(v).parameters.keys()),
+            get_code(v)
         ) for k, v in args.items()
     }
-    programs[constraint] = constraint.register_bash_program(
+    programs[constraint] = constraint.register_spark_program(
         "",
         entrypoint,
         [],
         args_str,
     )
 
+def aorist_bash(programs, constraint, entrypoint, args):
+    args_str = {
+        k : (
+            list(inspect.signature(v).parameters.keys()),
+            dill.dumps(lambda x: v(*x)).decode('latin-1')
+        ) for k, v in args.items()
+    }
+    # a recipe can be shared by several constraints
+    for c in (constraint if isinstance(constraint, list) else [constraint]):
+        programs[c] = c.register_bash_program(
+            "",
+            entrypoint,
+            [],
+            args_str,
+        )
+
 def aorist_r(programs, constraint, preamble, entrypoint, args):
     args_str = {
         k : (
@@ -137,6 +167,18 @@ def aorist_r(programs, constraint, preamble, entrypoint, args):
         args_str,
     )
 
+# structural SQL nodes, which the args of SQL recipes can build statements
+# with instead of formatting query strings
+SQL_NODES = [
+    "ColumnRef",
+    "BinaryOp",
+    "FunctionCall",
+    "Select",
+    "ColumnDef",
+    "CreateTable",
+    "InsertInto",
+]
+
 def sql_module(filename):
     text = open(filename).read()
     assert(text.index('/***') == 0)
@@ -147,29 +189,36 @@ def sql_module(filename):
 
     tree = ast.parse(program)
     assert(len(tree.body[0].value.args) == 2)
+    # @aorist_presto, @aorist_duckdb or @aorist_spark
+    dialect = tree.body[0].value.func.id
+    assert(dialect in ("aorist_presto", "aorist_duckdb", "aorist_spark"))
     constraint = tree.body[0].value.args[1]
     tree.body[0].value.args += [ast.Constant(entrypoint)]
     tree.body.insert(0, ast.Import(
         [ast.alias(name="builtins", asname=None)],
     ))
     tree.body.insert(0, ast.ImportFrom("aorist", [
-        ast.alias(name="aorist_presto", asname=None),
+        ast.alias(name=dialect, asname=None),
         ast.alias(name=constraint.id, asname=None),
-    ], 0))
-    tree.body.insert(2, ast.Assign(
-        [ast.Attribute(
-            ast.Name(id="builtins", ctx=ast.Load()),
-            constraint.id,
-            ast.Store(),
-        )],
-        ast.Name(id=constraint.id, ctx=ast.Load()),
-    ))
-    tree.body.insert(3, ast.Assign(
+    ] + [ast.alias(name=x, asname=None) for x in SQL_NODES], 0))
+    # args are unpickled elsewhere, so the names they use go in builtins
+    for (i, name) in enumerate([constraint.id] + SQL_NODES):
+        tree.body.insert(2 + i, ast.Assign(
+            [ast.Attribute(
+                ast.Name(id="builtins", ctx=ast.Load()),
+                name,
+                ast.Store(),
+            )],
+            ast.Name(id=name, ctx=ast.Load()),
+        ))
+    tree.body.insert(3 + len(SQL_NODES), ast.Assign(
         [ast.Name(id="programs", ctx=ast.Store())],
         ast.Dict([],[]),
     ))
     code = astor.to_source(tree)
-    module_name = filename.replace('presto.sql', '').split('/')[-1]
+    module_name = filename.split('/')[-1]
+    for suffix in ('presto.sql', 'duckdb.sql', 'spark.sql'):
+        module_name = module_name.replace(suffix, '')
     module = imp.new_module(module_name)
     exec(code, module.__dict__)
     return module
@@ -185,23 +234,26 @@ def bash_module(filename):
     tree = ast.parse(program)
     assert(len(tree.body[0].value.args) == 2)
     constraint = tree.body[0].value.args[1]
+    constraints = constraint.elts if isinstance(constraint, ast.List) else [constraint]
     tree.body[0].value.args += [ast.Constant(entrypoint)]
     tree.body.insert(0, ast.Import(
         [ast.alias(name="builtins", asname=None)],
     ))
     tree.body.insert(0, ast.ImportFrom("aorist", [
         ast.alias(name="aorist_bash", asname=None),
-        ast.alias(name=constraint.id, asname=None),
+    ] + [
+        ast.alias(name=c.id, asname=None) for c in constraints
     ], 0))
-    tree.body.insert(2, ast.Assign(
-        [ast.Attribute(
-            ast.Name(id="builtins", ctx=ast.Load()),
-            constraint.id,
-            ast.Store(),
-        )],
-        ast.Name(id=constraint.id, ctx=ast.Load()),
-    ))
-    tree.body.insert(3, ast.Assign(
+    for (i, c) in enumerate(constraints):
+        tree.body.insert(2 + i, ast.Assign(
+            [ast.Attribute(
+                ast.Name(id="builtins", ctx=ast.Load()),
+                c.id,
+                ast.Store(),
+            )],
+            ast.Name(id=c.id, ctx=ast.Load()),
+        ))
+    tree.body.insert(2 + len(constraints), ast.Assign(
         [ast.Name(id="programs", ctx=ast.Store())],
         ast.Dict([],[]),
     ))
@@ -0,0 +1,452 @@
+//! Structural Bash nodes. Commands are built out of words rather than by
+//! substituting values into command strings, so that every value is quoted
+//! when the script is rendered. Scripts always run with `set -euo pipefail`.
+use crate::{StringLiteral, AST};
+use abi_stable::std_types::ROption;
+use aorist_util::{AOption, AString, AVec};
+use linked_hash_map::LinkedHashMap;
+
+const INDENT: &str = "  ";
+
+/// Quotes a value so that Bash reads it back as a single, literal word.
+/// Values made only of characters without special meaning are left as-is.
+pub fn quote(value: &str) -> String {
+    let is_plain = !value.is_empty()
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "_-./:=@%+,".contains(c));
+    match is_plain {
+        true => value.to_string(),
+        false => format!("'{}'", value.replace('\'', "'\"'\"'")),
+    }
+}
+
+/// Replaces the `{key}` placeholders of a hand-written command template by
+/// words. Each word is rendered outside of any quotes, so a placeholder
+/// inside a quoted string closes the quotes around it; e.g. `'{x}.csv'`
+/// becomes `''"${x}"'.csv'`. `${...}` expansions and placeholders for other
+/// keys are left as they are.
+pub fn substitute_placeholders(template: &str, words: &LinkedHashMap<AString, Word>) -> AString {
+    #[derive(Clone, Copy, PartialEq)]
+    enum Quoting {
+        None,
+        Single,
+        Double,
+    }
+    let chars = template.chars().collect::<Vec<char>>();
+    let mut quoting = Quoting::None;
+    let mut out = String::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c == '{' && (i == 0 || chars[i - 1] != '$') {
+            let end = chars[i + 1..].iter().position(|x| *x == '}');
+            let key = end.map(|n| chars[i + 1..i + 1 + n].iter().collect::<String>());
+            if let Some(word) = key.and_then(|x| words.get(&x.as_str().into()).cloned()) {
+                let (open, close) = match quoting {
+                    Quoting::None => ("", ""),
+                    Quoting::Single => ("'", "'"),
+                    Quoting::Double => ("\"", "\""),
+                };
+                out += &format!("{}{}{}", close, word.to_bash(), open);
+                i += end.unwrap() + 2;
+                continue;
+            }
+        }
+        out.push(c);
+        match (quoting, c) {
+            // escaped characters are copied as they are
+            (Quoting::None, '\\') | (Quoting::Double, '\\') if i + 1 < chars.len() => {
+                out.push(chars[i + 1]);
+                i += 1;
+            }
+            (Quoting::None, '\'') => quoting = Quoting::Single,
+            (Quoting::None, '"') => quoting = Quoting::Double,
+            (Quoting::Single, '\'') | (Quoting::Double, '"') => quoting = Quoting::None,
+            _ => (),
+        }
+        i += 1;
+    }
+    out.as_str().into()
+}
+
+/// Any Bash node that can be rendered to source.
+pub trait ToBash {
+    fn to_bash(&self) -> AString;
+    /// Wraps the rendered source in a string literal, so it can be passed
+    /// to Python / R task calls.
+    fn to_string_literal(&self) -> AST {
+        AST::StringLiteral(StringLiteral::new_wrapped(self.to_bash(), false))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Word {
+    /// always rendered as a single word, whatever it contains
+    Literal(AString),
+    /// expands to the value of a variable, without word splitting
+    Variable(AString),
+    /// rendered verbatim (e.g. globs); never use for untrusted values
+    Raw(AString),
+    /// adjacent words, rendered without separators, e.g. "${dir}"/'data.csv'
+    Concat(AVec<Word>),
+}
+impl Word {
+    /// Converts scalar literal nodes; other nodes have no Bash equivalent.
+    pub fn from_ast(ast: &AST) -> AOption<Word> {
+        let value: AString = match ast {
+            AST::StringLiteral(ref x) => x.read().value(),
+            AST::BigIntLiteral(ref x) => x.read().val().to_string().as_str().into(),
+            AST::FloatLiteral(ref x) => x.read().val().as_f64().to_string().as_str().into(),
+            AST::BooleanLiteral(ref x) => x.read().val().to_string().as_str().into(),
+            _ => return AOption(ROption::RNone),
+        };
+        AOption(ROption::RSome(Word::Literal(value)))
+    }
+}
+impl ToBash for Word {
+    fn to_bash(&self) -> AString {
+        match self {
+            Word::Literal(x) => quote(x.as_str()).as_str().into(),
+            Word::Variable(x) => format!("\"${{{}}}\"", x).as_str().into(),
+            Word::Raw(x) => x.clone(),
+            Word::Concat(x) => x
+                .iter()
+                .map(|w| w.to_bash().as_str().to_string())
+                .collect::<AVec<String>>()
+                .join("")
+                .as_str()
+                .into(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Command {
+    pub program: AString,
+    pub args: AVec<Word>,
+    /// variables set for this command only
+    pub env: LinkedHashMap<AString, Word>,
+    /// fed to the command's standard input, as a quoted here-document (so
+    /// the body is never expanded by Bash)
+    pub heredoc: AOption<AString>,
+}
+impl Command {
+    pub fn new(program: AString, args: AVec<Word>) -> Self {
+        Self {
+            program,
+            args,
+            env: LinkedHashMap::new(),
+            heredoc: AOption(ROption::RNone),
+        }
+    }
+    fn heredoc_delimiter(body: &str) -> String {
+        let mut delimiter = "EOF".to_string();
+        while body.lines().any(|x| x == delimiter) {
+            delimiter = format!("_{}", delimiter);
+        }
+        delimiter
+    }
+    /// The command line (with the here-document redirection, if any), and
+    /// the here-document itself, which has to follow the whole line.
+    fn to_bash_parts(&self) -> (String, Option<String>) {
+        let line = self
+            .env
+            .iter()
+            .map(|(k, v)| format!("{}={}", k, v.to_bash()))
+            .chain(std::iter::once(quote(self.program.as_str())))
+            .chain(self.args.iter().map(|x| x.to_bash().as_str().to_string()))
+            .collect::<AVec<String>>()
+            .join(" ");
+        match &self.heredoc {
+            // here-document bodies must not be indented
+            AOption(ROption::RSome(body)) => {
+                let delimiter = Self::heredoc_delimiter(body.as_str());
+                (
+                    format!("{} <<'{}'", line, delimiter),
+                    Some(format!("{}\n{}", body.as_str().trim_end(), delimiter)),
+                )
+            }
+            AOption(ROption::RNone) => (line, None),
+        }
+    }
+    fn to_bash_indented(&self, indent: &str) -> String {
+        match self.to_bash_parts() {
+            (line, Some(heredoc)) => format!("{}{}\n{}", indent, line, heredoc),
+            (line, None) => format!("{}{}", indent, line),
+        }
+    }
+}
+impl ToBash for Command {
+    fn to_bash(&self) -> AString {
+        self.to_bash_indented("").as_str().into()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Pipeline {
+    pub commands: AVec<Command>,
+}
+impl Pipeline {
+    pub fn new(commands: AVec<Command>) -> Self {
+        Self { commands }
+    }
+}
+impl ToBash for Pipeline {
+    /// Here-documents of the commands come after the pipeline, in order.
+    fn to_bash(&self) -> AString {
+        let (lines, heredocs): (Vec<String>, Vec<Option<String>>) =
+            self.commands.iter().map(|x| x.to_bash_parts()).unzip();
+        vec![lines.join(" | ")]
+            .into_iter()
+            .chain(heredocs.into_iter().flatten())
+            .collect::<AVec<String>>()
+            .join("\n")
+            .as_str()
+            .into()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum VariableScope {
+    Shell,
+    /// only valid inside functions
+    Local,
+    /// also visible to child processes
+    Export,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct VariableAssignment {
+    pub name: AString,
+    pub value: Word,
+    pub scope: VariableScope,
+}
+impl VariableAssignment {
+    pub fn new(name: AString, value: Word, scope: VariableScope) -> Self {
+        Self { name, value, scope }
+    }
+}
+impl ToBash for VariableAssignment {
+    fn to_bash(&self) -> AString {
+        let prefix = match self.scope {
+            VariableScope::Shell => "",
+            VariableScope::Local => "local ",
+            VariableScope::Export => "export ",
+        };
+        format!("{}{}={}", prefix, self.name, self.value.to_bash())
+            .as_str()
+            .into()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Function {
+    pub name: AString,
+    pub body: AVec<Statement>,
+}
+impl Function {
+    pub fn new(name: AString, body: AVec<Statement>) -> Self {
+        Self { name, body }
+    }
+    /// Makes a valid function name out of an arbitrary identifier.
+    pub fn sanitize_name(name: &str) -> AString {
+        let sanitized = name
+            .chars()
+            .map(|c| match c.is_ascii_alphanumeric() {
+                true => c,
+                false => '_',
+            })
+            .collect::<String>();
+        match sanitized.chars().next() {
+            Some(c) if c.is_ascii_digit() => format!("_{}", sanitized).as_str().into(),
+            _ => sanitized.as_str().into(),
+        }
+    }
+    fn to_bash_indented(&self, indent: &str) -> String {
+        let inner = format!("{}{}", indent, INDENT);
+        let body = match self.body.len() {
+            0 => format!("{}:", inner),
+            _ => self
+                .body
+                .iter()
+                .map(|x| x.to_bash_indented(&inner))
+                .collect::<AVec<String>>()
+                .join("\n"),
+        };
+        format!("{}{}() {{\n{}\n{}}}", indent, self.name, body, indent)
+    }
+}
+impl ToBash for Function {
+    fn to_bash(&self) -> AString {
+        self.to_bash_indented("").as_str().into()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Statement {
+    Command(Command),
+    Pipeline(Pipeline),
+    Assignment(VariableAssignment),
+    Function(Function),
+    Comment(AString),
+    /// source written by hand (e.g. a recipe's command template)
+    Raw(AString),
+    Blank,
+}
+impl Statement {
+    fn to_bash_indented(&self, indent: &str) -> String {
+        match self {
+            Statement::Command(x) => x.to_bash_indented(indent),
+            Statement::Function(x) => x.to_bash_indented(indent),
+            Statement::Pipeline(x) => format!("{}{}", indent, x.to_bash()),
+            Statement::Assignment(x) => format!("{}{}", indent, x.to_bash()),
+            Statement::Comment(x) => x
+                .as_str()
+                .lines()
+                .map(|l| format!("{}# {}", indent, l).trim_end().to_string())
+                .collect::<AVec<String>>()
+                .join("\n"),
+            Statement::Raw(x) => x
+                .as_str()
+                .trim_end()
+                .lines()
+                .map(|l| format!("{}{}", indent, l))
+                .collect::<AVec<String>>()
+                .join("\n"),
+            Statement::Blank => "".to_string(),
+        }
+    }
+}
+impl ToBash for Statement {
+    fn to_bash(&self) -> AString {
+        self.to_bash_indented("").as_str().into()
+    }
+}
+
+/// A standalone script: shebang, strict-mode prologue, then statements.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Script {
+    pub statements: AVec<Statement>,
+}
+impl Script {
+    pub fn new(statements: AVec<Statement>) -> Self {
+        Self { statements }
+    }
+}
+impl ToBash for Script {
+    fn to_bash(&self) -> AString {
+        vec![
+            "#!/usr/bin/env bash".to_string(),
+            "set -euo pipefail".to_string(),
+            "".to_string(),
+        ]
+        .into_iter()
+        .chain(self.statements.iter().map(|x| x.to_bash_indented("")))
+        .collect::<AVec<String>>()
+        .join("\n")
+        .as_str()
+        .into()
+    }
+}
+
+#[cfg(test)]
+mod bash_tests {
+    use crate::bash::*;
+
+    #[test]
+    fn test_quote() {
+        assert_eq!(quote("data/file-1.csv"), "data/file-1.csv");
+        assert_eq!(quote(""), "''");
+        assert_eq!(quote("a b; rm -rf /"), "'a b; rm -rf /'");
+        assert_eq!(quote("it's"), "'it'\"'\"'s'");
+    }
+
+    #[test]
+    fn test_substitute_placeholders() {
+        let mut words = LinkedHashMap::new();
+        words.insert("dir".into(), Word::Variable("arg_dir".into()));
+        words.insert("name".into(), Word::Literal("a b".into()));
+        assert_eq!(
+            substitute_placeholders(
+                "cd {dir} && echo '{name}: {other}' \"${HOME}/{name}\" \\{dir}",
+                &words
+            )
+            .as_str(),
+            "cd \"${arg_dir}\" && echo '''a b'': {other}' \"${HOME}/\"'a b'\"\" \\{dir}"
+        );
+    }
+    #[test]
+    fn test_pipeline() {
+        let mut curl = Command::new(
+            "curl".into(),
+            vec![
+                Word::Literal("-sS".into()),
+                Word::Concat(
+                    vec![
+                        Word::Variable("base_url".into()),
+                        Word::Literal("/q?a=1&b=2".into()),
+                    ]
+                    .into_iter()
+                    .collect(),
+                ),
+            ]
+            .into_iter()
+            .collect(),
+        );
+        curl.env.insert("LC_ALL".into(), Word::Literal("C".into()));
+        let gunzip = Command::new("gunzip".into(), AVec::new());
+        let pipeline = Pipeline::new(vec![curl, gunzip].into_iter().collect());
+        assert_eq!(
+            pipeline.to_bash().as_str(),
+            "LC_ALL=C curl -sS \"${base_url}\"'/q?a=1&b=2' | gunzip"
+        );
+        let mut cat = Command::new("cat".into(), AVec::new());
+        cat.heredoc = AOption(ROption::RSome("EOF\n".into()));
+        let wc = Command::new(
+            "wc".into(),
+            vec![Word::Literal("-l".into())].into_iter().collect(),
+        );
+        let pipeline = Pipeline::new(vec![cat, wc].into_iter().collect());
+        assert_eq!(
+            pipeline.to_bash().as_str(),
+            "cat <<'_EOF' | wc -l\nEOF\n_EOF"
+        );
+    }
+
+    #[test]
+    fn test_script() {
+        let mut python = Command::new(
+            "python3".into(),
+            vec![Word::Literal("-".into())].into_iter().collect(),
+        );
+        python.heredoc = AOption(ROption::RSome("print('$HOME')\n".into()));
+        let function = Function::new(
+            Function::sanitize_name("1-download"),
+            vec![
+                Statement::Assignment(VariableAssignment::new(
+                    "dest".into(),
+                    Word::Literal("/tmp/my data".into()),
+                    VariableScope::Local,
+                )),
+                Statement::Command(python),
+            ]
+            .into_iter()
+            .collect(),
+        );
+        let script = Script::new(
+            vec![
+                Statement::Function(function),
+                Statement::Command(Command::new("_1_download".into(), AVec::new())),
+            ]
+            .into_iter()
+            .collect(),
+        );
+        assert_eq!(
+            script.to_bash().as_str(),
+            "#!/usr/bin/env bash\nset -euo pipefail\n\n\
+             _1_download() {\n  local dest='/tmp/my data'\n  python3 - <<'EOF'\nprint('$HOME')\nEOF\n}\n\
+             _1_download"
+        );
+    }
+}
@@ -69,6 +69,8 @@ pub fn extract_arg(arg: &PyAny) -> PyResult<AST> {
         Ok(AST::SimpleIdentifier(SimpleIdentifier::new_wrapped(
             extracted_type.name()?.into(),
         )))
+    } else if let Some(statement) = sql_statement(arg) {
+        Ok(statement)
     } else {
         let class = arg.getattr("__class__")?;
         let module = class.getattr("__module__")?.extract::<String>()?;
@@ -123,6 +125,15 @@ pub fn extract_arg(arg: &PyAny) -> PyResult<AST> {
         }
     }
 }
+#[cfg(feature = "sql")]
+fn sql_statement(arg: &PyAny) -> Option<AST> {
+    crate::sql::SQLNode::from_py(arg)
+        .map(|x| AST::SQLStatement(crate::SQLStatement::new_wrapped(x)))
+}
+#[cfg(not(feature = "sql"))]
+fn sql_statement(_arg: &PyAny) -> Option<AST> {
+    Option::None
+}
 pub fn extract_arg_with_context(
     arg: &PyAny,
     context: &mut Context,
@@ -1,22 +1,28 @@
 use aorist_util::{AOption, AString, AVec};
 mod ancestor_record;
 mod assignment_target;
+pub mod bash;
 mod extract_arg;
+mod optimization;
 mod string_literal;
+#[cfg(feature = "sql")]
+pub mod sql;
 
 pub use ancestor_record::*;
 pub use assignment_target::*;
 pub use extract_arg::*;
+pub use optimization::*;
 pub use string_literal::*;
 
 use abi_stable::external_types::parking_lot::rw_lock::RRwLock;
 use abi_stable::std_types::{RArc, ROption};
 use aorist_derive::Optimizable;
 use aorist_extendr_api::prelude::*;
-use aorist_primitives::{define_ast_node, register_ast_nodes};
+use aorist_primitives::{define_ast_node, register_ast_nodes, Dialect};
 use linked_hash_map::LinkedHashMap;
+use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
-use pyo3::types::{PyList, PyModule, PyString, PyTuple};
+use pyo3::types::{IntoPyDict, PyList, PyModule, PyString, PyTuple};
 use std::collections::VecDeque;
 use std::hash::{Hash, Hasher};
 
@@ -27,10 +33,10 @@ define_ast_node!(
         import.to_python_ast_node(py, ast_module, depth)
     },
     |import: &ImportNode, depth: usize| {
-        r!(Language::from_values(&[
+        Ok(r!(Language::from_values(&[
             r!(Symbol::from_string("library")),
-            import.inner.to_r_ast_node(depth)
-        ]))
+            import.inner.to_r_ast_node(depth)?
+        ])))
     },
     inner: AST,
 );
@@ -42,15 +48,7 @@ define_ast_node!(
         .chain(for_loop.body.clone().into_iter())
         .collect(),
     |for_loop: &ForLoop, py: Python, ast_module: &'a PyModule, depth: usize| {
-        let body_ast = for_loop
-            .body
-            .iter()
-            .map(|x| match &x {
-                AST::Assignment(_) | AST::Expression(_) => x,
-                _ => panic!("AST node of type {} found in for loop body", x.name()),
-            })
-            .map(|x| x.to_python_ast_node(py, ast_module, depth + 1))
-            .collect::<PyResult<Vec<_>>>()?;
+        let body_ast = python_statements(&for_loop.body, ast_module, depth, "for loop body")?;
         let body_list = PyList::new(py, body_ast);
         let empty_vec: Vec<String> = Vec::new();
         let empty_list = PyList::new(py, empty_vec);
@@ -66,21 +64,12 @@ define_ast_node!(
             let res = make_lang("call");
             let mut tail = res.get();
             tail = append_with_name(tail, r!("for"), "name");
-            tail = append(tail, for_loop.target.to_r_ast_node(depth));
-            tail = append(tail, for_loop.iter.to_r_ast_node(depth));
-
-            let body = make_lang("call");
-            let mut body_tail = body.get();
-            body_tail = append_with_name(body_tail, r!("{"), "name");
-            for x in for_loop.body.iter() {
-                body_tail = append(body_tail, x.to_r_ast_node(depth));
-            }
-            let _ = body_tail;
-
-            tail = append(tail, body);
+            tail = append(tail, for_loop.target.to_r_ast_node(depth)?);
+            tail = append(tail, for_loop.iter.to_r_ast_node(depth)?);
+            tail = append(tail, r_block(&for_loop.body, depth)?);
 
             let _ = tail;
-            res
+            Ok(res)
         }
     },
     target: AST,
@@ -99,7 +88,12 @@ define_ast_node!(
             AST::List(ref x) => AST::List(x.read().as_wrapped_assignment_target()),
             AST::Tuple(ref x) => AST::Tuple(x.read().as_wrapped_assignment_target()),
             AST::SimpleIdentifier(_) => assign.target.clone(),
-            _ => panic!("Assignment target not supported."),
+            _ => {
+                return Err(PyValueError::new_err(format!(
+                    "AST node of type {} not supported as assignment target",
+                    assign.target.name()
+                )))
+            }
         };
         let targets = PyList::new(
             py,
@@ -115,10 +109,10 @@ define_ast_node!(
             let res = make_lang("call");
             let mut tail = res.get();
             tail = append_with_name(tail, r!("<-"), "name");
-            tail = append(tail, assign.target.to_r_ast_node(depth));
-            tail = append(tail, assign.call.to_r_ast_node(depth));
+            tail = append(tail, assign.target.to_r_ast_node(depth)?);
+            tail = append(tail, assign.call.to_r_ast_node(depth)?);
             let _ = tail;
-            res
+            Ok(res)
         }
     },
     target: AST,
@@ -141,7 +135,11 @@ define_ast_node!(
     |_node: &Add, _py: Python, ast_module: &'a PyModule, _depth: usize| {
         ast_module.getattr("Add")?.call0()
     },
-    |_add: &Add, _depth: usize| { panic!("Should not call to_r_ast_node on Add objects directly") },
+    |_add: &Add, _depth: usize| {
+        Err(PyValueError::new_err(
+            "Should not call to_r_ast_node on Add objects directly",
+        ))
+    },
 );
 define_ast_node!(
     BinOp,
@@ -156,15 +154,11 @@ define_ast_node!(
         ))
     },
     |binop: &BinOp, depth: usize| {
-        let op_str = match binop.op {
-            AST::Add(_) => "+",
-            _ => panic!("AST node not supported as R operator"),
-        };
-        r!(Language::from_values(&[
-            r!(Symbol::from_string(op_str)),
-            binop.left.to_r_ast_node(depth),
-            binop.right.to_r_ast_node(depth)
-        ]))
+        Ok(r!(Language::from_values(&[
+            r!(Symbol::from_string(binop.op.get_r_operator()?)),
+            binop.left.to_r_ast_node(depth)?,
+            binop.right.to_r_ast_node(depth)?
+        ])))
     },
     left: AST,
     op: AST,
@@ -195,9 +189,9 @@ define_ast_node!(
             .elems
             .iter()
             .map(|x| x.to_r_ast_node(depth))
-            .collect::<Vec<_>>();
+            .collect::<PyResult<Vec<_>>>()?;
         elems.insert(0, r!(Symbol::from_string("list")));
-        r!(Language::from_values(&elems))
+        Ok(r!(Language::from_values(&elems)))
     },
     elems: AVec<AST>,
     store: bool,
@@ -239,11 +233,11 @@ define_ast_node!(
             .elems
             .values()
             .map(|x| x.to_r_ast_node(depth))
-            .collect::<Vec<_>>();
+            .collect::<PyResult<Vec<_>>>()?;
         let obj = r!(aorist_extendr_api::List::from_values(&elems));
         obj.set_names(dict.elems.keys().map(|x| x.as_str()))
             .unwrap();
-        obj
+        Ok(obj)
     },
     elems: LinkedHashMap<AString, AST>,
 );
@@ -275,7 +269,9 @@ define_ast_node!(
             .getattr("Tuple")?
             .call1((children_list.as_ref(), mode))
     },
-    |_tuple: &Tuple, _depth: usize| { panic!("No R correspondent for Tuple nodes") },
+    |_tuple: &Tuple, _depth: usize| {
+        Err(PyValueError::new_err("No R correspondent for Tuple nodes"))
+    },
     elems: AVec<AST>,
     store: bool,
 );
@@ -305,7 +301,9 @@ define_ast_node!(
             .getattr("Attribute")?
             .call1((val_ast, name_ast.as_ref(), mode))
     },
-    |_attribute: &Attribute, _depth: usize| { panic!("No R correspondent for Attribute nodes") },
+    |_attribute: &Attribute, _depth: usize| {
+        Err(PyValueError::new_err("No R correspondent for Attribute nodes"))
+    },
     value: AST,
     name: AString,
     store: bool,
@@ -359,19 +357,23 @@ define_ast_node!(
         unsafe {
             let fn_name = match call.function {
                 AST::SimpleIdentifier(ref x) => x.read().name(),
-                _ => panic!("function name must be SimpleIdentifier"),
+                _ => {
+                    return Err(PyValueError::new_err(
+                        "function name must be SimpleIdentifier",
+                    ))
+                }
             };
             let res = make_lang("call");
             let mut tail = res.get();
             tail = append_with_name(tail, r!(fn_name.as_str()), "name");
             for arg in call.args.iter() {
-                tail = append(tail, arg.to_r_ast_node(depth));
+                tail = append(tail, arg.to_r_ast_node(depth)?);
             }
             for (k, v) in &call.keywords {
-                tail = append_with_name(tail, v.to_r_ast_node(depth), k.as_str());
+                tail = append_with_name(tail, v.to_r_ast_node(depth)?, k.as_str());
             }
             let _ = tail;
-            res
+            Ok(res)
         }
     },
     function: AST,
@@ -422,12 +424,12 @@ define_ast_node!(
             let res = make_lang("call");
             let mut tail = res.get();
             tail = append_with_name(tail, r!("glue"), "name");
-            tail = append(tail, formatted.fmt.to_r_ast_node(depth));
+            tail = append(tail, formatted.fmt.to_r_ast_node(depth)?);
             for (k, v) in &formatted.keywords {
-                tail = append_with_name(tail, v.to_r_ast_node(depth), k.as_str());
+                tail = append_with_name(tail, v.to_r_ast_node(depth)?, k.as_str());
             }
             let _ = tail;
-            res
+            Ok(res)
         }
     },
     fmt: AST,
@@ -451,8 +453,8 @@ define_ast_node!(
         ast_module.getattr("Subscript")?.call1((value, idx, mode))
     },
     |subscript: &Subscript, depth: usize| {
-        let a_node = subscript.a.to_r_ast_node(depth);
-        let b_node = subscript.b.to_r_ast_node(depth);
+        let a_node = subscript.a.to_r_ast_node(depth)?;
+        let b_node = subscript.b.to_r_ast_node(depth)?;
         unsafe {
             let res = make_lang("call");
             let mut tail = res.get();
@@ -460,7 +462,7 @@ define_ast_node!(
             tail = append(tail, a_node);
             tail = append(tail, b_node);
             let _ = tail;
-            res
+            Ok(res)
         }
     },
     a: AST,
@@ -486,7 +488,7 @@ define_ast_node!(
             .call1((PyString::new(py, simple_identifier.name.as_str()).as_ref(),))
     },
     |simple_identifier: &SimpleIdentifier, _depth: usize| {
-        call!("call", r!("as.name"), r!(simple_identifier.name.as_str())).unwrap()
+        Ok(call!("call", r!("as.name"), r!(simple_identifier.name.as_str())).unwrap())
     },
     name: AString,
 );
@@ -497,7 +499,7 @@ define_ast_node!(
     |lit: &BooleanLiteral, _py: Python, ast_module: &'a PyModule, _depth: usize| {
         ast_module.getattr("Constant")?.call1((lit.val,))
     },
-    |lit: &BooleanLiteral, _depth: usize| { Robj::from(lit.val) },
+    |lit: &BooleanLiteral, _depth: usize| { Ok(Robj::from(lit.val)) },
     val: bool,
 );
 
@@ -512,24 +514,9 @@ define_ast_node!(
         })
         .collect(),
     |if_else: &If, py: Python, ast_module: &'a PyModule, depth: usize| {
-        let body_ast = if_else
-            .body
-            .iter()
-            .map(|x| match &x {
-                AST::Assignment(_) | AST::Expression(_) => x,
-                _ => panic!("AST node of type {} found in if body", x.name()),
-            })
-            .map(|x| x.to_python_ast_node(py, ast_module, depth + 1))
-            .collect::<PyResult<Vec<_>>>()?;
+        let body_ast = python_statements(&if_else.body, ast_module, depth, "if body")?;
         let orelse_ast = match &if_else.orelse {
-            AOption(ROption::RSome(x)) => x
-                .iter()
-                .map(|x| match &x {
-                    AST::Assignment(_) | AST::Expression(_) => x,
-                    _ => panic!("AST node of type {} found in if orelse", x.name()),
-                })
-                .map(|x| x.to_python_ast_node(py, ast_module, depth + 1))
-                .collect::<PyResult<Vec<_>>>()?,
+            AOption(ROption::RSome(x)) => python_statements(x, ast_module, depth, "if orelse")?,
             AOption(ROption::RNone) => Vec::new(),
         };
 
@@ -542,8 +529,19 @@ define_ast_node!(
             orelse_list.as_ref(),
         ))
     },
-    |_if_else: &If, _depth: usize| {
-        panic!("if - else unimplemented in R");
+    |if_else: &If, depth: usize| {
+        unsafe {
+            let res = make_lang("call");
+            let mut tail = res.get();
+            tail = append_with_name(tail, r!("if"), "name");
+            tail = append(tail, if_else.test.to_r_ast_node(depth)?);
+            tail = append(tail, r_block(&if_else.body, depth)?);
+            if let AOption(ROption::RSome(ref orelse)) = if_else.orelse {
+                tail = append(tail, r_block(orelse, depth)?);
+            }
+            let _ = tail;
+            Ok(res)
+        }
     },
     test: AST,
     body: AVec<AST>,
@@ -555,7 +553,7 @@ define_ast_node!(
     |lit: &BigIntLiteral, _py: Python, ast_module: &'a PyModule, _depth: usize| {
         ast_module.getattr("Constant")?.call1((lit.val,))
     },
-    |lit: &BigIntLiteral, _depth: usize| { r!(lit.val) },
+    |lit: &BigIntLiteral, _depth: usize| { Ok(r!(lit.val)) },
     // TODO: deprecate use of BigInt when removing rustpython
     val: i64,
 );
@@ -565,7 +563,7 @@ define_ast_node!(
     |lit: &FloatLiteral, _py: Python, ast_module: &'a PyModule, _depth: usize| {
         ast_module.getattr("Constant")?.call1((lit.val.as_f64(),))
     },
-    |lit: &FloatLiteral, _depth: usize| { r!(lit.val.as_f64()) },
+    |lit: &FloatLiteral, _depth: usize| { Ok(r!(lit.val.as_f64())) },
     val: aorist_attributes::FloatValue,
 );
 define_ast_node!(
@@ -576,55 +574,1022 @@ define_ast_node!(
             .getattr("Constant")?
             .call1((py.None().as_ref(py),))
     },
-    |_none, _depth| { r!(NULL) },
+    |_none, _depth| { Ok(r!(NULL)) },
 );
 define_ast_node!(
     FunctionDef,
-    |_fun: &FunctionDef| AVec::new(),
+    |fun: &FunctionDef| fun
+        .kwargs
+        .values()
+        .cloned()
+        .chain(fun.kwonlyargs.values().flat_map(optional_ast))
+        .chain(fun.body.clone().into_iter())
+        .collect(),
     |fun: &FunctionDef, py: Python, ast_module: &'a PyModule, depth: usize| {
-        let mut args_py = Vec::new();
-        let mut kwargs_py = Vec::new();
-        let mut kwargs_defaults = Vec::new();
-        for arg in fun.args.iter() {
-            let arg_py = ast_module.getattr("arg")?.call1((
-                arg.to_python_ast_node(py, ast_module, depth)?,
-                py.None().as_ref(py),
-            ))?;
-            args_py.push(arg_py);
-        }
-        for (k, v) in &fun.kwargs {
-            let arg_py = ast_module.getattr("arg")?.call1((
-                v.to_python_ast_node(py, ast_module, depth)?,
-                py.None().as_ref(py),
-            ))?;
-            kwargs_py.push(arg_py);
-            let default_py = AST::StringLiteral(StringLiteral::new_wrapped(k.clone(), false))
-                .to_python_ast_node(py, ast_module, depth)?;
-            kwargs_defaults.push(default_py);
-        }
-        let arguments = ast_module.getattr("arguments")?.call1((
-            args_py,
-            py.None().as_ref(py),
-            kwargs_py,
-            py.None().as_ref(py),
-            py.None().as_ref(py),
-            kwargs_defaults,
-        ))?;
-        let body_py = fun
-            .body
-            .iter()
-            .map(|x| x.to_python_ast_node(py, ast_module, depth))
-            .collect::<PyResult<Vec<_>>>()?;
-        ast_module
-            .getattr("FunctionDef")?
-            .call1((fun.name.as_str(), arguments, body_py))
+        let arguments = python_arguments(
+            ast_module,
+            depth,
+            &fun.args,
+            &fun.kwargs,
+            &fun.kwonlyargs,
+        )?;
+        let body_py = python_statements(&fun.body, ast_module, depth, "function body")?;
+        let empty_vec: Vec<String> = Vec::new();
+        ast_module.getattr("FunctionDef")?.call1((
+            fun.name.as_str(),
+            arguments,
+            PyList::new(py, body_py).as_ref(),
+            PyList::new(py, empty_vec).as_ref(),
+        ))
+    },
+    |fun: &FunctionDef, depth: usize| {
+        unsafe {
+            let res = make_lang("call");
+            let mut tail = res.get();
+            tail = append_with_name(tail, r!("<-"), "name");
+            tail = append(
+                tail,
+                SimpleIdentifier::new(fun.name.clone()).to_r_ast_node(depth)?,
+            );
+            tail = append(
+                tail,
+                r_function(
+                    &fun.args,
+                    &fun.kwargs,
+                    &fun.kwonlyargs,
+                    r_block(&fun.body, depth)?,
+                    depth,
+                )?,
+            );
+            let _ = tail;
+            Ok(res)
+        }
     },
-    |_fun: &FunctionDef, _depth: usize| { panic!("Function defs not supported in R") },
     name: AString,
     args: AVec<AST>,
+    // argument name => default value
     kwargs: LinkedHashMap<AString, AST>,
+    // keyword-only argument name => default value (if any)
+    kwonlyargs: LinkedHashMap<AString, AOption<AST>>,
+    body: AVec<AST>,
+);
+define_ast_node!(
+    Lambda,
+    |lambda: &Lambda| vec![lambda.body.clone()].into_iter().collect(),
+    |lambda: &Lambda, _py: Python, ast_module: &'a PyModule, depth: usize| {
+        let arguments = python_arguments(
+            ast_module,
+            depth,
+            &lambda.args,
+            &LinkedHashMap::new(),
+            &LinkedHashMap::new(),
+        )?;
+        ast_module.getattr("Lambda")?.call1((
+            arguments,
+            lambda.body.to_python_ast_node(ast_module.py(), ast_module, depth)?,
+        ))
+    },
+    |lambda: &Lambda, depth: usize| {
+        r_function(
+            &lambda.args,
+            &LinkedHashMap::new(),
+            &LinkedHashMap::new(),
+            lambda.body.to_r_ast_node(depth)?,
+            depth,
+        )
+    },
+    args: AVec<AST>,
+    body: AST,
+);
+define_ast_node!(
+    Return,
+    |ret: &Return| optional_ast(&ret.value),
+    |ret: &Return, _py: Python, ast_module: &'a PyModule, depth: usize| {
+        ast_module
+            .getattr("Return")?
+            .call1((python_optional(&ret.value, ast_module, depth)?,))
+    },
+    |ret: &Return, depth: usize| {
+        unsafe {
+            let res = make_lang("call");
+            let mut tail = res.get();
+            tail = append_with_name(tail, r!("return"), "name");
+            if let AOption(ROption::RSome(ref value)) = ret.value {
+                tail = append(tail, value.to_r_ast_node(depth)?);
+            }
+            let _ = tail;
+            Ok(res)
+        }
+    },
+    value: AOption<AST>,
+);
+define_ast_node!(
+    Raise,
+    |raise: &Raise| optional_ast(&raise.exc)
+        .into_iter()
+        .chain(optional_ast(&raise.cause).into_iter())
+        .collect(),
+    |raise: &Raise, _py: Python, ast_module: &'a PyModule, depth: usize| {
+        ast_module.getattr("Raise")?.call1((
+            python_optional(&raise.exc, ast_module, depth)?,
+            python_optional(&raise.cause, ast_module, depth)?,
+        ))
+    },
+    |raise: &Raise, depth: usize| {
+        // R conditions are not chained, so the cause is dropped. Bare raises
+        // in except handlers have been rewritten to re-signal the handled
+        // condition (see r_reraise), so any left are outside of handlers.
+        let exc = match raise.exc {
+            AOption(ROption::RSome(ref x)) => x.to_r_ast_node(depth)?,
+            AOption(ROption::RNone) => r!("No active exception to reraise"),
+        };
+        unsafe {
+            let res = make_lang("call");
+            let mut tail = res.get();
+            tail = append_with_name(tail, r!("stop"), "name");
+            tail = append(tail, exc);
+            let _ = tail;
+            Ok(res)
+        }
+    },
+    exc: AOption<AST>,
+    cause: AOption<AST>,
+);
+define_ast_node!(
+    While,
+    |while_loop: &While| vec![while_loop.test.clone()]
+        .into_iter()
+        .chain(while_loop.body.clone().into_iter())
+        .collect(),
+    |while_loop: &While, py: Python, ast_module: &'a PyModule, depth: usize| {
+        let body_ast = python_statements(&while_loop.body, ast_module, depth, "while body")?;
+        let empty_vec: Vec<String> = Vec::new();
+        ast_module.getattr("While")?.call1((
+            while_loop.test.to_python_ast_node(py, ast_module, depth)?,
+            PyList::new(py, body_ast).as_ref(),
+            PyList::new(py, empty_vec).as_ref(),
+        ))
+    },
+    |while_loop: &While, depth: usize| {
+        unsafe {
+            let res = make_lang("call");
+            let mut tail = res.get();
+            tail = append_with_name(tail, r!("while"), "name");
+            tail = append(tail, while_loop.test.to_r_ast_node(depth)?);
+            tail = append(tail, r_block(&while_loop.body, depth)?);
+            let _ = tail;
+            Ok(res)
+        }
+    },
+    test: AST,
+    body: AVec<AST>,
+);
+define_ast_node!(
+    ExceptHandler,
+    |handler: &ExceptHandler| optional_ast(&handler.exception_type)
+        .into_iter()
+        .chain(handler.body.clone().into_iter())
+        .collect(),
+    |handler: &ExceptHandler, py: Python, ast_module: &'a PyModule, depth: usize| {
+        let name = match handler.name {
+            AOption(ROption::RSome(ref x)) => PyString::new(py, x.as_str()).as_ref(),
+            AOption(ROption::RNone) => py.None().into_ref(py),
+        };
+        let body_ast = python_statements(&handler.body, ast_module, depth, "except body")?;
+        ast_module.getattr("ExceptHandler")?.call1((
+            python_optional(&handler.exception_type, ast_module, depth)?,
+            name,
+            PyList::new(py, body_ast).as_ref(),
+        ))
+    },
+    |handler: &ExceptHandler, depth: usize| {
+        // R handlers are functions of the condition object. The condition
+        // classes they catch are given by the enclosing tryCatch (see
+        // ExceptHandler::get_r_condition_classes).
+        let name = match handler.name {
+            AOption(ROption::RSome(ref x)) => x.clone(),
+            AOption(ROption::RNone) => "e".into(),
+        };
+        r_function(
+            &vec![AST::SimpleIdentifier(SimpleIdentifier::new_wrapped(
+                name.clone(),
+            ))]
+            .into_iter()
+            .collect::<AVec<AST>>(),
+            &LinkedHashMap::new(),
+            &LinkedHashMap::new(),
+            r_block(&r_reraise(&handler.body, &name), depth)?,
+            depth,
+        )
+    },
+    exception_type: AOption<AST>,
+    name: AOption<AString>,
+    body: AVec<AST>,
+);
+impl ExceptHandler {
+    /// R condition classes caught by the handler. Python exception names
+    /// are used as condition classes, except for the catch-all ones.
+    pub fn get_r_condition_classes(&self) -> PyResult<AVec<AString>> {
+        fn class_names(exception_type: &AST) -> PyResult<Vec<AString>> {
+            match exception_type {
+                AST::SimpleIdentifier(ref x) => Ok(vec![x.read().name()]),
+                AST::Attribute(ref x) => Ok(vec![x.read().name()]),
+                AST::Tuple(ref x) => Ok(x
+                    .read()
+                    .elems()
+                    .iter()
+                    .map(class_names)
+                    .collect::<PyResult<Vec<_>>>()?
+                    .into_iter()
+                    .flatten()
+                    .collect()),
+                _ => Err(PyValueError::new_err(format!(
+                    "AST node of type {} not supported as R condition class",
+                    exception_type.name()
+                ))),
+            }
+        }
+        let names = match self.exception_type {
+            AOption(ROption::RSome(ref x)) => class_names(x)?,
+            AOption(ROption::RNone) => vec!["Exception".into()],
+        };
+        let mut classes: AVec<AString> = AVec::new();
+        for name in names {
+            let class: AString = match name.as_str() {
+                "Exception" | "BaseException" => "error".into(),
+                "KeyboardInterrupt" => "interrupt".into(),
+                x if x.ends_with("Warning") => "warning".into(),
+                _ => name.clone(),
+            };
+            if !classes.iter().any(|x| *x == class) {
+                classes.push(class);
+            }
+        }
+        Ok(classes)
+    }
+}
+define_ast_node!(
+    Try,
+    |try_node: &Try| try_node
+        .body
+        .clone()
+        .into_iter()
+        .chain(try_node.handlers.clone().into_iter())
+        .chain(match try_node.orelse {
+            AOption(ROption::RSome(ref x)) => x.clone().into_iter(),
+            AOption(ROption::RNone) => AVec::new().into_iter(),
+        })
+        .chain(match try_node.finalbody {
+            AOption(ROption::RSome(ref x)) => x.clone().into_iter(),
+            AOption(ROption::RNone) => AVec::new().into_iter(),
+        })
+        .collect(),
+    |try_node: &Try, py: Python, ast_module: &'a PyModule, depth: usize| {
+        let body_ast = python_statements(&try_node.body, ast_module, depth, "try body")?;
+        let handlers_ast = try_node
+            .handlers
+            .iter()
+            .map(|x| match &x {
+                AST::ExceptHandler(_) => x.to_python_ast_node(py, ast_module, depth),
+                _ => Err(PyValueError::new_err(format!(
+                    "AST node of type {} found in try handlers",
+                    x.name()
+                ))),
+            })
+            .collect::<PyResult<Vec<_>>>()?;
+        let orelse_ast = match &try_node.orelse {
+            AOption(ROption::RSome(x)) => python_statements(x, ast_module, depth, "try orelse")?,
+            AOption(ROption::RNone) => Vec::new(),
+        };
+        let finalbody_ast = match &try_node.finalbody {
+            AOption(ROption::RSome(x)) => python_statements(x, ast_module, depth, "finally body")?,
+            AOption(ROption::RNone) => Vec::new(),
+        };
+        ast_module.getattr("Try")?.call1((
+            PyList::new(py, body_ast).as_ref(),
+            PyList::new(py, handlers_ast).as_ref(),
+            PyList::new(py, orelse_ast).as_ref(),
+            PyList::new(py, finalbody_ast).as_ref(),
+        ))
+    },
+    |try_node: &Try, depth: usize| {
+        let try_catch = |body: Robj,
+                         handlers: &AVec<AST>,
+                         finalbody: AOption<Robj>|
+         -> PyResult<Robj> {
+            let res = unsafe { make_lang("call") };
+            let mut tail = unsafe { res.get() };
+            tail = unsafe { append_with_name(tail, r!("tryCatch"), "name") };
+            tail = unsafe { append(tail, body) };
+            for handler in handlers.iter() {
+                let classes = match handler {
+                    AST::ExceptHandler(ref x) => x.read().get_r_condition_classes()?,
+                    _ => {
+                        return Err(PyValueError::new_err(format!(
+                            "AST node of type {} found in try handlers",
+                            handler.name()
+                        )))
+                    }
+                };
+                let function = handler.to_r_ast_node(depth)?;
+                for class in classes.iter() {
+                    tail = unsafe { append_with_name(tail, function.clone(), class.as_str()) };
+                }
+            }
+            if let AOption(ROption::RSome(finalbody)) = finalbody {
+                tail = unsafe { append_with_name(tail, finalbody, "finally") };
+            }
+            let _ = tail;
+            Ok(res)
+        };
+        let finalbody = match try_node.finalbody {
+            AOption(ROption::RSome(ref x)) => AOption(ROption::RSome(r_block(x, depth)?)),
+            AOption(ROption::RNone) => AOption(ROption::RNone),
+        };
+        match try_node.orelse {
+            AOption(ROption::RNone) => {
+                try_catch(r_block(&try_node.body, depth)?, &try_node.handlers, finalbody)
+            }
+            // The else block runs if the body did not fail, and is not
+            // guarded by the handlers:
+            // if (tryCatch({body; TRUE}, error = function(e) {handler; FALSE})) {orelse}
+            AOption(ROption::RSome(ref orelse)) => {
+                let succeeded = |value: bool| {
+                    AST::BooleanLiteral(BooleanLiteral::new_wrapped(value))
+                };
+                let body = try_node
+                    .body
+                    .iter()
+                    .cloned()
+                    .chain(vec![succeeded(true)].into_iter())
+                    .collect::<AVec<AST>>();
+                let handlers = try_node
+                    .handlers
+                    .iter()
+                    .map(|x| match x {
+                        AST::ExceptHandler(ref rw) => {
+                            let handler = rw.read();
+                            AST::ExceptHandler(ExceptHandler::new_wrapped(
+                                handler.exception_type(),
+                                handler.name(),
+                                handler
+                                    .body()
+                                    .into_iter()
+                                    .chain(vec![succeeded(false)].into_iter())
+                                    .collect(),
+                            ))
+                        }
+                        _ => x.clone(),
+                    })
+                    .collect::<AVec<AST>>();
+                let guarded =
+                    try_catch(r_block(&body, depth)?, &handlers, AOption(ROption::RNone))?;
+                let if_else = unsafe {
+                    let res = make_lang("call");
+                    let mut tail = res.get();
+                    tail = append_with_name(tail, r!("if"), "name");
+                    tail = append(tail, guarded);
+                    tail = append(tail, r_block(orelse, depth)?);
+                    let _ = tail;
+                    res
+                };
+                match finalbody {
+                    AOption(ROption::RSome(_)) => try_catch(
+                        r_block_from_values(vec![if_else]),
+                        &AVec::new(),
+                        finalbody,
+                    ),
+                    AOption(ROption::RNone) => Ok(if_else),
+                }
+            }
+        }
+    },
+    body: AVec<AST>,
+    handlers: AVec<AST>,
+    orelse: AOption<AVec<AST>>,
+    finalbody: AOption<AVec<AST>>,
+);
+define_ast_node!(
+    WithItem,
+    |item: &WithItem| vec![item.context_expr.clone()]
+        .into_iter()
+        .chain(optional_ast(&item.optional_vars).into_iter())
+        .collect(),
+    |item: &WithItem, py: Python, ast_module: &'a PyModule, depth: usize| {
+        let optional_vars = match item.optional_vars {
+            AOption(ROption::RSome(ref x)) => {
+                x.as_wrapped_assignment_target()
+                    .to_python_ast_node(py, ast_module, depth)?
+            }
+            AOption(ROption::RNone) => py.None().into_ref(py),
+        };
+        ast_module.getattr("withitem")?.call1((
+            item.context_expr.to_python_ast_node(py, ast_module, depth)?,
+            optional_vars,
+        ))
+    },
+    |_item: &WithItem, _depth: usize| {
+        Err(PyValueError::new_err(
+            "Should not call to_r_ast_node on WithItem objects directly",
+        ))
+    },
+    context_expr: AST,
+    optional_vars: AOption<AST>,
+);
+define_ast_node!(
+    With,
+    |with: &With| with
+        .items
+        .clone()
+        .into_iter()
+        .chain(with.body.clone().into_iter())
+        .collect(),
+    |with: &With, py: Python, ast_module: &'a PyModule, depth: usize| {
+        let items_ast = with
+            .items
+            .iter()
+            .map(|x| match &x {
+                AST::WithItem(_) => x.to_python_ast_node(py, ast_module, depth),
+                _ => Err(PyValueError::new_err(format!(
+                    "AST node of type {} found in with items",
+                    x.name()
+                ))),
+            })
+            .collect::<PyResult<Vec<_>>>()?;
+        let body_ast = python_statements(&with.body, ast_module, depth, "with body")?;
+        ast_module.getattr("With")?.call1((
+            PyList::new(py, items_ast).as_ref(),
+            PyList::new(py, body_ast).as_ref(),
+        ))
+    },
+    |with: &With, depth: usize| {
+        // R has no context managers: bound resources are closed on exit
+        // from a local() block instead.
+        let mut statements = Vec::new();
+        for item in with.items.iter() {
+            let (context_expr, optional_vars) = match item {
+                AST::WithItem(ref x) => {
+                    let read = x.read();
+                    (read.context_expr(), read.optional_vars())
+                }
+                _ => {
+                    return Err(PyValueError::new_err(format!(
+                        "AST node of type {} found in with items",
+                        item.name()
+                    )))
+                }
+            };
+            match optional_vars {
+                AOption(ROption::RSome(var)) => {
+                    statements.push(
+                        AST::Assignment(Assignment::new_wrapped(var.clone(), context_expr))
+                            .to_r_ast_node(depth)?,
+                    );
+                    unsafe {
+                        let close = make_lang("call");
+                        let mut close_tail = close.get();
+                        close_tail = append_with_name(close_tail, r!("close"), "name");
+                        close_tail = append(close_tail, var.to_r_ast_node(depth)?);
+                        let _ = close_tail;
+
+                        let on_exit = make_lang("call");
+                        let mut on_exit_tail = on_exit.get();
+                        on_exit_tail = append_with_name(on_exit_tail, r!("on.exit"), "name");
+                        on_exit_tail = append(on_exit_tail, close);
+                        on_exit_tail = append_with_name(on_exit_tail, r!(true), "add");
+                        let _ = on_exit_tail;
+                        statements.push(on_exit);
+                    }
+                }
+                AOption(ROption::RNone) => statements.push(context_expr.to_r_ast_node(depth)?),
+            }
+        }
+        for x in with.body.iter() {
+            statements.push(x.to_r_ast_node(depth)?);
+        }
+        unsafe {
+            let res = make_lang("call");
+            let mut tail = res.get();
+            tail = append_with_name(tail, r!("local"), "name");
+            tail = append(tail, r_block_from_values(statements));
+            let _ = tail;
+            Ok(res)
+        }
+    },
+    items: AVec<AST>,
     body: AVec<AST>,
 );
+define_ast_node!(
+    Comprehension,
+    |comprehension: &Comprehension| vec![
+        comprehension.target.clone(),
+        comprehension.iter.clone()
+    ]
+    .into_iter()
+    .chain(comprehension.ifs.clone().into_iter())
+    .collect(),
+    |comprehension: &Comprehension, py: Python, ast_module: &'a PyModule, depth: usize| {
+        let ifs_ast = comprehension
+            .ifs
+            .iter()
+            .map(|x| x.to_python_ast_node(py, ast_module, depth))
+            .collect::<PyResult<Vec<_>>>()?;
+        ast_module.getattr("comprehension")?.call1((
+            comprehension
+                .target
+                .as_wrapped_assignment_target()
+                .to_python_ast_node(py, ast_module, depth)?,
+            comprehension.iter.to_python_ast_node(py, ast_module, depth)?,
+            PyList::new(py, ifs_ast).as_ref(),
+            0,
+        ))
+    },
+    |comprehension: &Comprehension, depth: usize| {
+        // only the (filtered) iterable: the target is bound by the enclosing
+        // comprehension's function
+        let mut iter = comprehension.iter.to_r_ast_node(depth)?;
+        for cond in comprehension.ifs.iter() {
+            iter = r!(Language::from_values(&[
+                r!(Symbol::from_string("Filter")),
+                r_function(
+                    &vec![comprehension.target.clone()]
+                        .into_iter()
+                        .collect::<AVec<AST>>(),
+                    &LinkedHashMap::new(),
+                    &LinkedHashMap::new(),
+                    cond.to_r_ast_node(depth)?,
+                    depth,
+                )?,
+                iter
+            ]));
+        }
+        Ok(iter)
+    },
+    target: AST,
+    iter: AST,
+    ifs: AVec<AST>,
+);
+define_ast_node!(
+    ListComp,
+    |list_comp: &ListComp| vec![list_comp.elt.clone()]
+        .into_iter()
+        .chain(list_comp.generators.clone().into_iter())
+        .collect(),
+    |list_comp: &ListComp, py: Python, ast_module: &'a PyModule, depth: usize| {
+        ast_module.getattr("ListComp")?.call1((
+            list_comp.elt.to_python_ast_node(py, ast_module, depth)?,
+            python_generators(&list_comp.generators, ast_module, depth)?,
+        ))
+    },
+    |list_comp: &ListComp, depth: usize| {
+        let (target, iter) = r_single_generator(&list_comp.generators, depth)?;
+        Ok(r!(Language::from_values(&[
+            r!(Symbol::from_string("lapply")),
+            iter,
+            r_function(
+                &vec![target].into_iter().collect::<AVec<AST>>(),
+                &LinkedHashMap::new(),
+                &LinkedHashMap::new(),
+                list_comp.elt.to_r_ast_node(depth)?,
+                depth,
+            )?
+        ])))
+    },
+    elt: AST,
+    generators: AVec<AST>,
+);
+define_ast_node!(
+    DictComp,
+    |dict_comp: &DictComp| vec![dict_comp.key.clone(), dict_comp.value.clone()]
+        .into_iter()
+        .chain(dict_comp.generators.clone().into_iter())
+        .collect(),
+    |dict_comp: &DictComp, py: Python, ast_module: &'a PyModule, depth: usize| {
+        ast_module.getattr("DictComp")?.call1((
+            dict_comp.key.to_python_ast_node(py, ast_module, depth)?,
+            dict_comp.value.to_python_ast_node(py, ast_module, depth)?,
+            python_generators(&dict_comp.generators, ast_module, depth)?,
+        ))
+    },
+    |dict_comp: &DictComp, depth: usize| {
+        let (target, iter) = r_single_generator(&dict_comp.generators, depth)?;
+        let targets: AVec<AST> = vec![target].into_iter().collect();
+        let apply = |fun: &str, body: &AST| -> PyResult<Robj> {
+            Ok(r!(Language::from_values(&[
+                r!(Symbol::from_string(fun)),
+                iter.clone(),
+                r_function(
+                    &targets,
+                    &LinkedHashMap::new(),
+                    &LinkedHashMap::new(),
+                    body.to_r_ast_node(depth)?,
+                    depth,
+                )?
+            ])))
+        };
+        Ok(r!(Language::from_values(&[
+            r!(Symbol::from_string("setNames")),
+            apply("lapply", &dict_comp.value)?,
+            apply("sapply", &dict_comp.key)?
+        ])))
+    },
+    key: AST,
+    value: AST,
+    generators: AVec<AST>,
+);
+define_ast_node!(
+    Compare,
+    |compare: &Compare| vec![compare.left.clone()]
+        .into_iter()
+        .chain(compare.ops.clone().into_iter())
+        .chain(compare.comparators.clone().into_iter())
+        .collect(),
+    |compare: &Compare, py: Python, ast_module: &'a PyModule, depth: usize| {
+        let ops_ast = compare
+            .ops
+            .iter()
+            .map(|x| x.to_python_ast_node(py, ast_module, depth))
+            .collect::<PyResult<Vec<_>>>()?;
+        let comparators_ast = compare
+            .comparators
+            .iter()
+            .map(|x| x.to_python_ast_node(py, ast_module, depth))
+            .collect::<PyResult<Vec<_>>>()?;
+        ast_module.getattr("Compare")?.call1((
+            compare.left.to_python_ast_node(py, ast_module, depth)?,
+            PyList::new(py, ops_ast).as_ref(),
+            PyList::new(py, comparators_ast).as_ref(),
+        ))
+    },
+    |compare: &Compare, depth: usize| {
+        // chained comparisons (a < b < c) become (a < b) && (b < c)
+        let mut left = compare.left.to_r_ast_node(depth)?;
+        let mut res: Option<Robj> = Option::None;
+        for (op, comparator) in compare.ops.iter().zip(compare.comparators.iter()) {
+            let right = comparator.to_r_ast_node(depth)?;
+            let comparison = r_comparison(op, left, right.clone())?;
+            res = Some(match res {
+                Some(prev) => r!(Language::from_values(&[
+                    r!(Symbol::from_string("&&")),
+                    prev,
+                    comparison
+                ])),
+                Option::None => comparison,
+            });
+            left = right;
+        }
+        res.ok_or_else(|| PyValueError::new_err("Compare node without comparators"))
+    },
+    left: AST,
+    ops: AVec<AST>,
+    comparators: AVec<AST>,
+);
+define_ast_node!(
+    BoolOp,
+    |node: &BoolOp| vec![node.op.clone()]
+        .into_iter()
+        .chain(node.values.clone().into_iter())
+        .collect(),
+    |node: &BoolOp, py: Python, ast_module: &'a PyModule, depth: usize| {
+        let values_ast = node
+            .values
+            .iter()
+            .map(|x| x.to_python_ast_node(py, ast_module, depth))
+            .collect::<PyResult<Vec<_>>>()?;
+        ast_module.getattr("BoolOp")?.call1((
+            node.op.to_python_ast_node(py, ast_module, depth)?,
+            PyList::new(py, values_ast).as_ref(),
+        ))
+    },
+    |node: &BoolOp, depth: usize| {
+        let op_str = node.op.get_r_operator()?;
+        node.values
+            .iter()
+            .map(|x| x.to_r_ast_node(depth))
+            .collect::<PyResult<Vec<_>>>()?
+            .into_iter()
+            .reduce(|left, right| {
+                r!(Language::from_values(&[
+                    r!(Symbol::from_string(op_str)),
+                    left,
+                    right
+                ]))
+            })
+            .ok_or_else(|| PyValueError::new_err("BoolOp node without values"))
+    },
+    op: AST,
+    values: AVec<AST>,
+);
+define_ast_node!(
+    UnaryOp,
+    |node: &UnaryOp| vec![node.op.clone(), node.operand.clone()]
+        .into_iter()
+        .collect(),
+    |node: &UnaryOp, py: Python, ast_module: &'a PyModule, depth: usize| {
+        ast_module.getattr("UnaryOp")?.call1((
+            node.op.to_python_ast_node(py, ast_module, depth)?,
+            node.operand.to_python_ast_node(py, ast_module, depth)?,
+        ))
+    },
+    |node: &UnaryOp, depth: usize| {
+        Ok(r!(Language::from_values(&[
+            r!(Symbol::from_string(node.op.get_r_operator()?)),
+            node.operand.to_r_ast_node(depth)?
+        ])))
+    },
+    op: AST,
+    operand: AST,
+);
+
+// A statement built by a SQL recipe's arguments. Statements are rendered
+// for the engine running the recipe (see `AST::render_sql`) before the
+// program's parameters reach a flow.
+#[cfg(feature = "sql")]
+define_ast_node!(
+    SQLStatement,
+    |_node: &SQLStatement| AVec::new(),
+    |_node: &SQLStatement, _py: Python, _ast_module: &'a PyModule, _depth: usize| {
+        Err(PyValueError::new_err(
+            "SQL statements can only be passed to programs running SQL",
+        ))
+    },
+    |_node: &SQLStatement, _depth: usize| {
+        Err(PyValueError::new_err(
+            "Should not call to_r_ast_node on SQLStatement objects directly",
+        ))
+    },
+    statement: sql::SQLNode,
+);
+
+/// Operators are leaf nodes rendered as the Python `ast` class of the same
+/// name. In R they only make sense as part of the enclosing expression (see
+/// AST::get_r_operator).
+macro_rules! define_operator_node {
+    ($name:ident, $py_name:expr) => {
+        define_ast_node!(
+            $name,
+            |_node: &$name| AVec::new(),
+            |_node: &$name, _py: Python, ast_module: &'a PyModule, _depth: usize| {
+                ast_module.getattr($py_name)?.call0()
+            },
+            |_node: &$name, _depth: usize| {
+                Err(PyValueError::new_err(format!(
+                    "Should not call to_r_ast_node on {} objects directly",
+                    stringify!($name)
+                )))
+            },
+        );
+    };
+}
+define_operator_node!(Equals, "Eq");
+define_operator_node!(NotEquals, "NotEq");
+define_operator_node!(Lt, "Lt");
+define_operator_node!(LtE, "LtE");
+define_operator_node!(Gt, "Gt");
+define_operator_node!(GtE, "GtE");
+define_operator_node!(In, "In");
+define_operator_node!(NotIn, "NotIn");
+define_operator_node!(Is, "Is");
+define_operator_node!(IsNot, "IsNot");
+define_operator_node!(And, "And");
+define_operator_node!(Or, "Or");
+define_operator_node!(Not, "Not");
+define_operator_node!(USub, "USub");
+
+fn optional_ast(node: &AOption<AST>) -> AVec<AST> {
+    match node {
+        AOption(ROption::RSome(x)) => vec![x.clone()].into_iter().collect(),
+        AOption(ROption::RNone) => AVec::new(),
+    }
+}
+fn arg_name(arg: &AST) -> PyResult<AString> {
+    match arg {
+        AST::SimpleIdentifier(ref x) => Ok(x.read().name()),
+        _ => Err(PyValueError::new_err(format!(
+            "AST node of type {} not supported as argument",
+            arg.name()
+        ))),
+    }
+}
+fn python_optional<'a>(
+    node: &AOption<AST>,
+    ast_module: &'a PyModule,
+    depth: usize,
+) -> PyResult<&'a PyAny> {
+    let py = ast_module.py();
+    match node {
+        AOption(ROption::RSome(x)) => x.to_python_ast_node(py, ast_module, depth),
+        AOption(ROption::RNone) => Ok(py.None().into_ref(py)),
+    }
+}
+/// Renders the body of a compound statement. Bodies can nest arbitrarily,
+/// but may only contain statements.
+fn python_statements<'a>(
+    body: &AVec<AST>,
+    ast_module: &'a PyModule,
+    depth: usize,
+    context: &str,
+) -> PyResult<Vec<&'a PyAny>> {
+    body.iter()
+        .map(|x| match x.is_statement() {
+            true => x.to_python_ast_node(ast_module.py(), ast_module, depth + 1),
+            false => Err(PyValueError::new_err(format!(
+                "AST node of type {} found in {}",
+                x.name(),
+                context
+            ))),
+        })
+        .collect()
+}
+fn python_generators<'a>(
+    generators: &AVec<AST>,
+    ast_module: &'a PyModule,
+    depth: usize,
+) -> PyResult<&'a PyAny> {
+    let py = ast_module.py();
+    let generators_ast = generators
+        .iter()
+        .map(|x| match &x {
+            AST::Comprehension(_) => x.to_python_ast_node(py, ast_module, depth),
+            _ => Err(PyValueError::new_err(format!(
+                "AST node of type {} found in generators",
+                x.name()
+            ))),
+        })
+        .collect::<PyResult<Vec<_>>>()?;
+    Ok(PyList::new(py, generators_ast).as_ref())
+}
+/// Positional arguments must be SimpleIdentifiers. Arguments with defaults
+/// follow them; keyword-only arguments without a default are required.
+fn python_arguments<'a>(
+    ast_module: &'a PyModule,
+    depth: usize,
+    args: &AVec<AST>,
+    kwargs: &LinkedHashMap<AString, AST>,
+    kwonlyargs: &LinkedHashMap<AString, AOption<AST>>,
+) -> PyResult<&'a PyAny> {
+    let py = ast_module.py();
+    let none = py.None().into_ref(py);
+    let make_arg =
+        |name: &str| -> PyResult<&'a PyAny> { ast_module.getattr("arg")?.call1((name, none)) };
+    let mut args_py = args
+        .iter()
+        .map(|x| make_arg(arg_name(x)?.as_str()))
+        .collect::<PyResult<Vec<_>>>()?;
+    let mut defaults_py = Vec::new();
+    for (k, v) in kwargs.iter() {
+        args_py.push(make_arg(k.as_str())?);
+        defaults_py.push(v.to_python_ast_node(py, ast_module, depth)?);
+    }
+    let mut kwonlyargs_py = Vec::new();
+    let mut kw_defaults_py = Vec::new();
+    for (k, v) in kwonlyargs.iter() {
+        kwonlyargs_py.push(make_arg(k.as_str())?);
+        kw_defaults_py.push(python_optional(v, ast_module, depth)?);
+    }
+    let empty_vec: Vec<String> = Vec::new();
+    let arguments = vec![
+        ("posonlyargs", PyList::new(py, empty_vec).as_ref()),
+        ("args", PyList::new(py, args_py).as_ref()),
+        ("vararg", none),
+        ("kwonlyargs", PyList::new(py, kwonlyargs_py).as_ref()),
+        ("kw_defaults", PyList::new(py, kw_defaults_py).as_ref()),
+        ("kwarg", none),
+        ("defaults", PyList::new(py, defaults_py).as_ref()),
+    ];
+    ast_module
+        .getattr("arguments")?
+        .call((), Some(arguments.into_py_dict(py)))
+}
+fn r_block_from_values(statements: Vec<Robj>) -> Robj {
+    unsafe {
+        let res = make_lang("call");
+        let mut tail = res.get();
+        tail = append_with_name(tail, r!("{"), "name");
+        for x in statements.into_iter() {
+            tail = append(tail, x);
+        }
+        let _ = tail;
+        res
+    }
+}
+fn r_block(body: &AVec<AST>, depth: usize) -> PyResult<Robj> {
+    Ok(r_block_from_values(
+        body.iter()
+            .map(|x| x.to_r_ast_node(depth))
+            .collect::<PyResult<_>>()?,
+    ))
+}
+/// Bare `raise` statements in an except handler re-signal the handled
+/// condition, which R handlers get as their argument. Nested handlers and
+/// functions are left alone.
+fn r_reraise(body: &AVec<AST>, name: &AString) -> AVec<AST> {
+    fn rewrite(ast: &AST, name: &AString) -> AST {
+        match ast {
+            AST::Raise(ref x) if x.read().exc().is_none() => AST::Raise(Raise::new_wrapped(
+                AOption(ROption::RSome(AST::SimpleIdentifier(
+                    SimpleIdentifier::new_wrapped(name.clone()),
+                ))),
+                AOption(ROption::RNone),
+            )),
+            AST::ExceptHandler(_) | AST::FunctionDef(_) | AST::Lambda(_) => ast.clone(),
+            _ => ast.rewrite_children(&mut |x: &AST| rewrite(x, name)),
+        }
+    }
+    body.iter().map(|x| rewrite(x, name)).collect()
+}
+/// R rendering of a single comparison. Identity and negated membership have
+/// no R operator.
+fn r_comparison(op: &AST, left: Robj, right: Robj) -> PyResult<Robj> {
+    let call = |function: &str, args: Vec<Robj>| {
+        r!(Language::from_values(
+            &vec![r!(Symbol::from_string(function))]
+                .into_iter()
+                .chain(args.into_iter())
+                .collect::<Vec<Robj>>()
+        ))
+    };
+    Ok(match op {
+        AST::Is(_) => call("identical", vec![left, right]),
+        AST::IsNot(_) => call("!", vec![call("identical", vec![left, right])]),
+        AST::NotIn(_) => call("!", vec![call("(", vec![call("%in%", vec![left, right])])]),
+        _ => call(op.get_r_operator()?, vec![left, right]),
+    })
+}
+/// R has no keyword-only arguments, so these simply follow the other formals.
+/// Each formal is built separately (alist() for required ones, list() for the
+/// ones with defaults) so that their order is preserved.
+fn r_function(
+    args: &AVec<AST>,
+    kwargs: &LinkedHashMap<AString, AST>,
+    kwonlyargs: &LinkedHashMap<AString, AOption<AST>>,
+    body: Robj,
+    depth: usize,
+) -> PyResult<Robj> {
+    let formal = |name: &str, default: AOption<&AST>| -> PyResult<Robj> {
+        let value = match default {
+            AOption(ROption::RSome(x)) => x.to_r_ast_node(depth)?,
+            AOption(ROption::RNone) => r!(missing_arg()),
+        };
+        unsafe {
+            let res = match default {
+                AOption(ROption::RSome(_)) => make_lang("list"),
+                AOption(ROption::RNone) => make_lang("alist"),
+            };
+            append_with_name(res.get(), value, name);
+            Ok(res)
+        }
+    };
+    unsafe {
+        let formals = make_lang("c");
+        let mut formals_tail = formals.get();
+        for arg in args.iter() {
+            formals_tail = append(
+                formals_tail,
+                formal(arg_name(arg)?.as_str(), AOption(ROption::RNone))?,
+            );
+        }
+        for (k, v) in kwargs.iter() {
+            formals_tail = append(
+                formals_tail,
+                formal(k.as_str(), AOption(ROption::RSome(v)))?,
+            );
+        }
+        for (k, v) in kwonlyargs.iter() {
+            formals_tail = append(formals_tail, formal(k.as_str(), v.as_ref())?);
+        }
+        let _ = formals_tail;
+
+        let pairlist = make_lang("as.pairlist");
+        append(pairlist.get(), formals);
+
+        let res = make_lang("call");
+        let mut tail = res.get();
+        tail = append_with_name(tail, r!("function"), "name");
+        tail = append(tail, pairlist);
+        tail = append(tail, body);
+        let _ = tail;
+        Ok(res)
+    }
+}
+/// R comprehensions are rendered as an apply over a single (filtered) iterable.
+fn r_single_generator(generators: &AVec<AST>, depth: usize) -> PyResult<(AST, Robj)> {
+    match generators.len() {
+        1 => match generators[0] {
+            AST::Comprehension(ref x) => {
+                let read = x.read();
+                Ok((read.target(), read.to_r_ast_node(depth)?))
+            }
+            _ => Err(PyValueError::new_err(format!(
+                "AST node of type {} found in generators",
+                generators[0].name()
+            ))),
+        },
+        _ => Err(PyValueError::new_err(
+            "Only comprehensions with a single generator are supported in R",
+        )),
+    }
+}
 
 register_ast_nodes!(
     AST,
@@ -648,6 +1613,37 @@ register_ast_nodes!(
     BinOp,
     FunctionDef,
     FloatLiteral,
+    If,
+    While,
+    Try,
+    ExceptHandler,
+    With,
+    WithItem,
+    Return,
+    Raise,
+    Lambda,
+    Comprehension,
+    ListComp,
+    DictComp,
+    Compare,
+    BoolOp,
+    UnaryOp,
+    Equals,
+    NotEquals,
+    Lt,
+    LtE,
+    Gt,
+    GtE,
+    In,
+    NotIn,
+    Is,
+    IsNot,
+    And,
+    Or,
+    Not,
+    USub,
+    #[cfg(feature = "sql")]
+    SQLStatement,
 );
 
 impl Formatted {
@@ -687,6 +1683,73 @@ impl AST {
         let source = astor.getattr("to_source")?.call1((module,));
         source.and_then(|x| Ok(x.to_string()))
     }
+    /// Whether the node can appear in the body of a compound statement.
+    pub fn is_statement(&self) -> bool {
+        matches!(
+            self,
+            AST::Assignment(_)
+                | AST::Expression(_)
+                | AST::ForLoop(_)
+                | AST::If(_)
+                | AST::While(_)
+                | AST::Try(_)
+                | AST::With(_)
+                | AST::Return(_)
+                | AST::Raise(_)
+                | AST::FunctionDef(_)
+        )
+    }
+    pub fn get_r_operator(&self) -> PyResult<&'static str> {
+        Ok(match self {
+            AST::Add(_) => "+",
+            AST::Equals(_) => "==",
+            AST::NotEquals(_) => "!=",
+            AST::Lt(_) => "<",
+            AST::LtE(_) => "<=",
+            AST::Gt(_) => ">",
+            AST::GtE(_) => ">=",
+            AST::In(_) => "%in%",
+            AST::And(_) => "&&",
+            AST::Or(_) => "||",
+            AST::Not(_) => "!",
+            AST::USub(_) => "-",
+            // Is, IsNot and NotIn are function calls in R (see r_comparison)
+            _ => {
+                return Err(PyValueError::new_err(format!(
+                    "AST node of type {} not supported as R operator",
+                    self.name()
+                )))
+            }
+        })
+    }
+    /// Renders the SQL statements among a program's parameters (on their
+    /// own, or in lists of queries) for the engine running the program.
+    pub fn render_sql(&self, dialect: &Dialect) -> PyResult<AST> {
+        match self {
+            #[cfg(feature = "sql")]
+            AST::SQLStatement(ref x) => match sql::SQLDialect::for_program(dialect) {
+                Some(engine) => {
+                    use sql::ToSQL;
+                    Ok(x.read().statement().to_string_literal(engine))
+                }
+                None => Err(PyValueError::new_err(format!(
+                    "SQL statements can only be passed to programs running SQL, not {:?}",
+                    dialect
+                ))),
+            },
+            AST::List(ref x) => {
+                let read = x.read();
+                Ok(AST::List(List::new_wrapped(
+                    read.elems()
+                        .iter()
+                        .map(|e| e.render_sql(dialect))
+                        .collect::<PyResult<AVec<AST>>>()?,
+                    read.store(),
+                )))
+            }
+            _ => Ok(self.clone()),
+        }
+    }
     pub fn optimize(&self) -> AOption<AST> {
         match self {
             AST::Formatted(ref rw) => {
@@ -706,90 +1769,218 @@ mod r_ast_tests {
     #[test]
     fn test_string_literal() {
         test! {
-            let s = StringLiteral::new_wrapped("test".to_string(), false);
-            assert_eq!(s.read().to_r_ast_node(0), r!("test"));
+            let s = StringLiteral::new_wrapped("test".into(), false);
+            assert_eq!(s.read().to_r_ast_node(0).unwrap(), r!("test"));
         }
     }
     #[test]
     fn test_simple_identifier() {
         test! {
-            let s = SimpleIdentifier::new_wrapped("test".to_string());
-            assert_eq!(s.read().to_r_ast_node(0), sym!(test));
+            let s = SimpleIdentifier::new_wrapped("test".into());
+            assert_eq!(s.read().to_r_ast_node(0).unwrap(), sym!(test));
         }
     }
     #[test]
     fn test_assignment() {
         test! {
-            let sym = AST::SimpleIdentifier(SimpleIdentifier::new_wrapped("a".to_string()));
-            let val = AST::StringLiteral(StringLiteral::new_wrapped("b".to_string(), false));
+            let sym = AST::SimpleIdentifier(SimpleIdentifier::new_wrapped("a".into()));
+            let val = AST::StringLiteral(StringLiteral::new_wrapped("b".into(), false));
             let assign = AST::Assignment(Assignment::new_wrapped(sym, val));
-            let r_node = assign.to_r_ast_node(0);
+            let r_node = assign.to_r_ast_node(0).unwrap();
             assert_eq!(r_node, eval_string("call('<-', rlang::sym('a'), 'b')").unwrap());
         }
     }
     #[test]
     fn test_import() {
         test! {
-            let sym = AST::SimpleIdentifier(SimpleIdentifier::new_wrapped("ggplot".to_string()));
+            let sym = AST::SimpleIdentifier(SimpleIdentifier::new_wrapped("ggplot".into()));
             let import = AST::ImportNode(ImportNode::new_wrapped(sym));
-            let r_node = import.to_r_ast_node(0);
+            let r_node = import.to_r_ast_node(0).unwrap();
             assert_eq!(r_node, eval_string("call('library', rlang::sym('ggplot'))").unwrap());
         }
     }
     #[test]
     fn test_for_loop() {
         test! {
-            let it = AST::SimpleIdentifier(SimpleIdentifier::new_wrapped("i".to_string()));
-            let vec = AST::SimpleIdentifier(SimpleIdentifier::new_wrapped("vec".to_string()));
+            let it = AST::SimpleIdentifier(SimpleIdentifier::new_wrapped("i".into()));
+            let vec = AST::SimpleIdentifier(SimpleIdentifier::new_wrapped("vec".into()));
 
-            let sym = AST::SimpleIdentifier(SimpleIdentifier::new_wrapped("a".to_string()));
+            let sym = AST::SimpleIdentifier(SimpleIdentifier::new_wrapped("a".into()));
             let assign = AST::Assignment(Assignment::new_wrapped(sym, it.clone()));
-            let for_loop = AST::ForLoop(ForLoop::new_wrapped(it, vec, vec![assign]));
-            let r_node = for_loop.to_r_ast_node(0);
+            let for_loop = AST::ForLoop(ForLoop::new_wrapped(it, vec, vec![assign].into_iter().collect()));
+            let r_node = for_loop.to_r_ast_node(0).unwrap();
             assert_eq!(r_node, eval_string(
                 "call('for', rlang::sym('i'), rlang::sym('vec'), call('{', list(call('<-', rlang::sym('a'), rlang::sym('i')))))"
             ).unwrap());
         }
     }
     #[test]
+    fn test_while() {
+        test! {
+            let test = AST::BooleanLiteral(BooleanLiteral::new_wrapped(true));
+            let sym = AST::SimpleIdentifier(SimpleIdentifier::new_wrapped("a".into()));
+            let val = AST::StringLiteral(StringLiteral::new_wrapped("b".into(), false));
+            let assign = AST::Assignment(Assignment::new_wrapped(sym, val));
+            let while_loop = AST::While(While::new_wrapped(test, vec![assign].into_iter().collect()));
+            let r_node = while_loop.to_r_ast_node(0).unwrap();
+            assert_eq!(r_node, eval_string(
+                "call('while', TRUE, call('{', list(call('<-', rlang::sym('a'), 'b'))))"
+            ).unwrap());
+        }
+    }
+    #[test]
+    fn test_return() {
+        test! {
+            let sym = AST::SimpleIdentifier(SimpleIdentifier::new_wrapped("a".into()));
+            let ret = AST::Return(Return::new_wrapped(AOption(ROption::RSome(sym))));
+            let r_node = ret.to_r_ast_node(0).unwrap();
+            assert_eq!(r_node, eval_string("call('return', rlang::sym('a'))").unwrap());
+        }
+    }
+    #[test]
+    fn test_compare() {
+        test! {
+            let sym_a = AST::SimpleIdentifier(SimpleIdentifier::new_wrapped("a".into()));
+            let sym_b = AST::SimpleIdentifier(SimpleIdentifier::new_wrapped("b".into()));
+            let op = AST::Lt(Lt::new_wrapped());
+            let compare = AST::Compare(Compare::new_wrapped(
+                sym_a,
+                vec![op].into_iter().collect(),
+                vec![sym_b].into_iter().collect(),
+            ));
+            let r_node = compare.to_r_ast_node(0).unwrap();
+            assert_eq!(r_node, eval_string("call('<', rlang::sym('a'), rlang::sym('b'))").unwrap());
+        }
+    }
+    #[test]
+    fn test_compare_identity_and_membership() {
+        test! {
+            let sym_a = AST::SimpleIdentifier(SimpleIdentifier::new_wrapped("a".into()));
+            let sym_b = AST::SimpleIdentifier(SimpleIdentifier::new_wrapped("b".into()));
+            let compare = |op: AST| AST::Compare(Compare::new_wrapped(
+                sym_a.clone(),
+                vec![op].into_iter().collect(),
+                vec![sym_b.clone()].into_iter().collect(),
+            )).to_r_ast_node(0).unwrap();
+            assert_eq!(
+                compare(AST::Is(Is::new_wrapped())),
+                eval_string("quote(identical(a, b))").unwrap()
+            );
+            assert_eq!(
+                compare(AST::IsNot(IsNot::new_wrapped())),
+                eval_string("quote(!identical(a, b))").unwrap()
+            );
+            assert_eq!(
+                compare(AST::NotIn(NotIn::new_wrapped())),
+                eval_string("quote(!(a %in% b))").unwrap()
+            );
+        }
+    }
+    #[test]
+    fn test_try_else() {
+        test! {
+            let call = |name: &str| AST::Expression(crate::Expression::new_wrapped(
+                AST::Call(Call::new_wrapped(
+                    AST::SimpleIdentifier(SimpleIdentifier::new_wrapped(name.into())),
+                    AVec::new(),
+                    LinkedHashMap::new(),
+                )),
+            ));
+            let handler = AST::ExceptHandler(ExceptHandler::new_wrapped(
+                AOption(ROption::RSome(AST::SimpleIdentifier(
+                    SimpleIdentifier::new_wrapped("ValueError".into()),
+                ))),
+                AOption(ROption::RNone),
+                vec![
+                    call("g"),
+                    AST::Raise(Raise::new_wrapped(AOption(ROption::RNone), AOption(ROption::RNone))),
+                ].into_iter().collect(),
+            ));
+            let try_node = AST::Try(Try::new_wrapped(
+                vec![call("f")].into_iter().collect(),
+                vec![handler].into_iter().collect(),
+                AOption(ROption::RSome(vec![call("h")].into_iter().collect())),
+                AOption(ROption::RNone),
+            ));
+            assert_eq!(
+                try_node.to_r_ast_node(0).unwrap(),
+                eval_string(
+                    "quote(if (tryCatch({f(); TRUE}, ValueError = function(e) {g(); stop(e); FALSE})) {h()})"
+                ).unwrap()
+            );
+        }
+    }
+    #[test]
+    fn test_r_condition_classes() {
+        let name = |x: &str| AST::SimpleIdentifier(SimpleIdentifier::new_wrapped(x.into()));
+        let classes = |exception_type: AOption<AST>| {
+            ExceptHandler::new(exception_type, AOption(ROption::RNone), AVec::new())
+                .get_r_condition_classes()
+                .unwrap()
+                .iter()
+                .map(|x| x.as_str().to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(classes(AOption(ROption::RNone)), vec!["error"]);
+        assert_eq!(
+            classes(AOption(ROption::RSome(AST::Tuple(Tuple::new_wrapped(
+                vec![name("Exception"), name("UserWarning"), name("DeprecationWarning"), name("KeyError")]
+                    .into_iter()
+                    .collect(),
+                false,
+            ))))),
+            vec!["error", "warning", "KeyError"]
+        );
+    }
+    #[test]
+    fn test_unary_op() {
+        test! {
+            let sym = AST::SimpleIdentifier(SimpleIdentifier::new_wrapped("a".into()));
+            let op = AST::Not(Not::new_wrapped());
+            let unary_op = AST::UnaryOp(UnaryOp::new_wrapped(op, sym));
+            let r_node = unary_op.to_r_ast_node(0).unwrap();
+            assert_eq!(r_node, eval_string("call('!', rlang::sym('a'))").unwrap());
+        }
+    }
+    #[test]
     fn test_expression() {
         test! {
-            let sym = AST::SimpleIdentifier(SimpleIdentifier::new_wrapped("ggplot".to_string()));
-            let expr = AST::Expression(crate::python::Expression::new_wrapped(sym));
-            assert_eq!(expr.to_r_ast_node(0), sym!(ggplot));
+            let sym = AST::SimpleIdentifier(SimpleIdentifier::new_wrapped("ggplot".into()));
+            let expr = AST::Expression(crate::Expression::new_wrapped(sym));
+            assert_eq!(expr.to_r_ast_node(0).unwrap(), sym!(ggplot));
         }
     }
     #[test]
     fn test_binop() {
         test! {
-            let sym_a = AST::SimpleIdentifier(SimpleIdentifier::new_wrapped("a".to_string()));
-            let sym_b = AST::SimpleIdentifier(SimpleIdentifier::new_wrapped("b".to_string()));
+            let sym_a = AST::SimpleIdentifier(SimpleIdentifier::new_wrapped("a".into()));
+            let sym_b = AST::SimpleIdentifier(SimpleIdentifier::new_wrapped("b".into()));
             let op = AST::Add(Add::new_wrapped());
             let binop = AST::BinOp(BinOp::new_wrapped(sym_a, op, sym_b));
-            let r_node = binop.to_r_ast_node(0);
+            let r_node = binop.to_r_ast_node(0).unwrap();
             assert_eq!(r_node, eval_string("call('+', rlang::sym('a'), rlang::sym('b'))").unwrap());
         }
     }
     #[test]
     fn test_list() {
         test! {
-            let sym_a = AST::SimpleIdentifier(SimpleIdentifier::new_wrapped("a".to_string()));
-            let sym_b = AST::SimpleIdentifier(SimpleIdentifier::new_wrapped("b".to_string()));
-            let list = AST::List(crate::python::List::new_wrapped(vec![sym_a, sym_b].into_iter().collect(), false));
-            let r_node = list.to_r_ast_node(0);
+            let sym_a = AST::SimpleIdentifier(SimpleIdentifier::new_wrapped("a".into()));
+            let sym_b = AST::SimpleIdentifier(SimpleIdentifier::new_wrapped("b".into()));
+            let list = AST::List(crate::List::new_wrapped(vec![sym_a, sym_b].into_iter().collect(), false));
+            let r_node = list.to_r_ast_node(0).unwrap();
             assert_eq!(r_node, eval_string("call('list', rlang::sym('a'), rlang::sym('b'))").unwrap());
         }
     }
     #[test]
     fn test_dict() {
         test! {
-            let sym_a = AST::SimpleIdentifier(SimpleIdentifier::new_wrapped("a".to_string()));
-            let sym_b = AST::SimpleIdentifier(SimpleIdentifier::new_wrapped("b".to_string()));
+            let sym_a = AST::SimpleIdentifier(SimpleIdentifier::new_wrapped("a".into()));
+            let sym_b = AST::SimpleIdentifier(SimpleIdentifier::new_wrapped("b".into()));
             let mut map = linked_hash_map::LinkedHashMap::new();
-            map.insert("x".to_string(), sym_a);
-            map.insert("y".to_string(), sym_b);
-            let dict = AST::Dict(crate::python::Dict::new_wrapped(map));
-            let r_node = dict.to_r_ast_node(0);
+            map.insert("x".into(), sym_a);
+            map.insert("y".into(), sym_b);
+            let dict = AST::Dict(crate::Dict::new_wrapped(map));
+            let r_node = dict.to_r_ast_node(0).unwrap();
             assert_eq!(r_node, eval_string("list(x=rlang::sym('a'), y=rlang::sym('b'))").unwrap());
             // N.B.: this also evaluates as correct -- names don't seem to matter
             assert_eq!(r_node, eval_string("list(z=rlang::sym('a'), y=rlang::sym('b'))").unwrap());
@@ -798,38 +1989,38 @@ mod r_ast_tests {
     #[test]
     fn test_call() {
         test! {
-            let sym_fun = AST::StringLiteral(StringLiteral::new_wrapped("fun".to_string(), false));
-            let sym_a = AST::SimpleIdentifier(SimpleIdentifier::new_wrapped("a".to_string()));
-            let sym_b = AST::SimpleIdentifier(SimpleIdentifier::new_wrapped("b".to_string()));
+            let sym_fun = AST::StringLiteral(StringLiteral::new_wrapped("fun".into(), false));
+            let sym_a = AST::SimpleIdentifier(SimpleIdentifier::new_wrapped("a".into()));
+            let sym_b = AST::SimpleIdentifier(SimpleIdentifier::new_wrapped("b".into()));
             let mut map = linked_hash_map::LinkedHashMap::new();
-            map.insert("x".to_string(), sym_a);
-            map.insert("y".to_string(), sym_b);
-            let dict = AST::Call(crate::python::Call::new_wrapped(sym_fun, AVec::new(), map));
-            let r_node = dict.to_r_ast_node(0);
+            map.insert("x".into(), sym_a);
+            map.insert("y".into(), sym_b);
+            let dict = AST::Call(crate::Call::new_wrapped(sym_fun, AVec::new(), map));
+            let r_node = dict.to_r_ast_node(0).unwrap();
             assert_eq!(r_node, eval_string("call('call', name='fun', x=rlang::sym('a'), y=rlang::sym('b'))").unwrap());
         }
     }
     #[test]
     fn test_fmt() {
         test! {
-            let fmt = AST::StringLiteral(StringLiteral::new_wrapped("{x} {y}".to_string(), false));
-            let sym_a = AST::SimpleIdentifier(SimpleIdentifier::new_wrapped("a".to_string()));
-            let sym_b = AST::SimpleIdentifier(SimpleIdentifier::new_wrapped("b".to_string()));
+            let fmt = AST::StringLiteral(StringLiteral::new_wrapped("{x} {y}".into(), false));
+            let sym_a = AST::SimpleIdentifier(SimpleIdentifier::new_wrapped("a".into()));
+            let sym_b = AST::SimpleIdentifier(SimpleIdentifier::new_wrapped("b".into()));
             let mut map = linked_hash_map::LinkedHashMap::new();
-            map.insert("x".to_string(), sym_a);
-            map.insert("y".to_string(), sym_b);
-            let dict = AST::Formatted(crate::python::Formatted::new_wrapped(fmt, map));
-            let r_node = dict.to_r_ast_node(0);
+            map.insert("x".into(), sym_a);
+            map.insert("y".into(), sym_b);
+            let dict = AST::Formatted(crate::Formatted::new_wrapped(fmt, map));
+            let r_node = dict.to_r_ast_node(0).unwrap();
             assert_eq!(r_node, eval_string("call('call', name='glue', fmt='{x} {y}', x=rlang::sym('a'), y=rlang::sym('b'))").unwrap());
         }
     }
     #[test]
     fn test_subscript() {
         test! {
-            let sym_a = AST::SimpleIdentifier(SimpleIdentifier::new_wrapped("a".to_string()));
-            let sym_b = AST::SimpleIdentifier(SimpleIdentifier::new_wrapped("b".to_string()));
-            let subscript = AST::Subscript(crate::python::Subscript::new_wrapped(sym_a, sym_b, false));
-            let r_node = subscript.to_r_ast_node(0);
+            let sym_a = AST::SimpleIdentifier(SimpleIdentifier::new_wrapped("a".into()));
+            let sym_b = AST::SimpleIdentifier(SimpleIdentifier::new_wrapped("b".into()));
+            let subscript = AST::Subscript(crate::Subscript::new_wrapped(sym_a, sym_b, false));
+            let r_node = subscript.to_r_ast_node(0).unwrap();
             assert_eq!(r_node, eval_string("quote(a[[b]])").unwrap());
 
         }
@@ -839,7 +2030,7 @@ mod r_ast_tests {
     fn test_boolean_literal() {
         test! {
             let sym = AST::BooleanLiteral(BooleanLiteral::new_wrapped(true));
-            let r_node = sym.to_r_ast_node(0);
+            let r_node = sym.to_r_ast_node(0).unwrap();
             assert_eq!(r_node, eval_string("quote(TRUE)").unwrap());
         }
     }
@@ -848,7 +2039,7 @@ mod r_ast_tests {
     fn test_bigint_literal() {
         test! {
             let sym = AST::BigIntLiteral(BigIntLiteral::new_wrapped(1));
-            let r_node = sym.to_r_ast_node(0);
+            let r_node = sym.to_r_ast_node(0).unwrap();
             assert_eq!(r_node, eval_string("as.integer(1)").unwrap());
         }
     }
@@ -857,8 +2048,36 @@ mod r_ast_tests {
     fn test_none() {
         test! {
             let sym = AST::None(None::new_wrapped());
-            let r_node = sym.to_r_ast_node(0);
+            let r_node = sym.to_r_ast_node(0).unwrap();
             assert_eq!(r_node, eval_string("quote(NULL)").unwrap());
         }
     }
+    #[test]
+    fn test_unsupported_nodes_raise() {
+        test! {
+            let name = |x: &str| AST::SimpleIdentifier(SimpleIdentifier::new_wrapped(x.into()));
+            let generator = || {
+                AST::Comprehension(Comprehension::new_wrapped(
+                    name("x"),
+                    name("xs"),
+                    AVec::new(),
+                ))
+            };
+            let list_comp = AST::ListComp(ListComp::new_wrapped(
+                name("x"),
+                vec![generator(), generator()].into_iter().collect(),
+            ));
+            assert!(list_comp.to_r_ast_node(0).is_err());
+            let item = AST::WithItem(WithItem::new_wrapped(
+                name("resource"),
+                AOption(ROption::RNone),
+            ));
+            assert!(item.to_r_ast_node(0).is_err());
+            let with = AST::With(With::new_wrapped(
+                vec![name("resource")].into_iter().collect(),
+                AVec::new(),
+            ));
+            assert!(with.to_r_ast_node(0).is_err());
+        }
+    }
 }
@@ -0,0 +1,702 @@
+//! Optimization passes over generated code. Passes work on blocks of
+//! top-level statements (typically one block per constraint) and do not
+//! depend on the language the statements are eventually rendered to.
+use crate::{Assignment, SimpleIdentifier, StringLiteral, AST};
+use abi_stable::std_types::ROption;
+use aorist_util::{AOption, AString, AVec};
+use linked_hash_map::LinkedHashMap;
+use std::collections::{HashMap, HashSet};
+
+/// Implemented by the types of AST node fields, so that a node's
+/// descendants can be rewritten without knowing the node's type.
+pub trait TRewritable {
+    fn rewrite(&self, f: &mut dyn FnMut(&AST) -> AST) -> Self;
+}
+impl TRewritable for AST {
+    fn rewrite(&self, f: &mut dyn FnMut(&AST) -> AST) -> Self {
+        f(self)
+    }
+}
+impl TRewritable for AVec<AST> {
+    fn rewrite(&self, f: &mut dyn FnMut(&AST) -> AST) -> Self {
+        self.iter().map(f).collect()
+    }
+}
+impl TRewritable for AOption<AST> {
+    fn rewrite(&self, f: &mut dyn FnMut(&AST) -> AST) -> Self {
+        match self {
+            AOption(ROption::RSome(x)) => AOption(ROption::RSome(f(x))),
+            AOption(ROption::RNone) => AOption(ROption::RNone),
+        }
+    }
+}
+impl TRewritable for AOption<AVec<AST>> {
+    fn rewrite(&self, f: &mut dyn FnMut(&AST) -> AST) -> Self {
+        match self {
+            AOption(ROption::RSome(x)) => AOption(ROption::RSome(x.rewrite(f))),
+            AOption(ROption::RNone) => AOption(ROption::RNone),
+        }
+    }
+}
+impl TRewritable for LinkedHashMap<AString, AST> {
+    fn rewrite(&self, f: &mut dyn FnMut(&AST) -> AST) -> Self {
+        self.iter().map(|(k, v)| (k.clone(), f(v))).collect()
+    }
+}
+impl TRewritable for LinkedHashMap<AString, AOption<AST>> {
+    fn rewrite(&self, f: &mut dyn FnMut(&AST) -> AST) -> Self {
+        self.iter()
+            .map(|(k, v)| (k.clone(), v.rewrite(f)))
+            .collect()
+    }
+}
+macro_rules! define_leaf_rewritable {
+    ($($leaf: ty,)+) => {
+        $(
+            impl TRewritable for $leaf {
+                fn rewrite(&self, _f: &mut dyn FnMut(&AST) -> AST) -> Self {
+                    self.clone()
+                }
+            }
+        )+
+    };
+}
+define_leaf_rewritable!(
+    AString,
+    AOption<AString>,
+    bool,
+    i64,
+    aorist_attributes::FloatValue,
+);
+#[cfg(feature = "sql")]
+define_leaf_rewritable!(crate::sql::SQLNode,);
+
+impl AST {
+    /// Rewrites the tree bottom-up: `f` is called on every node, after its
+    /// descendants have been rewritten.
+    pub fn transform(&self, f: &mut dyn FnMut(AST) -> AST) -> AST {
+        let node = self.rewrite_children(&mut |child: &AST| child.transform(f));
+        f(node)
+    }
+    /// Deep copy of the tree without ancestry, so that nodes generated for
+    /// different concepts compare (and hash) equal if they render the same.
+    pub fn strip_ancestors(&self) -> AST {
+        self.transform(&mut |x| x.clone_without_ancestors())
+    }
+    /// Literals, and containers made up only of literals.
+    pub fn is_constant(&self) -> bool {
+        match self {
+            AST::StringLiteral(_)
+            | AST::BigIntLiteral(_)
+            | AST::FloatLiteral(_)
+            | AST::BooleanLiteral(_)
+            | AST::None(_) => true,
+            AST::List(ref x) if x.read().store() => false,
+            AST::Tuple(ref x) if x.read().store() => false,
+            AST::List(_) | AST::Tuple(_) | AST::Dict(_) => self
+                .get_direct_descendants()
+                .iter()
+                .all(|x| x.is_constant()),
+            _ => false,
+        }
+    }
+    /// Constants that cannot be mutated: literals, and tuples made up only
+    /// of such constants.
+    pub fn is_immutable_constant(&self) -> bool {
+        match self {
+            AST::Tuple(_) => {
+                self.is_constant()
+                    && self
+                        .get_direct_descendants()
+                        .iter()
+                        .all(|x| x.is_immutable_constant())
+            }
+            AST::List(_) | AST::Dict(_) => false,
+            _ => self.is_constant(),
+        }
+    }
+    /// Whether evaluating the node can be skipped without changing what the
+    /// program does.
+    pub fn is_side_effect_free(&self) -> bool {
+        match self {
+            AST::SimpleIdentifier(_) => true,
+            AST::List(_) | AST::Tuple(_) | AST::Dict(_) | AST::Formatted(_) => self
+                .get_direct_descendants()
+                .iter()
+                .all(|x| x.is_side_effect_free()),
+            _ => self.is_constant(),
+        }
+    }
+    /// Rough length of the rendered node, in characters.
+    fn get_source_length(&self) -> usize {
+        match self {
+            AST::StringLiteral(ref x) => x.read().len() + 2,
+            AST::BigIntLiteral(ref x) => x.read().val().to_string().len(),
+            AST::Dict(ref x) => x
+                .read()
+                .elems()
+                .iter()
+                .map(|(k, v)| k.len() + v.get_source_length() + 6)
+                .sum::<usize>()
+                .max(2),
+            AST::List(_) | AST::Tuple(_) => self
+                .get_direct_descendants()
+                .iter()
+                .map(|x| x.get_source_length() + 2)
+                .sum::<usize>()
+                .max(2),
+            _ => 5,
+        }
+    }
+}
+
+/// A group of top-level statements, e.g. those generated for one
+/// constraint.
+#[derive(Clone, Debug)]
+pub struct StatementBlock {
+    pub name: AString,
+    pub title: AOption<AString>,
+    pub statements: AVec<AST>,
+}
+impl StatementBlock {
+    pub fn new(name: AString, title: AOption<AString>, statements: AVec<AST>) -> Self {
+        Self {
+            name,
+            title,
+            statements,
+        }
+    }
+}
+
+/// A transformation of generated code that does not change what the code
+/// does. Passes may add new blocks, but only ahead of the existing ones,
+/// which they must keep (possibly empty) and in order.
+pub trait TOptimizationPass {
+    fn run(&self, blocks: AVec<StatementBlock>) -> AVec<StatementBlock>;
+}
+
+/// Runs passes one after the other.
+pub fn optimize(
+    blocks: AVec<StatementBlock>,
+    passes: &[&dyn TOptimizationPass],
+) -> AVec<StatementBlock> {
+    passes.iter().fold(blocks, |blocks, pass| pass.run(blocks))
+}
+
+/// Replaces `Formatted` nodes whose format string and keywords are all
+/// string or integer literals with the resulting string literal. Other
+/// literal types are left alone, since Python and R format them differently.
+pub struct ConstantFolding;
+impl ConstantFolding {
+    fn format(fmt: &str, values: &HashMap<String, String>) -> Option<String> {
+        let mut out = String::new();
+        let mut chars = fmt.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    out.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    out.push('}');
+                }
+                '{' => {
+                    let mut name = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(c) if c.is_alphanumeric() || c == '_' => name.push(c),
+                            // format specs, conversions, attribute access...
+                            _ => return None,
+                        }
+                    }
+                    out.push_str(values.get(&name)?);
+                }
+                '}' => return None,
+                c => out.push(c),
+            }
+        }
+        Some(out)
+    }
+    /// Folds a single node (its descendants are not visited).
+    pub fn fold(ast: AST) -> AST {
+        let folded = match ast {
+            AST::Formatted(ref rw) => {
+                let formatted = rw.read();
+                let values = formatted
+                    .keywords()
+                    .iter()
+                    .map(|(k, v)| match v {
+                        AST::StringLiteral(ref x) => Some((
+                            k.as_str().to_string(),
+                            x.read().value().as_str().to_string(),
+                        )),
+                        AST::BigIntLiteral(ref x) => {
+                            Some((k.as_str().to_string(), x.read().val().to_string()))
+                        }
+                        _ => None,
+                    })
+                    .collect::<Option<HashMap<_, _>>>();
+                match (formatted.fmt(), values) {
+                    (AST::StringLiteral(ref fmt), Some(values)) => {
+                        let fmt = fmt.read();
+                        Self::format(fmt.value().as_str(), &values).map(|x| {
+                            AST::StringLiteral(StringLiteral::new_wrapped(
+                                x.as_str().into(),
+                                fmt.is_sql(),
+                            ))
+                        })
+                    }
+                    _ => None,
+                }
+            }
+            _ => None,
+        };
+        folded.unwrap_or(ast)
+    }
+}
+impl TOptimizationPass for ConstantFolding {
+    fn run(&self, blocks: AVec<StatementBlock>) -> AVec<StatementBlock> {
+        blocks
+            .into_iter()
+            .map(|block| StatementBlock {
+                statements: block
+                    .statements
+                    .iter()
+                    .map(|x| x.transform(&mut Self::fold))
+                    .collect(),
+                ..block
+            })
+            .collect()
+    }
+}
+
+/// Removes top-level assignments to names that are never read, when
+/// computing the assigned value has no side effects. Runs until no more
+/// assignments can be removed, since removing one assignment may make
+/// another one unused.
+pub struct DeadAssignmentElimination {
+    /// names read by code outside of the blocks (e.g. preamble functions)
+    external_read_names: HashSet<AString>,
+}
+impl DeadAssignmentElimination {
+    pub fn new(external_read_names: HashSet<AString>) -> Self {
+        Self {
+            external_read_names,
+        }
+    }
+    fn get_assigned_name(statement: &AST) -> AOption<AString> {
+        if let AST::Assignment(ref rw) = statement {
+            let assignment = rw.read();
+            if let AST::SimpleIdentifier(ref target) = assignment.target() {
+                return AOption(ROption::RSome(target.read().name()));
+            }
+        }
+        AOption(ROption::RNone)
+    }
+    fn get_read_names(&self, blocks: &AVec<StatementBlock>) -> HashSet<AString> {
+        let mut names = self.external_read_names.clone();
+        for statement in blocks.iter().flat_map(|x| x.statements.iter()) {
+            let read = match (statement, Self::get_assigned_name(statement)) {
+                (AST::Assignment(ref rw), AOption(ROption::RSome(_))) => rw.read().call(),
+                _ => statement.clone(),
+            };
+            for node in read.get_descendants() {
+                if let AST::SimpleIdentifier(ref x) = node {
+                    names.insert(x.read().name());
+                }
+            }
+        }
+        names
+    }
+    fn is_dead(statement: &AST, read_names: &HashSet<AString>) -> bool {
+        match (statement, Self::get_assigned_name(statement)) {
+            (AST::Assignment(ref rw), AOption(ROption::RSome(name))) => {
+                !read_names.contains(&name) && rw.read().call().is_side_effect_free()
+            }
+            _ => false,
+        }
+    }
+}
+impl TOptimizationPass for DeadAssignmentElimination {
+    fn run(&self, mut blocks: AVec<StatementBlock>) -> AVec<StatementBlock> {
+        loop {
+            let read_names = self.get_read_names(&blocks);
+            let mut removed = false;
+            for block in blocks.iter_mut() {
+                let num_statements = block.statements.len();
+                block.statements = block
+                    .statements
+                    .iter()
+                    .filter(|x| !Self::is_dead(x, &read_names))
+                    .cloned()
+                    .collect();
+                removed |= block.statements.len() < num_statements;
+            }
+            if !removed {
+                return blocks;
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+struct Occurrences {
+    count: usize,
+    /// dict keys / keyword names the expression was found under
+    keys: HashSet<AString>,
+    /// whether the expression was also found outside of dicts / keywords
+    unkeyed: bool,
+    blocks: HashSet<AString>,
+}
+
+/// Hoists immutable constant sub-expressions (see
+/// `AST::is_immutable_constant`) that occur more than once into module-level
+/// constants, assigned in a new block placed ahead of all others. Larger
+/// expressions are hoisted first, and only if the constant's name is shorter
+/// than the expression itself.
+///
+/// Constants are named after the dict key or keyword they are passed as,
+/// suffixed by the name of the block if they only occur in one block (e.g.
+/// `URL__DOWNLOAD_DATA`). Lists and dicts are never hoisted, since
+/// generated code may mutate them, and would then mutate every use of the
+/// shared constant.
+pub struct SubexpressionHoisting {
+    block_name: AString,
+    block_title: AOption<AString>,
+}
+impl SubexpressionHoisting {
+    pub fn new(block_name: AString, block_title: AOption<AString>) -> Self {
+        Self {
+            block_name,
+            block_title,
+        }
+    }
+    fn count(
+        ast: &AST,
+        key: AOption<AString>,
+        block_name: &AString,
+        occurrences: &mut LinkedHashMap<AST, Occurrences>,
+    ) {
+        if (ast.is_immutable_constant() && !ast.get_direct_descendants().is_empty())
+            || matches!(ast, AST::StringLiteral(_) | AST::BigIntLiteral(_))
+        {
+            let entry = occurrences.entry(ast.clone()).or_default();
+            entry.count += 1;
+            entry.blocks.insert(block_name.clone());
+            match key {
+                AOption(ROption::RSome(ref k)) => {
+                    entry.keys.insert(k.clone());
+                }
+                AOption(ROption::RNone) => entry.unkeyed = true,
+            }
+        }
+        let keyed_children: AVec<(AString, AST)> = match ast {
+            AST::ImportNode(_) => return,
+            AST::Dict(ref x) => x.read().elems().into_iter().collect(),
+            AST::Call(ref x) => x.read().keywords().into_iter().collect(),
+            AST::Formatted(ref x) => x.read().keywords().into_iter().collect(),
+            _ => AVec::new(),
+        };
+        let unkeyed_children = match ast {
+            AST::Dict(_) => AVec::new(),
+            AST::Call(ref x) => vec![x.read().function()]
+                .into_iter()
+                .chain(x.read().args())
+                .collect(),
+            AST::Formatted(ref x) => vec![x.read().fmt()].into_iter().collect(),
+            _ => ast.get_direct_descendants(),
+        };
+        for (k, child) in keyed_children.into_iter() {
+            Self::count(&child, AOption(ROption::RSome(k)), block_name, occurrences);
+        }
+        for child in unkeyed_children.into_iter() {
+            Self::count(&child, AOption(ROption::RNone), block_name, occurrences);
+        }
+    }
+    fn get_name(occurrences: &Occurrences) -> String {
+        let name = match (occurrences.unkeyed, occurrences.keys.len()) {
+            (false, 1) => {
+                let key = occurrences.keys.iter().next().unwrap();
+                match occurrences.blocks.len() {
+                    1 => format!("{}__{}", key, occurrences.blocks.iter().next().unwrap()),
+                    _ => key.as_str().to_string(),
+                }
+            }
+            _ => "constant".to_string(),
+        };
+        let name = name
+            .chars()
+            .map(|c| match c.is_ascii_alphanumeric() {
+                true => c.to_ascii_uppercase(),
+                false => '_',
+            })
+            .collect::<String>();
+        match name.chars().next() {
+            Some(c) if c.is_ascii_digit() => format!("_{}", name),
+            _ => name,
+        }
+    }
+    fn replace(ast: &AST, hoisted: &HashMap<AST, AST>) -> AST {
+        if let Some(identifier) = hoisted.get(ast) {
+            return identifier.clone();
+        }
+        match ast {
+            AST::ImportNode(_) => ast.clone(),
+            _ => ast.rewrite_children(&mut |child: &AST| Self::replace(child, hoisted)),
+        }
+    }
+}
+impl TOptimizationPass for SubexpressionHoisting {
+    fn run(&self, blocks: AVec<StatementBlock>) -> AVec<StatementBlock> {
+        let blocks: AVec<StatementBlock> = blocks
+            .into_iter()
+            .map(|block| StatementBlock {
+                statements: block
+                    .statements
+                    .iter()
+                    .map(|x| x.strip_ancestors())
+                    .collect(),
+                ..block
+            })
+            .collect();
+        let mut occurrences: LinkedHashMap<AST, Occurrences> = LinkedHashMap::new();
+        let mut used_names: HashSet<String> = HashSet::new();
+        for block in blocks.iter() {
+            for statement in block.statements.iter() {
+                Self::count(
+                    statement,
+                    AOption(ROption::RNone),
+                    &block.name,
+                    &mut occurrences,
+                );
+                for node in statement.get_descendants() {
+                    if let AST::SimpleIdentifier(ref x) = node {
+                        used_names.insert(x.read().name().as_str().to_string());
+                    }
+                }
+            }
+        }
+
+        let mut candidates: Vec<AST> = occurrences.keys().cloned().collect();
+        candidates.sort_by_key(|x| std::cmp::Reverse(x.get_source_length()));
+        let mut hoisted: HashMap<AST, AST> = HashMap::new();
+        for candidate in candidates.into_iter() {
+            let count = occurrences[&candidate].count;
+            let base_name = Self::get_name(&occurrences[&candidate]);
+            if count < 2 || base_name.len() >= candidate.get_source_length() {
+                continue;
+            }
+            let mut name = base_name.clone();
+            let mut suffix = 1;
+            while used_names.contains(&name) {
+                suffix += 1;
+                name = format!("{}_{}", base_name, suffix);
+            }
+            // occurrences nested in the hoisted expression are gone too
+            for descendant in candidate.get_descendants().into_iter().skip(1) {
+                if let Some(x) = occurrences.get_mut(&descendant) {
+                    x.count = x.count.saturating_sub(count);
+                }
+            }
+            hoisted.insert(
+                candidate,
+                AST::SimpleIdentifier(SimpleIdentifier::new_wrapped(name.as_str().into())),
+            );
+            used_names.insert(name);
+        }
+        if hoisted.is_empty() {
+            return blocks;
+        }
+
+        // constants are assigned in order of first occurrence
+        let constants = occurrences
+            .keys()
+            .filter_map(|x| {
+                hoisted
+                    .get(x)
+                    .map(|name| AST::Assignment(Assignment::new_wrapped(name.clone(), x.clone())))
+            })
+            .collect();
+        vec![StatementBlock::new(
+            self.block_name.clone(),
+            self.block_title.clone(),
+            constants,
+        )]
+        .into_iter()
+        .chain(blocks.into_iter().map(|block| {
+            StatementBlock {
+                statements: block
+                    .statements
+                    .iter()
+                    .map(|x| Self::replace(x, &hoisted))
+                    .collect(),
+                ..block
+            }
+        }))
+        .collect()
+    }
+}
+
+#[cfg(test)]
+mod optimization_tests {
+    use crate::*;
+    use abi_stable::std_types::ROption;
+    use aorist_util::{AOption, AString, AVec};
+    use linked_hash_map::LinkedHashMap;
+    use std::collections::HashSet;
+
+    fn string(value: &str) -> AST {
+        AST::StringLiteral(StringLiteral::new_wrapped(value.into(), false))
+    }
+    fn identifier(name: &str) -> AST {
+        AST::SimpleIdentifier(SimpleIdentifier::new_wrapped(name.into()))
+    }
+    fn assign(name: &str, value: AST) -> AST {
+        AST::Assignment(Assignment::new_wrapped(identifier(name), value))
+    }
+    fn dict(elems: Vec<(&str, AST)>) -> AST {
+        AST::Dict(Dict::new_wrapped(
+            elems.into_iter().map(|(k, v)| (k.into(), v)).collect(),
+        ))
+    }
+    fn block(name: &str, statements: Vec<AST>) -> StatementBlock {
+        StatementBlock::new(
+            name.into(),
+            AOption(ROption::RNone),
+            statements.into_iter().collect(),
+        )
+    }
+
+    #[test]
+    fn test_constant_folding() {
+        let mut keywords: LinkedHashMap<AString, AST> = LinkedHashMap::new();
+        keywords.insert("table".into(), string("trips"));
+        keywords.insert(
+            "n".into(),
+            AST::BigIntLiteral(BigIntLiteral::new_wrapped(10)),
+        );
+        let formatted = AST::Formatted(Formatted::new_wrapped(
+            string("SELECT * FROM {table} LIMIT {n} -- {{x}}"),
+            keywords.clone(),
+        ));
+        keywords.insert("t".into(), identifier("t"));
+        let not_literal = AST::Formatted(Formatted::new_wrapped(string("{t}"), keywords));
+        let blocks = optimize(
+            vec![block(
+                "query",
+                vec![assign("a", formatted), assign("b", not_literal.clone())],
+            )]
+            .into_iter()
+            .collect(),
+            &[&ConstantFolding],
+        );
+        let statements = &blocks[0].statements;
+        assert_eq!(
+            statements[0],
+            assign("a", string("SELECT * FROM trips LIMIT 10 -- {x}"))
+        );
+        assert_eq!(statements[1], assign("b", not_literal));
+    }
+
+    #[test]
+    fn test_dead_assignment_elimination() {
+        let call = AST::Call(Call::new_wrapped(
+            identifier("run"),
+            vec![identifier("b")].into_iter().collect(),
+            LinkedHashMap::new(),
+        ));
+        let blocks = optimize(
+            vec![
+                block(
+                    "first",
+                    vec![
+                        assign("a", string("unused")),
+                        assign("c", identifier("a")),
+                        assign("b", string("used")),
+                        assign("d", call.clone()),
+                        assign("f", string("read by a preamble")),
+                    ],
+                ),
+                block("second", vec![assign("e", identifier("c"))]),
+            ]
+            .into_iter()
+            .collect(),
+            &[&DeadAssignmentElimination::new(
+                vec!["f".into()].into_iter().collect::<HashSet<AString>>(),
+            )],
+        );
+        assert_eq!(
+            blocks[0].statements,
+            vec![
+                assign("b", string("used")),
+                assign("d", call),
+                assign("f", string("read by a preamble")),
+            ]
+            .into_iter()
+            .collect::<AVec<_>>()
+        );
+        assert!(blocks[1].statements.is_empty());
+    }
+
+    #[test]
+    fn test_subexpression_hoisting() {
+        let url = string("https://example.com/a/very/long/path/to/data.csv");
+        let params = || dict(vec![("url", url.clone()), ("format", string("csv"))]);
+        let blocks = optimize(
+            vec![
+                block(
+                    "download",
+                    vec![assign("a", params()), assign("b", params())],
+                ),
+                block(
+                    "check",
+                    vec![
+                        assign("c", dict(vec![("url", url.clone()), ("n", string("1"))])),
+                        assign("d", dict(vec![("url", url.clone()), ("n", string("2"))])),
+                    ],
+                ),
+            ]
+            .into_iter()
+            .collect(),
+            &[&SubexpressionHoisting::new(
+                "constants".into(),
+                AOption(ROption::RNone),
+            )],
+        );
+        assert_eq!(blocks.len(), 3);
+        assert_eq!(
+            blocks[0].statements,
+            vec![assign("URL", url.clone())]
+                .into_iter()
+                .collect::<AVec<_>>()
+        );
+        // dicts are mutable, so a and b must not share one
+        let hoisted_params = dict(vec![("url", identifier("URL")), ("format", string("csv"))]);
+        assert_eq!(
+            blocks[1].statements,
+            vec![
+                assign("a", hoisted_params.clone()),
+                assign("b", hoisted_params)
+            ]
+            .into_iter()
+            .collect::<AVec<_>>()
+        );
+        assert_eq!(
+            blocks[2].statements,
+            vec![
+                assign(
+                    "c",
+                    dict(vec![("url", identifier("URL")), ("n", string("1"))])
+                ),
+                assign(
+                    "d",
+                    dict(vec![("url", identifier("URL")), ("n", string("2"))])
+                ),
+            ]
+            .into_iter()
+            .collect::<AVec<_>>()
+        );
+    }
+}
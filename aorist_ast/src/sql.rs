@@ -0,0 +1,1026 @@
+//! Structural SQL nodes. Rather than substituting `{key}` placeholders into
+//! query strings, recipes can build queries out of these nodes, and the same
+//! logical query will then be rendered according to each engine's syntax.
+//! Expressions and queries are rendered via `sqlparser`'s own AST.
+//!
+//! The nodes are also exposed to Python, so that the arguments of SQL
+//! recipes can return statements; these are rendered for the recipe's
+//! engine when the program's parameters are computed.
+use crate::{StringLiteral, AST};
+use abi_stable::std_types::ROption;
+use aorist_primitives::Dialect;
+use aorist_util::{AOption, AString, AVec};
+use linked_hash_map::LinkedHashMap;
+use pyo3::exceptions::{PyTypeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::PyTuple;
+pub use sqlparser::ast::{BinaryOperator, DataType};
+use sqlparser::ast::{
+    Expr, Function, FunctionArg, Ident, ObjectName, Query, Select as SelectNode, SelectItem,
+    SetExpr, TableFactor, TableWithJoins, Value,
+};
+use sqlparser::dialect::keywords::ALL_KEYWORDS;
+use sqlparser::dialect::GenericDialect;
+use sqlparser::parser::Parser;
+use sqlparser::tokenizer::Tokenizer;
+
+/// Engines we know how to render SQL for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SQLDialect {
+    /// Trino / Presto
+    Presto,
+    SQLite,
+    Postgres,
+    BigQuery,
+    DuckDB,
+    Spark,
+}
+impl SQLDialect {
+    /// The engine running programs of a given dialect, if it runs SQL.
+    pub fn for_program(dialect: &Dialect) -> Option<Self> {
+        match dialect {
+            Dialect::Presto(_) => Some(SQLDialect::Presto),
+            Dialect::DuckDB(_) => Some(SQLDialect::DuckDB),
+            Dialect::Spark(_) => Some(SQLDialect::Spark),
+            Dialect::Python(_) | Dialect::R(_) | Dialect::Bash(_) => None,
+        }
+    }
+    fn from_name(name: &str) -> PyResult<Self> {
+        match name.to_lowercase().as_str() {
+            "presto" | "trino" => Ok(SQLDialect::Presto),
+            "sqlite" => Ok(SQLDialect::SQLite),
+            "postgres" | "postgresql" => Ok(SQLDialect::Postgres),
+            "bigquery" => Ok(SQLDialect::BigQuery),
+            "duckdb" => Ok(SQLDialect::DuckDB),
+            "spark" => Ok(SQLDialect::Spark),
+            _ => Err(PyValueError::new_err(format!(
+                "Cannot render SQL for unknown engine: {}",
+                name
+            ))),
+        }
+    }
+    fn quote_style(&self) -> char {
+        match self {
+            SQLDialect::BigQuery | SQLDialect::Spark => '`',
+            _ => '"',
+        }
+    }
+    /// Identifiers are only quoted if they could not be parsed otherwise:
+    /// when they are not lowercase, or when they are SQL keywords (e.g.
+    /// `order` or `date`). Keywords are quoted even where an engine does not
+    /// reserve them, as quoted lowercase names resolve the same way. Quote
+    /// characters within quoted identifiers are doubled.
+    fn ident(&self, name: &str) -> Ident {
+        let mut chars = name.chars();
+        let is_plain = match chars.next() {
+            Some(c) => {
+                (c.is_ascii_lowercase() || c == '_')
+                    && chars.all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+            }
+            None => false,
+        } && !ALL_KEYWORDS.contains(&name.to_uppercase().as_str());
+        match is_plain {
+            true => Ident::new(name),
+            false => {
+                let quote = self.quote_style();
+                Ident::with_quote(quote, name.replace(quote, &format!("{}{}", quote, quote)))
+            }
+        }
+    }
+    fn object_name(&self, name: &str) -> ObjectName {
+        ObjectName(name.split('.').map(|x| self.ident(x)).collect())
+    }
+    /// Maps a logical column type to the engine's name for it.
+    pub fn type_name(&self, data_type: &DataType) -> AString {
+        let name = match (self, data_type) {
+            (SQLDialect::Presto, DataType::Text) | (SQLDialect::Presto, DataType::String) => {
+                "VARCHAR".to_string()
+            }
+            (SQLDialect::Presto, DataType::Varchar(None)) => "VARCHAR".to_string(),
+            (SQLDialect::Presto, DataType::Double) | (SQLDialect::Presto, DataType::Float(_)) => {
+                "DOUBLE".to_string()
+            }
+            (SQLDialect::Presto, DataType::Binary(_))
+            | (SQLDialect::Presto, DataType::Varbinary(_))
+            | (SQLDialect::Presto, DataType::Blob(_))
+            | (SQLDialect::Presto, DataType::Bytea) => "VARBINARY".to_string(),
+            (SQLDialect::Presto, DataType::Array(inner)) => {
+                format!("ARRAY({})", self.type_name(inner))
+            }
+            (SQLDialect::SQLite, DataType::SmallInt)
+            | (SQLDialect::SQLite, DataType::Int)
+            | (SQLDialect::SQLite, DataType::BigInt)
+            | (SQLDialect::SQLite, DataType::Boolean) => "INTEGER".to_string(),
+            (SQLDialect::SQLite, DataType::Real)
+            | (SQLDialect::SQLite, DataType::Double)
+            | (SQLDialect::SQLite, DataType::Float(_))
+            | (SQLDialect::SQLite, DataType::Decimal(_, _)) => "REAL".to_string(),
+            (SQLDialect::SQLite, DataType::Binary(_))
+            | (SQLDialect::SQLite, DataType::Varbinary(_))
+            | (SQLDialect::SQLite, DataType::Blob(_))
+            | (SQLDialect::SQLite, DataType::Bytea) => "BLOB".to_string(),
+            (SQLDialect::SQLite, _) => "TEXT".to_string(),
+            (SQLDialect::Postgres, DataType::Varchar(None))
+            | (SQLDialect::Postgres, DataType::String) => "TEXT".to_string(),
+            (SQLDialect::Postgres, DataType::Double)
+            | (SQLDialect::Postgres, DataType::Float(_)) => "DOUBLE PRECISION".to_string(),
+            (SQLDialect::Postgres, DataType::Binary(_))
+            | (SQLDialect::Postgres, DataType::Varbinary(_))
+            | (SQLDialect::Postgres, DataType::Blob(_)) => "BYTEA".to_string(),
+            (SQLDialect::Postgres, DataType::Array(inner)) => {
+                format!("{}[]", self.type_name(inner))
+            }
+            (SQLDialect::BigQuery, DataType::Char(_))
+            | (SQLDialect::BigQuery, DataType::Varchar(_))
+            | (SQLDialect::BigQuery, DataType::Text)
+            | (SQLDialect::BigQuery, DataType::String)
+            | (SQLDialect::BigQuery, DataType::Uuid) => "STRING".to_string(),
+            (SQLDialect::BigQuery, DataType::SmallInt)
+            | (SQLDialect::BigQuery, DataType::Int)
+            | (SQLDialect::BigQuery, DataType::BigInt) => "INT64".to_string(),
+            (SQLDialect::BigQuery, DataType::Real)
+            | (SQLDialect::BigQuery, DataType::Double)
+            | (SQLDialect::BigQuery, DataType::Float(_)) => "FLOAT64".to_string(),
+            (SQLDialect::BigQuery, DataType::Decimal(_, _)) => "NUMERIC".to_string(),
+            (SQLDialect::BigQuery, DataType::Boolean) => "BOOL".to_string(),
+            (SQLDialect::BigQuery, DataType::Binary(_))
+            | (SQLDialect::BigQuery, DataType::Varbinary(_))
+            | (SQLDialect::BigQuery, DataType::Blob(_))
+            | (SQLDialect::BigQuery, DataType::Bytea) => "BYTES".to_string(),
+            (SQLDialect::BigQuery, DataType::Array(inner)) => {
+                format!("ARRAY<{}>", self.type_name(inner))
+            }
+            (SQLDialect::DuckDB, DataType::Text)
+            | (SQLDialect::DuckDB, DataType::String)
+            | (SQLDialect::DuckDB, DataType::Varchar(None)) => "VARCHAR".to_string(),
+            (SQLDialect::DuckDB, DataType::Float(_)) => "DOUBLE".to_string(),
+            (SQLDialect::DuckDB, DataType::Binary(_))
+            | (SQLDialect::DuckDB, DataType::Varbinary(_))
+            | (SQLDialect::DuckDB, DataType::Blob(_))
+            | (SQLDialect::DuckDB, DataType::Bytea) => "BLOB".to_string(),
+            (SQLDialect::DuckDB, DataType::Array(inner)) => {
+                format!("{}[]", self.type_name(inner))
+            }
+            (SQLDialect::Spark, DataType::Char(_))
+            | (SQLDialect::Spark, DataType::Varchar(None))
+            | (SQLDialect::Spark, DataType::Text)
+            | (SQLDialect::Spark, DataType::Uuid) => "STRING".to_string(),
+            (SQLDialect::Spark, DataType::Float(_)) => "DOUBLE".to_string(),
+            (SQLDialect::Spark, DataType::Binary(_))
+            | (SQLDialect::Spark, DataType::Varbinary(_))
+            | (SQLDialect::Spark, DataType::Blob(_))
+            | (SQLDialect::Spark, DataType::Bytea) => "BINARY".to_string(),
+            (SQLDialect::Spark, DataType::Array(inner)) => {
+                format!("ARRAY<{}>", self.type_name(inner))
+            }
+            (_, DataType::Varchar(Some(n))) => format!("VARCHAR({})", n),
+            (_, x) => x.to_string(),
+        };
+        name.as_str().into()
+    }
+}
+
+/// Any SQL node that can be rendered to source for a given engine.
+pub trait ToSQL {
+    fn to_sql(&self, dialect: SQLDialect) -> AString;
+    /// Wraps the rendered statement in a (SQL-flagged) string literal, so it
+    /// can be passed to Python / R task calls.
+    fn to_string_literal(&self, dialect: SQLDialect) -> AST {
+        AST::StringLiteral(StringLiteral::new_wrapped(self.to_sql(dialect), true))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Literal {
+    String(AString),
+    Integer(i64),
+    /// kept as a string so that the node stays hashable
+    Decimal(AString),
+    Boolean(bool),
+    Null,
+}
+impl Literal {
+    fn to_sqlparser_value(&self, dialect: SQLDialect) -> Value {
+        match (self, dialect) {
+            (Literal::String(x), _) => Value::SingleQuotedString(x.as_str().into()),
+            (Literal::Integer(x), _) => Value::Number(x.to_string(), false),
+            (Literal::Decimal(x), _) => Value::Number(x.as_str().into(), false),
+            // SQLite has no boolean type
+            (Literal::Boolean(x), SQLDialect::SQLite) => {
+                Value::Number((*x as i64).to_string(), false)
+            }
+            (Literal::Boolean(x), _) => Value::Boolean(*x),
+            (Literal::Null, _) => Value::Null,
+        }
+    }
+}
+impl<'source> FromPyObject<'source> for Literal {
+    fn extract(ob: &'source PyAny) -> PyResult<Self> {
+        if ob.is_none() {
+            Ok(Literal::Null)
+        } else if let Ok(x) = ob.extract::<bool>() {
+            Ok(Literal::Boolean(x))
+        } else if let Ok(x) = ob.extract::<i64>() {
+            Ok(Literal::Integer(x))
+        } else if let Ok(x) = ob.downcast::<pyo3::types::PyFloat>() {
+            Ok(Literal::Decimal(x.repr()?.to_str()?.into()))
+        } else if let Ok(x) = ob.extract::<&str>() {
+            Ok(Literal::String(x.into()))
+        } else {
+            Err(PyTypeError::new_err(format!(
+                "Cannot use {} as a SQL literal",
+                ob.get_type().name()?
+            )))
+        }
+    }
+}
+
+#[pyclass]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ColumnRef {
+    pub table: AOption<AString>,
+    pub name: AString,
+}
+impl ColumnRef {
+    pub fn new(table: AOption<AString>, name: AString) -> Self {
+        Self { table, name }
+    }
+}
+#[pymethods]
+impl ColumnRef {
+    #[new]
+    #[args(table = "None")]
+    fn py_new(name: &str, table: Option<&str>) -> Self {
+        Self::new(optional_name(table), name.into())
+    }
+}
+
+#[pyclass]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct BinaryOp {
+    pub left: Box<SQLExpr>,
+    pub op: BinaryOperator,
+    pub right: Box<SQLExpr>,
+}
+impl BinaryOp {
+    pub fn new(left: SQLExpr, op: BinaryOperator, right: SQLExpr) -> Self {
+        Self {
+            left: Box::new(left),
+            op,
+            right: Box::new(right),
+        }
+    }
+}
+#[pymethods]
+impl BinaryOp {
+    #[new]
+    fn py_new(left: SQLExpr, op: &str, right: SQLExpr) -> PyResult<Self> {
+        let op = match op.to_uppercase().as_str() {
+            "+" => BinaryOperator::Plus,
+            "-" => BinaryOperator::Minus,
+            "*" => BinaryOperator::Multiply,
+            "/" => BinaryOperator::Divide,
+            "%" => BinaryOperator::Modulus,
+            "||" => BinaryOperator::StringConcat,
+            ">" => BinaryOperator::Gt,
+            "<" => BinaryOperator::Lt,
+            ">=" => BinaryOperator::GtEq,
+            "<=" => BinaryOperator::LtEq,
+            "=" => BinaryOperator::Eq,
+            "!=" | "<>" => BinaryOperator::NotEq,
+            "AND" => BinaryOperator::And,
+            "OR" => BinaryOperator::Or,
+            "LIKE" => BinaryOperator::Like,
+            "NOT LIKE" => BinaryOperator::NotLike,
+            _ => {
+                return Err(PyValueError::new_err(format!(
+                    "Unsupported SQL operator: {}",
+                    op
+                )))
+            }
+        };
+        Ok(Self::new(left, op, right))
+    }
+}
+
+#[pyclass]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct FunctionCall {
+    pub name: AString,
+    pub args: AVec<SQLExpr>,
+}
+impl FunctionCall {
+    pub fn new(name: AString, args: AVec<SQLExpr>) -> Self {
+        Self { name, args }
+    }
+}
+#[pymethods]
+impl FunctionCall {
+    #[new]
+    fn py_new(name: &str, args: Vec<SQLExpr>) -> Self {
+        Self::new(name.into(), args.into_iter().collect())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum SQLExpr {
+    ColumnRef(ColumnRef),
+    Literal(Literal),
+    BinaryOp(BinaryOp),
+    FunctionCall(FunctionCall),
+    Wildcard,
+}
+impl SQLExpr {
+    fn to_sqlparser_expr(&self, dialect: SQLDialect) -> Expr {
+        match self {
+            SQLExpr::ColumnRef(x) => match &x.table {
+                AOption(ROption::RSome(table)) => Expr::CompoundIdentifier(
+                    table
+                        .as_str()
+                        .split('.')
+                        .chain(std::iter::once(x.name.as_str()))
+                        .map(|y| dialect.ident(y))
+                        .collect(),
+                ),
+                AOption(ROption::RNone) => Expr::Identifier(dialect.ident(x.name.as_str())),
+            },
+            SQLExpr::Literal(x) => Expr::Value(x.to_sqlparser_value(dialect)),
+            SQLExpr::BinaryOp(x) => Expr::Nested(Box::new(Expr::BinaryOp {
+                left: Box::new(x.left.to_sqlparser_expr(dialect)),
+                op: x.op.clone(),
+                right: Box::new(x.right.to_sqlparser_expr(dialect)),
+            })),
+            SQLExpr::FunctionCall(x) => Expr::Function(Function {
+                name: ObjectName(vec![Ident::new(x.name.as_str())]),
+                args: x
+                    .args
+                    .iter()
+                    .map(|y| FunctionArg::Unnamed(y.to_sqlparser_expr(dialect)))
+                    .collect(),
+                over: None,
+                distinct: false,
+            }),
+            SQLExpr::Wildcard => Expr::Wildcard,
+        }
+    }
+}
+impl ToSQL for SQLExpr {
+    fn to_sql(&self, dialect: SQLDialect) -> AString {
+        self.to_sqlparser_expr(dialect).to_string().as_str().into()
+    }
+}
+/// Python values are taken as literals; columns, operations and function
+/// calls are built with their own classes.
+impl<'source> FromPyObject<'source> for SQLExpr {
+    fn extract(ob: &'source PyAny) -> PyResult<Self> {
+        if let Ok(x) = ob.extract::<ColumnRef>() {
+            Ok(SQLExpr::ColumnRef(x))
+        } else if let Ok(x) = ob.extract::<BinaryOp>() {
+            Ok(SQLExpr::BinaryOp(x))
+        } else if let Ok(x) = ob.extract::<FunctionCall>() {
+            Ok(SQLExpr::FunctionCall(x))
+        } else {
+            Ok(SQLExpr::Literal(ob.extract::<Literal>()?))
+        }
+    }
+}
+
+#[pyclass]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Select {
+    /// expressions, with optional aliases
+    pub projection: AVec<(SQLExpr, AOption<AString>)>,
+    pub from: AString,
+    pub selection: AOption<SQLExpr>,
+    pub group_by: AVec<SQLExpr>,
+    pub limit: AOption<u64>,
+}
+impl Select {
+    pub fn new(projection: AVec<(SQLExpr, AOption<AString>)>, from: AString) -> Self {
+        Self {
+            projection,
+            from,
+            selection: AOption(ROption::RNone),
+            group_by: AVec::new(),
+            limit: AOption(ROption::RNone),
+        }
+    }
+    fn to_sqlparser_query(&self, dialect: SQLDialect) -> Query {
+        let projection = self
+            .projection
+            .iter()
+            .map(|(expr, alias)| match (expr, alias) {
+                (SQLExpr::Wildcard, _) => SelectItem::Wildcard,
+                (_, AOption(ROption::RSome(alias))) => SelectItem::ExprWithAlias {
+                    expr: expr.to_sqlparser_expr(dialect),
+                    alias: dialect.ident(alias.as_str()),
+                },
+                (_, AOption(ROption::RNone)) => {
+                    SelectItem::UnnamedExpr(expr.to_sqlparser_expr(dialect))
+                }
+            })
+            .collect();
+        let select = SelectNode {
+            distinct: false,
+            top: None,
+            projection,
+            from: vec![TableWithJoins {
+                relation: TableFactor::Table {
+                    name: dialect.object_name(self.from.as_str()),
+                    alias: None,
+                    args: vec![],
+                    with_hints: vec![],
+                },
+                joins: vec![],
+            }],
+            lateral_views: vec![],
+            selection: match &self.selection {
+                AOption(ROption::RSome(x)) => Some(x.to_sqlparser_expr(dialect)),
+                AOption(ROption::RNone) => None,
+            },
+            group_by: self
+                .group_by
+                .iter()
+                .map(|x| x.to_sqlparser_expr(dialect))
+                .collect(),
+            cluster_by: vec![],
+            distribute_by: vec![],
+            sort_by: vec![],
+            having: None,
+        };
+        Query {
+            with: None,
+            body: SetExpr::Select(Box::new(select)),
+            order_by: vec![],
+            limit: match &self.limit {
+                AOption(ROption::RSome(x)) => {
+                    Some(Expr::Value(Value::Number(x.to_string(), false)))
+                }
+                AOption(ROption::RNone) => None,
+            },
+            offset: None,
+            fetch: None,
+        }
+    }
+}
+impl ToSQL for Select {
+    fn to_sql(&self, dialect: SQLDialect) -> AString {
+        self.to_sqlparser_query(dialect).to_string().as_str().into()
+    }
+}
+#[pymethods]
+impl Select {
+    /// Projections are expressions, or (expression, alias) tuples. A list
+    /// of conditions is combined with AND.
+    #[new]
+    #[args(selection = "None", group_by = "None", limit = "None")]
+    fn py_new(
+        projection: Vec<&PyAny>,
+        table: &str,
+        selection: Option<&PyAny>,
+        group_by: Option<Vec<SQLExpr>>,
+        limit: Option<u64>,
+    ) -> PyResult<Self> {
+        let mut select = Self::new(
+            projection
+                .into_iter()
+                .map(|x| match x.downcast::<PyTuple>() {
+                    Ok(_) => {
+                        let (expr, alias) = x.extract::<(SQLExpr, &str)>()?;
+                        Ok((expr, AOption(ROption::RSome(alias.into()))))
+                    }
+                    Err(_) => Ok((x.extract::<SQLExpr>()?, AOption(ROption::RNone))),
+                })
+                .collect::<PyResult<_>>()?,
+            table.into(),
+        );
+        let conditions = match selection {
+            Some(x) if x.downcast::<pyo3::types::PyList>().is_ok() => {
+                x.extract::<Vec<SQLExpr>>()?
+            }
+            Some(x) => vec![x.extract::<SQLExpr>()?],
+            None => Vec::new(),
+        };
+        select.selection = AOption(
+            conditions
+                .into_iter()
+                .reduce(|a, b| SQLExpr::BinaryOp(BinaryOp::new(a, BinaryOperator::And, b)))
+                .into(),
+        );
+        select.group_by = group_by.unwrap_or_default().into_iter().collect();
+        select.limit = AOption(limit.into());
+        Ok(select)
+    }
+    #[pyo3(name = "to_sql")]
+    fn py_to_sql(&self, dialect: &str) -> PyResult<String> {
+        Ok(self.to_sql(SQLDialect::from_name(dialect)?).to_string())
+    }
+}
+
+#[pyclass]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ColumnDef {
+    pub name: AString,
+    pub data_type: DataType,
+    pub nullable: bool,
+    pub comment: AOption<AString>,
+}
+impl ColumnDef {
+    pub fn new(
+        name: AString,
+        data_type: DataType,
+        nullable: bool,
+        comment: AOption<AString>,
+    ) -> Self {
+        Self {
+            name,
+            data_type,
+            nullable,
+            comment,
+        }
+    }
+    fn to_column_sql(&self, dialect: SQLDialect) -> String {
+        let mut out = format!(
+            "{} {}",
+            dialect.ident(self.name.as_str()),
+            dialect.type_name(&self.data_type)
+        );
+        if !self.nullable {
+            out += " NOT NULL";
+        }
+        if let AOption(ROption::RSome(ref comment)) = self.comment {
+            let comment = Value::SingleQuotedString(comment.as_str().into());
+            match dialect {
+                SQLDialect::Presto | SQLDialect::Spark => out += &format!(" COMMENT {}", comment),
+                SQLDialect::BigQuery => out += &format!(" OPTIONS(description={})", comment),
+                // SQLite has no column comments, and Postgres and DuckDB need
+                // a separate COMMENT ON statement.
+                SQLDialect::SQLite | SQLDialect::Postgres | SQLDialect::DuckDB => (),
+            }
+        }
+        out
+    }
+}
+
+#[pymethods]
+impl ColumnDef {
+    /// Types are parsed from their SQL name, e.g. `BIGINT` or `VARCHAR`.
+    #[new]
+    #[args(nullable = "true", comment = "None")]
+    fn py_new(
+        name: &str,
+        data_type: &str,
+        nullable: bool,
+        comment: Option<&str>,
+    ) -> PyResult<Self> {
+        let dialect = GenericDialect {};
+        let tokens = Tokenizer::new(&dialect, data_type)
+            .tokenize()
+            .map_err(|err| PyValueError::new_err(format!("{:?}", err)))?;
+        let data_type = Parser::new(tokens, &dialect)
+            .parse_data_type()
+            .map_err(|err| PyValueError::new_err(err.to_string()))?;
+        Ok(Self::new(
+            name.into(),
+            data_type,
+            nullable,
+            optional_name(comment),
+        ))
+    }
+}
+
+#[pyclass]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct CreateTable {
+    pub name: AString,
+    pub columns: AVec<ColumnDef>,
+    pub if_not_exists: bool,
+    /// table properties, e.g. `format` for Presto / Trino tables. These are
+    /// engine-specific, so they are only rendered for Presto and BigQuery.
+    pub properties: LinkedHashMap<AString, Literal>,
+}
+impl CreateTable {
+    pub fn new(name: AString, columns: AVec<ColumnDef>, if_not_exists: bool) -> Self {
+        Self {
+            name,
+            columns,
+            if_not_exists,
+            properties: LinkedHashMap::new(),
+        }
+    }
+}
+impl ToSQL for CreateTable {
+    fn to_sql(&self, dialect: SQLDialect) -> AString {
+        let columns = self
+            .columns
+            .iter()
+            .map(|x| format!("    {}", x.to_column_sql(dialect)))
+            .collect::<AVec<String>>()
+            .join(",\n");
+        let mut out = format!(
+            "CREATE TABLE {}{} (\n{}\n)",
+            match self.if_not_exists {
+                true => "IF NOT EXISTS ",
+                false => "",
+            },
+            dialect.object_name(self.name.as_str()),
+            columns
+        );
+        if !self.properties.is_empty() {
+            let properties = self
+                .properties
+                .iter()
+                .map(|(k, v)| format!("{} = {}", k, v.to_sqlparser_value(dialect)))
+                .collect::<AVec<String>>()
+                .join(", ");
+            match dialect {
+                SQLDialect::Presto => out += &format!(" WITH ({})", properties),
+                SQLDialect::BigQuery => out += &format!(" OPTIONS({})", properties),
+                SQLDialect::SQLite
+                | SQLDialect::Postgres
+                | SQLDialect::DuckDB
+                | SQLDialect::Spark => (),
+            }
+        }
+        out.as_str().into()
+    }
+}
+#[pymethods]
+impl CreateTable {
+    #[new]
+    #[args(if_not_exists = "false", properties = "None")]
+    fn py_new(
+        name: &str,
+        columns: Vec<ColumnDef>,
+        if_not_exists: bool,
+        properties: Option<&pyo3::types::PyDict>,
+    ) -> PyResult<Self> {
+        let mut create = Self::new(name.into(), columns.into_iter().collect(), if_not_exists);
+        if let Some(properties) = properties {
+            for (k, v) in properties.iter() {
+                create
+                    .properties
+                    .insert(k.extract::<&str>()?.into(), v.extract::<Literal>()?);
+            }
+        }
+        Ok(create)
+    }
+    #[pyo3(name = "to_sql")]
+    fn py_to_sql(&self, dialect: &str) -> PyResult<String> {
+        Ok(self.to_sql(SQLDialect::from_name(dialect)?).to_string())
+    }
+}
+
+#[pyclass]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct InsertInto {
+    pub table: AString,
+    pub columns: AVec<AString>,
+    pub query: Select,
+}
+impl InsertInto {
+    pub fn new(table: AString, columns: AVec<AString>, query: Select) -> Self {
+        Self {
+            table,
+            columns,
+            query,
+        }
+    }
+}
+impl ToSQL for InsertInto {
+    fn to_sql(&self, dialect: SQLDialect) -> AString {
+        let columns = match self.columns.len() {
+            0 => "".to_string(),
+            _ => format!(
+                " ({})",
+                self.columns
+                    .iter()
+                    .map(|x| dialect.ident(x.as_str()).to_string())
+                    .collect::<AVec<String>>()
+                    .join(", ")
+            ),
+        };
+        format!(
+            "INSERT INTO {}{} {}",
+            dialect.object_name(self.table.as_str()),
+            columns,
+            self.query.to_sql(dialect)
+        )
+        .as_str()
+        .into()
+    }
+}
+#[pymethods]
+impl InsertInto {
+    #[new]
+    fn py_new(table: &str, columns: Vec<&str>, query: Select) -> Self {
+        Self::new(
+            table.into(),
+            columns.into_iter().map(|x| x.into()).collect(),
+            query,
+        )
+    }
+    #[pyo3(name = "to_sql")]
+    fn py_to_sql(&self, dialect: &str) -> PyResult<String> {
+        Ok(self.to_sql(SQLDialect::from_name(dialect)?).to_string())
+    }
+}
+
+/// The statements recipes can build.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum SQLNode {
+    Select(Select),
+    CreateTable(CreateTable),
+    InsertInto(InsertInto),
+}
+impl SQLNode {
+    /// The statement a Python object stands for, if it is one.
+    pub fn from_py(ob: &PyAny) -> Option<Self> {
+        if let Ok(x) = ob.extract::<Select>() {
+            Some(SQLNode::Select(x))
+        } else if let Ok(x) = ob.extract::<CreateTable>() {
+            Some(SQLNode::CreateTable(x))
+        } else if let Ok(x) = ob.extract::<InsertInto>() {
+            Some(SQLNode::InsertInto(x))
+        } else {
+            None
+        }
+    }
+}
+impl ToSQL for SQLNode {
+    fn to_sql(&self, dialect: SQLDialect) -> AString {
+        match self {
+            SQLNode::Select(x) => x.to_sql(dialect),
+            SQLNode::CreateTable(x) => x.to_sql(dialect),
+            SQLNode::InsertInto(x) => x.to_sql(dialect),
+        }
+    }
+}
+
+fn optional_name(name: Option<&str>) -> AOption<AString> {
+    AOption(match name {
+        Some(x) => ROption::RSome(x.into()),
+        None => ROption::RNone,
+    })
+}
+
+pub fn sql_module(_py: Python, m: &PyModule) -> PyResult<()> {
+    m.add_class::<ColumnRef>()?;
+    m.add_class::<BinaryOp>()?;
+    m.add_class::<FunctionCall>()?;
+    m.add_class::<Select>()?;
+    m.add_class::<ColumnDef>()?;
+    m.add_class::<CreateTable>()?;
+    m.add_class::<InsertInto>()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod sql_tests {
+    use crate::sql::*;
+    use crate::AST;
+
+    fn example_select() -> Select {
+        let mut select = Select::new(
+            vec![
+                (
+                    SQLExpr::ColumnRef(ColumnRef::new(AOption(ROption::RNone), "id".into())),
+                    AOption(ROption::RNone),
+                ),
+                (
+                    SQLExpr::FunctionCall(FunctionCall::new(
+                        "lower".into(),
+                        vec![SQLExpr::ColumnRef(ColumnRef::new(
+                            AOption(ROption::RNone),
+                            "Name".into(),
+                        ))]
+                        .into_iter()
+                        .collect(),
+                    )),
+                    AOption(ROption::RSome("name".into())),
+                ),
+            ]
+            .into_iter()
+            .collect(),
+            "graphs.edges".into(),
+        );
+        select.selection = AOption(ROption::RSome(SQLExpr::BinaryOp(BinaryOp::new(
+            SQLExpr::ColumnRef(ColumnRef::new(AOption(ROption::RNone), "active".into())),
+            BinaryOperator::Eq,
+            SQLExpr::Literal(Literal::Boolean(true)),
+        ))));
+        select
+    }
+
+    #[test]
+    fn test_select() {
+        let select = example_select();
+        assert_eq!(
+            select.to_sql(SQLDialect::Presto).as_str(),
+            "SELECT id, lower(\"Name\") AS name FROM graphs.edges WHERE (active = true)"
+        );
+        assert_eq!(
+            select.to_sql(SQLDialect::BigQuery).as_str(),
+            "SELECT id, lower(`Name`) AS name FROM graphs.edges WHERE (active = true)"
+        );
+        assert_eq!(
+            select.to_sql(SQLDialect::SQLite).as_str(),
+            "SELECT id, lower(\"Name\") AS name FROM graphs.edges WHERE (active = 1)"
+        );
+    }
+    #[test]
+    fn test_create_table() {
+        let mut create = CreateTable::new(
+            "edges".into(),
+            vec![
+                ColumnDef::new(
+                    "from_id".into(),
+                    DataType::BigInt,
+                    false,
+                    AOption(ROption::RNone),
+                ),
+                ColumnDef::new(
+                    "label".into(),
+                    DataType::Varchar(None),
+                    true,
+                    AOption(ROption::RSome("edge label".into())),
+                ),
+            ]
+            .into_iter()
+            .collect(),
+            true,
+        );
+        create
+            .properties
+            .insert("format".into(), Literal::String("ORC".into()));
+        assert_eq!(
+            create.to_sql(SQLDialect::Presto).as_str(),
+            "CREATE TABLE IF NOT EXISTS edges (\n    from_id BIGINT NOT NULL,\n    label VARCHAR COMMENT 'edge label'\n) WITH (format = 'ORC')"
+        );
+        assert_eq!(
+            create.to_sql(SQLDialect::Postgres).as_str(),
+            "CREATE TABLE IF NOT EXISTS edges (\n    from_id BIGINT NOT NULL,\n    label TEXT\n)"
+        );
+        assert_eq!(
+            create.to_sql(SQLDialect::BigQuery).as_str(),
+            "CREATE TABLE IF NOT EXISTS edges (\n    from_id INT64 NOT NULL,\n    label STRING OPTIONS(description='edge label')\n) OPTIONS(format = 'ORC')"
+        );
+    }
+    #[test]
+    fn test_insert_into() {
+        let insert = InsertInto::new(
+            "edges_orc".into(),
+            vec!["id".into(), "name".into()].into_iter().collect(),
+            example_select(),
+        );
+        assert_eq!(
+            insert.to_sql(SQLDialect::Postgres).as_str(),
+            "INSERT INTO edges_orc (id, name) SELECT id, lower(\"Name\") AS name FROM graphs.edges WHERE (active = true)"
+        );
+    }
+    #[test]
+    fn test_keywords_are_quoted() {
+        let mut select = Select::new(
+            vec![
+                (
+                    SQLExpr::ColumnRef(ColumnRef::new(AOption(ROption::RNone), "date".into())),
+                    AOption(ROption::RNone),
+                ),
+                (
+                    SQLExpr::ColumnRef(ColumnRef::new(
+                        AOption(ROption::RSome("orders".into())),
+                        "order".into(),
+                    )),
+                    AOption(ROption::RSome("select".into())),
+                ),
+            ]
+            .into_iter()
+            .collect(),
+            "orders".into(),
+        );
+        select.selection = AOption(ROption::RSome(SQLExpr::BinaryOp(BinaryOp::new(
+            SQLExpr::ColumnRef(ColumnRef::new(AOption(ROption::RNone), "group".into())),
+            BinaryOperator::Eq,
+            SQLExpr::Literal(Literal::String("a".into())),
+        ))));
+        assert_eq!(
+            select.to_sql(SQLDialect::Presto).as_str(),
+            "SELECT \"date\", orders.\"order\" AS \"select\" FROM orders WHERE (\"group\" = 'a')"
+        );
+        assert_eq!(
+            select.to_sql(SQLDialect::BigQuery).as_str(),
+            "SELECT `date`, orders.`order` AS `select` FROM orders WHERE (`group` = 'a')"
+        );
+    }
+    #[test]
+    fn test_quotes_in_identifiers_are_doubled() {
+        let select = Select::new(
+            vec![(
+                SQLExpr::ColumnRef(ColumnRef::new(AOption(ROption::RNone), "a\"b".into())),
+                AOption(ROption::RSome("c`d".into())),
+            )]
+            .into_iter()
+            .collect(),
+            "t".into(),
+        );
+        assert_eq!(
+            select.to_sql(SQLDialect::Presto).as_str(),
+            "SELECT \"a\"\"b\" AS \"c`d\" FROM t"
+        );
+        assert_eq!(
+            select.to_sql(SQLDialect::Spark).as_str(),
+            "SELECT `a\"b` AS `c``d` FROM t"
+        );
+    }
+    #[test]
+    fn test_program_engines() {
+        let create = CreateTable::new(
+            "edges".into(),
+            vec![
+                ColumnDef::new(
+                    "id".into(),
+                    DataType::BigInt,
+                    false,
+                    AOption(ROption::RNone),
+                ),
+                ColumnDef::new(
+                    "label".into(),
+                    DataType::Text,
+                    true,
+                    AOption(ROption::RSome("edge label".into())),
+                ),
+            ]
+            .into_iter()
+            .collect(),
+            false,
+        );
+        assert_eq!(
+            create.to_sql(SQLDialect::Spark).as_str(),
+            "CREATE TABLE edges (\n    id BIGINT NOT NULL,\n    label STRING COMMENT 'edge label'\n)"
+        );
+        assert_eq!(
+            create.to_sql(SQLDialect::DuckDB).as_str(),
+            "CREATE TABLE edges (\n    id BIGINT NOT NULL,\n    label VARCHAR\n)"
+        );
+        assert_eq!(
+            SQLDialect::for_program(&Dialect::Spark(aorist_primitives::Spark::new())),
+            Some(SQLDialect::Spark)
+        );
+        assert_eq!(
+            SQLDialect::for_program(&Dialect::Bash(aorist_primitives::Bash::new())),
+            None
+        );
+    }
+    #[test]
+    fn test_statements_from_python() {
+        Python::with_gil(|py| {
+            let locals = pyo3::types::PyDict::new(py);
+            sql_module(py, PyModule::new(py, "sql").unwrap()).unwrap();
+            for (name, class) in vec![
+                ("ColumnRef", py.get_type::<ColumnRef>()),
+                ("BinaryOp", py.get_type::<BinaryOp>()),
+                ("FunctionCall", py.get_type::<FunctionCall>()),
+                ("Select", py.get_type::<Select>()),
+                ("InsertInto", py.get_type::<InsertInto>()),
+            ] {
+                locals.set_item(name, class).unwrap();
+            }
+            let insert = py
+                .eval(
+                    "InsertInto('graphs.edges', [], Select(\
+                        [ColumnRef('id'), (FunctionCall('lower', [ColumnRef('Name')]), 'name')],\
+                        'tmp_edges',\
+                        selection=BinaryOp(ColumnRef('dt'), '=', '{partition_date}'),\
+                    ))",
+                    None,
+                    Some(locals),
+                )
+                .unwrap();
+            let ast = crate::extract_arg(insert).unwrap();
+            assert!(matches!(ast, AST::SQLStatement(_)));
+            let rendered = AST::List(crate::List::new_wrapped(
+                vec![ast].into_iter().collect(),
+                false,
+            ))
+            .render_sql(&Dialect::Presto(aorist_primitives::Presto::new()))
+            .unwrap();
+            match rendered {
+                AST::List(ref x) => match x.read().elems()[0] {
+                    AST::StringLiteral(ref q) => assert_eq!(
+                        q.read().value().as_str(),
+                        "INSERT INTO graphs.edges SELECT id, lower(\"Name\") AS name FROM tmp_edges WHERE (dt = '{partition_date}')"
+                    ),
+                    _ => panic!("SQL statement was not rendered"),
+                },
+                _ => panic!("list was not kept"),
+            }
+            assert!(insert
+                .call_method1("to_sql", ("sqlite",))
+                .unwrap()
+                .extract::<&str>()
+                .unwrap()
+                .starts_with("INSERT INTO graphs.edges SELECT"));
+            assert!(insert.call_method1("to_sql", ("oracle",)).is_err());
+        });
+    }
+}
@@ -97,12 +97,12 @@ impl StringLiteral {
         ast_module.getattr("Constant")?.call1((value.as_str(),))
     }
 
-    pub fn to_r_ast_node(&self, depth: usize) -> Robj {
+    pub fn to_r_ast_node(&self, depth: usize) -> PyResult<Robj> {
         let value = match self.is_sql {
             false => self.value.clone(),
             true => self.pretty_sql_value(depth),
         };
-        Robj::from(vec![value.as_str()])
+        Ok(Robj::from(vec![value.as_str()]))
     }
 
     pub fn new_wrapped(value: AString, is_sql: bool) -> RArc<RRwLock<Self>> {
@@ -117,8 +117,14 @@ impl StringLiteral {
     pub fn is_multiline(&self) -> bool {
         self.value.contains('\n')
     }
+    pub fn is_sql(&self) -> bool {
+        self.is_sql
+    }
     pub fn get_direct_descendants(&self) -> AVec<AST> {
         AVec::new()
     }
+    pub fn rewrite_children(&self, _f: &mut dyn FnMut(&AST) -> AST) -> Self {
+        self.clone()
+    }
     pub fn optimize_fields(&self) {}
 }
@@ -55,6 +55,26 @@ fn process_attributes(raw_objects: &Vec<HashMap<String, Value>>) -> AResult<()>
         .iter()
         .map(|x| x.get("bigquery").unwrap().as_str().unwrap().to_string())
         .collect::<HashSet<_>>();
+    let duckdb_derive_macros = attributes
+        .iter()
+        .map(|x| x.get("duckdb").unwrap().as_str().unwrap().to_string())
+        .collect::<HashSet<_>>();
+    let spark_derive_macros = attributes
+        .iter()
+        .map(|x| x.get("spark").unwrap().as_str().unwrap().to_string())
+        .collect::<HashSet<_>>();
+    let parquet_derive_macros = attributes
+        .iter()
+        .map(|x| x.get("parquet").unwrap().as_str().unwrap().to_string())
+        .collect::<HashSet<_>>();
+    let avro_derive_macros = attributes
+        .iter()
+        .map(|x| x.get("avro").unwrap().as_str().unwrap().to_string())
+        .collect::<HashSet<_>>();
+    let arrow_derive_macros = attributes
+        .iter()
+        .map(|x| x.get("arrow").unwrap().as_str().unwrap().to_string())
+        .collect::<HashSet<_>>();
 
     cfg_if::cfg_if! {
         if #[cfg(feature = "sql")] {
@@ -73,6 +93,11 @@ fn process_attributes(raw_objects: &Vec<HashMap<String, Value>>) -> AResult<()>
         .chain(sqlite_derive_macros.into_iter())
         .chain(postgres_derive_macros.into_iter())
         .chain(bigquery_derive_macros.into_iter())
+        .chain(duckdb_derive_macros.into_iter())
+        .chain(spark_derive_macros.into_iter())
+        .chain(parquet_derive_macros.into_iter())
+        .chain(avro_derive_macros.into_iter())
+        .chain(arrow_derive_macros.into_iter())
         .collect::<HashSet<_>>();
 
     for item in derive_macros {
@@ -121,6 +146,31 @@ fn process_attributes(raw_objects: &Vec<HashMap<String, Value>>) -> AResult<()>
             .as_str()
             .unwrap()
             .to_string();
+        let duckdb = attribute
+            .get("duckdb")
+            .unwrap()
+            .as_str()
+            .unwrap()
+            .to_string();
+        let spark = attribute
+            .get("spark")
+            .unwrap()
+            .as_str()
+            .unwrap()
+            .to_string();
+        let parquet = attribute
+            .get("parquet")
+            .unwrap()
+            .as_str()
+            .unwrap()
+            .to_string();
+        let avro = attribute.get("avro").unwrap().as_str().unwrap().to_string();
+        let arrow = attribute
+            .get("arrow")
+            .unwrap()
+            .as_str()
+            .unwrap()
+            .to_string();
         let python = match attribute.get("python").unwrap().as_str().unwrap() {
             "str" => "pyo3::types::PyString",
             "int" => "pyo3::types::PyLong",
@@ -130,8 +180,22 @@ fn process_attributes(raw_objects: &Vec<HashMap<String, Value>>) -> AResult<()>
         .to_string();
 
         let define = format!(
-            "define_attribute!({}, {}, {}, {}, {}, {}, {}, {}, {}, {});",
-            name, orc, presto, sql, sqlite, postgres, bigquery, value, key, python
+            "define_attribute!({}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {});",
+            name,
+            orc,
+            presto,
+            sql,
+            sqlite,
+            postgres,
+            bigquery,
+            duckdb,
+            spark,
+            parquet,
+            avro,
+            arrow,
+            value,
+            key,
+            python
         );
         scope.raw(&define);
         attribute_names.push(name.clone());
@@ -218,6 +218,58 @@ pub trait TSQLiteAttribute: TAttribute {
             .into()
     }
 }
+pub trait TDuckDBAttribute: TAttribute {
+    fn get_duckdb_type(&self) -> AString;
+    fn get_duckdb_coldef(&self) -> AString {
+        format!("{} {}", self.get_name(), self.get_duckdb_type())
+            .as_str()
+            .into()
+    }
+}
+pub trait TSparkAttribute: TAttribute {
+    fn get_spark_type(&self) -> AString;
+    fn get_spark_coldef(&self) -> AString {
+        format!("{} {}", self.get_name(), self.get_spark_type())
+            .as_str()
+            .into()
+    }
+}
+pub trait TParquetAttribute: TAttribute {
+    fn get_parquet_physical_type(&self) -> AString;
+    fn get_parquet_logical_type(&self) -> AOption<AString>;
+    /// Field declaration in Parquet's message schema syntax, e.g.
+    /// `optional binary name (STRING);`
+    fn get_parquet_schema(&self) -> AString {
+        let physical = match self.get_parquet_physical_type().as_str() {
+            "BYTE_ARRAY" => "binary".to_string(),
+            x => x.to_lowercase(),
+        };
+        let logical = match self.get_parquet_logical_type() {
+            AOption(ROption::RSome(x)) => format!(" ({})", x),
+            AOption(ROption::RNone) => "".to_string(),
+        };
+        format!(
+            "{} {} {}{};",
+            match self.is_nullable() {
+                true => "optional",
+                false => "required",
+            },
+            physical,
+            self.get_name(),
+            logical
+        )
+        .as_str()
+        .into()
+    }
+}
+pub trait TAvroAttribute: TAttribute {
+    fn get_avro_type(&self) -> AString;
+}
+/// Arrow data types are named after the pyarrow / R arrow functions
+/// constructing them, e.g. `int64`.
+pub trait TArrowAttribute: TAttribute {
+    fn get_arrow_type(&self) -> AString;
+}
 pub trait TPostgresAttribute: TAttribute {
     fn get_postgres_type(&self) -> AString;
     fn get_postgres_coldef(&self) -> AString {
@@ -1,4 +1,4 @@
-use crate::code::Preamble;
+use crate::code::{ParamsFiles, Preamble};
 use crate::constraint::OuterConstraint;
 use crate::constraint_state::ConstraintState;
 use crate::flow::{CompressibleETLTask, CompressibleTask, ETLFlow, ETLTask, StandaloneTask};
@@ -116,6 +116,7 @@ where
                 dependencies,
                 x.get_preamble(),
                 x.get_dialect(),
+                x.get_execution_policy(),
             ));
         }
         Ok((tasks, task_identifiers, params))
@@ -137,6 +138,8 @@ pub trait CodeBlockWithDefaultConstructor<
         tasks_dict: AOption<AST>,
         identifiers: &HashMap<AUuid, AST>,
         render_dependencies: bool,
+        params_files: AOption<ParamsFiles>,
+        dynamic_mapping: bool,
     ) -> Result<Self>;
 }
 pub trait CodeBlockWithForLoopCompression<
@@ -159,7 +162,9 @@ pub trait CodeBlockWithForLoopCompression<
         tasks: &mut AVec<Self::E>,
         constraint_name: AString,
         render_dependencies: bool,
-    );
+        params_files: AOption<ParamsFiles>,
+        dynamic_mapping: bool,
+    ) -> Result<()>;
     fn separate_compressible_tasks(
         tasks: AVec<<Self::E as ETLTask<T, U>>::S>,
     ) -> (
@@ -201,6 +206,8 @@ where
         tasks_dict: AOption<AST>,
         identifiers: &HashMap<AUuid, AST>,
         render_dependencies: bool,
+        params_files: AOption<ParamsFiles>,
+        dynamic_mapping: bool,
     ) -> Result<Self> {
         let (standalone_tasks, task_identifiers, params) =
             Self::create_standalone_tasks(members, tasks_dict.clone(), identifiers)?;
@@ -210,7 +217,9 @@ where
             &mut tasks,
             constraint_name,
             render_dependencies,
-        );
+            params_files,
+            dynamic_mapping,
+        )?;
         Ok(Self::construct(tasks_dict, tasks, task_identifiers, params))
     }
 }
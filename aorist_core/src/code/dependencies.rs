@@ -0,0 +1,215 @@
+use anyhow::Result;
+use aorist_util::{AString, AVec};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+/// Packages a generated flow needs at runtime: the pip requirements of the
+/// programs' Python dialects, the third-party modules the flow imports, the
+/// R packages its R programs load and the (Debian) system packages its
+/// tools need. Pip requirements are keyed by distribution name, so that a
+/// pinned requirement from a dialect wins over the bare name derived from
+/// an import.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FlowDependencies {
+    pip: BTreeMap<AString, AString>,
+    r: BTreeSet<AString>,
+    system: BTreeSet<AString>,
+}
+impl FlowDependencies {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    fn get_requirement_name(requirement: &str) -> AString {
+        requirement
+            .split(|c: char| "<>=!~[;@ ".contains(c))
+            .next()
+            .unwrap()
+            .trim()
+            .to_lowercase()
+            .replace('_', "-")
+            .as_str()
+            .into()
+    }
+    pub fn add_pip_requirement(&mut self, requirement: AString) {
+        let name = Self::get_requirement_name(requirement.as_str());
+        match self.pip.get(&name) {
+            Some(existing) if existing.len() >= requirement.len() => {}
+            _ => {
+                self.pip.insert(name, requirement);
+            }
+        }
+    }
+    pub fn add_r_package(&mut self, package: AString) {
+        self.r.insert(package);
+    }
+    pub fn add_system_package(&mut self, package: AString) {
+        self.system.insert(package);
+    }
+    /// Packages loaded with library(), require() or requireNamespace().
+    pub fn add_r_packages_from(&mut self, code: &str) {
+        for function in ["library(", "require(", "requireNamespace("] {
+            for (pos, _) in code.match_indices(function) {
+                let preceded_by_identifier = code[..pos]
+                    .chars()
+                    .last()
+                    .is_some_and(|c| c.is_alphanumeric() || c == '.' || c == '_');
+                if preceded_by_identifier {
+                    continue;
+                }
+                let package = code[pos + function.len()..]
+                    .split([')', ','])
+                    .next()
+                    .unwrap()
+                    .trim()
+                    .trim_matches(['"', '\'']);
+                if !package.is_empty() && package.chars().all(|c| c.is_alphanumeric() || c == '.') {
+                    self.add_r_package(package.into());
+                }
+            }
+        }
+    }
+    pub fn extend(&mut self, other: FlowDependencies) {
+        for requirement in other.pip.into_values() {
+            self.add_pip_requirement(requirement);
+        }
+        self.r.extend(other.r);
+        self.system.extend(other.system);
+    }
+    pub fn has_pip_requirement(&self, name: &str) -> bool {
+        self.pip.contains_key(&Self::get_requirement_name(name))
+    }
+    pub fn get_pip_requirements(&self) -> AVec<AString> {
+        self.pip.values().cloned().collect()
+    }
+    pub fn get_r_packages(&self) -> AVec<AString> {
+        self.r.iter().cloned().collect()
+    }
+    pub fn get_system_packages(&self) -> AVec<AString> {
+        self.system.iter().cloned().collect()
+    }
+    pub fn to_requirements_txt(&self) -> AString {
+        self.pip
+            .values()
+            .map(|x| format!("{}\n", x))
+            .collect::<String>()
+            .as_str()
+            .into()
+    }
+    /// A conda environment, with R packages taken from conda-forge and
+    /// Python requirements installed by pip.
+    pub fn to_environment_yml(&self, name: &str) -> AString {
+        let mut lines = vec![
+            format!("name: {}", name),
+            "channels:".to_string(),
+            "  - conda-forge".to_string(),
+            "dependencies:".to_string(),
+            "  - python".to_string(),
+        ];
+        if !self.r.is_empty() {
+            lines.push("  - r-base".to_string());
+            for package in self.r.iter() {
+                lines.push(format!("  - r-{}", package.as_str().to_lowercase()));
+            }
+        }
+        if !self.pip.is_empty() {
+            lines.push("  - pip".to_string());
+            lines.push("  - pip:".to_string());
+            for requirement in self.pip.values() {
+                lines.push(format!("    - {}", requirement));
+            }
+        }
+        format!("{}\n", lines.join("\n")).as_str().into()
+    }
+    /// An R DESCRIPTION file importing the R packages, so that they can be
+    /// installed with remotes::install_deps().
+    pub fn to_r_description(&self, name: &str) -> AString {
+        let package = name
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '.' })
+            .collect::<String>();
+        let imports = self
+            .r
+            .iter()
+            .map(|x| format!("    {}", x))
+            .collect::<Vec<_>>()
+            .join(",\n");
+        format!(
+            "Package: {}\nTitle: Dependencies of the {} flow\nVersion: 0.0.1\nImports:\n{}\n",
+            package, name, imports
+        )
+        .as_str()
+        .into()
+    }
+    /// Writes requirements.txt and environment.yml to `directory`, as well
+    /// as DESCRIPTION if the flow runs any R code.
+    pub fn write(&self, directory: &str, name: &str) -> Result<()> {
+        let directory = Path::new(directory);
+        std::fs::create_dir_all(directory)?;
+        std::fs::write(
+            directory.join("requirements.txt"),
+            self.to_requirements_txt().as_str(),
+        )?;
+        std::fs::write(
+            directory.join("environment.yml"),
+            self.to_environment_yml(name).as_str(),
+        )?;
+        if !self.r.is_empty() {
+            std::fs::write(
+                directory.join("DESCRIPTION"),
+                self.to_r_description(name).as_str(),
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pinned_requirement_wins() {
+        let mut dependencies = FlowDependencies::new();
+        dependencies.add_pip_requirement("pandas".into());
+        dependencies.add_pip_requirement("pandas==1.3.5".into());
+        dependencies.add_pip_requirement("Pandas".into());
+        dependencies.add_pip_requirement("python_snappy".into());
+        assert!(dependencies.has_pip_requirement("python-snappy"));
+        assert_eq!(
+            dependencies.to_requirements_txt().as_str(),
+            "pandas==1.3.5\npython_snappy\n"
+        );
+    }
+    #[test]
+    fn test_r_packages_from() {
+        let mut dependencies = FlowDependencies::new();
+        dependencies.add_r_packages_from(
+            "library(dplyr)\nrequire(\"sf\")\nrequireNamespace('DBI', quietly = TRUE)\n\
+             my_library(ignored)\nlibrary(x[1])",
+        );
+        assert_eq!(
+            dependencies
+                .get_r_packages()
+                .iter()
+                .map(|x| x.as_str())
+                .collect::<Vec<_>>(),
+            vec!["DBI", "dplyr", "sf"]
+        );
+    }
+    #[test]
+    fn test_environment_and_description() {
+        let mut dependencies = FlowDependencies::new();
+        dependencies.add_pip_requirement("trino".into());
+        dependencies.add_r_package("DBI".into());
+        assert_eq!(
+            dependencies.to_environment_yml("my_flow").as_str(),
+            "name: my_flow\nchannels:\n  - conda-forge\ndependencies:\n  - python\n  \
+             - r-base\n  - r-dbi\n  - pip\n  - pip:\n    - trino\n"
+        );
+        assert_eq!(
+            dependencies.to_r_description("my_flow").as_str(),
+            "Package: my.flow\nTitle: Dependencies of the my_flow flow\nVersion: 0.0.1\n\
+             Imports:\n    DBI\n"
+        );
+    }
+}
@@ -1,7 +1,11 @@
 mod code_block;
+mod dependencies;
 mod import;
+mod params_file;
 mod preamble;
 
 pub use code_block::*;
+pub use dependencies::FlowDependencies;
 pub use import::Import;
+pub use params_file::*;
 pub use preamble::Preamble;
@@ -0,0 +1,111 @@
+use abi_stable::std_types::ROption;
+use anyhow::{bail, Result};
+use aorist_ast::AST;
+use aorist_util::{AOption, AString};
+use serde_json::{Map, Number, Value};
+use std::path::Path;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ParamsFileFormat {
+    JSON,
+    YAML,
+}
+impl ParamsFileFormat {
+    pub fn from_name(name: &str) -> Result<Self> {
+        match name.to_lowercase().as_str() {
+            "json" => Ok(Self::JSON),
+            "yaml" | "yml" => Ok(Self::YAML),
+            _ => bail!("Unknown params file format: {}", name),
+        }
+    }
+    pub fn get_extension(&self) -> &'static str {
+        match self {
+            Self::JSON => "json",
+            Self::YAML => "yaml",
+        }
+    }
+}
+
+/// Sidecar files holding the parameters of for-loop compressed tasks, so
+/// that they do not have to be embedded in the generated flow. Files are
+/// written when tasks are compressed, and read by the flow at runtime from
+/// the same absolute path (relative directories are resolved against the
+/// directory the flow is generated from).
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ParamsFiles {
+    directory: AString,
+    format: ParamsFileFormat,
+}
+impl ParamsFiles {
+    pub fn new(directory: AString, format: ParamsFileFormat) -> Result<Self> {
+        let directory = std::env::current_dir()?.join(directory.as_str());
+        Ok(Self {
+            directory: directory.to_string_lossy().as_ref().into(),
+            format,
+        })
+    }
+    pub fn get_format(&self) -> ParamsFileFormat {
+        self.format
+    }
+    pub fn get_path(&self, name: &str) -> AString {
+        Path::new(self.directory.as_str())
+            .join(format!("{}.{}", name, self.format.get_extension()))
+            .to_string_lossy()
+            .as_ref()
+            .into()
+    }
+    /// Literals and containers of literals; other nodes (e.g. references to
+    /// other tasks) only exist in the flow.
+    fn to_value(ast: &AST) -> AOption<Value> {
+        let value = match ast {
+            AST::StringLiteral(ref x) => Value::String(x.read().value().as_str().to_string()),
+            AST::BigIntLiteral(ref x) => Value::Number(x.read().val().into()),
+            AST::BooleanLiteral(ref x) => Value::Bool(x.read().val()),
+            AST::None(_) => Value::Null,
+            AST::FloatLiteral(ref x) => match Number::from_f64(x.read().val().as_f64()) {
+                Some(n) => Value::Number(n),
+                None => return AOption(ROption::RNone),
+            },
+            AST::List(_) | AST::Tuple(_) => {
+                let mut values = Vec::new();
+                for elem in ast.get_direct_descendants() {
+                    match Self::to_value(&elem) {
+                        AOption(ROption::RSome(v)) => values.push(v),
+                        AOption(ROption::RNone) => return AOption(ROption::RNone),
+                    }
+                }
+                Value::Array(values)
+            }
+            AST::Dict(ref x) => {
+                let mut values = Map::new();
+                for (k, v) in x.read().elems() {
+                    match Self::to_value(&v) {
+                        AOption(ROption::RSome(v)) => {
+                            values.insert(k.as_str().to_string(), v);
+                        }
+                        AOption(ROption::RNone) => return AOption(ROption::RNone),
+                    }
+                }
+                Value::Object(values)
+            }
+            _ => return AOption(ROption::RNone),
+        };
+        AOption(ROption::RSome(value))
+    }
+    /// Writes `params` to the file for `name`, returning its path, unless
+    /// `params` cannot be represented outside of the flow.
+    pub fn write(&self, name: &str, params: &AST) -> Result<AOption<AString>> {
+        let value = match Self::to_value(params) {
+            AOption(ROption::RSome(v)) => v,
+            AOption(ROption::RNone) => return Ok(AOption(ROption::RNone)),
+        };
+        let content = match self.format {
+            ParamsFileFormat::JSON => serde_json::to_string_pretty(&value)? + "\n",
+            ParamsFileFormat::YAML => serde_yaml::to_string(&value)?,
+        };
+        std::fs::create_dir_all(self.directory.as_str())?;
+        let path = self.get_path(name);
+        std::fs::write(path.as_str(), content)?;
+        Ok(AOption(ROption::RSome(path)))
+    }
+}
@@ -47,7 +47,7 @@ where
             .map(|x| x.2.clone().into_iter())
             .flatten()
             .collect::<BTreeSet<_>>();
-        Self::BuilderInputType::new(
+        self.decorate_builder_input(Self::BuilderInputType::new(
             self.get_task_val_assignments()
                 .into_iter()
                 .chain(preambles_and_statements.into_iter().map(|x| x.0).flatten())
@@ -57,7 +57,12 @@ where
             self.get_constraint_name(),
             self.get_constraint_title(),
             self.get_constraint_body(),
-        )
+        ))
+    }
+    /// Attaches renderer-specific information (beyond plain statements) to
+    /// the flow builder input.
+    fn decorate_builder_input(&self, input: Self::BuilderInputType) -> Self::BuilderInputType {
+        input
     }
 
     fn get_identifiers(&self) -> HashMap<AUuid, AST>;
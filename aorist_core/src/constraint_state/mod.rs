@@ -1,12 +1,14 @@
 use crate::constraint::OuterConstraint;
 use crate::parameter_tuple::ParameterTuple;
 use crate::program::TOuterProgram;
+use crate::shell::{fill_sql, pipe_sql, sql_command, SchemaChange};
 use abi_stable::external_types::parking_lot::rw_lock::RRwLock;
 use abi_stable::std_types::RArc;
 use abi_stable::std_types::ROption;
 use anyhow::{bail, Result};
+use aorist_ast::bash::ToBash;
 use aorist_ast::{AncestorRecord, Formatted, SimpleIdentifier, StringLiteral, AST};
-use aorist_primitives::Dialect;
+use aorist_primitives::{Dialect, TaskPolicy};
 use aorist_primitives::{Context, ToplineConcept, Ancestry};
 use aorist_util::AOption;
 use aorist_util::AUuid;
@@ -34,6 +36,7 @@ pub struct ConstraintState<T: OuterConstraint, P: TOuterProgram<TAncestry = T::T
     params: AOption<ParameterTuple>,
     task_name: AOption<AString>,
     context: Context,
+    execution_policy: AOption<TaskPolicy>,
 }
 impl<T: OuterConstraint, P: TOuterProgram<TAncestry = T::TAncestry>>
     ConstraintState<T, P>
@@ -67,7 +70,9 @@ impl<T: OuterConstraint, P: TOuterProgram<TAncestry = T::TAncestry>>
                 SimpleIdentifier::new_wrapped(self.get_call().unwrap()),
             )),
             AOption(ROption::RSome(Dialect::Bash(_)))
-            | AOption(ROption::RSome(Dialect::Presto(_))) => Ok(AST::SimpleIdentifier(
+            | AOption(ROption::RSome(Dialect::Presto(_)))
+            | AOption(ROption::RSome(Dialect::DuckDB(_)))
+            | AOption(ROption::RSome(Dialect::Spark(_))) => Ok(AST::SimpleIdentifier(
                 SimpleIdentifier::new_wrapped("ShellTask".into()),
             )),
             AOption(ROption::RNone) => Ok(AST::SimpleIdentifier(SimpleIdentifier::new_wrapped(
@@ -85,6 +90,8 @@ impl<T: OuterConstraint, P: TOuterProgram<TAncestry = T::TAncestry>>
                 Ok(AVec::new())
             }
             (_, AOption(ROption::RSome(Dialect::Presto(_)))) => Ok(AVec::new()),
+            (_, AOption(ROption::RSome(Dialect::DuckDB(_)))) => Ok(AVec::new()),
+            (_, AOption(ROption::RSome(Dialect::Spark(_)))) => Ok(AVec::new()),
             (_, AOption(ROption::RSome(Dialect::Bash(_)))) => Ok(AVec::new()),
             (_, AOption(ROption::RNone)) => Ok(vec![AST::StringLiteral(
                 StringLiteral::new_wrapped(self.constraint.read().get_name().clone(), false),
@@ -100,18 +107,32 @@ impl<T: OuterConstraint, P: TOuterProgram<TAncestry = T::TAncestry>>
                 AOption(ROption::RSome(ref p)) => Ok(p.get_kwargs()),
                 AOption(ROption::RNone) => Ok(LinkedHashMap::new()),
             },
-            AOption(ROption::RSome(Dialect::Presto(_))) => {
-                let raw_command = format!("presto -e '{}'", self.get_call().unwrap().clone());
-                let format_string = StringLiteral::new_wrapped(raw_command.as_str().into(), true);
-                let command = match self.params {
-                    AOption(ROption::RSome(ref p)) => AST::Formatted(Formatted::new_wrapped(
-                        AST::StringLiteral(format_string),
-                        p.get_kwargs(),
-                    )),
-                    AOption(ROption::RNone) => AST::StringLiteral(format_string),
+            // queries are fed through a here-document, so that their quotes
+            // need no escaping
+            AOption(ROption::RSome(
+                ref dialect @ (Dialect::Presto(_) | Dialect::DuckDB(_) | Dialect::Spark(_)),
+            )) => {
+                let kwargs = match self.params {
+                    AOption(ROption::RSome(ref p)) => p.get_kwargs(),
+                    AOption(ROption::RNone) => LinkedHashMap::new(),
                 };
+                let sql = fill_sql(self.get_call().unwrap().as_str(), &kwargs);
+                let client = sql_command(dialect, &kwargs);
+                let command = vec![pipe_sql(client.clone(), sql.as_str())]
+                    .into_iter()
+                    .chain(
+                        SchemaChange::from_kwargs(&kwargs)
+                            .iter()
+                            .map(|x| x.to_statement(&client)),
+                    )
+                    .map(|x| x.to_bash().as_str().to_string())
+                    .collect::<AVec<String>>()
+                    .join("\n");
                 let mut keywords: LinkedHashMap<AString, AST> = LinkedHashMap::new();
-                keywords.insert("command".into(), command);
+                keywords.insert(
+                    "command".into(),
+                    AST::StringLiteral(StringLiteral::new_wrapped(command.as_str().into(), true)),
+                );
                 Ok(keywords)
             }
             AOption(ROption::RSome(Dialect::Bash(_))) => {
@@ -178,6 +199,12 @@ impl<T: OuterConstraint, P: TOuterProgram<TAncestry = T::TAncestry>>
     pub fn get_key(&self) -> AOption<AString> {
         self.key.clone()
     }
+    pub fn set_execution_policy(&mut self, policy: AOption<TaskPolicy>) {
+        self.execution_policy = policy;
+    }
+    pub fn get_execution_policy(&self) -> AOption<TaskPolicy> {
+        self.execution_policy.clone()
+    }
     pub fn find_best_program<'b>(
         preferences: &AVec<Dialect>,
         programs: &'b AVec<P>,
@@ -216,12 +243,21 @@ impl<T: OuterConstraint, P: TOuterProgram<TAncestry = T::TAncestry>>
             );
         }
     }
-    pub fn get_dedup_key(&self) -> (AString, AString, ParameterTuple, AOption<Dialect>) {
+    pub fn get_dedup_key(
+        &self,
+    ) -> (
+        AString,
+        AString,
+        ParameterTuple,
+        AOption<Dialect>,
+        AOption<TaskPolicy>,
+    ) {
         (
             self.preamble.as_ref().unwrap().clone(),
             self.call.as_ref().unwrap().clone(),
             self.params.as_ref().unwrap().clone(),
             self.dialect.clone(),
+            self.execution_policy.clone(),
         )
     }
     pub fn new(
@@ -278,6 +314,7 @@ impl<T: OuterConstraint, P: TOuterProgram<TAncestry = T::TAncestry>>
             task_name: AOption(ROption::RNone),
             // will accumulate dependencies' contexts as they are satisfied
             context: Context::new(),
+            execution_policy: AOption(ROption::RNone),
         })
     }
     pub fn compute_task_key(&mut self) -> AString {
@@ -1,6 +1,8 @@
 use abi_stable::std_types::ROption;
 use crate::code::CodeBlock;
 use crate::code::CodeBlockWithDefaultConstructor;
+use crate::code::FlowDependencies;
+use crate::code::ParamsFiles;
 use crate::constraint::TConstraintEnum;
 use crate::constraint::{OuterConstraint, TBuilder};
 use crate::constraint_block::ConstraintBlock;
@@ -8,7 +10,7 @@ use crate::constraint_state::ConstraintState;
 use crate::flow::{FlowBuilderBase, FlowBuilderMaterialize};
 use crate::parameter_tuple::ParameterTuple;
 use crate::program::TOuterProgram;
-use aorist_primitives::Dialect;
+use aorist_primitives::{Dialect, ExecutionPolicies, Schedule};
 //use crate::task_name_shortener::TaskNameShortener;
 use abi_stable::external_types::parking_lot::rw_lock::{RReadGuard, RRwLock};
 use abi_stable::std_types::RArc;
@@ -229,6 +231,11 @@ where
         for (id, state) in block.clone() {
             let mut write = state.write();
 
+            let policy = self.get_execution_policies().resolve(
+                &constraint_name,
+                write.get_ancestors().iter().rev().map(|x| x.get_key()),
+            );
+            write.set_execution_policy(policy);
             write.compute_task_key();
             drop(write);
 
@@ -316,6 +323,8 @@ where
                 tasks_dict.clone(),
                 identifiers,
                 self.get_render_dependencies(),
+                self.get_params_files(),
+                self.get_dynamic_mapping(),
             )?;
             for (key, val) in block.get_identifiers() {
                 for mapped_key in uuid_mappings.get(&key).unwrap().iter() {
@@ -329,6 +338,10 @@ where
         Ok((blocks, tasks_dict))
     }
     fn get_render_dependencies(&self) -> bool;
+    fn get_params_files(&self) -> AOption<ParamsFiles>;
+    fn get_dynamic_mapping(&self) -> bool;
+    fn get_schedule(&self) -> AOption<Schedule>;
+    fn get_execution_policies(&self) -> &ExecutionPolicies;
     fn get_constraint_explanation(
         &self,
         constraint_name: &AString,
@@ -399,8 +412,8 @@ where
     }
     fn get_programs_for(&self, constraint_name: &AString) -> AVec<P>;
     fn get_endpoints(&self) -> U::TEndpoints;
-    fn get_dependencies(&self) -> AVec<AString>;
-    fn run(&mut self, flow_name: AOption<AString>) -> Result<(AString, AVec<AString>)> {
+    fn get_dependencies(&self) -> FlowDependencies;
+    fn run(&mut self, flow_name: AOption<AString>) -> Result<(AString, FlowDependencies)> {
         self.satisfy_constraints()?;
         let etl = D::new();
         let endpoints = self.get_endpoints().clone();
@@ -410,9 +423,11 @@ where
             .map(|x| x.get_statements(endpoints.clone()))
             .collect::<AVec<_>>();
 
+        let mut dependencies = self.get_dependencies();
+        dependencies.extend(etl.get_dependencies(&statements_and_preambles, self.get_schedule()));
         Ok((
-            etl.materialize(statements_and_preambles, flow_name)?,
-            self.get_dependencies(),
+            etl.materialize(statements_and_preambles, flow_name, self.get_schedule())?,
+            dependencies,
         ))
     }
     fn get_blocks(&self) -> &AVec<Self::CB>;
@@ -426,6 +441,10 @@ where
         programs: LinkedHashMap<AString, AVec<P>>,
         preferences: AVec<Dialect>,
         render_dependencies: bool,
+        params_files: AOption<ParamsFiles>,
+        dynamic_mapping: bool,
+        schedule: AOption<Schedule>,
+        execution_policies: ExecutionPolicies,
     ) -> Self;
 
     fn generate_constraint_states_map(
@@ -727,6 +746,10 @@ where
         programs: LinkedHashMap<AString, AVec<P>>,
         preferences: AVec<Dialect>,
         render_dependencies: bool,
+        params_files: AOption<ParamsFiles>,
+        dynamic_mapping: bool,
+        schedule: AOption<Schedule>,
+        execution_policies: ExecutionPolicies,
     ) -> Result<Self>
     where
         Self: Sized,
@@ -777,6 +800,10 @@ where
             programs,
             preferences,
             render_dependencies,
+            params_files,
+            dynamic_mapping,
+            schedule,
+            execution_policies,
         ))
     }
     fn generate_family_trees(
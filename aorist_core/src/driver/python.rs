@@ -1,21 +1,30 @@
 use abi_stable::std_types::ROption;
+use crate::code::{FlowDependencies, ParamsFiles};
 use crate::constraint::TConstraintEnum;
 use crate::constraint::{OuterConstraint, TBuilder};
+use crate::constraint_block::ConstraintBlock;
 use crate::constraint_state::ConstraintState;
 use crate::driver::{ConstraintsBlockMap, Driver};
-use crate::flow::{ETLFlow, FlowBuilderBase, PythonBasedFlowBuilder};
+use crate::flow::{
+    ETLFlow, FlowBuilderBase, FlowBuilderInput, FlowBuilderMaterialize, PythonBasedFlowBuilder,
+};
 use crate::program::TOuterProgram;
-use crate::python::{PythonBasedConstraintBlock, PythonImport, PythonPreamble};
+use crate::python::{
+    get_failures_init, get_report_failures, get_run_checks, get_validation_preamble,
+    PythonBasedConstraintBlock, PythonFlowBuilderInput, PythonImport, PythonPreamble,
+};
 use abi_stable::external_types::parking_lot::rw_lock::RRwLock;
 use abi_stable::std_types::RArc;
 use anyhow::Result;
-use aorist_ast::AncestorRecord;
-use aorist_primitives::Dialect;
+use aorist_ast::{AncestorRecord, AST};
+use aorist_primitives::{
+    Dialect, ExecutionPolicies, Schedule, ValidationCheck, ValidationChecks,
+};
 use aorist_primitives::{Ancestry, AoristConceptBase, AoristUniverse, ToplineConcept};
 use aorist_util::{AString, AVec, AOption, AUuid, ATaskId};
 use linked_hash_map::LinkedHashMap;
 use linked_hash_set::LinkedHashSet;
-use std::collections::{BTreeSet, HashMap};
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::marker::PhantomData;
 
 pub struct PythonBasedDriver<B, D, U, C, A, P>
@@ -47,6 +56,10 @@ where
     programs: LinkedHashMap<AString, AVec<P>>,
     preferences: AVec<Dialect>,
     render_dependencies: bool,
+    params_files: AOption<ParamsFiles>,
+    dynamic_mapping: bool,
+    schedule: AOption<Schedule>,
+    execution_policies: ExecutionPolicies,
 }
 impl<B, D, U, C, A, P> Driver<B, D, U, C, A, P> for PythonBasedDriver<B, D, U, C, A, P>
 where
@@ -123,21 +136,42 @@ where
     fn get_blocks(&self) -> &AVec<Self::CB> {
         &self.blocks
     }
-    fn get_dependencies(&self) -> AVec<AString> {
-        self.satisfied_constraints
-            .values()
-            .map(|x| match x.read().get_dialect() {
+    fn get_dependencies(&self) -> FlowDependencies {
+        let mut dependencies = FlowDependencies::new();
+        for state in self.satisfied_constraints.values() {
+            let read = state.read();
+            match read.get_dialect() {
                 AOption(ROption::RSome(Dialect::Python(x))) => {
-                    AOption(ROption::RSome(x.get_pip_requirements()))
+                    for requirement in x.get_pip_requirements() {
+                        dependencies.add_pip_requirement(requirement);
+                    }
                 }
-                _ => AOption(ROption::RNone),
-            })
-            .filter(|x| x.is_some())
-            .map(|x| x.unwrap().into_iter())
-            .flatten()
-            .collect::<BTreeSet<AString>>()
-            .into_iter()
-            .collect()
+                AOption(ROption::RSome(Dialect::R(_))) => {
+                    // rpy2 is built against the R headers
+                    dependencies.add_system_package("r-base".into());
+                    dependencies.add_system_package("r-base-dev".into());
+                    if let AOption(ROption::RSome(preamble)) = read.get_preamble() {
+                        dependencies.add_r_packages_from(preamble.as_str());
+                    }
+                    if let AOption(ROption::RSome(call)) = read.get_call() {
+                        dependencies.add_r_packages_from(call.as_str());
+                    }
+                }
+                AOption(ROption::RSome(Dialect::Presto(_))) => {
+                    dependencies.add_pip_requirement("trino".into());
+                }
+                AOption(ROption::RSome(Dialect::DuckDB(_))) => {
+                    dependencies.add_pip_requirement("duckdb".into());
+                }
+                AOption(ROption::RSome(Dialect::Spark(_))) => {
+                    // pyspark runs Spark on the JVM
+                    dependencies.add_pip_requirement("pyspark".into());
+                    dependencies.add_system_package("default-jre-headless".into());
+                }
+                _ => {}
+            }
+        }
+        dependencies
     }
     fn _new(
         concepts: RArc<RRwLock<HashMap<ATaskId, C>>>,
@@ -149,6 +183,10 @@ where
         programs: LinkedHashMap<AString, AVec<P>>,
         preferences: AVec<Dialect>,
         render_dependencies: bool,
+        params_files: AOption<ParamsFiles>,
+        dynamic_mapping: bool,
+        schedule: AOption<Schedule>,
+        execution_policies: ExecutionPolicies,
     ) -> Self {
         Self {
             concepts,
@@ -166,10 +204,151 @@ where
             topline_constraint_names,
             programs,
             preferences,
-            render_dependencies
+            render_dependencies,
+            params_files,
+            dynamic_mapping,
+            schedule,
+            execution_policies,
         }
     }
     fn get_render_dependencies(&self) -> bool {
         self.render_dependencies
     }
+    fn get_params_files(&self) -> AOption<ParamsFiles> {
+        self.params_files.clone()
+    }
+    fn get_dynamic_mapping(&self) -> bool {
+        self.dynamic_mapping
+    }
+    fn get_schedule(&self) -> AOption<Schedule> {
+        self.schedule.clone()
+    }
+    fn get_execution_policies(&self) -> &ExecutionPolicies {
+        &self.execution_policies
+    }
+}
+impl<B, D, U, C, A, P> PythonBasedDriver<B, D, U, C, A, P>
+where
+    U: AoristConceptBase + AoristUniverse,
+    B: TBuilder<TEnum = C, TAncestry = A>,
+    D: FlowBuilderBase<U> + PythonBasedFlowBuilder<U>,
+    <D as FlowBuilderBase<U>>::T:
+        ETLFlow<U, ImportType = PythonImport, PreambleType = PythonPreamble>,
+    A: Ancestry,
+    C: ToplineConcept<TUniverse = U>,
+    <B as TBuilder>::OuterType: OuterConstraint<TAncestry = A>,
+    <<B as TBuilder>::OuterType as OuterConstraint>::TAncestry: Ancestry<TConcept = C>,
+    <<<B as TBuilder>::OuterType as OuterConstraint>::TAncestry as Ancestry>::TConcept:
+        ToplineConcept<TUniverse = U>,
+    P: TOuterProgram<TAncestry = A>,
+{
+    /// Builds a flow that, instead of running the tasks, checks their
+    /// preconditions and reports all failures at once. Checks derived from
+    /// a concept are reported against the first task rooted at the nearest
+    /// ancestor of that concept rooting any task, and pip requirements
+    /// against the first Python task needing them.
+    pub fn validate(
+        &mut self,
+        checks: &ValidationChecks,
+        flow_name: AOption<AString>,
+    ) -> Result<(AString, FlowDependencies)> {
+        self.satisfy_constraints()?;
+        let mut by_constraint: LinkedHashMap<AString, Vec<_>> = self
+            .blocks
+            .iter()
+            .map(|x| (x.get_constraint_name(), Vec::new()))
+            .collect();
+        for state in self.satisfied_constraints.values() {
+            if let Some(states) = by_constraint.get_mut(&state.read().get_name()) {
+                states.push(state.clone());
+            }
+        }
+        let mut roots: HashMap<ATaskId, AString> = HashMap::new();
+        let mut tasks = AVec::new();
+        for (constraint_name, mut states) in by_constraint.into_iter() {
+            states.sort_by_key(|x| x.read().get_fully_qualified_task_name());
+            for state in states {
+                let read = state.read();
+                let task_name = read.get_fully_qualified_task_name();
+                if let Some(root) = read.get_ancestors().last() {
+                    roots.entry(root.get_key()).or_insert(task_name.clone());
+                }
+                tasks.push((constraint_name.clone(), task_name, read.get_dialect()));
+            }
+        }
+
+        let mut task_checks: HashMap<AString, AVec<ValidationCheck>> = HashMap::new();
+        for (id, concept_checks) in checks.get_concept_checks() {
+            let task_name = self.ancestors.get(id).and_then(|x| {
+                x.iter()
+                    .rev()
+                    .filter_map(|ancestor| roots.get(&ancestor.get_key()))
+                    .next()
+            });
+            if let Some(task_name) = task_name {
+                let entry = task_checks.entry(task_name.clone()).or_insert(AVec::new());
+                for check in concept_checks.iter() {
+                    entry.push(check.clone());
+                }
+            }
+        }
+        let mut seen: HashSet<ValidationCheck> = HashSet::new();
+        let mut statements: LinkedHashMap<AString, AVec<AST>> = LinkedHashMap::new();
+        for (constraint_name, task_name, dialect) in tasks {
+            let mut checks = task_checks.remove(&task_name).unwrap_or(AVec::new());
+            if let AOption(ROption::RSome(Dialect::Python(x))) = dialect {
+                for requirement in x.get_pip_requirements() {
+                    checks.push(ValidationCheck::PackageInstalled(requirement));
+                }
+            }
+            let checks = checks
+                .into_iter()
+                .filter(|x| seen.insert(x.clone()))
+                .collect::<AVec<_>>();
+            if !checks.is_empty() {
+                statements
+                    .entry(constraint_name)
+                    .or_insert(AVec::new())
+                    .push(get_run_checks(task_name, &checks));
+            }
+        }
+
+        let mut endpoint_statements = vec![get_failures_init()];
+        if !checks.get_endpoint_checks().is_empty() {
+            endpoint_statements.push(get_run_checks(
+                "endpoints".into(),
+                checks.get_endpoint_checks(),
+            ));
+        }
+        let mut inputs = vec![PythonFlowBuilderInput::new(
+            endpoint_statements.into_iter().collect(),
+            vec![get_validation_preamble()].into_iter().collect(),
+            BTreeSet::new(),
+            "endpoints".into(),
+            AOption(ROption::RSome("Check that endpoints are reachable".into())),
+            AOption(ROption::RNone),
+        )];
+        for (constraint_name, constraint_statements) in statements.into_iter() {
+            let (title, body) = self.get_constraint_explanation(&constraint_name);
+            inputs.push(PythonFlowBuilderInput::statements_only(
+                constraint_statements,
+                constraint_name,
+                title,
+                body,
+            ));
+        }
+        inputs.push(PythonFlowBuilderInput::statements_only(
+            vec![get_report_failures()].into_iter().collect(),
+            "report_failures".into(),
+            AOption(ROption::RSome("Report all failed checks".into())),
+            AOption(ROption::RNone),
+        ));
+        let inputs = inputs.into_iter().collect();
+        let etl = D::new();
+        let dependencies = etl.get_dependencies(&inputs, AOption(ROption::RNone));
+        Ok((
+            etl.materialize(inputs, flow_name, AOption(ROption::RNone))?,
+            dependencies,
+        ))
+    }
 }
@@ -1,19 +1,21 @@
-use crate::flow::etl_flow::ETLFlow;
+use crate::flow::etl_flow::{varies_with, ETLFlow};
 use crate::flow::flow_builder::FlowBuilderBase;
 use crate::flow::python_based_flow::PythonBasedFlow;
 use crate::flow::python_based_flow_builder::PythonBasedFlowBuilder;
 use crate::python::{
-    BashPythonTask, ConstantPythonTask, NativePythonPreamble, NativePythonTask, PrestoPythonTask,
-    PythonFlowBuilderInput, PythonImport, PythonPreamble, PythonTask, RPythonTask,
+    BashPythonTask, ConstantPythonTask, DuckDBPythonTask, NativePythonPreamble, NativePythonTask,
+    PrestoPythonTask, PythonFlowBuilderInput, PythonImport, PythonPreamble, PythonTask,
+    RPythonTask, SparkPythonTask,
 };
 use abi_stable::std_types::ROption;
 use aorist_ast::{
     Assignment, Attribute, BigIntLiteral, BooleanLiteral, Call, Dict, Expression, Formatted, List,
-    None, SimpleIdentifier, StringLiteral, AST,
+    ListComp, None, SimpleIdentifier, StringLiteral, AST,
 };
 use aorist_primitives::AoristUniverse;
 use aorist_primitives::Dialect;
-use aorist_primitives::TPrestoEndpoints;
+use aorist_primitives::{Schedule, ScheduleFrequency, TaskPolicy};
+use aorist_primitives::{TPrestoEndpoints, TSparkEndpoints};
 use aorist_util::AOption;
 use aorist_util::{AString, AVec};
 use linked_hash_map::LinkedHashMap;
@@ -22,7 +24,7 @@ use std::marker::PhantomData;
 #[derive(Clone, Hash, PartialEq)]
 pub struct AirflowPythonBasedFlow<U: AoristUniverse>
 where
-    U::TEndpoints: TPrestoEndpoints,
+    U::TEndpoints: TPrestoEndpoints + TSparkEndpoints,
 {
     task_id: AST,
     task_val: AST,
@@ -34,11 +36,12 @@ where
     dialect: AOption<Dialect>,
     endpoints: U::TEndpoints,
     node: PythonTask,
+    execution_policy: AOption<TaskPolicy>,
     _universe: PhantomData<U>,
 }
 impl<U: AoristUniverse> PythonBasedFlow<U> for AirflowPythonBasedFlow<U>
 where
-    U::TEndpoints: TPrestoEndpoints,
+    U::TEndpoints: TPrestoEndpoints + TSparkEndpoints,
 {
     fn get_preamble_string(&self) -> AOption<AString> {
         self.preamble.clone()
@@ -46,7 +49,7 @@ where
 }
 impl<U: AoristUniverse> AirflowPythonBasedFlow<U>
 where
-    U::TEndpoints: TPrestoEndpoints,
+    U::TEndpoints: TPrestoEndpoints + TSparkEndpoints,
 {
     fn compute_task_args(&self) -> AVec<AST> {
         AVec::new()
@@ -61,6 +64,8 @@ where
                 AOption(ROption::RSome(Dialect::Python(_)))
                 | AOption(ROption::RSome(Dialect::R(_)))
                 | AOption(ROption::RSome(Dialect::Presto(_)))
+                | AOption(ROption::RSome(Dialect::DuckDB(_)))
+                | AOption(ROption::RSome(Dialect::Spark(_)))
                 | AOption(ROption::RNone) => "python_callable".into(),
                 AOption(ROption::RSome(Dialect::Bash(_))) => "bash_command".into(),
             };
@@ -87,6 +92,8 @@ where
             if let AOption(ROption::RSome(Dialect::Python(_)))
             | AOption(ROption::RSome(Dialect::R(_)))
             | AOption(ROption::RSome(Dialect::Presto(_)))
+            | AOption(ROption::RSome(Dialect::DuckDB(_)))
+            | AOption(ROption::RSome(Dialect::Spark(_)))
             | AOption(ROption::RNone) = self.dialect
             {
                 let call = self.node.get_call().unwrap();
@@ -112,8 +119,132 @@ where
             AST::SimpleIdentifier(SimpleIdentifier::new_wrapped("dag".into())),
         );
         kwargs.insert("task_id".into(), self.task_id.clone());
+        if let AOption(ROption::RSome(ref policy)) = self.execution_policy {
+            kwargs.extend(Self::compute_policy_kwargs(policy));
+        }
         kwargs
     }
+    fn compute_policy_kwargs(policy: &TaskPolicy) -> LinkedHashMap<AString, AST> {
+        let mut kwargs = LinkedHashMap::new();
+        if let AOption(ROption::RSome(retries)) = policy.retries {
+            kwargs.insert(
+                "retries".into(),
+                AST::BigIntLiteral(BigIntLiteral::new_wrapped(retries)),
+            );
+        }
+        if let AOption(ROption::RSome(delay)) = policy.retry_delay {
+            kwargs.insert(
+                "retry_delay".into(),
+                AST::BigIntLiteral(BigIntLiteral::new_wrapped(delay)),
+            );
+        }
+        if let AOption(ROption::RSome(backoff)) = policy.exponential_backoff {
+            kwargs.insert(
+                "retry_exponential_backoff".into(),
+                AST::BooleanLiteral(BooleanLiteral::new_wrapped(backoff)),
+            );
+        }
+        if let AOption(ROption::RSome(timeout)) = policy.timeout {
+            let mut timedelta_kwargs = LinkedHashMap::new();
+            timedelta_kwargs.insert(
+                "seconds".into(),
+                AST::BigIntLiteral(BigIntLiteral::new_wrapped(timeout)),
+            );
+            kwargs.insert(
+                "execution_timeout".into(),
+                AST::Call(Call::new_wrapped(
+                    AST::SimpleIdentifier(SimpleIdentifier::new_wrapped("timedelta".into())),
+                    AVec::new(),
+                    timedelta_kwargs,
+                )),
+            );
+        }
+        if let AOption(ROption::RSome(ref pool)) = policy.pool {
+            kwargs.insert(
+                "pool".into(),
+                AST::StringLiteral(StringLiteral::new_wrapped(pool.clone(), false)),
+            );
+        }
+        if policy.has_resources() {
+            kwargs.insert(
+                "executor_config".into(),
+                Self::compute_executor_config(policy),
+            );
+        }
+        kwargs
+    }
+    fn k8s_call(name: &str, kwargs: LinkedHashMap<AString, AST>) -> AST {
+        AST::Call(Call::new_wrapped(
+            AST::Attribute(Attribute::new_wrapped(
+                AST::SimpleIdentifier(SimpleIdentifier::new_wrapped("k8s".into())),
+                name.into(),
+                false,
+            )),
+            AVec::new(),
+            kwargs,
+        ))
+    }
+    /// Resource requests of the task's pod, for the KubernetesExecutor:
+    /// {"pod_override": k8s.V1Pod(spec=k8s.V1PodSpec(containers=[
+    ///     k8s.V1Container(name="base", resources=k8s.V1ResourceRequirements(
+    ///         requests={"memory": ..., "cpu": ...}))]))}
+    fn compute_executor_config(policy: &TaskPolicy) -> AST {
+        let mut requests = LinkedHashMap::new();
+        for (name, value) in [("memory", &policy.memory), ("cpu", &policy.cpu)] {
+            if let AOption(ROption::RSome(ref x)) = value {
+                requests.insert(
+                    name.into(),
+                    AST::StringLiteral(StringLiteral::new_wrapped(x.clone(), false)),
+                );
+            }
+        }
+        let mut resources = LinkedHashMap::new();
+        resources.insert("requests".into(), AST::Dict(Dict::new_wrapped(requests)));
+        let mut container = LinkedHashMap::new();
+        container.insert(
+            "name".into(),
+            AST::StringLiteral(StringLiteral::new_wrapped("base".into(), false)),
+        );
+        container.insert(
+            "resources".into(),
+            Self::k8s_call("V1ResourceRequirements", resources),
+        );
+        let mut spec = LinkedHashMap::new();
+        spec.insert(
+            "containers".into(),
+            AST::List(List::new_wrapped(
+                vec![Self::k8s_call("V1Container", container)]
+                    .into_iter()
+                    .collect(),
+                false,
+            )),
+        );
+        let mut pod = LinkedHashMap::new();
+        pod.insert("spec".into(), Self::k8s_call("V1PodSpec", spec));
+        let mut config = LinkedHashMap::new();
+        config.insert("pod_override".into(), Self::k8s_call("V1Pod", pod));
+        AST::Dict(Dict::new_wrapped(config))
+    }
+    fn get_creation_statements(&self, creation_expr: AST) -> AVec<AST> {
+        let mut statements = vec![AST::Assignment(Assignment::new_wrapped(
+            self.task_val.clone(),
+            creation_expr,
+        ))];
+        if let AOption(ROption::RSome(ref dependencies)) = self.dep_list {
+            statements.push(AST::Expression(Expression::new_wrapped(AST::Call(
+                Call::new_wrapped(
+                    AST::Attribute(Attribute::new_wrapped(
+                        self.get_task_val(),
+                        "set_upstream".into(),
+                        false,
+                    )),
+                    vec![dependencies.clone()].into_iter().collect(),
+                    LinkedHashMap::new(),
+                ),
+            ))));
+        }
+        statements.into_iter().collect()
+    }
     fn compute_task_call(&self) -> AST {
         match self.dialect {
             AOption(ROption::RSome(Dialect::Python(_))) => {
@@ -122,7 +253,9 @@ where
             AOption(ROption::RSome(Dialect::Bash(_))) => {
                 AST::SimpleIdentifier(SimpleIdentifier::new_wrapped("BashOperator".into()))
             }
-            AOption(ROption::RSome(Dialect::Presto(_))) => {
+            AOption(ROption::RSome(Dialect::Presto(_)))
+            | AOption(ROption::RSome(Dialect::DuckDB(_)))
+            | AOption(ROption::RSome(Dialect::Spark(_))) => {
                 AST::SimpleIdentifier(SimpleIdentifier::new_wrapped("PythonOperator".into()))
             }
             AOption(ROption::RSome(Dialect::R(_))) => {
@@ -136,15 +269,17 @@ where
 }
 impl<U: AoristUniverse> ETLFlow<U> for AirflowPythonBasedFlow<U>
 where
-    U::TEndpoints: TPrestoEndpoints,
+    U::TEndpoints: TPrestoEndpoints + TSparkEndpoints,
 {
     type ImportType = PythonImport;
     type PreambleType = PythonPreamble;
     type ErrorType = pyo3::PyErr;
     fn get_imports(&self) -> AVec<PythonImport> {
-        match self.dialect {
+        let mut imports = match self.dialect {
             AOption(ROption::RSome(Dialect::Python(_)))
-            | AOption(ROption::RSome(Dialect::R(_))) => vec![PythonImport::PythonFromImport(
+            | AOption(ROption::RSome(Dialect::R(_)))
+            | AOption(ROption::RSome(Dialect::DuckDB(_)))
+            | AOption(ROption::RSome(Dialect::Spark(_))) => vec![PythonImport::PythonFromImport(
                 "airflow.operators.python_operator".into(),
                 "PythonOperator".into(),
                 AOption(ROption::RNone),
@@ -162,9 +297,24 @@ where
                 "DummyOperator".into(),
                 AOption(ROption::RNone),
             )],
+        };
+        if let AOption(ROption::RSome(ref policy)) = self.execution_policy {
+            if policy.timeout.is_some() {
+                imports.push(PythonImport::PythonFromImport(
+                    "datetime".into(),
+                    "timedelta".into(),
+                    AOption(ROption::RNone),
+                ));
+            }
+            if policy.has_resources() {
+                imports.push(PythonImport::PythonFromImport(
+                    "kubernetes.client".into(),
+                    "models".into(),
+                    AOption(ROption::RSome("k8s".into())),
+                ));
+            }
         }
-        .into_iter()
-        .collect()
+        imports.into_iter().collect()
     }
     fn get_preamble(&self) -> Result<AVec<PythonPreamble>, pyo3::PyErr> {
         // TODO: this should be deprecated
@@ -194,24 +344,56 @@ where
             self.compute_task_args(),
             self.compute_task_kwargs(),
         ));
-        let mut statements = vec![AST::Assignment(Assignment::new_wrapped(
-            self.task_val.clone(),
-            creation_expr,
-        ))];
+        self.get_creation_statements(creation_expr)
+    }
+    /// Dynamic task mapping (Airflow 2.3+):
+    /// task = Operator.partial(**fixed_kwargs).expand(kwarg=[... for ...])
+    fn get_mapped_statements(&self, generator: AST) -> AOption<(AVec<AST>, AVec<PythonImport>)> {
+        if varies_with(&self.task_id, &generator) {
+            return AOption(ROption::RNone);
+        }
         if let AOption(ROption::RSome(ref dependencies)) = self.dep_list {
-            statements.push(AST::Expression(Expression::new_wrapped(AST::Call(
-                Call::new_wrapped(
-                    AST::Attribute(Attribute::new_wrapped(
-                        self.get_task_val(),
-                        "set_upstream".into(),
-                        false,
-                    )),
-                    vec![dependencies.clone()].into_iter().collect(),
-                    LinkedHashMap::new(),
-                ),
-            ))));
+            if varies_with(dependencies, &generator) {
+                return AOption(ROption::RNone);
+            }
         }
-        statements.into_iter().collect()
+        let (expanded, fixed): (LinkedHashMap<AString, AST>, LinkedHashMap<AString, AST>) = self
+            .compute_task_kwargs()
+            .into_iter()
+            .partition(|(_, v)| varies_with(v, &generator));
+        // expand() over several kwargs maps over their cross product
+        if expanded.len() != 1 {
+            return AOption(ROption::RNone);
+        }
+        let partial = AST::Call(Call::new_wrapped(
+            AST::Attribute(Attribute::new_wrapped(
+                self.compute_task_call(),
+                "partial".into(),
+                false,
+            )),
+            self.compute_task_args(),
+            fixed,
+        ));
+        let creation_expr = AST::Call(Call::new_wrapped(
+            AST::Attribute(Attribute::new_wrapped(partial, "expand".into(), false)),
+            AVec::new(),
+            expanded
+                .into_iter()
+                .map(|(k, v)| {
+                    (
+                        k,
+                        AST::ListComp(ListComp::new_wrapped(
+                            v,
+                            vec![generator.clone()].into_iter().collect(),
+                        )),
+                    )
+                })
+                .collect(),
+        ));
+        AOption(ROption::RSome((
+            self.get_creation_statements(creation_expr),
+            self.get_imports(),
+        )))
     }
     fn new(
         task_id: AST,
@@ -223,9 +405,12 @@ where
         preamble: AOption<AString>,
         dialect: AOption<Dialect>,
         endpoints: U::TEndpoints,
+        execution_policy: AOption<TaskPolicy>,
     ) -> Self {
         let command = match &dialect {
-            AOption(ROption::RSome(Dialect::Presto(_))) => AST::StringLiteral(
+            AOption(ROption::RSome(Dialect::Presto(_)))
+            | AOption(ROption::RSome(Dialect::DuckDB(_)))
+            | AOption(ROption::RSome(Dialect::Spark(_))) => AST::StringLiteral(
                 StringLiteral::new_wrapped(call.as_ref().unwrap().clone(), true),
             ),
             AOption(ROption::RSome(_)) => AST::StringLiteral(StringLiteral::new_wrapped(
@@ -236,30 +421,50 @@ where
                 AST::StringLiteral(StringLiteral::new_wrapped("Done".into(), false))
             }
         };
+        let sql_kwargs = || -> LinkedHashMap<AString, AST> {
+            kwargs
+                .iter()
+                .map(|(k, v)| {
+                    (
+                        k.clone(),
+                        match *v {
+                            AST::StringLiteral(ref x) => AST::StringLiteral(
+                                StringLiteral::new_wrapped(x.read().value().clone(), true),
+                            ),
+                            _ => v.clone(),
+                        },
+                    )
+                })
+                .collect()
+        };
         let node = match &dialect {
             AOption(ROption::RSome(Dialect::Presto(_))) => {
                 let presto_endpoints = endpoints.presto_config();
                 PythonTask::PrestoPythonTask(PrestoPythonTask::new_wrapped(
                     command,
-                    kwargs
-                        .iter()
-                        .map(|(k, v)| {
-                            (
-                                k.clone(),
-                                match *v {
-                                    AST::StringLiteral(ref x) => AST::StringLiteral(
-                                        StringLiteral::new_wrapped(x.read().value().clone(), true),
-                                    ),
-                                    _ => v.clone(),
-                                },
-                            )
-                        })
-                        .collect(),
+                    sql_kwargs(),
                     task_val.clone(),
                     presto_endpoints,
                     dep_list.clone(),
                 ))
             }
+            AOption(ROption::RSome(Dialect::DuckDB(_))) => {
+                PythonTask::DuckDBPythonTask(DuckDBPythonTask::new_wrapped(
+                    command,
+                    sql_kwargs(),
+                    task_val.clone(),
+                    dep_list.clone(),
+                ))
+            }
+            AOption(ROption::RSome(Dialect::Spark(_))) => {
+                PythonTask::SparkPythonTask(SparkPythonTask::new_wrapped(
+                    command,
+                    sql_kwargs(),
+                    task_val.clone(),
+                    endpoints.spark_config(),
+                    dep_list.clone(),
+                ))
+            }
             AOption(ROption::RSome(Dialect::Bash(_))) => {
                 PythonTask::BashPythonTask(BashPythonTask::new_wrapped(
                     command,
@@ -311,6 +516,7 @@ where
             dialect,
             endpoints,
             node,
+            execution_policy,
             _universe: PhantomData,
         }
     }
@@ -324,7 +530,7 @@ pub struct AirflowFlowBuilder<U: AoristUniverse> {
 }
 impl<U: AoristUniverse> FlowBuilderBase<U> for AirflowFlowBuilder<U>
 where
-    <U as AoristUniverse>::TEndpoints: TPrestoEndpoints,
+    <U as AoristUniverse>::TEndpoints: TPrestoEndpoints + TSparkEndpoints,
 {
     type T = AirflowPythonBasedFlow<U>;
     fn new() -> Self {
@@ -333,15 +539,42 @@ where
         }
     }
 }
+impl<U: AoristUniverse> AirflowFlowBuilder<U> {
+    fn datetime((year, month, day): (i64, i64, i64)) -> AST {
+        AST::Call(Call::new_wrapped(
+            AST::SimpleIdentifier(SimpleIdentifier::new_wrapped("datetime".into())),
+            vec![year, month, day]
+                .into_iter()
+                .map(|x| AST::BigIntLiteral(BigIntLiteral::new_wrapped(x)))
+                .collect(),
+            LinkedHashMap::new(),
+        ))
+    }
+    /// The partition a run is responsible for, as a Jinja template.
+    fn get_partition_date_template(frequency: &ScheduleFrequency) -> AString {
+        match frequency {
+            ScheduleFrequency::Daily | ScheduleFrequency::Weekly | ScheduleFrequency::Monthly => {
+                "{{ ds }}".into()
+            }
+            _ => format!(
+                "{{{{ data_interval_start.strftime('{}') }}}}",
+                frequency.get_partition_format()
+            )
+            .as_str()
+            .into(),
+        }
+    }
+}
 impl<U: AoristUniverse> PythonBasedFlowBuilder<U> for AirflowFlowBuilder<U>
 where
-    <U as AoristUniverse>::TEndpoints: TPrestoEndpoints,
+    <U as AoristUniverse>::TEndpoints: TPrestoEndpoints + TSparkEndpoints,
 {
     /// Takes a set of statements and mutates them so as make a valid ETL flow
     fn augment_statements(
         &self,
         mut statements: AVec<PythonFlowBuilderInput>,
         flow_name: AOption<AString>,
+        schedule: AOption<Schedule>,
     ) -> AVec<PythonFlowBuilderInput> {
         let default_args =
             AST::SimpleIdentifier(SimpleIdentifier::new_wrapped("default_args".into()));
@@ -400,21 +633,37 @@ where
                 false,
             )),
         );
-        kwargs.insert("schedule_interval".into(), AST::None(None::new_wrapped()));
+        let (start_date, end_date) = match schedule {
+            AOption(ROption::RSome(ref x)) => (x.get_start_date(), x.get_end_date()),
+            AOption(ROption::RNone) => (AOption(ROption::RNone), AOption(ROption::RNone)),
+        };
+        kwargs.insert(
+            "schedule_interval".into(),
+            match schedule {
+                AOption(ROption::RSome(ref x)) => AST::StringLiteral(StringLiteral::new_wrapped(
+                    x.get_frequency().get_airflow_schedule_interval(),
+                    false,
+                )),
+                AOption(ROption::RNone) => AST::None(None::new_wrapped()),
+            },
+        );
         kwargs.insert(
             "start_date".into(),
-            AST::Call(Call::new_wrapped(
-                AST::SimpleIdentifier(SimpleIdentifier::new_wrapped("datetime".into())),
-                vec![
-                    AST::BigIntLiteral(BigIntLiteral::new_wrapped(2021)),
-                    AST::BigIntLiteral(BigIntLiteral::new_wrapped(1)),
-                    AST::BigIntLiteral(BigIntLiteral::new_wrapped(1)),
-                ]
-                .into_iter()
-                .collect(),
-                LinkedHashMap::new(),
-            )),
+            Self::datetime(match start_date.clone() {
+                AOption(ROption::RSome(x)) => x,
+                AOption(ROption::RNone) => (2021, 1, 1),
+            }),
         );
+        if let AOption(ROption::RSome(x)) = end_date {
+            kwargs.insert("end_date".into(), Self::datetime(x));
+        }
+        if schedule.is_some() {
+            // only backfill if the assets say from when
+            kwargs.insert(
+                "catchup".into(),
+                AST::BooleanLiteral(BooleanLiteral::new_wrapped(start_date.is_some())),
+            );
+        }
         kwargs.insert(
             "tags".into(),
             AST::List(List::new_wrapped(
@@ -441,12 +690,22 @@ where
             kwargs,
         ));
         let dag_call_assign = AST::Assignment(Assignment::new_wrapped(dag, dag_call));
+        let mut setup = vec![default_args_assign, dag_call_assign];
+        if let AOption(ROption::RSome(ref x)) = schedule {
+            // rendered by Airflow in templated operator fields (and dropped
+            // as a dead assignment unless a task refers to it)
+            setup.push(AST::Assignment(Assignment::new_wrapped(
+                AST::SimpleIdentifier(SimpleIdentifier::new_wrapped("partition_date".into())),
+                AST::StringLiteral(StringLiteral::new_wrapped(
+                    Self::get_partition_date_template(x.get_frequency()),
+                    false,
+                )),
+            )));
+        }
         statements.insert(
             0,
             PythonFlowBuilderInput::statements_only(
-                vec![default_args_assign, dag_call_assign]
-                    .into_iter()
-                    .collect(),
+                setup.into_iter().collect(),
                 "Setting up Airflow FlowBuilder".into(),
                 AOption(ROption::RNone),
                 AOption(ROption::RNone),
@@ -454,7 +713,7 @@ where
         );
         statements
     }
-    fn get_flow_imports(&self) -> AVec<PythonImport> {
+    fn get_flow_imports(&self, _schedule: AOption<Schedule>) -> AVec<PythonImport> {
         vec![
             PythonImport::PythonFromImport("airflow".into(), "DAG".into(), AOption(ROption::RNone)),
             PythonImport::PythonFromImport(
@@ -467,3 +726,174 @@ where
         .collect()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aorist_ast::Comprehension;
+    use aorist_primitives::{PrestoConfig, SparkConfig};
+
+    #[derive(Clone, Hash, PartialEq)]
+    struct TestEndpoints {}
+    impl TPrestoEndpoints for TestEndpoints {
+        fn presto_config(&self) -> PrestoConfig {
+            unimplemented!()
+        }
+    }
+    impl TSparkEndpoints for TestEndpoints {
+        fn spark_config(&self) -> SparkConfig {
+            unimplemented!()
+        }
+    }
+    struct TestUniverse {}
+    impl AoristUniverse for TestUniverse {
+        type TEndpoints = TestEndpoints;
+        fn get_endpoints(&self) -> TestEndpoints {
+            TestEndpoints {}
+        }
+    }
+
+    fn identifier(name: &str) -> AST {
+        AST::SimpleIdentifier(SimpleIdentifier::new_wrapped(name.into()))
+    }
+    fn string(value: &str) -> AST {
+        AST::StringLiteral(StringLiteral::new_wrapped(value.into(), false))
+    }
+    // for (t, path) in params.items()
+    fn generator() -> AST {
+        AST::Comprehension(Comprehension::new_wrapped(
+            AST::Tuple(aorist_ast::Tuple::new_wrapped(
+                vec![identifier("t"), identifier("path")]
+                    .into_iter()
+                    .collect(),
+                true,
+            )),
+            AST::Call(Call::new_wrapped(
+                AST::Attribute(Attribute::new_wrapped(
+                    identifier("params"),
+                    "items".into(),
+                    false,
+                )),
+                AVec::new(),
+                LinkedHashMap::new(),
+            )),
+            AVec::new(),
+        ))
+    }
+    fn flow(
+        task_id: AST,
+        kwargs: Vec<(&str, AST)>,
+        dep_list: AOption<AST>,
+        dialect: AOption<Dialect>,
+    ) -> AirflowPythonBasedFlow<TestUniverse> {
+        <AirflowPythonBasedFlow<TestUniverse> as ETLFlow<TestUniverse>>::new(
+            task_id,
+            identifier("task"),
+            AOption(ROption::RSome("download".into())),
+            AVec::new(),
+            kwargs.into_iter().map(|(k, v)| (k.into(), v)).collect(),
+            dep_list,
+            AOption(ROption::RNone),
+            dialect,
+            TestEndpoints {},
+            AOption(ROption::RNone),
+        )
+    }
+    fn python() -> AOption<Dialect> {
+        AOption(ROption::RSome(Dialect::Python(
+            aorist_primitives::Python::new(vec![]),
+        )))
+    }
+
+    #[test]
+    fn test_mapped_statements_expand_the_varying_kwarg() {
+        let flow = flow(
+            string("download"),
+            vec![("path", identifier("path")), ("mode", string("w"))],
+            AOption(ROption::RNone),
+            python(),
+        );
+        let statements = match flow.get_mapped_statements(generator()) {
+            AOption(ROption::RSome((statements, _))) => statements,
+            AOption(ROption::RNone) => panic!("task should be mapped"),
+        };
+        assert_eq!(statements.len(), 1);
+        let expand = match statements[0] {
+            AST::Assignment(ref x) => match x.read().call() {
+                AST::Call(ref x) => x.clone(),
+                _ => panic!("task should be created with a call"),
+            },
+            _ => panic!("task should be assigned"),
+        };
+        let expand = expand.read();
+        // Operator.partial(**fixed).expand(op_kwargs=[... for ...])
+        let partial = match expand.function() {
+            AST::Attribute(ref x) => {
+                assert_eq!(x.read().name().as_str(), "expand");
+                match x.read().value() {
+                    AST::Call(ref x) => x.clone(),
+                    _ => panic!("expand should be called on partial()"),
+                }
+            }
+            _ => panic!("expand should be an attribute"),
+        };
+        assert_eq!(
+            expand
+                .keywords()
+                .iter()
+                .map(|(k, v)| (k.as_str(), matches!(v, AST::ListComp(_))))
+                .collect::<Vec<_>>(),
+            vec![("op_kwargs", true)]
+        );
+        let partial = partial.read();
+        match partial.function() {
+            AST::Attribute(ref x) => assert_eq!(x.read().name().as_str(), "partial"),
+            _ => panic!("partial should be an attribute"),
+        }
+        assert_eq!(
+            partial
+                .keywords()
+                .keys()
+                .map(|x| x.as_str())
+                .collect::<Vec<_>>(),
+            vec!["python_callable", "dag", "task_id"]
+        );
+    }
+    #[test]
+    fn test_mapped_statements_fall_back_to_a_loop() {
+        // task ids must be unique, so mapped tasks share a fixed one
+        let varying_task_id = flow(
+            identifier("t"),
+            vec![("path", identifier("path"))],
+            AOption(ROption::RNone),
+            python(),
+        );
+        assert!(varying_task_id.get_mapped_statements(generator()).is_none());
+        let varying_dependencies = flow(
+            string("download"),
+            vec![("path", identifier("path"))],
+            AOption(ROption::RSome(identifier("t"))),
+            python(),
+        );
+        assert!(varying_dependencies
+            .get_mapped_statements(generator())
+            .is_none());
+        // expand() over several kwargs would map over their cross product
+        let several_varying_kwargs = flow(
+            string("download"),
+            vec![("path", identifier("path")), ("table", identifier("t"))],
+            AOption(ROption::RNone),
+            AOption(ROption::RNone),
+        );
+        assert!(several_varying_kwargs
+            .get_mapped_statements(generator())
+            .is_none());
+        let fixed_kwargs = flow(
+            string("download"),
+            vec![("path", string("data.csv"))],
+            AOption(ROption::RNone),
+            python(),
+        );
+        assert!(fixed_kwargs.get_mapped_statements(generator()).is_none());
+    }
+}
@@ -0,0 +1,119 @@
+use crate::flow::flow_builder::FlowBuilderBase;
+use crate::flow::flow_builder_input::FlowBuilderInput;
+use crate::flow::native_python_based_flow::NativePythonBasedFlow;
+use crate::flow::python_based_flow_builder::PythonBasedFlowBuilder;
+use crate::python::{PythonFlowBuilderInput, PythonImport};
+use crate::shell::PARTITION_DATE_VARIABLE;
+use abi_stable::std_types::ROption;
+use aorist_ast::bash::{
+    quote, Command, Script, Statement, ToBash, VariableAssignment, VariableScope, Word,
+};
+use aorist_primitives::{AoristUniverse, Schedule, TPrestoEndpoints, TSparkEndpoints};
+use aorist_util::AOption;
+use aorist_util::{AString, AVec};
+use pyo3::PyResult;
+use std::marker::PhantomData;
+
+/// Renders a flow as a standalone Bash script: every task becomes a shell
+/// function, called right after it is defined, so tasks run in dependency
+/// order and the script stops at the first failure. Bash tasks run natively;
+/// other dialects are passed to their interpreter (or the Presto CLI).
+/// Scheduled flows run for the partition given in the PARTITION_DATE
+/// environment variable, or the current one.
+pub struct BashFlowBuilder<U: AoristUniverse>
+where
+    U::TEndpoints: TPrestoEndpoints + TSparkEndpoints,
+{
+    _universe: PhantomData<U>,
+}
+impl<U: AoristUniverse> BashFlowBuilder<U>
+where
+    U::TEndpoints: TPrestoEndpoints + TSparkEndpoints,
+{
+    fn build_script(
+        &self,
+        statements: &AVec<PythonFlowBuilderInput>,
+        flow_name: AOption<AString>,
+        schedule: AOption<Schedule>,
+    ) -> PyResult<AString> {
+        let mut script: AVec<Statement> = AVec::new();
+        if let AOption(ROption::RSome(name)) = flow_name {
+            script.push(Statement::Comment(name));
+            script.push(Statement::Blank);
+        }
+        if let AOption(ROption::RSome(x)) = schedule {
+            script.push(Statement::Assignment(VariableAssignment::new(
+                PARTITION_DATE_VARIABLE.into(),
+                Word::Raw(
+                    format!(
+                        "\"${{{}:-$(date {})}}\"",
+                        PARTITION_DATE_VARIABLE,
+                        quote(&format!("+{}", x.get_frequency().get_partition_format()))
+                    )
+                    .as_str()
+                    .into(),
+                ),
+                VariableScope::Export,
+            )));
+            script.push(Statement::Blank);
+        }
+        for input in statements.iter() {
+            let chunks = input
+                .get_literate_chunks()
+                .into_iter()
+                .filter(|x| x.get_dialect().is_some())
+                .collect::<AVec<_>>();
+            if chunks.len() == 0 {
+                continue;
+            }
+            script.push(Statement::Comment(
+                match input.get_constraint_title() {
+                    AOption(ROption::RSome(t)) => format!("## {}", t),
+                    AOption(ROption::RNone) => format!("## {}", input.get_constraint_name()),
+                }
+                .as_str()
+                .into(),
+            ));
+            if let AOption(ROption::RSome(b)) = input.get_constraint_body() {
+                script.push(Statement::Comment(b.as_str().trim().into()));
+            }
+            for chunk in chunks {
+                let function = chunk.to_bash_function()?;
+                let call = Command::new(function.name.clone(), AVec::new());
+                script.push(Statement::Function(function));
+                script.push(Statement::Command(call));
+            }
+            script.push(Statement::Blank);
+        }
+        Ok(Script::new(script).to_bash())
+    }
+}
+impl<U: AoristUniverse> FlowBuilderBase<U> for BashFlowBuilder<U>
+where
+    U::TEndpoints: TPrestoEndpoints + TSparkEndpoints,
+{
+    type T = NativePythonBasedFlow<U>;
+    fn new() -> Self {
+        Self {
+            _universe: PhantomData,
+        }
+    }
+}
+impl<U: AoristUniverse> PythonBasedFlowBuilder<U> for BashFlowBuilder<U>
+where
+    U::TEndpoints: TPrestoEndpoints + TSparkEndpoints,
+{
+    fn get_flow_imports(&self, _schedule: AOption<Schedule>) -> AVec<PythonImport> {
+        AVec::new()
+    }
+    fn build_native_file(
+        &self,
+        statements: &AVec<PythonFlowBuilderInput>,
+        flow_name: AOption<AString>,
+        schedule: AOption<Schedule>,
+    ) -> AOption<PyResult<AString>> {
+        AOption(ROption::RSome(
+            self.build_script(statements, flow_name, schedule),
+        ))
+    }
+}
@@ -0,0 +1,109 @@
+use crate::code::FlowDependencies;
+use anyhow::Result;
+use aorist_util::AString;
+use std::path::Path;
+
+/// Runtime image of a generated flow, built on the same Debian release as
+/// aorist's own image. Packages are listed in sorted order, so that the
+/// same universe always yields the same Dockerfile. The image expects
+/// requirements.txt (as written by FlowDependencies) next to the
+/// Dockerfile.
+pub struct ContainerImage {
+    dependencies: FlowDependencies,
+}
+impl ContainerImage {
+    pub fn new(dependencies: FlowDependencies) -> Self {
+        Self { dependencies }
+    }
+    fn get_system_packages(&self) -> Vec<String> {
+        let mut packages = self.dependencies.clone();
+        for package in ["ca-certificates", "python3", "python3-pip"] {
+            packages.add_system_package(package.into());
+        }
+        // the GDAL bindings are built against the installed library
+        if self.dependencies.has_pip_requirement("gdal") {
+            for package in ["g++", "gdal-bin", "libgdal-dev"] {
+                packages.add_system_package(package.into());
+            }
+        }
+        packages
+            .get_system_packages()
+            .into_iter()
+            .map(|x| x.as_str().to_string())
+            .collect()
+    }
+    pub fn to_dockerfile(&self, name: &str) -> AString {
+        let mut sections = vec![
+            format!("# Runtime image for the {} flow", name),
+            "FROM library/debian:bullseye-slim".to_string(),
+            format!(
+                "RUN apt-get update -y \\\n  && apt-get install -y \\\n      {} \\\n  \
+                 && apt-get clean \\\n  && rm -rf /var/lib/apt/lists /var/cache/apt/archives",
+                self.get_system_packages().join(" \\\n      ")
+            ),
+        ];
+        if !self.dependencies.get_pip_requirements().is_empty() {
+            sections.push(
+                "COPY requirements.txt /flow/requirements.txt\n\
+                 RUN pip3 install --no-cache-dir -r /flow/requirements.txt"
+                    .to_string(),
+            );
+        }
+        let r_packages = self.dependencies.get_r_packages();
+        if !r_packages.is_empty() {
+            sections.push(format!(
+                "RUN Rscript -e 'install.packages(c({}), repos = \"https://cloud.r-project.org\")'",
+                r_packages
+                    .iter()
+                    .map(|x| format!("\"{}\"", x))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+        sections.push("WORKDIR /flow".to_string());
+        format!("{}\n", sections.join("\n\n")).as_str().into()
+    }
+    /// Writes the Dockerfile to `directory`.
+    pub fn write(&self, directory: &str, name: &str) -> Result<()> {
+        let directory = Path::new(directory);
+        std::fs::create_dir_all(directory)?;
+        std::fs::write(
+            directory.join("Dockerfile"),
+            self.to_dockerfile(name).as_str(),
+        )?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dockerfile() {
+        let mut dependencies = FlowDependencies::new();
+        dependencies.add_pip_requirement("gdal==3.2.2".into());
+        dependencies.add_r_package("sf".into());
+        dependencies.add_system_package("r-base".into());
+        assert_eq!(
+            ContainerImage::new(dependencies).to_dockerfile("my_flow").as_str(),
+            "# Runtime image for the my_flow flow\n\n\
+             FROM library/debian:bullseye-slim\n\n\
+             RUN apt-get update -y \\\n  && apt-get install -y \\\n      \
+             ca-certificates \\\n      g++ \\\n      gdal-bin \\\n      libgdal-dev \\\n      \
+             python3 \\\n      python3-pip \\\n      r-base \\\n  \
+             && apt-get clean \\\n  && rm -rf /var/lib/apt/lists /var/cache/apt/archives\n\n\
+             COPY requirements.txt /flow/requirements.txt\n\
+             RUN pip3 install --no-cache-dir -r /flow/requirements.txt\n\n\
+             RUN Rscript -e 'install.packages(c(\"sf\"), repos = \"https://cloud.r-project.org\")'\n\n\
+             WORKDIR /flow\n"
+        );
+    }
+    #[test]
+    fn test_dockerfile_without_requirements() {
+        let dockerfile = ContainerImage::new(FlowDependencies::new()).to_dockerfile("my_flow");
+        assert!(!dockerfile.as_str().contains("requirements.txt"));
+        assert!(!dockerfile.as_str().contains("Rscript"));
+        assert!(dockerfile.as_str().ends_with("\n\nWORKDIR /flow\n"));
+    }
+}
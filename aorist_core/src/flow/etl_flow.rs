@@ -1,7 +1,8 @@
 use crate::code::{Import, Preamble};
+use abi_stable::std_types::ROption;
 use aorist_ast::AST;
 use aorist_primitives::AoristUniverse;
-use aorist_primitives::Dialect;
+use aorist_primitives::{Dialect, TaskPolicy};
 use aorist_util::AOption;
 use aorist_util::{AString, AVec};
 use linked_hash_map::LinkedHashMap;
@@ -30,8 +31,42 @@ where
         preamble: AOption<AString>,
         dialect: AOption<Dialect>,
         endpoints: U::TEndpoints,
+        execution_policy: AOption<TaskPolicy>,
     ) -> Self;
     fn get_statements(&self) -> AVec<AST>;
     fn get_type() -> String;
     fn get_imports(&self) -> AVec<Self::ImportType>;
+    /// Statements (and the imports they need) creating a single task that
+    /// fans out over `generator`, a `Comprehension` binding the names used
+    /// in this task's arguments, via the flow's native mapping primitive.
+    /// RNone if the flow has no such primitive or cannot map this task, in
+    /// which case compressed tasks are created one at a time in a for loop.
+    fn get_mapped_statements(
+        &self,
+        _generator: AST,
+    ) -> AOption<(AVec<AST>, AVec<Self::ImportType>)> {
+        AOption(ROption::RNone)
+    }
+}
+
+/// Whether `ast` reads any of the names bound by the comprehension
+/// `generator`, i.e. whether it varies across the tasks being mapped over.
+pub fn varies_with(ast: &AST, generator: &AST) -> bool {
+    let bound = match generator {
+        AST::Comprehension(ref x) => x
+            .read()
+            .target()
+            .get_descendants()
+            .into_iter()
+            .filter_map(|x| match x {
+                AST::SimpleIdentifier(ref y) => Some(y.read().name()),
+                _ => None,
+            })
+            .collect::<Vec<_>>(),
+        _ => panic!("generator must be a Comprehension"),
+    };
+    ast.get_descendants().iter().any(|x| match x {
+        AST::SimpleIdentifier(ref y) => bound.contains(&y.read().name()),
+        _ => false,
+    })
 }
@@ -1,10 +1,10 @@
-use crate::code::Preamble;
+use crate::code::{FlowDependencies, Preamble};
 use crate::flow::etl_flow::ETLFlow;
 use crate::flow::flow_builder_input::FlowBuilderInput;
 use abi_stable::external_types::parking_lot::rw_lock::RRwLock;
 use abi_stable::std_types::RArc;
 use aorist_ast::{Assignment, Dict, SimpleIdentifier, AST};
-use aorist_primitives::AoristUniverse;
+use aorist_primitives::{AoristUniverse, Schedule};
 use aorist_util::AOption;
 use aorist_util::{AString, AVec};
 use linked_hash_map::LinkedHashMap;
@@ -33,7 +33,15 @@ where
         &self,
         statements_and_preambles: AVec<Self::BuilderInputType>,
         flow_name: AOption<AString>,
+        schedule: AOption<Schedule>,
     ) -> Result<AString, Self::ErrorType>;
+    /// Third-party packages imported by the flow materialized from
+    /// `statements_and_preambles`.
+    fn get_dependencies(
+        &self,
+        statements_and_preambles: &AVec<Self::BuilderInputType>,
+        schedule: AOption<Schedule>,
+    ) -> FlowDependencies;
 
     fn literals_to_assignments(
         literals: LinkedHashMap<AST, LinkedHashMap<AString, AVec<(AString, RArc<RRwLock<Dict>>)>>>,
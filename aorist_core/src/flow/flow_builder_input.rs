@@ -2,7 +2,7 @@ use crate::code::{Import, Preamble};
 use abi_stable::external_types::parking_lot::rw_lock::RRwLock;
 use abi_stable::std_types::RArc;
 use abi_stable::std_types::ROption;
-use aorist_ast::{Dict, AST};
+use aorist_ast::{Dict, StatementBlock, AST};
 use aorist_util::AOption;
 use aorist_util::{AString, AVec};
 use linked_hash_map::LinkedHashMap;
@@ -31,6 +31,14 @@ where
     fn get_constraint_name(&self) -> AString;
     fn get_constraint_title(&self) -> AOption<AString>;
     fn get_constraint_body(&self) -> AOption<AString>;
+    /// Statements as a block that optimization passes can work on.
+    fn get_statement_block(&self) -> StatementBlock {
+        StatementBlock::new(
+            self.get_constraint_name(),
+            self.get_constraint_title(),
+            self.get_statements(),
+        )
+    }
     fn get_block_comment(&self) -> AString {
         match self.get_constraint_title() {
             AOption(ROption::RSome(t)) => match self.get_constraint_body() {
@@ -1,9 +1,9 @@
 use crate::flow::flow_builder::FlowBuilderBase;
-use crate::flow::native_python_based_flow::NativePythonBasedFlow;
+use crate::flow::native_python_based_flow::{NativePythonBasedFlow, PythonFlowBuilder};
 use crate::flow::python_based_flow_builder::PythonBasedFlowBuilder;
-use crate::python::{format_code, PythonImport};
+use crate::python::{format_code, PythonFlowBuilderInput, PythonImport};
 use abi_stable::std_types::ROption;
-use aorist_primitives::{AoristUniverse, TPrestoEndpoints};
+use aorist_primitives::{AoristUniverse, Schedule, TPrestoEndpoints, TSparkEndpoints};
 use aorist_util::AOption;
 use aorist_util::{AString, AVec};
 use pyo3::PyResult;
@@ -12,13 +12,13 @@ use std::marker::PhantomData;
 
 pub struct JupyterFlowBuilder<U: AoristUniverse>
 where
-    U::TEndpoints: TPrestoEndpoints,
+    U::TEndpoints: TPrestoEndpoints + TSparkEndpoints,
 {
     _universe: PhantomData<U>,
 }
 impl<U: AoristUniverse> FlowBuilderBase<U> for JupyterFlowBuilder<U>
 where
-    U::TEndpoints: TPrestoEndpoints,
+    U::TEndpoints: TPrestoEndpoints + TSparkEndpoints,
 {
     type T = NativePythonBasedFlow<U>;
     fn new() -> Self {
@@ -29,10 +29,19 @@ where
 }
 impl<U: AoristUniverse> PythonBasedFlowBuilder<U> for JupyterFlowBuilder<U>
 where
-    U::TEndpoints: TPrestoEndpoints,
+    U::TEndpoints: TPrestoEndpoints + TSparkEndpoints,
 {
-    fn get_flow_imports(&self) -> AVec<PythonImport> {
-        AVec::new()
+    /// Notebooks run for the same partition as native Python flows.
+    fn augment_statements(
+        &self,
+        statements: AVec<PythonFlowBuilderInput>,
+        flow_name: AOption<AString>,
+        schedule: AOption<Schedule>,
+    ) -> AVec<PythonFlowBuilderInput> {
+        PythonFlowBuilder::<U>::new().augment_statements(statements, flow_name, schedule)
+    }
+    fn get_flow_imports(&self, schedule: AOption<Schedule>) -> AVec<PythonImport> {
+        PythonFlowBuilder::<U>::new().get_flow_imports(schedule)
     }
     fn build_file(
         &self,
@@ -0,0 +1,353 @@
+use crate::flow::flow_builder::FlowBuilderBase;
+use crate::flow::flow_builder_input::FlowBuilderInput;
+use crate::flow::native_python_based_flow::NativePythonBasedFlow;
+use crate::flow::python_based_flow_builder::PythonBasedFlowBuilder;
+use crate::python::{LiterateChunk, PythonFlowBuilderInput, PythonImport};
+use crate::shell::PARTITION_DATE_VARIABLE;
+use abi_stable::std_types::ROption;
+use aorist_primitives::{AoristUniverse, Dialect, Schedule, TPrestoEndpoints, TSparkEndpoints};
+use aorist_util::AOption;
+use aorist_util::{AString, AVec};
+use linked_hash_map::LinkedHashMap;
+use linked_hash_set::LinkedHashSet;
+use pyo3::PyResult;
+use std::marker::PhantomData;
+
+/// Name of the DBI connection used by Presto `{sql}` chunks.
+const SQL_CONNECTION: &str = "presto_connection";
+/// Name of the DBI connection used by DuckDB `{sql}` chunks.
+const DUCKDB_CONNECTION: &str = "duckdb_connection";
+/// Name of the sparklyr connection used by Spark `{sql}` chunks.
+const SPARK_CONNECTION: &str = "spark_connection";
+
+/// The two literate formats we know how to write. They share the markdown
+/// body, but differ in the YAML header and in how chunk options are passed.
+#[derive(Clone, Copy, PartialEq)]
+enum LiterateFormat {
+    Quarto,
+    RMarkdown,
+}
+impl LiterateFormat {
+    fn header(&self, title: &str) -> String {
+        // JSON strings are valid double-quoted YAML scalars
+        let title = serde_json::to_string(title).unwrap();
+        match self {
+            LiterateFormat::Quarto => format!(
+                "---\ntitle: {}\nformat: html\nengine: knitr\n---\n",
+                title
+            ),
+            LiterateFormat::RMarkdown => {
+                format!("---\ntitle: {}\noutput: html_document\n---\n", title)
+            }
+        }
+    }
+    /// Quarto takes chunk options as `#|` comments, R Markdown inside the
+    /// chunk header.
+    fn chunk(&self, engine: &str, label: &str, options: AVec<(&str, &str)>, body: &str) -> String {
+        match self {
+            LiterateFormat::Quarto => format!(
+                "```{{{}}}\n#| label: {}\n{}{}\n```\n",
+                engine,
+                label,
+                options
+                    .iter()
+                    .map(|(k, v)| format!("#| {}: {}\n", k, v))
+                    .collect::<AVec<String>>()
+                    .join(""),
+                body.trim_end(),
+            ),
+            LiterateFormat::RMarkdown => format!(
+                "```{{{} {}{}}}\n{}\n```\n",
+                engine,
+                label,
+                options
+                    .iter()
+                    .map(|(k, v)| format!(", {}={}", k, v))
+                    .collect::<AVec<String>>()
+                    .join(""),
+                body.trim_end(),
+            ),
+        }
+    }
+    fn chunk_options(&self, chunk: &LiterateChunk) -> AVec<(&'static str, &'static str)> {
+        match chunk.get_dialect() {
+            AOption(ROption::RSome(Dialect::Presto(_))) => {
+                vec![("connection", SQL_CONNECTION)].into_iter().collect()
+            }
+            AOption(ROption::RSome(Dialect::DuckDB(_))) => vec![("connection", DUCKDB_CONNECTION)]
+                .into_iter()
+                .collect(),
+            AOption(ROption::RSome(Dialect::Spark(_))) => {
+                vec![("connection", SPARK_CONNECTION)].into_iter().collect()
+            }
+            _ => AVec::new(),
+        }
+    }
+    /// Body of a chunk. knitr fills in `?partition_date` in SQL chunks
+    /// with the (quoted) value of the R variable.
+    fn chunk_source(&self, chunk: &LiterateChunk) -> PyResult<AString> {
+        let source = chunk.get_source()?;
+        Ok(match chunk.get_engine() {
+            AOption(ROption::RSome(ref x)) if x.as_str() == "sql" => source
+                .as_str()
+                .replace("'{partition_date}'", "?partition_date")
+                .as_str()
+                .into(),
+            _ => source,
+        })
+    }
+    /// Scheduled documents are rendered for the partition given in the
+    /// PARTITION_DATE environment variable, or the current one. Bash chunks
+    /// read it from the environment, Python chunks from R.
+    fn partition_date_setup(&self, schedule: &Schedule, uses_python: bool) -> AVec<String> {
+        let mut setup = vec![self.chunk(
+            "r",
+            "setup-partition-date",
+            AVec::new(),
+            &format!(
+                "partition_date <- Sys.getenv(\"{0}\", format(Sys.time(), \"{1}\"))\nSys.setenv({0} = partition_date)",
+                PARTITION_DATE_VARIABLE,
+                schedule.get_frequency().get_partition_format()
+            ),
+        )];
+        if uses_python {
+            setup.push(self.chunk(
+                "python",
+                "setup-partition-date-python",
+                AVec::new(),
+                "partition_date = r.partition_date",
+            ));
+        }
+        setup.into_iter().collect()
+    }
+    fn render(
+        &self,
+        statements: &AVec<PythonFlowBuilderInput>,
+        flow_name: AOption<AString>,
+        schedule: AOption<Schedule>,
+    ) -> PyResult<AString> {
+        let title = match flow_name {
+            AOption(ROption::RSome(name)) => name,
+            AOption(ROption::RNone) => "Aorist flow".into(),
+        };
+        // preambles are shared between tasks, so we emit each of them once,
+        // in a setup chunk per engine
+        let mut preambles: LinkedHashMap<AString, LinkedHashSet<AString>> = LinkedHashMap::new();
+        let mut sections: AVec<String> = AVec::new();
+        let mut uses_presto = false;
+        let mut uses_duckdb = false;
+        let mut uses_spark = false;
+        let mut uses_python = false;
+        for input in statements.iter() {
+            let chunks = input
+                .get_literate_chunks()
+                .into_iter()
+                .filter(|x| x.get_engine().is_some())
+                .collect::<AVec<LiterateChunk>>();
+            if chunks.len() == 0 && !input.has_statements() {
+                continue;
+            }
+            let mut section = match input.get_constraint_title() {
+                AOption(ROption::RSome(t)) => format!("## {}\n", t),
+                AOption(ROption::RNone) => format!("## {}\n", input.get_constraint_name()),
+            };
+            if let AOption(ROption::RSome(b)) = input.get_constraint_body() {
+                section += &format!("\n{}\n", b.as_str().trim());
+            }
+            for chunk in chunks {
+                let engine = chunk.get_engine().unwrap();
+                match chunk.get_dialect() {
+                    AOption(ROption::RSome(Dialect::Presto(_))) => uses_presto = true,
+                    AOption(ROption::RSome(Dialect::DuckDB(_))) => uses_duckdb = true,
+                    AOption(ROption::RSome(Dialect::Spark(_))) => uses_spark = true,
+                    AOption(ROption::RSome(Dialect::Python(_))) => uses_python = true,
+                    _ => {}
+                }
+                if let AOption(ROption::RSome(p)) = chunk.get_preamble() {
+                    preambles
+                        .entry(engine.clone())
+                        .or_insert(LinkedHashSet::new())
+                        .insert(p);
+                }
+                section += "\n";
+                section += &self.chunk(
+                    engine.as_str(),
+                    chunk.get_label().as_str(),
+                    self.chunk_options(&chunk),
+                    self.chunk_source(&chunk)?.as_str(),
+                );
+            }
+            sections.push(section);
+        }
+        let mut setup: AVec<String> = match schedule {
+            AOption(ROption::RSome(ref x)) => self.partition_date_setup(x, uses_python),
+            AOption(ROption::RNone) => AVec::new(),
+        };
+        if uses_presto {
+            setup.push(self.chunk(
+                "r",
+                "setup-sql",
+                AVec::new(),
+                &format!(
+                    "{} <- DBI::dbConnect(\n  RPresto::Presto(),\n  host = Sys.getenv(\"PRESTO_SERVER\", \"localhost\"),\n  port = as.integer(Sys.getenv(\"PRESTO_HTTP_PORT\", \"8080\")),\n  user = Sys.getenv(\"PRESTO_USER\", \"aorist\"),\n  catalog = \"hive\",\n  schema = \"default\"\n)",
+                    SQL_CONNECTION
+                ),
+            ));
+        }
+        if uses_duckdb {
+            setup.push(self.chunk(
+                "r",
+                "setup-duckdb",
+                AVec::new(),
+                &format!(
+                    "{} <- DBI::dbConnect(\n  duckdb::duckdb(),\n  dbdir = Sys.getenv(\"DUCKDB_DATABASE\", \"aorist.duckdb\")\n)",
+                    DUCKDB_CONNECTION
+                ),
+            ));
+        }
+        if uses_spark {
+            setup.push(self.chunk(
+                "r",
+                "setup-spark",
+                AVec::new(),
+                &format!(
+                    "{} <- sparklyr::spark_connect(\n  master = Sys.getenv(\"SPARK_MASTER\", \"local[*]\")\n)",
+                    SPARK_CONNECTION
+                ),
+            ));
+        }
+        for (engine, bodies) in preambles.into_iter() {
+            setup.push(
+                self.chunk(
+                    engine.as_str(),
+                    &format!("setup-{}", engine),
+                    AVec::new(),
+                    &bodies
+                        .into_iter()
+                        .map(|x| x.as_str().trim().to_string())
+                        .collect::<AVec<String>>()
+                        .join("\n\n"),
+                ),
+            );
+        }
+        Ok(vec![self.header(title.as_str())]
+            .into_iter()
+            .chain(setup.into_iter())
+            .chain(sections.into_iter())
+            .collect::<AVec<String>>()
+            .join("\n")
+            .as_str()
+            .into())
+    }
+}
+
+/// Renders a flow as a Quarto (.qmd) document: prose from constraint titles
+/// and bodies, and a fenced chunk per task in the task's own dialect.
+pub struct QuartoFlowBuilder<U: AoristUniverse>
+where
+    U::TEndpoints: TPrestoEndpoints + TSparkEndpoints,
+{
+    _universe: PhantomData<U>,
+}
+impl<U: AoristUniverse> FlowBuilderBase<U> for QuartoFlowBuilder<U>
+where
+    U::TEndpoints: TPrestoEndpoints + TSparkEndpoints,
+{
+    type T = NativePythonBasedFlow<U>;
+    fn new() -> Self {
+        Self {
+            _universe: PhantomData,
+        }
+    }
+}
+impl<U: AoristUniverse> PythonBasedFlowBuilder<U> for QuartoFlowBuilder<U>
+where
+    U::TEndpoints: TPrestoEndpoints + TSparkEndpoints,
+{
+    fn get_flow_imports(&self, _schedule: AOption<Schedule>) -> AVec<PythonImport> {
+        AVec::new()
+    }
+    fn build_native_file(
+        &self,
+        statements: &AVec<PythonFlowBuilderInput>,
+        flow_name: AOption<AString>,
+        schedule: AOption<Schedule>,
+    ) -> AOption<PyResult<AString>> {
+        AOption(ROption::RSome(
+            LiterateFormat::Quarto.render(statements, flow_name, schedule),
+        ))
+    }
+}
+
+/// Same as QuartoFlowBuilder, but writes an R Markdown (.Rmd) document.
+pub struct RMarkdownFlowBuilder<U: AoristUniverse>
+where
+    U::TEndpoints: TPrestoEndpoints + TSparkEndpoints,
+{
+    _universe: PhantomData<U>,
+}
+impl<U: AoristUniverse> FlowBuilderBase<U> for RMarkdownFlowBuilder<U>
+where
+    U::TEndpoints: TPrestoEndpoints + TSparkEndpoints,
+{
+    type T = NativePythonBasedFlow<U>;
+    fn new() -> Self {
+        Self {
+            _universe: PhantomData,
+        }
+    }
+}
+impl<U: AoristUniverse> PythonBasedFlowBuilder<U> for RMarkdownFlowBuilder<U>
+where
+    U::TEndpoints: TPrestoEndpoints + TSparkEndpoints,
+{
+    fn get_flow_imports(&self, _schedule: AOption<Schedule>) -> AVec<PythonImport> {
+        AVec::new()
+    }
+    fn build_native_file(
+        &self,
+        statements: &AVec<PythonFlowBuilderInput>,
+        flow_name: AOption<AString>,
+        schedule: AOption<Schedule>,
+    ) -> AOption<PyResult<AString>> {
+        AOption(ROption::RSome(
+            LiterateFormat::RMarkdown.render(statements, flow_name, schedule),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_header_escapes_title() {
+        let header = LiterateFormat::Quarto.header("The \"best\" flow\never");
+        assert_eq!(
+            header,
+            "---\ntitle: \"The \\\"best\\\" flow\\never\"\nformat: html\nengine: knitr\n---\n"
+        );
+        let yaml: serde_yaml::Value =
+            serde_yaml::from_str(header.trim_start_matches("---\n").trim_end_matches("---\n"))
+                .unwrap();
+        assert_eq!(yaml["title"].as_str(), Some("The \"best\" flow\never"));
+        let header = LiterateFormat::RMarkdown.header("a: b");
+        assert_eq!(
+            header,
+            "---\ntitle: \"a: b\"\noutput: html_document\n---\n"
+        );
+    }
+
+    #[test]
+    fn test_chunk_options() {
+        let options: AVec<(&str, &str)> = vec![("connection", SQL_CONNECTION)].into_iter().collect();
+        assert_eq!(
+            LiterateFormat::Quarto.chunk("sql", "query", options.clone(), "SELECT 1\n"),
+            "```{sql}\n#| label: query\n#| connection: presto_connection\nSELECT 1\n```\n"
+        );
+        assert_eq!(
+            LiterateFormat::RMarkdown.chunk("sql", "query", options, "SELECT 1\n"),
+            "```{sql query, connection=presto_connection}\nSELECT 1\n```\n"
+        );
+    }
+}
@@ -6,6 +6,8 @@ mod flow_builder_input;
 pub use flow_builder_input::*;
 mod flow_builder;
 pub use flow_builder::*;
+mod container_image;
+pub use container_image::*;
 #[cfg(feature = "python")]
 mod python_based_flow_builder;
 #[cfg(feature = "python")]
@@ -19,6 +21,14 @@ mod jupyter_python_based_flow;
 #[cfg(feature = "python")]
 pub use jupyter_python_based_flow::*;
 #[cfg(feature = "python")]
+mod literate_python_based_flow;
+#[cfg(feature = "python")]
+pub use literate_python_based_flow::*;
+#[cfg(feature = "python")]
+mod bash_python_based_flow;
+#[cfg(feature = "python")]
+pub use bash_python_based_flow::*;
+#[cfg(feature = "python")]
 mod airflow_python_based_flow;
 #[cfg(feature = "python")]
 pub use airflow_python_based_flow::*;
@@ -3,14 +3,19 @@ use crate::flow::flow_builder::FlowBuilderBase;
 use crate::flow::python_based_flow::PythonBasedFlow;
 use crate::flow::python_based_flow_builder::PythonBasedFlowBuilder;
 use crate::python::{
-    BashPythonTask, ConstantPythonTask, NativePythonTask, PrestoPythonTask, PythonImport,
-    PythonPreamble, PythonTask, RPythonTask,
+    BashPythonTask, ConstantPythonTask, DuckDBPythonTask, NativePythonPreamble, NativePythonTask,
+    PrestoPythonTask, PythonFlowBuilderInput, PythonImport, PythonPreamble, PythonTask,
+    RPythonTask, SparkPythonTask,
 };
 use abi_stable::std_types::ROption;
-use aorist_ast::{Call, SimpleIdentifier, StringLiteral, AST};
+use aorist_ast::{
+    Assignment, Attribute, BigIntLiteral, BooleanLiteral, Call, Expression, Lambda,
+    SimpleIdentifier, StringLiteral, AST,
+};
 use aorist_primitives::AoristUniverse;
 use aorist_primitives::Dialect;
-use aorist_primitives::TPrestoEndpoints;
+use aorist_primitives::Schedule;
+use aorist_primitives::{TPrestoEndpoints, TSparkEndpoints, TaskPolicy};
 use aorist_util::AOption;
 use aorist_util::{AString, AVec};
 use linked_hash_map::LinkedHashMap;
@@ -20,7 +25,7 @@ use std::marker::PhantomData;
 #[derive(Clone, Hash, PartialEq)]
 pub struct NativePythonBasedFlow<U: AoristUniverse>
 where
-    U::TEndpoints: TPrestoEndpoints,
+    U::TEndpoints: TPrestoEndpoints + TSparkEndpoints,
 {
     task_id: AST,
     task_val: AST,
@@ -32,11 +37,12 @@ where
     dialect: AOption<Dialect>,
     endpoints: U::TEndpoints,
     node: PythonTask,
+    execution_policy: AOption<TaskPolicy>,
     _universe: PhantomData<U>,
 }
 impl<U: AoristUniverse> PythonBasedFlow<U> for NativePythonBasedFlow<U>
 where
-    U::TEndpoints: TPrestoEndpoints,
+    U::TEndpoints: TPrestoEndpoints + TSparkEndpoints,
 {
     fn get_preamble_string(&self) -> AOption<AString> {
         self.preamble.clone()
@@ -45,7 +51,7 @@ where
 
 impl<U: AoristUniverse> ETLFlow<U> for NativePythonBasedFlow<U>
 where
-    U::TEndpoints: TPrestoEndpoints,
+    U::TEndpoints: TPrestoEndpoints + TSparkEndpoints,
 {
     type ImportType = PythonImport;
     type PreambleType = PythonPreamble;
@@ -57,6 +63,9 @@ where
         if let AOption(ROption::RSome(p)) = self.node.get_preamble() {
             preambles.push(p)
         }
+        if self.execution_policy.is_some() {
+            preambles.push(Self::get_policy_preamble());
+        }
         Ok(preambles.into_iter().collect())
     }
     fn get_imports(&self) -> AVec<PythonImport> {
@@ -69,7 +78,15 @@ where
         self.task_val.clone()
     }
     fn get_statements(&self) -> AVec<AST> {
-        self.node.get_statements()
+        match self.execution_policy {
+            AOption(ROption::RSome(ref policy)) => self
+                .node
+                .get_statements()
+                .into_iter()
+                .map(|x| Self::apply_policy(x, policy))
+                .collect(),
+            AOption(ROption::RNone) => self.node.get_statements(),
+        }
     }
     fn new(
         task_id: AST,
@@ -81,9 +98,12 @@ where
         preamble: AOption<AString>,
         dialect: AOption<Dialect>,
         endpoints: U::TEndpoints,
+        execution_policy: AOption<TaskPolicy>,
     ) -> Self {
         let command = match &dialect {
-            AOption(ROption::RSome(Dialect::Presto(_))) => AST::StringLiteral(
+            AOption(ROption::RSome(Dialect::Presto(_)))
+            | AOption(ROption::RSome(Dialect::DuckDB(_)))
+            | AOption(ROption::RSome(Dialect::Spark(_))) => AST::StringLiteral(
                 StringLiteral::new_wrapped(call.as_ref().unwrap().clone(), true),
             ),
             AOption(ROption::RSome(_)) => AST::StringLiteral(StringLiteral::new_wrapped(
@@ -94,36 +114,56 @@ where
                 AST::StringLiteral(StringLiteral::new_wrapped("Done".into(), false))
             }
         };
+        let sql_kwargs = || -> LinkedHashMap<AString, AST> {
+            kwargs
+                .iter()
+                .map(|(k, v)| {
+                    (
+                        k.clone(),
+                        match *v {
+                            AST::StringLiteral(ref x) => {
+                                if x.read().len() == 0 {
+                                    panic!("Cannot process empty string for key: {}", k);
+                                }
+                                AST::StringLiteral(StringLiteral::new_wrapped(
+                                    x.read().value().clone(),
+                                    true,
+                                ))
+                            }
+                            _ => v.clone(),
+                        },
+                    )
+                })
+                .collect()
+        };
         let node = match &dialect {
             AOption(ROption::RSome(Dialect::Presto(_))) => {
                 let presto_endpoints = endpoints.presto_config();
                 PythonTask::PrestoPythonTask(PrestoPythonTask::new_wrapped(
                     command,
-                    kwargs
-                        .iter()
-                        .map(|(k, v)| {
-                            (
-                                k.clone(),
-                                match *v {
-                                    AST::StringLiteral(ref x) => {
-                                        if x.read().len() == 0 {
-                                            panic!("Cannot process empty string for key: {}", k);
-                                        }
-                                        AST::StringLiteral(StringLiteral::new_wrapped(
-                                            x.read().value().clone(),
-                                            true,
-                                        ))
-                                    }
-                                    _ => v.clone(),
-                                },
-                            )
-                        })
-                        .collect(),
+                    sql_kwargs(),
                     task_val.clone(),
                     presto_endpoints,
                     dep_list.clone(),
                 ))
             }
+            AOption(ROption::RSome(Dialect::DuckDB(_))) => {
+                PythonTask::DuckDBPythonTask(DuckDBPythonTask::new_wrapped(
+                    command,
+                    sql_kwargs(),
+                    task_val.clone(),
+                    dep_list.clone(),
+                ))
+            }
+            AOption(ROption::RSome(Dialect::Spark(_))) => {
+                PythonTask::SparkPythonTask(SparkPythonTask::new_wrapped(
+                    command,
+                    sql_kwargs(),
+                    task_val.clone(),
+                    endpoints.spark_config(),
+                    dep_list.clone(),
+                ))
+            }
             AOption(ROption::RSome(Dialect::Bash(_))) => {
                 PythonTask::BashPythonTask(BashPythonTask::new_wrapped(
                     command,
@@ -173,6 +213,7 @@ where
             dialect: dialect.clone(),
             endpoints,
             node,
+            execution_policy,
             _universe: PhantomData,
         }
     }
@@ -180,15 +221,120 @@ where
         "python".into()
     }
 }
+/// Retries a task and bounds how long each attempt may take. The timeout is
+/// not enforced: Python threads cannot be interrupted, so an attempt that
+/// times out keeps running in the background. It fails the task, and is not
+/// retried, so that attempts never run concurrently.
+const RUN_WITH_POLICY: &str = "
+def run_with_policy(task, retries=0, retry_delay=0, exponential_backoff=False, timeout=None):
+    attempt = 0
+    while True:
+        executor = ThreadPoolExecutor(max_workers=1)
+        future = executor.submit(task)
+        try:
+            return future.result(timeout=timeout)
+        except Exception:
+            # the attempt is still running if it timed out
+            if not future.done() or attempt >= retries:
+                raise
+            time.sleep(retry_delay * (2 ** attempt if exponential_backoff else 1))
+            attempt += 1
+        finally:
+            executor.shutdown(wait=False)
+";
+
+impl<U: AoristUniverse> NativePythonBasedFlow<U>
+where
+    U::TEndpoints: TPrestoEndpoints + TSparkEndpoints,
+{
+    /// Defines run_with_policy (see RUN_WITH_POLICY).
+    fn get_policy_preamble() -> PythonPreamble {
+        PythonPreamble::NativePythonPreamble(NativePythonPreamble {
+            imports: vec![PythonImport::PythonModuleImport(
+                "time".into(),
+                AOption(ROption::RNone),
+            )]
+            .into_iter()
+            .collect(),
+            from_imports: vec![PythonImport::PythonFromImport(
+                "concurrent.futures".into(),
+                "ThreadPoolExecutor".into(),
+                AOption(ROption::RNone),
+            )]
+            .into_iter()
+            .collect(),
+            body: RUN_WITH_POLICY.into(),
+        })
+    }
+    /// run_with_policy(lambda: call, retries=..., ...)
+    fn wrap_call(call: AST, policy: &TaskPolicy) -> AST {
+        let mut kwargs = LinkedHashMap::new();
+        if let AOption(ROption::RSome(retries)) = policy.retries {
+            kwargs.insert(
+                "retries".into(),
+                AST::BigIntLiteral(BigIntLiteral::new_wrapped(retries)),
+            );
+        }
+        if let AOption(ROption::RSome(delay)) = policy.retry_delay {
+            kwargs.insert(
+                "retry_delay".into(),
+                AST::BigIntLiteral(BigIntLiteral::new_wrapped(delay)),
+            );
+        }
+        if let AOption(ROption::RSome(backoff)) = policy.exponential_backoff {
+            kwargs.insert(
+                "exponential_backoff".into(),
+                AST::BooleanLiteral(BooleanLiteral::new_wrapped(backoff)),
+            );
+        }
+        if let AOption(ROption::RSome(timeout)) = policy.timeout {
+            kwargs.insert(
+                "timeout".into(),
+                AST::BigIntLiteral(BigIntLiteral::new_wrapped(timeout)),
+            );
+        }
+        AST::Call(Call::new_wrapped(
+            AST::SimpleIdentifier(SimpleIdentifier::new_wrapped("run_with_policy".into())),
+            vec![AST::Lambda(Lambda::new_wrapped(AVec::new(), call))]
+                .into_iter()
+                .collect(),
+            kwargs,
+        ))
+    }
+    /// Runs the task calls among `statement` under the execution policy.
+    /// Resources and pools have no meaning outside of a scheduler, and are
+    /// ignored.
+    fn apply_policy(statement: AST, policy: &TaskPolicy) -> AST {
+        match statement {
+            AST::Assignment(ref x) => {
+                let read = x.read();
+                match read.call() {
+                    call @ AST::Call(_) => AST::Assignment(Assignment::new_wrapped(
+                        read.target(),
+                        Self::wrap_call(call, policy),
+                    )),
+                    _ => statement.clone(),
+                }
+            }
+            AST::Expression(ref x) => match x.read().inner() {
+                call @ AST::Call(_) => {
+                    AST::Expression(Expression::new_wrapped(Self::wrap_call(call, policy)))
+                }
+                _ => statement.clone(),
+            },
+            _ => statement,
+        }
+    }
+}
 pub struct PythonFlowBuilder<U: AoristUniverse>
 where
-    U::TEndpoints: TPrestoEndpoints,
+    U::TEndpoints: TPrestoEndpoints + TSparkEndpoints,
 {
     universe: PhantomData<U>,
 }
 impl<U: AoristUniverse> FlowBuilderBase<U> for PythonFlowBuilder<U>
 where
-    U::TEndpoints: TPrestoEndpoints,
+    U::TEndpoints: TPrestoEndpoints + TSparkEndpoints,
 {
     type T = NativePythonBasedFlow<U>;
     fn new() -> Self {
@@ -199,9 +345,136 @@ where
 }
 impl<U: AoristUniverse> PythonBasedFlowBuilder<U> for PythonFlowBuilder<U>
 where
-    U::TEndpoints: TPrestoEndpoints,
+    U::TEndpoints: TPrestoEndpoints + TSparkEndpoints,
 {
-    fn get_flow_imports(&self) -> AVec<PythonImport> {
-        AVec::new()
+    /// Scheduled flows run for the partition given in the PARTITION_DATE
+    /// environment variable, or the current one.
+    fn augment_statements(
+        &self,
+        mut statements: AVec<PythonFlowBuilderInput>,
+        _flow_name: AOption<AString>,
+        schedule: AOption<Schedule>,
+    ) -> AVec<PythonFlowBuilderInput> {
+        if let AOption(ROption::RSome(ref x)) = schedule {
+            let now = AST::Call(Call::new_wrapped(
+                AST::Attribute(Attribute::new_wrapped(
+                    AST::SimpleIdentifier(SimpleIdentifier::new_wrapped("datetime".into())),
+                    "now".into(),
+                    false,
+                )),
+                AVec::new(),
+                LinkedHashMap::new(),
+            ));
+            let default = AST::Call(Call::new_wrapped(
+                AST::Attribute(Attribute::new_wrapped(now, "strftime".into(), false)),
+                vec![AST::StringLiteral(StringLiteral::new_wrapped(
+                    x.get_frequency().get_partition_format(),
+                    false,
+                ))]
+                .into_iter()
+                .collect(),
+                LinkedHashMap::new(),
+            ));
+            let partition_date = AST::Call(Call::new_wrapped(
+                AST::Attribute(Attribute::new_wrapped(
+                    AST::Attribute(Attribute::new_wrapped(
+                        AST::SimpleIdentifier(SimpleIdentifier::new_wrapped("os".into())),
+                        "environ".into(),
+                        false,
+                    )),
+                    "get".into(),
+                    false,
+                )),
+                vec![
+                    AST::StringLiteral(StringLiteral::new_wrapped("PARTITION_DATE".into(), false)),
+                    default,
+                ]
+                .into_iter()
+                .collect(),
+                LinkedHashMap::new(),
+            ));
+            statements.insert(
+                0,
+                PythonFlowBuilderInput::statements_only(
+                    vec![AST::Assignment(Assignment::new_wrapped(
+                        AST::SimpleIdentifier(SimpleIdentifier::new_wrapped(
+                            "partition_date".into(),
+                        )),
+                        partition_date,
+                    ))]
+                    .into_iter()
+                    .collect(),
+                    "Setting up partition date".into(),
+                    AOption(ROption::RNone),
+                    AOption(ROption::RNone),
+                ),
+            );
+        }
+        statements
+    }
+    fn get_flow_imports(&self, schedule: AOption<Schedule>) -> AVec<PythonImport> {
+        match schedule {
+            AOption(ROption::RSome(_)) => vec![
+                PythonImport::PythonModuleImport("os".into(), AOption(ROption::RNone)),
+                PythonImport::PythonFromImport(
+                    "datetime".into(),
+                    "datetime".into(),
+                    AOption(ROption::RNone),
+                ),
+            ]
+            .into_iter()
+            .collect(),
+            AOption(ROption::RNone) => AVec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pyo3::prelude::*;
+    use pyo3::types::PyDict;
+
+    fn run(task: &str, policy: &str) -> (bool, i64) {
+        Python::with_gil(|py| {
+            let globals = PyDict::new(py);
+            py.run(
+                &format!(
+                    "import time\nfrom concurrent.futures import ThreadPoolExecutor\n{}",
+                    RUN_WITH_POLICY
+                ),
+                Some(globals),
+                None,
+            )
+            .unwrap();
+            py.run(
+                &format!(
+                    "calls = []\ndef task():\n    calls.append(1)\n    {}\n\
+                     try:\n    run_with_policy(task, {})\n    failed = False\n\
+                     except Exception:\n    failed = True\nattempts = len(calls)",
+                    task, policy
+                ),
+                Some(globals),
+                None,
+            )
+            .unwrap();
+            (
+                globals.get_item("failed").unwrap().extract().unwrap(),
+                globals.get_item("attempts").unwrap().extract().unwrap(),
+            )
+        })
+    }
+
+    #[test]
+    fn test_failed_attempts_are_retried() {
+        assert_eq!(run("raise ValueError()", "retries=2"), (true, 3));
+        assert_eq!(
+            run("if len(calls) < 2: raise ValueError()", "retries=2"),
+            (false, 2)
+        );
+    }
+    #[test]
+    fn test_timed_out_attempts_are_not_retried() {
+        assert_eq!(run("time.sleep(0.5)", "retries=2, timeout=0.1"), (true, 1));
     }
 }
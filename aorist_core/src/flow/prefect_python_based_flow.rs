@@ -1,21 +1,26 @@
-use crate::flow::etl_flow::ETLFlow;
+use crate::flow::etl_flow::{varies_with, ETLFlow};
 use crate::flow::flow_builder::FlowBuilderBase;
 use crate::flow::python_based_flow::PythonBasedFlow;
 use crate::flow::python_based_flow_builder::PythonBasedFlowBuilder;
 use crate::python::{
     NativePythonPreamble, PythonFlowBuilderInput, PythonImport, PythonPreamble, RPythonTask,
 };
+use crate::shell::{fill_sql, pipe_sql, sql_command, SchemaChange, PARTITION_DATE_VARIABLE};
 use abi_stable::external_types::parking_lot::rw_lock::RRwLock;
 use abi_stable::std_types::RArc;
 use abi_stable::std_types::ROption;
+use aorist_ast::bash::{Command, ToBash, Word};
 use aorist_ast::{
-    Assignment, Attribute, Call, Expression, ForLoop, Formatted, SimpleIdentifier, StringLiteral,
+    Assignment, Attribute, BigIntLiteral, BooleanLiteral, Call, Compare, ConstantFolding, Dict,
+    Equals, Expression, ForLoop, Formatted, If, ListComp, None, SimpleIdentifier, StringLiteral,
     AST,
 };
 use aorist_primitives::register_task_nodes;
 use aorist_primitives::AoristUniverse;
 use aorist_primitives::Dialect;
-use aorist_primitives::TPrestoEndpoints;
+use aorist_primitives::Schedule;
+use aorist_primitives::TaskPolicy;
+use aorist_primitives::{TPrestoEndpoints, TSparkEndpoints};
 use aorist_util::AOption;
 use aorist_util::{AString, AVec};
 use linked_hash_map::LinkedHashMap;
@@ -30,7 +35,10 @@ register_task_nodes! {
 }
 
 #[derive(Clone, Hash, PartialEq)]
-pub struct PrefectPythonBasedFlow<U: AoristUniverse> {
+pub struct PrefectPythonBasedFlow<U: AoristUniverse>
+where
+    U::TEndpoints: TPrestoEndpoints + TSparkEndpoints,
+{
     task_id: AST,
     task_val: AST,
     command: AOption<AString>,
@@ -41,19 +49,23 @@ pub struct PrefectPythonBasedFlow<U: AoristUniverse> {
     dialect: AOption<Dialect>,
     flow_identifier: AST,
     endpoints: U::TEndpoints,
+    execution_policy: AOption<TaskPolicy>,
     _universe: PhantomData<U>,
 }
 
 impl<U: AoristUniverse> PythonBasedFlow<U> for PrefectPythonBasedFlow<U>
 where
-    U::TEndpoints: TPrestoEndpoints,
+    U::TEndpoints: TPrestoEndpoints + TSparkEndpoints,
 {
     fn get_preamble_string(&self) -> AOption<AString> {
         self.preamble.clone()
     }
 }
 
-impl<U: AoristUniverse> ETLFlow<U> for PrefectPythonBasedFlow<U> {
+impl<U: AoristUniverse> ETLFlow<U> for PrefectPythonBasedFlow<U>
+where
+    U::TEndpoints: TPrestoEndpoints + TSparkEndpoints,
+{
     type ImportType = PythonImport;
     type PreambleType = PythonPreamble;
     type ErrorType = pyo3::PyErr;
@@ -79,15 +91,44 @@ impl<U: AoristUniverse> ETLFlow<U> for PrefectPythonBasedFlow<U> {
         self.task_val.clone()
     }
     fn get_statements(&self) -> AVec<AST> {
+        let mut kwargs = self.compute_task_kwargs();
+        if let AOption(ROption::RSome(Dialect::Bash(_)))
+        | AOption(ROption::RSome(Dialect::Presto(_)))
+        | AOption(ROption::RSome(Dialect::DuckDB(_)))
+        | AOption(ROption::RSome(Dialect::Spark(_)))
+        | AOption(ROption::RNone) = self.dialect
+        {
+            kwargs.extend(self.compute_task_options());
+        }
         let creation_expr = AST::Call(Call::new_wrapped(
             self.compute_task_call(),
             self.compute_task_args(),
-            self.compute_task_kwargs(),
+            kwargs,
         ));
         let task_creation =
             AST::Assignment(Assignment::new_wrapped(self.get_task_val(), creation_expr));
         let mut stmts = vec![task_creation];
         stmts.push(self.get_flow_node_addition());
+        if let AOption(ROption::RSome(env)) = self.get_partition_date_env() {
+            let mut keyword_tasks = LinkedHashMap::new();
+            keyword_tasks.insert("env".into(), env);
+            let mut set_dependencies_kwargs = LinkedHashMap::new();
+            set_dependencies_kwargs.insert(
+                "keyword_tasks".into(),
+                AST::Dict(Dict::new_wrapped(keyword_tasks)),
+            );
+            stmts.push(AST::Expression(Expression::new_wrapped(AST::Call(
+                Call::new_wrapped(
+                    AST::Attribute(Attribute::new_wrapped(
+                        self.get_flow_identifier(),
+                        "set_dependencies".into(),
+                        false,
+                    )),
+                    vec![self.get_task_val()].into_iter().collect(),
+                    set_dependencies_kwargs,
+                ),
+            ))));
+        }
         for stmt in self.get_edge_addition_statements() {
             stmts.push(stmt);
         }
@@ -103,6 +144,7 @@ impl<U: AoristUniverse> ETLFlow<U> for PrefectPythonBasedFlow<U> {
         preamble: AOption<AString>,
         dialect: AOption<Dialect>,
         endpoints: U::TEndpoints,
+        execution_policy: AOption<TaskPolicy>,
     ) -> Self {
         Self {
             task_id,
@@ -115,6 +157,7 @@ impl<U: AoristUniverse> ETLFlow<U> for PrefectPythonBasedFlow<U> {
             dialect,
             flow_identifier: AST::SimpleIdentifier(SimpleIdentifier::new_wrapped("flow".into())),
             endpoints,
+            execution_policy,
             _universe: PhantomData,
         }
     }
@@ -122,7 +165,7 @@ impl<U: AoristUniverse> ETLFlow<U> for PrefectPythonBasedFlow<U> {
         "prefect".into()
     }
     fn get_imports(&self) -> AVec<PythonImport> {
-        match self.dialect {
+        let mut imports = match self.dialect {
             AOption(ROption::RSome(Dialect::Python(_))) => vec![PythonImport::PythonFromImport(
                 "prefect".into(),
                 "task".into(),
@@ -130,6 +173,8 @@ impl<U: AoristUniverse> ETLFlow<U> for PrefectPythonBasedFlow<U> {
             )],
             AOption(ROption::RSome(Dialect::Bash(_)))
             | AOption(ROption::RSome(Dialect::Presto(_)))
+            | AOption(ROption::RSome(Dialect::DuckDB(_)))
+            | AOption(ROption::RSome(Dialect::Spark(_)))
             | AOption(ROption::RSome(Dialect::R(_))) => {
                 vec![PythonImport::PythonFromImport(
                     "prefect.tasks.shell".into(),
@@ -142,12 +187,111 @@ impl<U: AoristUniverse> ETLFlow<U> for PrefectPythonBasedFlow<U> {
                 "Constant".into(),
                 AOption(ROption::RNone),
             )],
+        };
+        if let AOption(ROption::RSome(ref policy)) = self.execution_policy {
+            if policy.retries.is_some() {
+                imports.push(PythonImport::PythonFromImport(
+                    "datetime".into(),
+                    "timedelta".into(),
+                    AOption(ROption::RNone),
+                ));
+            }
+        }
+        imports.into_iter().collect()
+    }
+    /// Mapping via the imperative API, with per-task keyword arguments
+    /// mapped over and the rest passed as `unmapped`:
+    /// flow.set_dependencies(task, keyword_tasks={...}, mapped=True)
+    fn get_mapped_statements(&self, generator: AST) -> AOption<(AVec<AST>, AVec<PythonImport>)> {
+        if let AOption(ROption::RSome(ref dependencies)) = self.dep_list {
+            if varies_with(dependencies, &generator) {
+                return AOption(ROption::RNone);
+            }
+        }
+        let task = match self.dialect {
+            // positional arguments cannot be passed as keyword tasks
+            AOption(ROption::RSome(Dialect::Python(_))) if self.args.len() == 0 => {
+                self.get_python_task_copy()
+            }
+            AOption(ROption::RSome(Dialect::Bash(_)))
+            | AOption(ROption::RSome(Dialect::Presto(_)))
+            | AOption(ROption::RSome(Dialect::DuckDB(_)))
+            | AOption(ROption::RSome(Dialect::Spark(_))) => AST::Call(Call::new_wrapped(
+                self.compute_task_call(),
+                AVec::new(),
+                self.compute_task_options(),
+            )),
+            _ => return AOption(ROption::RNone),
+        };
+        let mut kwargs = self.compute_task_kwargs();
+        if !kwargs.values().any(|v| varies_with(v, &generator)) {
+            return AOption(ROption::RNone);
         }
+        if let AOption(ROption::RSome(env)) = self.get_partition_date_env() {
+            kwargs.insert("env".into(), env);
+        }
+        let keyword_tasks = kwargs
+            .into_iter()
+            .map(|(k, v)| {
+                let v = match varies_with(&v, &generator) {
+                    true => AST::ListComp(ListComp::new_wrapped(
+                        v,
+                        vec![generator.clone()].into_iter().collect(),
+                    )),
+                    false => AST::Call(Call::new_wrapped(
+                        AST::SimpleIdentifier(SimpleIdentifier::new_wrapped("unmapped".into())),
+                        vec![v].into_iter().collect(),
+                        LinkedHashMap::new(),
+                    )),
+                };
+                (k, v)
+            })
+            .collect::<LinkedHashMap<AString, AST>>();
+        let mut set_dependencies_kwargs = LinkedHashMap::new();
+        set_dependencies_kwargs.insert(
+            "keyword_tasks".into(),
+            AST::Dict(Dict::new_wrapped(keyword_tasks)),
+        );
+        set_dependencies_kwargs.insert(
+            "mapped".into(),
+            AST::BooleanLiteral(BooleanLiteral::new_wrapped(true)),
+        );
+        let set_dependencies =
+            AST::Expression(Expression::new_wrapped(AST::Call(Call::new_wrapped(
+                AST::Attribute(Attribute::new_wrapped(
+                    self.get_flow_identifier(),
+                    "set_dependencies".into(),
+                    false,
+                )),
+                vec![self.get_task_val()].into_iter().collect(),
+                set_dependencies_kwargs,
+            ))));
+        let statements = vec![
+            AST::Assignment(Assignment::new_wrapped(self.get_task_val(), task)),
+            set_dependencies,
+        ]
         .into_iter()
-        .collect()
+        .chain(self.get_edge_addition_statements().into_iter())
+        .collect();
+        let imports = self
+            .get_imports()
+            .into_iter()
+            .chain(
+                vec![PythonImport::PythonFromImport(
+                    "prefect".into(),
+                    "unmapped".into(),
+                    AOption(ROption::RNone),
+                )]
+                .into_iter(),
+            )
+            .collect();
+        AOption(ROption::RSome((statements, imports)))
     }
 }
-impl<U: AoristUniverse> PrefectPythonBasedFlow<U> {
+impl<U: AoristUniverse> PrefectPythonBasedFlow<U>
+where
+    U::TEndpoints: TPrestoEndpoints + TSparkEndpoints,
+{
     fn compute_task_args(&self) -> AVec<AST> {
         if let AOption(ROption::RSome(Dialect::Python(_))) = self.dialect {
             return self.args.clone();
@@ -164,7 +308,9 @@ impl<U: AoristUniverse> PrefectPythonBasedFlow<U> {
         let mut kwargs = LinkedHashMap::new();
         let call_param_name = match self.dialect {
             AOption(ROption::RSome(Dialect::Bash(_)))
-            | AOption(ROption::RSome(Dialect::Presto(_))) => "command".into(),
+            | AOption(ROption::RSome(Dialect::Presto(_)))
+            | AOption(ROption::RSome(Dialect::DuckDB(_)))
+            | AOption(ROption::RSome(Dialect::Spark(_))) => "command".into(),
             _ => panic!("Dialect not supported"),
         };
         let call_param_value = match self.dialect {
@@ -173,29 +319,184 @@ impl<U: AoristUniverse> PrefectPythonBasedFlow<U> {
                     self.command.as_ref().unwrap().clone(),
                     false,
                 )),
-                self.kwargs.clone(),
+                self.get_shell_kwargs(),
             )),
-            AOption(ROption::RSome(Dialect::Presto(_))) => AST::Formatted(Formatted::new_wrapped(
+            AOption(ROption::RSome(Dialect::Presto(_))) => {
                 AST::StringLiteral(StringLiteral::new_wrapped(
-                    format!("presto -e '{}'", self.command.as_ref().unwrap())
-                        .as_str()
-                        .into(),
+                    pipe_sql(
+                        Command::new("presto".into(), AVec::new()),
+                        self.get_sql().as_str(),
+                    )
+                    .to_bash(),
                     true,
-                )),
-                self.kwargs.clone(),
-            )),
+                ))
+            }
+            AOption(ROption::RSome(ref dialect @ Dialect::DuckDB(_))) => {
+                AST::StringLiteral(StringLiteral::new_wrapped(
+                    pipe_sql(sql_command(dialect, &self.kwargs), self.get_sql().as_str()).to_bash(),
+                    true,
+                ))
+            }
+            AOption(ROption::RSome(Dialect::Spark(_))) => {
+                let spark = self.endpoints.spark_config();
+                let mut args = vec![
+                    Word::Literal("--master".into()),
+                    Word::Literal(spark.master),
+                ];
+                if let AOption(ROption::RSome(uri)) = spark.metastore_uri {
+                    args.push(Word::Literal("--conf".into()));
+                    args.push(Word::Literal(
+                        format!("spark.hadoop.hive.metastore.uris={}", uri)
+                            .as_str()
+                            .into(),
+                    ));
+                }
+                args.push(Word::Literal("-f".into()));
+                args.push(Word::Literal("/dev/stdin".into()));
+                let command = Command::new("spark-sql".into(), args.into_iter().collect());
+                let statements = vec![pipe_sql(command.clone(), self.get_sql().as_str())]
+                    .into_iter()
+                    .chain(
+                        SchemaChange::from_kwargs(&self.kwargs)
+                            .iter()
+                            .map(|x| x.to_statement(&command)),
+                    )
+                    .map(|x| x.to_bash().as_str().to_string())
+                    .collect::<AVec<String>>();
+                AST::StringLiteral(StringLiteral::new_wrapped(
+                    statements.join("\n").as_str().into(),
+                    true,
+                ))
+            }
             _ => panic!("Dialect not supported"),
         };
         kwargs.insert(call_param_name, call_param_value);
         kwargs
     }
+    /// The query, with its parameters filled in.
+    fn get_sql(&self) -> AString {
+        fill_sql(self.command.as_ref().unwrap().as_str(), &self.kwargs)
+    }
+    /// Parameters of Bash tasks, with the partition date read from the
+    /// environment of the shell, since Prefect parameters are only known
+    /// when the flow runs.
+    fn get_shell_kwargs(&self) -> LinkedHashMap<AString, AST> {
+        self.kwargs
+            .iter()
+            .map(|(k, v)| {
+                let v = v.transform(&mut |x| match x {
+                    AST::SimpleIdentifier(ref id)
+                        if id.read().name().as_str() == "partition_date" =>
+                    {
+                        AST::StringLiteral(StringLiteral::new_wrapped(
+                            format!("${{{}}}", PARTITION_DATE_VARIABLE).as_str().into(),
+                            false,
+                        ))
+                    }
+                    _ => ConstantFolding::fold(x),
+                });
+                (k.clone(), v)
+            })
+            .collect()
+    }
+    /// Shell tasks referring to the partition date get the partition_date
+    /// parameter in their environment: {"PARTITION_DATE": partition_date}
+    fn get_partition_date_env(&self) -> AOption<AST> {
+        let uses_partition_date = match self.dialect {
+            AOption(ROption::RSome(Dialect::Bash(_))) => self
+                .kwargs
+                .values()
+                .flat_map(|x| x.get_descendants().into_iter())
+                .any(|x| match x {
+                    AST::SimpleIdentifier(ref id) => id.read().name().as_str() == "partition_date",
+                    _ => false,
+                }),
+            AOption(ROption::RSome(Dialect::Presto(_)))
+            | AOption(ROption::RSome(Dialect::DuckDB(_)))
+            | AOption(ROption::RSome(Dialect::Spark(_))) => {
+                self.get_sql().as_str().contains("{partition_date}")
+            }
+            _ => false,
+        };
+        if !uses_partition_date {
+            return AOption(ROption::RNone);
+        }
+        let mut env = LinkedHashMap::new();
+        env.insert(
+            PARTITION_DATE_VARIABLE.into(),
+            AST::SimpleIdentifier(SimpleIdentifier::new_wrapped("partition_date".into())),
+        );
+        AOption(ROption::RSome(AST::Dict(Dict::new_wrapped(env))))
+    }
+    /// Task options set by the execution policy. Prefect has no notion of
+    /// exponential backoff, pools or resource requests, so these are not
+    /// rendered.
+    fn compute_task_options(&self) -> LinkedHashMap<AString, AST> {
+        let mut options = LinkedHashMap::new();
+        if let AOption(ROption::RSome(ref policy)) = self.execution_policy {
+            // max_retries requires a retry_delay
+            if let AOption(ROption::RSome(retries)) = policy.retries {
+                options.insert(
+                    "max_retries".into(),
+                    AST::BigIntLiteral(BigIntLiteral::new_wrapped(retries)),
+                );
+                let mut timedelta_kwargs = LinkedHashMap::new();
+                timedelta_kwargs.insert(
+                    "seconds".into(),
+                    AST::BigIntLiteral(BigIntLiteral::new_wrapped(
+                        match policy.retry_delay {
+                            AOption(ROption::RSome(delay)) => delay,
+                            AOption(ROption::RNone) => 0,
+                        },
+                    )),
+                );
+                options.insert(
+                    "retry_delay".into(),
+                    AST::Call(Call::new_wrapped(
+                        AST::SimpleIdentifier(SimpleIdentifier::new_wrapped("timedelta".into())),
+                        AVec::new(),
+                        timedelta_kwargs,
+                    )),
+                );
+            }
+            if let AOption(ROption::RSome(timeout)) = policy.timeout {
+                options.insert(
+                    "timeout".into(),
+                    AST::BigIntLiteral(BigIntLiteral::new_wrapped(timeout)),
+                );
+            }
+        }
+        options
+    }
+    /// A copy of the (decorated) Python task function, with the task
+    /// options of the execution policy: fn.copy(max_retries=...)
+    fn get_python_task_copy(&self) -> AST {
+        AST::Call(Call::new_wrapped(
+            AST::Attribute(Attribute::new_wrapped(
+                AST::SimpleIdentifier(SimpleIdentifier::new_wrapped(
+                    self.command.as_ref().unwrap().clone(),
+                )),
+                "copy".into(),
+                false,
+            )),
+            AVec::new(),
+            self.compute_task_options(),
+        ))
+    }
     fn compute_task_call(&self) -> AST {
         match self.dialect {
+            AOption(ROption::RSome(Dialect::Python(_)))
+                if self.compute_task_options().len() > 0 =>
+            {
+                self.get_python_task_copy()
+            }
             AOption(ROption::RSome(Dialect::Python(_))) => AST::SimpleIdentifier(
                 SimpleIdentifier::new_wrapped(self.command.as_ref().unwrap().clone()),
             ),
             AOption(ROption::RSome(Dialect::Bash(_)))
-            | AOption(ROption::RSome(Dialect::Presto(_))) => AST::SimpleIdentifier(
+            | AOption(ROption::RSome(Dialect::Presto(_)))
+            | AOption(ROption::RSome(Dialect::DuckDB(_)))
+            | AOption(ROption::RSome(Dialect::Spark(_))) => AST::SimpleIdentifier(
                 SimpleIdentifier::new_wrapped("ShellTask".into()),
             ),
             AOption(ROption::RNone) => AST::SimpleIdentifier(SimpleIdentifier::new_wrapped(
@@ -257,11 +558,17 @@ impl<U: AoristUniverse> PrefectPythonBasedFlow<U> {
         AST::Expression(Expression::new_wrapped(add_expr))
     }
 }
-pub struct PrefectFlowBuilder<U: AoristUniverse> {
+pub struct PrefectFlowBuilder<U: AoristUniverse>
+where
+    U::TEndpoints: TPrestoEndpoints + TSparkEndpoints,
+{
     flow_identifier: AST,
     universe: PhantomData<U>,
 }
-impl<U: AoristUniverse> FlowBuilderBase<U> for PrefectFlowBuilder<U> {
+impl<U: AoristUniverse> FlowBuilderBase<U> for PrefectFlowBuilder<U>
+where
+    U::TEndpoints: TPrestoEndpoints + TSparkEndpoints,
+{
     type T = PrefectPythonBasedFlow<U>;
     fn new() -> Self {
         Self {
@@ -270,40 +577,268 @@ impl<U: AoristUniverse> FlowBuilderBase<U> for PrefectFlowBuilder<U> {
         }
     }
 }
-impl<U: AoristUniverse> PythonBasedFlowBuilder<U> for PrefectFlowBuilder<U> {
-    fn get_flow_imports(&self) -> AVec<PythonImport> {
-        AVec::new()
+impl<U: AoristUniverse> PrefectFlowBuilder<U>
+where
+    U::TEndpoints: TPrestoEndpoints + TSparkEndpoints,
+{
+    fn identifier(name: &str) -> AST {
+        AST::SimpleIdentifier(SimpleIdentifier::new_wrapped(name.into()))
+    }
+    fn datetime(args: AVec<i64>) -> AST {
+        AST::Call(Call::new_wrapped(
+            Self::identifier("datetime"),
+            args.into_iter()
+                .map(|x| AST::BigIntLiteral(BigIntLiteral::new_wrapped(x)))
+                .collect(),
+            LinkedHashMap::new(),
+        ))
+    }
+    fn get_flow_method_call(
+        &self,
+        method: &str,
+        args: AVec<AST>,
+        kwargs: LinkedHashMap<AString, AST>,
+    ) -> AST {
+        AST::Call(Call::new_wrapped(
+            AST::Attribute(Attribute::new_wrapped(
+                self.flow_identifier.clone(),
+                method.into(),
+                false,
+            )),
+            args,
+            kwargs,
+        ))
+    }
+    /// flow = Flow("name", schedule=CronSchedule(...)), plus the
+    /// partition_date parameter of scheduled flows.
+    fn get_flow_definition(
+        &self,
+        flow_name: AOption<AString>,
+        schedule: &AOption<Schedule>,
+    ) -> AVec<AST> {
+        let mut kwargs = LinkedHashMap::new();
+        let mut statements = AVec::new();
+        if let AOption(ROption::RSome(ref x)) = schedule {
+            let mut schedule_kwargs = LinkedHashMap::new();
+            for (key, date) in vec![
+                ("start_date", x.get_start_date()),
+                ("end_date", x.get_end_date()),
+            ] {
+                if let AOption(ROption::RSome((year, month, day))) = date {
+                    schedule_kwargs.insert(
+                        key.into(),
+                        Self::datetime(vec![year, month, day].into_iter().collect()),
+                    );
+                }
+            }
+            kwargs.insert(
+                "schedule".into(),
+                AST::Call(Call::new_wrapped(
+                    Self::identifier("CronSchedule"),
+                    vec![AST::StringLiteral(StringLiteral::new_wrapped(
+                        x.get_frequency().get_cron_expression(),
+                        false,
+                    ))]
+                    .into_iter()
+                    .collect(),
+                    schedule_kwargs,
+                )),
+            );
+            let mut parameter_kwargs = LinkedHashMap::new();
+            parameter_kwargs.insert("default".into(), AST::None(None::new_wrapped()));
+            let partition_date = Self::identifier("partition_date");
+            statements.push(AST::Assignment(Assignment::new_wrapped(
+                partition_date.clone(),
+                AST::Call(Call::new_wrapped(
+                    Self::identifier("Parameter"),
+                    vec![AST::StringLiteral(StringLiteral::new_wrapped(
+                        "partition_date".into(),
+                        false,
+                    ))]
+                    .into_iter()
+                    .collect(),
+                    parameter_kwargs,
+                )),
+            )));
+            statements.push(AST::Expression(Expression::new_wrapped(
+                self.get_flow_method_call(
+                    "add_task",
+                    vec![partition_date].into_iter().collect(),
+                    LinkedHashMap::new(),
+                ),
+            )));
+        }
+        let flow = AST::Assignment(Assignment::new_wrapped(
+            self.flow_identifier.clone(),
+            AST::Call(Call::new_wrapped(
+                Self::identifier("Flow"),
+                vec![AST::StringLiteral(StringLiteral::new_wrapped(
+                    match flow_name {
+                        AOption(ROption::RSome(x)) => x,
+                        AOption(ROption::RNone) => "flow".into(),
+                    },
+                    false,
+                ))]
+                .into_iter()
+                .collect(),
+                kwargs,
+            )),
+        ));
+        statements.insert(0, flow);
+        statements
+    }
+    /// Runs the flow once per scheduled time in the backfill range, passing
+    /// the partition it covers:
+    /// for scheduled_time in flow.schedule.next(n, after=<day before start>):
+    ///     flow.run(parameters={"partition_date": ...}, run_on_schedule=False)
+    fn get_backfill(&self, schedule: &Schedule) -> AOption<AST> {
+        let limit = match schedule.get_backfill_limit() {
+            AOption(ROption::RSome(x)) => x,
+            AOption(ROption::RNone) => return AOption(ROption::RNone),
+        };
+        let (year, month, day) = Schedule::get_day_before(schedule.get_start_date().unwrap());
+        let mut next_kwargs = LinkedHashMap::new();
+        next_kwargs.insert(
+            "after".into(),
+            Self::datetime(vec![year, month, day, 23, 59, 59].into_iter().collect()),
+        );
+        let scheduled_times = AST::Call(Call::new_wrapped(
+            AST::Attribute(Attribute::new_wrapped(
+                AST::Attribute(Attribute::new_wrapped(
+                    self.flow_identifier.clone(),
+                    "schedule".into(),
+                    false,
+                )),
+                "next".into(),
+                false,
+            )),
+            vec![AST::BigIntLiteral(BigIntLiteral::new_wrapped(limit))]
+                .into_iter()
+                .collect(),
+            next_kwargs,
+        ));
+        let scheduled_time = Self::identifier("scheduled_time");
+        let mut parameters = LinkedHashMap::new();
+        parameters.insert(
+            "partition_date".into(),
+            AST::Call(Call::new_wrapped(
+                AST::Attribute(Attribute::new_wrapped(
+                    scheduled_time.clone(),
+                    "strftime".into(),
+                    false,
+                )),
+                vec![AST::StringLiteral(StringLiteral::new_wrapped(
+                    schedule.get_frequency().get_partition_format(),
+                    false,
+                ))]
+                .into_iter()
+                .collect(),
+                LinkedHashMap::new(),
+            )),
+        );
+        let mut run_kwargs = LinkedHashMap::new();
+        run_kwargs.insert(
+            "parameters".into(),
+            AST::Dict(Dict::new_wrapped(parameters)),
+        );
+        run_kwargs.insert(
+            "run_on_schedule".into(),
+            AST::BooleanLiteral(BooleanLiteral::new_wrapped(false)),
+        );
+        AOption(ROption::RSome(AST::ForLoop(ForLoop::new_wrapped(
+            scheduled_time,
+            scheduled_times,
+            vec![AST::Expression(Expression::new_wrapped(
+                self.get_flow_method_call("run", AVec::new(), run_kwargs),
+            ))]
+            .into_iter()
+            .collect(),
+        ))))
+    }
+}
+impl<U: AoristUniverse> PythonBasedFlowBuilder<U> for PrefectFlowBuilder<U>
+where
+    U::TEndpoints: TPrestoEndpoints + TSparkEndpoints,
+{
+    fn get_flow_imports(&self, schedule: AOption<Schedule>) -> AVec<PythonImport> {
+        let mut imports = vec![PythonImport::PythonFromImport(
+            "prefect".into(),
+            "Flow".into(),
+            AOption(ROption::RNone),
+        )];
+        if let AOption(ROption::RSome(x)) = schedule {
+            imports.push(PythonImport::PythonFromImport(
+                "prefect".into(),
+                "Parameter".into(),
+                AOption(ROption::RNone),
+            ));
+            imports.push(PythonImport::PythonFromImport(
+                "prefect.schedules".into(),
+                "CronSchedule".into(),
+                AOption(ROption::RNone),
+            ));
+            if x.get_start_date().is_some() || x.get_end_date().is_some() {
+                imports.push(PythonImport::PythonFromImport(
+                    "datetime".into(),
+                    "datetime".into(),
+                    AOption(ROption::RNone),
+                ));
+            }
+        }
+        imports.into_iter().collect()
     }
     /// Takes a set of statements and mutates them so as make a valid ETL flow
     fn augment_statements(
         &self,
         statements: AVec<PythonFlowBuilderInput>,
-        _flow_name: AOption<AString>,
+        flow_name: AOption<AString>,
+        schedule: AOption<Schedule>,
     ) -> AVec<PythonFlowBuilderInput> {
-        // TODO: add flow definition
-        statements
-            .into_iter()
-            .chain(
-                vec![PythonFlowBuilderInput::statements_only(
-                    vec![AST::Expression(Expression::new_wrapped(AST::Call(
-                        Call::new_wrapped(
-                            AST::Attribute(Attribute::new_wrapped(
-                                self.flow_identifier.clone(),
-                                "run".into(),
-                                false,
-                            )),
-                            AVec::new(),
-                            LinkedHashMap::new(),
-                        ),
-                    )))]
+        let run = match schedule {
+            AOption(ROption::RSome(ref x)) => self.get_backfill(x),
+            AOption(ROption::RNone) => AOption(ROption::RNone),
+        };
+        let run = match run {
+            AOption(ROption::RSome(x)) => x,
+            AOption(ROption::RNone) => AST::Expression(Expression::new_wrapped(
+                self.get_flow_method_call("run", AVec::new(), LinkedHashMap::new()),
+            )),
+        };
+        // the flow is only run when executed as a script, so that it can be
+        // imported (e.g. to register it with a Prefect server)
+        let run = AST::If(If::new_wrapped(
+            AST::Compare(Compare::new_wrapped(
+                Self::identifier("__name__"),
+                vec![AST::Equals(Equals::new_wrapped())]
                     .into_iter()
                     .collect(),
-                    "Run Prefect flow".into(),
-                    AOption(ROption::RNone),
-                    AOption(ROption::RNone),
-                )]
-                .into_iter(),
-            )
-            .collect()
+                vec![AST::StringLiteral(StringLiteral::new_wrapped(
+                    "__main__".into(),
+                    false,
+                ))]
+                .into_iter()
+                .collect(),
+            )),
+            vec![run].into_iter().collect(),
+            AOption(ROption::RNone),
+        ));
+        vec![PythonFlowBuilderInput::statements_only(
+            self.get_flow_definition(flow_name, &schedule),
+            "Setting up Prefect flow".into(),
+            AOption(ROption::RNone),
+            AOption(ROption::RNone),
+        )]
+        .into_iter()
+        .chain(statements.into_iter())
+        .chain(
+            vec![PythonFlowBuilderInput::statements_only(
+                vec![run].into_iter().collect(),
+                "Run Prefect flow".into(),
+                AOption(ROption::RNone),
+                AOption(ROption::RNone),
+            )]
+            .into_iter(),
+        )
+        .collect()
     }
 }
@@ -3,7 +3,7 @@ use crate::python::{NativePythonPreamble, PythonPreamble, RPythonPreamble};
 use abi_stable::std_types::ROption;
 use aorist_primitives::AoristUniverse;
 use aorist_primitives::Dialect;
-use aorist_primitives::TPrestoEndpoints;
+use aorist_primitives::{TPrestoEndpoints, TSparkEndpoints};
 use aorist_util::AOption;
 use aorist_util::{AString, AVec};
 use pyo3::prelude::*;
@@ -11,7 +11,7 @@ use pyo3::prelude::*;
 pub trait PythonBasedFlow<U>: ETLFlow<U>
 where
     U: AoristUniverse,
-    <U as AoristUniverse>::TEndpoints: TPrestoEndpoints,
+    <U as AoristUniverse>::TEndpoints: TPrestoEndpoints + TSparkEndpoints,
 {
     fn get_preamble_string(&self) -> AOption<AString>;
     fn get_python_preamble(&self) -> PyResult<AVec<PythonPreamble>> {
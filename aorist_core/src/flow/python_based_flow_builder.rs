@@ -1,17 +1,19 @@
+use crate::code::FlowDependencies;
 use crate::flow::etl_flow::ETLFlow;
 use crate::flow::flow_builder::{FlowBuilderBase, FlowBuilderMaterialize};
 use crate::flow::flow_builder_input::FlowBuilderInput;
 use crate::python::{format_code, PythonFlowBuilderInput, PythonImport, PythonPreamble};
 use abi_stable::std_types::ROption;
-use aorist_ast::AST;
-use aorist_primitives::AoristUniverse;
+use aorist_ast::{
+    optimize, ConstantFolding, DeadAssignmentElimination, SubexpressionHoisting, TOptimizationPass,
+};
+use aorist_primitives::{AoristUniverse, Schedule};
 use aorist_util::AOption;
 use aorist_util::{AString, AVec};
-use linked_hash_map::LinkedHashMap;
 use linked_hash_set::LinkedHashSet;
 use pyo3::prelude::*;
 use pyo3::types::{PyModule, PyString};
-use std::collections::BTreeSet;
+use std::collections::{BTreeSet, HashSet};
 
 impl<C, U> FlowBuilderMaterialize<U> for C
 where
@@ -24,26 +26,65 @@ where
     type BuilderInputType = PythonFlowBuilderInput;
     type ErrorType = PyErr;
 
+    fn get_dependencies(
+        &self,
+        statements_and_preambles: &AVec<PythonFlowBuilderInput>,
+        schedule: AOption<Schedule>,
+    ) -> FlowDependencies {
+        let preambles: LinkedHashSet<PythonPreamble> = statements_and_preambles
+            .iter()
+            .map(|x| x.get_preambles().into_iter())
+            .flatten()
+            .collect();
+        let mut dependencies = FlowDependencies::new();
+        for import in statements_and_preambles
+            .iter()
+            .map(|x| x.get_imports().into_iter())
+            .flatten()
+            .chain(self.get_flow_imports(schedule).into_iter())
+            .chain(Self::get_preamble_imports(&preambles).into_iter())
+        {
+            if let AOption(ROption::RSome(package)) = import.get_pip_package() {
+                dependencies.add_pip_requirement(package);
+            }
+        }
+        dependencies
+    }
     fn materialize(
         &self,
         statements_and_preambles: AVec<PythonFlowBuilderInput>,
         flow_name: AOption<AString>,
+        schedule: AOption<Schedule>,
     ) -> Result<AString, Self::ErrorType> {
+        if let AOption(ROption::RSome(native)) =
+            self.build_native_file(&statements_and_preambles, flow_name.clone(), schedule.clone())
+        {
+            return native;
+        }
         let gil = Python::acquire_gil();
         let py = gil.python();
 
         let ast = PyModule::import(py, "ast").unwrap();
         let astor = PyModule::import(py, "astor").unwrap();
 
-        let flow_imports = self.get_flow_imports().into_iter();
+        let flow_imports = self.get_flow_imports(schedule.clone()).into_iter();
 
-        let preambles: LinkedHashSet<PythonPreamble> = statements_and_preambles
-            .iter()
-            .map(|x| x.clone().get_preambles().into_iter())
-            .flatten()
-            .collect();
+        let preambles: LinkedHashSet<PythonPreamble> = PythonPreamble::merge_identical(
+            statements_and_preambles
+                .iter()
+                .map(|x| x.clone().get_preambles().into_iter())
+                .flatten()
+                .collect(),
+        );
 
         let preamble_imports = Self::get_preamble_imports(&preambles);
+        let preamble_read_names = preambles
+            .iter()
+            .map(|x| x.get_read_names())
+            .collect::<PyResult<AVec<_>>>()?
+            .into_iter()
+            .flat_map(|x| x.into_iter())
+            .collect::<HashSet<AString>>();
 
         let imports = statements_and_preambles
             .iter()
@@ -58,38 +99,24 @@ where
             .map(|x| x.to_python_ast_node(py, ast, 0).unwrap())
             .collect();
 
-        let mut statements_with_ast: AVec<_> = statements_and_preambles
+        let statements_with_ast: AVec<_> = statements_and_preambles
             .into_iter()
             .filter(|x| x.has_statements())
             .collect::<AVec<_>>();
+        let hoisting = SubexpressionHoisting::new(
+            "constants".into(),
+            AOption(ROption::RSome("Common constants".into())),
+        );
+        let statements_with_ast =
+            optimize_inputs(statements_with_ast, &[&ConstantFolding, &hoisting]);
 
-        // ast_value without ancestry => short_name => keys
-        let mut literals: LinkedHashMap<AST, LinkedHashMap<AString, AVec<_>>> =
-            LinkedHashMap::new();
-
-        for pfbi in statements_with_ast.iter() {
-            pfbi.extract_literals(&mut literals);
-        }
-        let assignments_ast = Self::literals_to_assignments(literals);
-
-        if assignments_ast.len() > 0 {
-            statements_with_ast.insert(
-                0,
-                PythonFlowBuilderInput::new(
-                    assignments_ast,
-                    LinkedHashSet::new(),
-                    BTreeSet::new(),
-                    "assignments".into(),
-                    AOption(ROption::RSome("Common string literals".into())),
-                    AOption(ROption::RNone),
-                ),
-            );
-        }
-
-        let augmented_statements: Vec<_> = self
-            .augment_statements(statements_with_ast, flow_name.clone())
-            .into_iter()
-            .collect();
+        // dead assignments can only be found once the flow is complete
+        let augmented_statements: Vec<_> = optimize_inputs(
+            self.augment_statements(statements_with_ast, flow_name.clone(), schedule),
+            &[&DeadAssignmentElimination::new(preamble_read_names)],
+        )
+        .into_iter()
+        .collect();
         let content: Vec<(AOption<AString>, Vec<&PyAny>)> = vec![(
             AOption(ROption::RNone),
             imports_ast.into_iter().collect::<Vec<_>>(),
@@ -164,6 +191,43 @@ where
     }
 }
 
+/// Runs optimization passes over the statements of all inputs. Blocks added
+/// by the passes (ahead of existing ones) become inputs of their own, and
+/// inputs left without statements are dropped.
+fn optimize_inputs(
+    inputs: AVec<PythonFlowBuilderInput>,
+    passes: &[&dyn TOptimizationPass],
+) -> AVec<PythonFlowBuilderInput> {
+    let blocks = optimize(
+        inputs.iter().map(|x| x.get_statement_block()).collect(),
+        passes,
+    );
+    let num_added = blocks.len() - inputs.len();
+    let mut blocks = blocks.into_iter();
+    let added: AVec<_> = blocks
+        .by_ref()
+        .take(num_added)
+        .map(|block| {
+            PythonFlowBuilderInput::statements_only(
+                block.statements,
+                block.name,
+                block.title,
+                AOption(ROption::RNone),
+            )
+        })
+        .collect();
+    added
+        .into_iter()
+        .chain(
+            inputs
+                .into_iter()
+                .zip(blocks)
+                .map(|(input, block)| input.with_statements(block.statements)),
+        )
+        .filter(|x| x.has_statements())
+        .collect()
+}
+
 /// Encapsulates all the necessary bits for the construction of a Flow written in
 /// Python.
 pub trait PythonBasedFlowBuilder<U>: FlowBuilderBase<U>
@@ -176,10 +240,22 @@ where
         &self,
         statements: AVec<PythonFlowBuilderInput>,
         _flow_name: AOption<AString>,
+        _schedule: AOption<Schedule>,
     ) -> AVec<PythonFlowBuilderInput> {
         statements
     }
-    fn get_flow_imports(&self) -> AVec<PythonImport>;
+    fn get_flow_imports(&self, schedule: AOption<Schedule>) -> AVec<PythonImport>;
+
+    /// Builders that do not emit Python (e.g. Quarto, Bash) render each task
+    /// in its own dialect, straight from the builder inputs.
+    fn build_native_file(
+        &self,
+        _statements: &AVec<PythonFlowBuilderInput>,
+        _flow_name: AOption<AString>,
+        _schedule: AOption<Schedule>,
+    ) -> AOption<PyResult<AString>> {
+        AOption(ROption::RNone)
+    }
 
     fn build_file(
         &self,
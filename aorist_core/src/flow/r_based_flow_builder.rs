@@ -20,6 +20,9 @@ pub enum RFlowBuilderError {
 
 pub struct RBasedFlowBuilder {}
 impl RBasedFlowBuilder {
+    fn conversion_error(err: pyo3::PyErr) -> RFlowBuilderError {
+        RFlowBuilderError::Generic(err.to_string().as_str().into())
+    }
     fn build_flow(
         &self,
         statements: AVec<(AString, AOption<AString>, AOption<AString>, AVec<AString>)>,
@@ -94,11 +97,11 @@ impl FlowBuilderMaterialize for RBasedFlowBuilder {
         let imports_ast: AVec<_> = imports
             .into_iter()
             .map(|x| {
-                let call = x.to_r_ast_node(0);
+                let call = x.to_r_ast_node(0).map_err(Self::conversion_error)?;
                 let deparsed = call!("deparse", call).unwrap();
-                Vec::<AString>::from_robj(&deparsed).unwrap().join("\n")
+                Ok(Vec::<AString>::from_robj(&deparsed).unwrap().join("\n"))
             })
-            .collect();
+            .collect::<Result<_, RFlowBuilderError>>()?;
 
         let statements: AVec<(AString, AOption<AString>, AOption<AString>, AVec<AST>)> =
             statements_and_preambles
@@ -141,19 +144,20 @@ impl FlowBuilderMaterialize for RBasedFlowBuilder {
         let statements_ast = statements_with_ast
             .into_iter()
             .map(|(name, title, body, x)| {
-                (
+                Ok((
                     name,
                     title,
                     body,
                     x.into_iter()
                         .map(|y| {
-                            let deparsed = call!("deparse", y.to_r_ast_node(0)).unwrap();
-                            Vec::<AString>::from_robj(&deparsed).unwrap().join("\n")
+                            let call = y.to_r_ast_node(0).map_err(Self::conversion_error)?;
+                            let deparsed = call!("deparse", call).unwrap();
+                            Ok(Vec::<AString>::from_robj(&deparsed).unwrap().join("\n"))
                         })
-                        .collect(),
-                )
+                        .collect::<Result<_, RFlowBuilderError>>()?,
+                ))
             })
-            .collect();
+            .collect::<Result<_, RFlowBuilderError>>()?;
 
         let flow = self.build_flow(statements_ast);
 
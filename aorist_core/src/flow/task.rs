@@ -2,7 +2,7 @@ use crate::flow::etl_flow::ETLFlow;
 use crate::parameter_tuple::{ParameterTuple, ParameterTupleDedupKey};
 use aorist_ast::AST;
 use aorist_primitives::AoristUniverse;
-use aorist_primitives::Dialect;
+use aorist_primitives::{Dialect, TaskPolicy};
 use aorist_util::AOption;
 use aorist_util::{AString, AVec};
 use std::hash::Hash;
@@ -26,6 +26,7 @@ where
         dependencies: AVec<AST>,
         preamble: AOption<AString>,
         dialect: AOption<Dialect>,
+        execution_policy: AOption<TaskPolicy>,
     ) -> Self;
 }
 pub trait CompressionKey: Clone + Hash + PartialEq + Eq {
@@ -35,12 +36,14 @@ pub trait CompressionKey: Clone + Hash + PartialEq + Eq {
         dedup_key: AOption<ParameterTupleDedupKey>,
         preamble: AOption<AString>,
         dialect: AOption<Dialect>,
+        execution_policy: AOption<TaskPolicy>,
     ) -> Self;
     fn get_dict_name(&self) -> AST;
     fn get_dedup_key(&self) -> AOption<ParameterTupleDedupKey>;
     fn get_call(&self) -> AOption<AString>;
     fn get_preamble(&self) -> AOption<AString>;
     fn get_dialect(&self) -> AOption<Dialect>;
+    fn get_execution_policy(&self) -> AOption<TaskPolicy>;
 }
 
 pub trait CompressibleTask
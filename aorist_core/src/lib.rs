@@ -7,10 +7,13 @@ mod constraint_state;
 mod driver;
 mod flow;
 mod parameter_tuple;
+mod shell;
 
+#[cfg(all(feature = "python", feature = "sql"))]
+pub use aorist_ast::sql::sql_module;
 #[cfg(feature = "python")]
 pub use aorist_primitives::dialects_module;
-pub use aorist_primitives::{Bash, Dialect, Presto, Python, R};
+pub use aorist_primitives::{Bash, Dialect, DuckDB, Presto, Python, Spark, R};
 pub use code::*;
 pub use constraint::*;
 pub use constraint_block::*;
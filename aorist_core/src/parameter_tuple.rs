@@ -1,4 +1,4 @@
-use aorist_ast::{AncestorRecord, List, StringLiteral, AST};
+use aorist_ast::{AncestorRecord, Formatted, List, SimpleIdentifier, StringLiteral, AST};
 use aorist_util::AUuid;
 use aorist_util::{AString, AVec};
 use linked_hash_map::LinkedHashMap;
@@ -41,14 +41,34 @@ impl ParameterTuple {
     ) -> Self {
         let args = args_v
             .into_iter()
-            .map(|x| AST::StringLiteral(StringLiteral::new_wrapped(x, is_sql)))
+            .map(|x| Self::to_ast(x, is_sql))
             .collect::<AVec<_>>();
         let kwargs = kwargs_v
             .into_iter()
-            .map(|(k, v)| (k, AST::StringLiteral(StringLiteral::new_wrapped(v, is_sql))))
+            .map(|(k, v)| (k, Self::to_ast(v, is_sql)))
             .collect::<LinkedHashMap<_, _>>();
         Self { args, kwargs }
     }
+    /// Program arguments referring to `{partition_date}` are bound to the
+    /// flow's `partition_date` variable. SQL is left as is, since queries
+    /// are only formatted by the task running them.
+    fn to_ast(value: AString, is_sql: bool) -> AST {
+        let literal = AST::StringLiteral(StringLiteral::new_wrapped(value.clone(), is_sql));
+        if is_sql || !value.as_str().contains("{partition_date}") {
+            return literal;
+        }
+        let partition_date =
+            AST::SimpleIdentifier(SimpleIdentifier::new_wrapped("partition_date".into()));
+        if value.as_str() == "{partition_date}" {
+            return partition_date;
+        }
+        AST::Formatted(Formatted::new_wrapped(
+            literal,
+            vec![("partition_date".into(), partition_date)]
+                .into_iter()
+                .collect(),
+        ))
+    }
     pub fn get_args(&self) -> AVec<AST> {
         self.args.clone()
     }
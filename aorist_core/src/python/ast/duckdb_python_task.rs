@@ -0,0 +1,84 @@
+use abi_stable::std_types::ROption;
+use aorist_util::AOption;
+
+use crate::python::ast::AirflowTaskBase;
+use crate::python::ast::PrestoPythonTask;
+use crate::python::ast::{PythonFunctionCallTask, PythonTaskBase};
+use crate::python::NativePythonPreamble;
+use crate::python::PythonImport;
+use abi_stable::external_types::parking_lot::rw_lock::RRwLock;
+use abi_stable::std_types::RArc;
+use aorist_ast::{Call, SimpleIdentifier, AST};
+use aorist_primitives::define_task_node;
+use aorist_util::{AString, AVec};
+use linked_hash_map::LinkedHashMap;
+use std::hash::Hash;
+
+define_task_node!(
+    DuckDBPythonTask,
+    |task: &DuckDBPythonTask| vec![task.sql.clone()].into_iter().collect(),
+    |task: &DuckDBPythonTask| { task.get_native_python_statements() },
+    |_task: &DuckDBPythonTask| {
+        vec![
+            PythonImport::PythonModuleImport("duckdb".into(), AOption(ROption::RNone)),
+            PythonImport::PythonModuleImport("os".into(), AOption(ROption::RNone)),
+        ]
+        .into_iter()
+        .collect()
+    },
+    PythonImport,
+    sql: AST,
+    kwargs: LinkedHashMap<AString, AST>,
+    task_val: AST,
+    dependencies: AOption<AST>,
+);
+
+impl PythonTaskBase for DuckDBPythonTask {
+    fn get_task_val(&self) -> AST {
+        self.task_val.clone()
+    }
+}
+impl PythonFunctionCallTask for DuckDBPythonTask {
+    fn get_preamble(&self) -> AOption<NativePythonPreamble> {
+        let duckdb = PythonImport::PythonModuleImport("duckdb".into(), AOption(ROption::RNone));
+        let os = PythonImport::PythonModuleImport("os".into(), AOption(ROption::RNone));
+        let body = "
+def execute_duckdb_sql(query, database=None):
+    if database is None:
+        database = os.environ.get('DUCKDB_DATABASE', 'aorist.duckdb')
+    connection = duckdb.connect(database)
+    for q in (query if isinstance(query, list) else [query]):
+        connection.execute(q)
+        print('Ran query: ' + chr(10) + ' ' + q)
+    connection.close()
+";
+        AOption(ROption::RSome(NativePythonPreamble {
+            imports: vec![duckdb, os].into_iter().collect(),
+            from_imports: AVec::new(),
+            body: body.into(),
+        }))
+    }
+    /// Programs binding a `database` parameter (e.g. the file of a
+    /// DuckDBLocation) run against that database, others against the one
+    /// named by DUCKDB_DATABASE.
+    fn get_call(&self) -> AST {
+        let mut kwargs: LinkedHashMap<AString, AST> = LinkedHashMap::new();
+        kwargs.insert(
+            "query".into(),
+            PrestoPythonTask::get_query(&self.sql, &self.kwargs),
+        );
+        if let Some(database) = self.kwargs.get(&("database".into())) {
+            kwargs.insert("database".into(), database.clone());
+        }
+        AST::Call(Call::new_wrapped(
+            AST::SimpleIdentifier(SimpleIdentifier::new_wrapped("execute_duckdb_sql".into())),
+            AVec::new(),
+            kwargs,
+        ))
+    }
+}
+impl AirflowTaskBase for DuckDBPythonTask {
+    fn get_dependencies(&self) -> AOption<AST> {
+        self.dependencies.clone()
+    }
+}
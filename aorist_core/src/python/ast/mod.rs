@@ -12,22 +12,26 @@ mod airflow_python_operator_task;
 mod airflow_task_base;
 mod bash_python_task;
 mod constant_python_task;
+mod duckdb_python_task;
 mod native_python_task;
 mod presto_python_task;
 mod python_function_call_task;
 mod python_subprocess_task;
 mod python_task_base;
 mod r_python_task;
+mod spark_python_task;
 
 pub use airflow_python_operator_task::AirflowPythonOperatorTask;
 pub use airflow_task_base::AirflowTaskBase;
 pub use bash_python_task::BashPythonTask;
 pub use constant_python_task::ConstantPythonTask;
+pub use duckdb_python_task::DuckDBPythonTask;
 pub use native_python_task::NativePythonTask;
 pub use presto_python_task::PrestoPythonTask;
 pub use python_function_call_task::PythonFunctionCallTask;
 pub use python_task_base::PythonTaskBase;
 pub use r_python_task::RPythonTask;
+pub use spark_python_task::SparkPythonTask;
 
 use crate::python::PythonPreamble;
 
@@ -39,6 +43,8 @@ register_task_nodes! {
     NativePythonTask,
     ConstantPythonTask,
     PrestoPythonTask,
+    DuckDBPythonTask,
+    SparkPythonTask,
 }
 
 impl PythonTask {
@@ -49,6 +55,8 @@ impl PythonTask {
             PythonTask::NativePythonTask(x) => x.read().get_preamble(),
             PythonTask::ConstantPythonTask(x) => x.read().get_preamble(),
             PythonTask::PrestoPythonTask(x) => x.read().get_preamble(),
+            PythonTask::DuckDBPythonTask(x) => x.read().get_preamble(),
+            PythonTask::SparkPythonTask(x) => x.read().get_preamble(),
         };
         if let AOption(ROption::RSome(p)) = inner {
             return AOption(ROption::RSome(PythonPreamble::NativePythonPreamble(p)));
@@ -62,6 +70,8 @@ impl PythonTask {
             PythonTask::NativePythonTask(x) => AOption(ROption::RSome(x.read().get_call())),
             PythonTask::ConstantPythonTask(x) => AOption(ROption::RSome(x.read().get_call())),
             PythonTask::PrestoPythonTask(x) => AOption(ROption::RSome(x.read().get_call())),
+            PythonTask::DuckDBPythonTask(x) => AOption(ROption::RSome(x.read().get_call())),
+            PythonTask::SparkPythonTask(x) => AOption(ROption::RSome(x.read().get_call())),
         }
     }
 }
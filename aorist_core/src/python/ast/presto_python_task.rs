@@ -7,7 +7,7 @@ use crate::python::NativePythonPreamble;
 use crate::python::PythonImport;
 use abi_stable::external_types::parking_lot::rw_lock::RRwLock;
 use abi_stable::std_types::RArc;
-use aorist_ast::{Call, Formatted, SimpleIdentifier, AST};
+use aorist_ast::{Call, Formatted, List, SimpleIdentifier, AST};
 use aorist_primitives::define_task_node;
 use aorist_primitives::PrestoConfig;
 use aorist_util::{AString, AVec};
@@ -79,24 +79,66 @@ def execute_trino_sql(query):
         }))
     }
     fn get_call(&self) -> AST {
-        let query;
-        if let AST::StringLiteral(ref s) = self.sql {
+        AST::Call(Call::new_wrapped(
+            AST::SimpleIdentifier(SimpleIdentifier::new_wrapped("execute_trino_sql".into())),
+            vec![].into_iter().collect(),
+            vec![("query".into(), Self::get_query(&self.sql, &self.kwargs))]
+                .into_iter()
+                .collect(),
+        ))
+    }
+}
+impl PrestoPythonTask {
+    /// The query (or list of queries) to run, with the SQL's placeholders
+    /// filled in from `kwargs`. Shared with other SQL-based tasks.
+    pub(crate) fn get_query(sql: &AST, kwargs: &LinkedHashMap<AString, AST>) -> AST {
+        if let AST::StringLiteral(ref s) = sql {
             if s.read().value().as_str() == "{queries}" {
-                query = self.kwargs.get(&("queries".into())).unwrap().clone();
+                match kwargs.get(&("queries".into())).unwrap() {
+                    AST::List(ref l) => AST::List(List::new_wrapped(
+                        l.read()
+                            .elems()
+                            .iter()
+                            .map(|x| Self::bind_partition_date(x.clone()))
+                            .collect(),
+                        false,
+                    )),
+                    x => Self::bind_partition_date(x.clone()),
+                }
             } else {
-                query = AST::Formatted(Formatted::new_wrapped(
-                    self.sql.clone(),
-                    self.kwargs.clone(),
-                ));
+                AST::Formatted(Formatted::new_wrapped(
+                    sql.clone(),
+                    Self::get_partition_date_kwargs(s.read().value(), kwargs.clone()),
+                ))
             }
         } else {
             panic!("SQL should be StringLiteral.");
         }
-        AST::Call(Call::new_wrapped(
-            AST::SimpleIdentifier(SimpleIdentifier::new_wrapped("execute_trino_sql".into())),
-            vec![].into_iter().collect(),
-            vec![("query".into(), query)].into_iter().collect(),
-        ))
+    }
+    /// Queries of partitioned tables refer to `{partition_date}`, which is
+    /// filled in at run time from the flow's `partition_date` variable.
+    fn get_partition_date_kwargs(
+        sql: AString,
+        mut kwargs: LinkedHashMap<AString, AST>,
+    ) -> LinkedHashMap<AString, AST> {
+        let key: AString = "partition_date".into();
+        if sql.as_str().contains("{partition_date}") && !kwargs.contains_key(&key) {
+            kwargs.insert(
+                key.clone(),
+                AST::SimpleIdentifier(SimpleIdentifier::new_wrapped(key)),
+            );
+        }
+        kwargs
+    }
+    fn bind_partition_date(query: AST) -> AST {
+        match query {
+            AST::StringLiteral(ref s) if s.read().value().as_str().contains("{partition_date}") => {
+                let kwargs =
+                    Self::get_partition_date_kwargs(s.read().value(), LinkedHashMap::new());
+                AST::Formatted(Formatted::new_wrapped(query.clone(), kwargs))
+            }
+            _ => query,
+        }
     }
 }
 impl AirflowTaskBase for PrestoPythonTask {
@@ -0,0 +1,108 @@
+use abi_stable::std_types::ROption;
+use aorist_util::AOption;
+
+use crate::python::ast::AirflowTaskBase;
+use crate::python::ast::PrestoPythonTask;
+use crate::python::ast::{PythonFunctionCallTask, PythonTaskBase};
+use crate::python::NativePythonPreamble;
+use crate::python::PythonImport;
+use abi_stable::external_types::parking_lot::rw_lock::RRwLock;
+use abi_stable::std_types::RArc;
+use aorist_ast::{Call, SimpleIdentifier, AST};
+use aorist_primitives::define_task_node;
+use aorist_primitives::SparkConfig;
+use aorist_util::{AString, AVec};
+use linked_hash_map::LinkedHashMap;
+use std::hash::Hash;
+
+define_task_node!(
+    SparkPythonTask,
+    |task: &SparkPythonTask| vec![task.sql.clone()].into_iter().collect(),
+    |task: &SparkPythonTask| { task.get_native_python_statements() },
+    |_task: &SparkPythonTask| {
+        vec![PythonImport::PythonFromImport(
+            "pyspark.sql".into(),
+            "SparkSession".into(),
+            AOption(ROption::RNone),
+        )]
+        .into_iter()
+        .collect()
+    },
+    PythonImport,
+    sql: AST,
+    kwargs: LinkedHashMap<AString, AST>,
+    task_val: AST,
+    endpoint: SparkConfig,
+    dependencies: AOption<AST>,
+);
+
+impl PythonTaskBase for SparkPythonTask {
+    fn get_task_val(&self) -> AST {
+        self.task_val.clone()
+    }
+}
+impl PythonFunctionCallTask for SparkPythonTask {
+    fn get_preamble(&self) -> AOption<NativePythonPreamble> {
+        let spark_session = PythonImport::PythonFromImport(
+            "pyspark.sql".into(),
+            "SparkSession".into(),
+            AOption(ROption::RNone),
+        );
+        // tables are registered in the metastore shared with Presto / Hive
+        let metastore = match self.endpoint.metastore_uri {
+            AOption(ROption::RSome(ref uri)) => format!(
+                "
+        .config('hive.metastore.uris', '{}')",
+                uri
+            ),
+            AOption(ROption::RNone) => "".to_string(),
+        };
+        // schema changes are dicts naming the column they add or drop (see
+        // shell::SchemaChange), and are skipped once applied
+        let body = format!(
+            "
+def execute_spark_sql(query):
+    spark = (
+        SparkSession.builder
+        .master('{master}')
+        .appName('aorist'){metastore}
+        .enableHiveSupport()
+        .getOrCreate()
+    )
+    for q in (query if isinstance(query, list) else [query]):
+        if isinstance(q, dict):
+            columns = [c.lower() for c in spark.table(q['table']).columns]
+            if (q['column'].lower() in columns) == q['added']:
+                print('Skipped query: ' + chr(10) + ' ' + q['query'])
+                continue
+            q = q['query']
+        spark.sql(q).collect()
+        print('Ran query: ' + chr(10) + ' ' + q)
+",
+            master = self.endpoint.master,
+            metastore = metastore,
+        );
+        AOption(ROption::RSome(NativePythonPreamble {
+            imports: AVec::new(),
+            from_imports: vec![spark_session].into_iter().collect(),
+            body: body.as_str().into(),
+        }))
+    }
+    fn get_call(&self) -> AST {
+        AST::Call(Call::new_wrapped(
+            AST::SimpleIdentifier(SimpleIdentifier::new_wrapped("execute_spark_sql".into())),
+            AVec::new(),
+            vec![(
+                "query".into(),
+                PrestoPythonTask::get_query(&self.sql, &self.kwargs),
+            )]
+            .into_iter()
+            .collect(),
+        ))
+    }
+}
+impl AirflowTaskBase for SparkPythonTask {
+    fn get_dependencies(&self) -> AOption<AST> {
+        self.dependencies.clone()
+    }
+}
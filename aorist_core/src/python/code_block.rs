@@ -1,13 +1,14 @@
-use crate::code::{CodeBlock, CodeBlockWithForLoopCompression};
+use crate::code::{CodeBlock, CodeBlockWithForLoopCompression, ParamsFiles};
 use crate::constraint::OuterConstraint;
 use crate::flow::{CompressibleTask, ETLFlow, ETLTask, ForLoopCompressedTask};
 use crate::parameter_tuple::ParameterTuple;
 use crate::program::TOuterProgram;
 use crate::python::{
-    ForLoopPythonBasedTask, Formatted, PythonBasedTask, PythonImport, PythonPreamble,
-    SimpleIdentifier, StringLiteral, Subscript, AST,
+    ForLoopPythonBasedTask, Formatted, LiterateChunk, PythonBasedTask, PythonImport,
+    PythonPreamble, SimpleIdentifier, StringLiteral, Subscript, AST,
 };
 use abi_stable::std_types::ROption;
+use anyhow::Result;
 use aorist_primitives::AoristUniverse;
 use aorist_util::AOption;
 use aorist_util::AUuid;
@@ -100,8 +101,22 @@ where
         self.params.clone()
     }
 }
-impl<T, C, U, P> CodeBlockWithForLoopCompression<T, C, U, P>
-    for PythonBasedCodeBlock<T, C, U, P>
+impl<T, C, U, P> PythonBasedCodeBlock<T, C, U, P>
+where
+    T: ETLFlow<U, ImportType = PythonImport, PreambleType = PythonPreamble>,
+    C: OuterConstraint,
+    U: AoristUniverse,
+    P: TOuterProgram<TAncestry = C::TAncestry>,
+{
+    pub fn get_literate_chunks(&self) -> AVec<LiterateChunk> {
+        self.python_based_tasks
+            .iter()
+            .map(|x| x.get_literate_chunks().into_iter())
+            .flatten()
+            .collect()
+    }
+}
+impl<T, C, U, P> CodeBlockWithForLoopCompression<T, C, U, P> for PythonBasedCodeBlock<T, C, U, P>
 where
     T: ETLFlow<U, ImportType = PythonImport, PreambleType = PythonPreamble>,
     C: OuterConstraint,
@@ -116,7 +131,10 @@ where
         python_based_tasks: &mut AVec<Self::E>,
         constraint_name: AString,
         render_dependencies: bool,
-    ) {
+        params_files: AOption<ParamsFiles>,
+        dynamic_mapping: bool,
+    ) -> Result<()> {
+        let mut num_compressions = 0;
         for (mut compression_key, tasks) in compressible.into_iter() {
             let num_tasks = tasks.len();
             // TODO: this is a magic number
@@ -126,8 +144,14 @@ where
                     num_tasks,
                     constraint_name
                 );
+                // one params dict (and sidecar file) per compression key
+                num_compressions += 1;
+                let params_name = match num_compressions {
+                    1 => format!("params_{}", constraint_name),
+                    n => format!("params_{}_{}", constraint_name, n),
+                };
                 let params_constraint = AST::SimpleIdentifier(SimpleIdentifier::new_wrapped(
-                    format!("params_{}", constraint_name).as_str().into(),
+                    params_name.as_str().into(),
                 ));
                 let mut maybe_uncompressible = tasks
                     .into_iter()
@@ -303,7 +327,7 @@ where
                     "There are now {} maybe_uncompressible tasks",
                     maybe_uncompressible.len()
                 );
-                let compressed_task = ForLoopPythonBasedTask::new(
+                let mut compressed_task = ForLoopPythonBasedTask::new(
                     params_constraint,
                     compression_key,
                     maybe_uncompressible,
@@ -311,6 +335,12 @@ where
                     insert_task_name,
                     render_dependencies,
                 );
+                if let AOption(ROption::RSome(ref files)) = params_files {
+                    compressed_task.externalize_params(files)?;
+                }
+                if dynamic_mapping {
+                    compressed_task.map_dynamically();
+                }
                 python_based_tasks.push(PythonBasedTask::ForLoopPythonBasedTask(compressed_task));
             } else {
                 for task in tasks.into_iter() {
@@ -318,5 +348,6 @@ where
                 }
             }
         }
+        Ok(())
     }
 }
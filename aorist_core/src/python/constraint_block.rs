@@ -5,7 +5,9 @@ use crate::flow::ETLFlow;
 use crate::parameter_tuple::ParameterTuple;
 use crate::program::TOuterProgram;
 use crate::python::PythonBasedCodeBlock;
-use crate::python::{Assignment, Dict, PythonFlowBuilderInput, PythonImport, PythonPreamble, AST};
+use crate::python::{
+    Assignment, Dict, LiterateChunk, PythonFlowBuilderInput, PythonImport, PythonPreamble, AST,
+};
 use abi_stable::std_types::ROption;
 use aorist_primitives::AoristUniverse;
 use aorist_util::AOption;
@@ -68,6 +70,15 @@ where
             _constraint: PhantomData,
         }
     }
+    fn decorate_builder_input(&self, input: PythonFlowBuilderInput) -> PythonFlowBuilderInput {
+        input.with_literate_chunks(
+            self.members
+                .iter()
+                .map(|x| x.get_literate_chunks().into_iter())
+                .flatten()
+                .collect::<AVec<LiterateChunk>>(),
+        )
+    }
     fn get_identifiers(&self) -> HashMap<AUuid, AST> {
         self.members
             .iter()
@@ -0,0 +1,610 @@
+use crate::parameter_tuple::ParameterTuple;
+use crate::shell::{pipe_sql, sql_command, SchemaChange, PARTITION_DATE_VARIABLE};
+use abi_stable::std_types::ROption;
+use aorist_ast::bash::{
+    substitute_placeholders, Command, Function, Statement, VariableAssignment, VariableScope,
+    Word,
+};
+use aorist_ast::{Call, ConstantFolding, SimpleIdentifier, StringLiteral, AST};
+use aorist_primitives::Dialect;
+use aorist_util::AOption;
+use aorist_util::{AString, AVec};
+use linked_hash_map::LinkedHashMap;
+use pyo3::exceptions::PyValueError;
+use pyo3::PyResult;
+
+/// Prefix of the local variables holding the parameters of Bash tasks.
+const BASH_VARIABLE_PREFIX: &str = "arg_";
+/// A single task rendered in its own dialect, as a fenced code chunk in a
+/// literate (Quarto / R Markdown) document. Unlike the Python flow, chunks
+/// are never compressed into for loops: every task gets its own chunk so
+/// that it can be read (and re-run) in isolation.
+#[derive(Clone, Hash, PartialEq, Eq, Debug)]
+pub struct LiterateChunk {
+    /// unique chunk label (the task id)
+    label: AString,
+    /// function called by the task, or the command / query template
+    call: AOption<AString>,
+    /// fully-resolved arguments passed to the call
+    params: AOption<ParameterTuple>,
+    /// source code the call depends on, in the chunk's dialect
+    preamble: AOption<AString>,
+    dialect: AOption<Dialect>,
+}
+
+impl LiterateChunk {
+    pub fn new(
+        label: AString,
+        call: AOption<AString>,
+        params: AOption<ParameterTuple>,
+        preamble: AOption<AString>,
+        dialect: AOption<Dialect>,
+    ) -> Self {
+        Self {
+            label,
+            call,
+            params,
+            preamble,
+            dialect,
+        }
+    }
+    /// Rebuilds the chunk for a single member of a for-loop compressed task:
+    /// compressed kwargs referring to the loop variable `t` are resolved
+    /// against `dict_key` before being merged with the task's own params.
+    pub fn from_compressed(
+        label: AString,
+        dict_key: &AString,
+        call: AOption<AString>,
+        compressed_kwargs: &LinkedHashMap<AString, AST>,
+        params: AOption<ParameterTuple>,
+        preamble: AOption<AString>,
+        dialect: AOption<Dialect>,
+    ) -> Self {
+        let (args, own_kwargs) = match params {
+            AOption(ROption::RSome(p)) => (p.args, p.kwargs),
+            AOption(ROption::RNone) => (AVec::new(), LinkedHashMap::new()),
+        };
+        let mut kwargs = compressed_kwargs
+            .iter()
+            .map(|(k, v)| (k.clone(), Self::resolve_loop_variable(v, dict_key)))
+            .collect::<LinkedHashMap<_, _>>();
+        for (k, v) in own_kwargs.into_iter() {
+            kwargs.insert(k, v);
+        }
+        let params = match args.len() + kwargs.len() {
+            0 => AOption(ROption::RNone),
+            _ => AOption(ROption::RSome(ParameterTuple { args, kwargs })),
+        };
+        Self::new(label, call, params, preamble, dialect)
+    }
+    /// Replaces the loop variable `t` by the key, then folds the format
+    /// strings that have become constant.
+    fn resolve_loop_variable(ast: &AST, dict_key: &AString) -> AST {
+        ast.transform(&mut |x| match x {
+            AST::SimpleIdentifier(ref id) if id.read().name().as_str() == "t" => {
+                AST::StringLiteral(StringLiteral::new_wrapped(dict_key.clone(), false))
+            }
+            _ => ConstantFolding::fold(x),
+        })
+    }
+    pub fn get_label(&self) -> AString {
+        self.label.clone()
+    }
+    pub fn get_dialect(&self) -> AOption<Dialect> {
+        self.dialect.clone()
+    }
+    pub fn get_preamble(&self) -> AOption<AString> {
+        self.preamble.clone()
+    }
+    /// Name of the knitr / Quarto engine used to run the chunk.
+    pub fn get_engine(&self) -> AOption<AString> {
+        match &self.dialect {
+            AOption(ROption::RSome(Dialect::Python(_))) => AOption(ROption::RSome("python".into())),
+            AOption(ROption::RSome(Dialect::R(_))) => AOption(ROption::RSome("r".into())),
+            AOption(ROption::RSome(Dialect::Bash(_))) => AOption(ROption::RSome("bash".into())),
+            AOption(ROption::RSome(Dialect::Presto(_)))
+            | AOption(ROption::RSome(Dialect::DuckDB(_)))
+            | AOption(ROption::RSome(Dialect::Spark(_))) => AOption(ROption::RSome("sql".into())),
+            AOption(ROption::RNone) => AOption(ROption::RNone),
+        }
+    }
+    fn get_args(&self) -> AVec<AST> {
+        match &self.params {
+            AOption(ROption::RSome(p)) => p.get_args(),
+            AOption(ROption::RNone) => AVec::new(),
+        }
+    }
+    fn get_kwargs(&self) -> LinkedHashMap<AString, AST> {
+        match &self.params {
+            AOption(ROption::RSome(p)) => p.get_kwargs(),
+            AOption(ROption::RNone) => LinkedHashMap::new(),
+        }
+    }
+    /// Whether the task is passed the flow's `partition_date`.
+    pub fn uses_partition_date(&self) -> bool {
+        self.get_args()
+            .iter()
+            .chain(self.get_kwargs().values())
+            .flat_map(|x| x.get_descendants().into_iter())
+            .any(|x| Self::is_partition_date(&x))
+    }
+    fn is_partition_date(ast: &AST) -> bool {
+        match ast {
+            AST::SimpleIdentifier(ref x) => x.read().name().as_str() == "partition_date",
+            _ => false,
+        }
+    }
+    /// Body of the chunk (without the preamble), in the chunk's dialect.
+    pub fn get_source(&self) -> PyResult<AString> {
+        let call = match &self.call {
+            AOption(ROption::RSome(c)) => c.clone(),
+            AOption(ROption::RNone) => return Ok("".into()),
+        };
+        match &self.dialect {
+            AOption(ROption::RSome(Dialect::Python(_))) => {
+                let call_ast = AST::Call(Call::new_wrapped(
+                    AST::SimpleIdentifier(SimpleIdentifier::new_wrapped(call)),
+                    self.get_args(),
+                    self.get_kwargs(),
+                ));
+                Ok(call_ast.to_python_source()?.as_str().into())
+            }
+            AOption(ROption::RSome(Dialect::R(_))) => {
+                let args = self
+                    .get_args()
+                    .iter()
+                    .map(|x| Self::to_r_literal(x))
+                    .chain(
+                        self.get_kwargs()
+                            .iter()
+                            .map(|(k, v)| Ok(format!("{} = {}", k, Self::to_r_literal(v)?))),
+                    )
+                    .collect::<PyResult<AVec<String>>>()?;
+                Ok(format!("{}({})\n", call, args.join(", ")).as_str().into())
+            }
+            AOption(ROption::RSome(Dialect::Bash(_)))
+            | AOption(ROption::RSome(Dialect::Presto(_)))
+            | AOption(ROption::RSome(Dialect::DuckDB(_)))
+            | AOption(ROption::RSome(Dialect::Spark(_))) => {
+                let mut source = call.as_str().to_string();
+                for (k, v) in self.get_kwargs().iter() {
+                    if let AOption(ROption::RSome(val)) = Self::to_template_value(v) {
+                        source = source.replace(&format!("{{{}}}", k), &val);
+                    }
+                }
+                Ok(format!("{}\n", source.trim_end()).as_str().into())
+            }
+            AOption(ROption::RNone) => Ok("".into()),
+        }
+    }
+    /// The chunk as a Bash function named after its label. Bash commands
+    /// get their parameters as (quoted) local variables, which replace the
+    /// `{key}` placeholders; other dialects are fed to their interpreter
+    /// through a here-document, preamble first.
+    pub fn to_bash_function(&self) -> PyResult<Function> {
+        let mut body: AVec<Statement> = AVec::new();
+        match &self.dialect {
+            AOption(ROption::RSome(Dialect::Bash(_))) => {
+                let command = match &self.call {
+                    AOption(ROption::RSome(c)) => c.clone(),
+                    AOption(ROption::RNone) => "".into(),
+                };
+                let mut variables = LinkedHashMap::new();
+                for (k, v) in self.get_kwargs().iter() {
+                    let value = match Self::to_bash_word(v) {
+                        AOption(ROption::RSome(value)) => value,
+                        AOption(ROption::RNone)
+                            if command.as_str().contains(&format!("{{{}}}", k)) =>
+                        {
+                            return Err(PyValueError::new_err(format!(
+                                "Argument {} of Bash task {} has no Bash equivalent: {}",
+                                k,
+                                self.label,
+                                v.name()
+                            )))
+                        }
+                        AOption(ROption::RNone) => continue,
+                    };
+                    // prefixed, so that parameters cannot shadow environment
+                    // variables (e.g. PATH) used by the command
+                    let variable: AString =
+                        format!("{}{}", BASH_VARIABLE_PREFIX, k).as_str().into();
+                    body.push(Statement::Assignment(VariableAssignment::new(
+                        variable.clone(),
+                        value,
+                        VariableScope::Local,
+                    )));
+                    variables.insert(k.clone(), Word::Variable(variable));
+                }
+                body.push(Statement::Raw(substitute_placeholders(
+                    command.as_str(),
+                    &variables,
+                )));
+            }
+            AOption(ROption::RSome(ref dialect)) => {
+                let mut command = match dialect {
+                    Dialect::Python(_) => Command::new(
+                        "python3".into(),
+                        vec![Word::Literal("-".into())].into_iter().collect(),
+                    ),
+                    Dialect::R(_) => Command::new(
+                        "Rscript".into(),
+                        vec![Word::Literal("-".into())].into_iter().collect(),
+                    ),
+                    _ => sql_command(dialect, &self.get_kwargs()),
+                };
+                match dialect {
+                    Dialect::Python(_) | Dialect::R(_) => {
+                        let mut source = self.get_source()?.as_str().to_string();
+                        if let AOption(ROption::RSome(p)) = &self.preamble {
+                            source = format!("{}\n\n{}", p.as_str().trim(), source);
+                        }
+                        if self.uses_partition_date() {
+                            let binding = match dialect {
+                                Dialect::Python(_) => format!(
+                                    "import os\npartition_date = os.environ[\"{}\"]",
+                                    PARTITION_DATE_VARIABLE
+                                ),
+                                _ => format!(
+                                    "partition_date <- Sys.getenv(\"{}\")",
+                                    PARTITION_DATE_VARIABLE
+                                ),
+                            };
+                            source = format!("{}\n\n{}", binding, source);
+                        }
+                        command.heredoc = AOption(ROption::RSome(source.as_str().into()));
+                        body.push(Statement::Command(command));
+                    }
+                    _ => {
+                        body.push(pipe_sql(command.clone(), self.get_source()?.as_str()));
+                        for change in SchemaChange::from_kwargs(&self.get_kwargs()) {
+                            body.push(change.to_statement(&command));
+                        }
+                    }
+                }
+            }
+            AOption(ROption::RNone) => {}
+        }
+        Ok(Function::new(
+            Function::sanitize_name(self.label.as_str()),
+            body,
+        ))
+    }
+    /// Value substituted for a placeholder of a command or query template.
+    /// The partition date is read from the environment when run. Lists of
+    /// queries are run one after the other, as in shell::fill_sql.
+    fn to_template_value(ast: &AST) -> AOption<String> {
+        match ast {
+            AST::List(ref x) => AOption(ROption::RSome(
+                x.read()
+                    .elems()
+                    .iter()
+                    .filter_map(|e| match Self::to_template_value(e) {
+                        AOption(ROption::RSome(v)) => Some(v),
+                        AOption(ROption::RNone) => Option::None,
+                    })
+                    .collect::<AVec<String>>()
+                    .join(";\n"),
+            )),
+            AST::StringLiteral(ref x) => AOption(ROption::RSome(x.read().value().as_str().into())),
+            AST::BigIntLiteral(ref x) => AOption(ROption::RSome(x.read().val().to_string())),
+            AST::BooleanLiteral(ref x) => AOption(ROption::RSome(x.read().val().to_string())),
+            AST::SimpleIdentifier(_) if Self::is_partition_date(ast) => {
+                AOption(ROption::RSome(format!("${{{}}}", PARTITION_DATE_VARIABLE)))
+            }
+            AST::Formatted(ref x) => {
+                let read = x.read();
+                let mut value = match Self::to_template_value(&read.fmt()) {
+                    AOption(ROption::RSome(fmt)) => fmt,
+                    AOption(ROption::RNone) => return AOption(ROption::RNone),
+                };
+                for (k, v) in read.keywords().iter() {
+                    match Self::to_template_value(v) {
+                        AOption(ROption::RSome(x)) => {
+                            value = value.replace(&format!("{{{}}}", k), &x);
+                        }
+                        AOption(ROption::RNone) => return AOption(ROption::RNone),
+                    }
+                }
+                AOption(ROption::RSome(value))
+            }
+            _ => AOption(ROption::RNone),
+        }
+    }
+    /// Word passed to a Bash task for one of its arguments: literals, and
+    /// values depending on the partition date of the run.
+    fn to_bash_word(ast: &AST) -> AOption<Word> {
+        match ast {
+            AST::SimpleIdentifier(_) if Self::is_partition_date(ast) => AOption(ROption::RSome(
+                Word::Variable(PARTITION_DATE_VARIABLE.into()),
+            )),
+            AST::Formatted(ref x) => {
+                let read = x.read();
+                let fmt = match read.fmt() {
+                    AST::StringLiteral(ref f) => f.read().value(),
+                    _ => return AOption(ROption::RNone),
+                };
+                let mut words = AVec::new();
+                let mut rest = fmt.as_str();
+                while let Some(start) = rest.find('{') {
+                    let end = match rest[start..].find('}') {
+                        Some(n) => start + n,
+                        None => break,
+                    };
+                    let value = match read.keywords().get(&rest[start + 1..end].into()) {
+                        Some(v) => Self::to_bash_word(v),
+                        None => AOption(ROption::RNone),
+                    };
+                    match value {
+                        AOption(ROption::RSome(word)) => {
+                            if start > 0 {
+                                words.push(Word::Literal(rest[..start].into()));
+                            }
+                            words.push(word);
+                        }
+                        AOption(ROption::RNone) => return AOption(ROption::RNone),
+                    }
+                    rest = &rest[end + 1..];
+                }
+                if rest.len() > 0 {
+                    words.push(Word::Literal(rest.into()));
+                }
+                AOption(ROption::RSome(Word::Concat(words)))
+            }
+            _ => Word::from_ast(ast),
+        }
+    }
+    /// R source for an argument of an R task. Format strings become
+    /// `glue::glue` calls, as in the R renderer of `Formatted`.
+    fn to_r_literal(ast: &AST) -> PyResult<String> {
+        let join = |elems: AVec<AST>| -> PyResult<String> {
+            Ok(elems
+                .iter()
+                .map(|e| Self::to_r_literal(e))
+                .collect::<PyResult<AVec<String>>>()?
+                .join(", "))
+        };
+        Ok(match ast {
+            AST::StringLiteral(ref x) => format!("{:?}", x.read().value().as_str()),
+            AST::BigIntLiteral(ref x) => format!("{}L", x.read().val()),
+            AST::FloatLiteral(ref x) => format!("{}", x.read().val().as_f64()),
+            AST::BooleanLiteral(ref x) => match x.read().val() {
+                true => "TRUE".into(),
+                false => "FALSE".into(),
+            },
+            AST::None(_) => "NULL".into(),
+            AST::SimpleIdentifier(ref x) => x.read().name().as_str().into(),
+            AST::List(ref x) => format!("list({})", join(x.read().elems())?),
+            AST::Tuple(ref x) => format!("list({})", join(x.read().elems())?),
+            AST::Dict(ref x) => format!(
+                "list({})",
+                x.read()
+                    .elems()
+                    .iter()
+                    .map(|(k, v)| Ok(format!("{:?} = {}", k.as_str(), Self::to_r_literal(v)?)))
+                    .collect::<PyResult<AVec<String>>>()?
+                    .join(", ")
+            ),
+            AST::Formatted(ref x) => {
+                let read = x.read();
+                let mut args = vec![Self::to_r_literal(&read.fmt())?];
+                for (k, v) in read.keywords().iter() {
+                    args.push(format!("{} = {}", k, Self::to_r_literal(v)?));
+                }
+                format!("glue::glue({})", args.join(", "))
+            }
+            AST::Call(ref x) => {
+                let read = x.read();
+                let function = match read.function() {
+                    AST::SimpleIdentifier(ref f) => f.read().name(),
+                    _ => {
+                        return Err(PyValueError::new_err(format!(
+                            "Only calls to named functions can be passed to R tasks, got {}",
+                            read.function().name()
+                        )))
+                    }
+                };
+                let mut args = read
+                    .args()
+                    .iter()
+                    .map(|e| Self::to_r_literal(e))
+                    .collect::<PyResult<Vec<String>>>()?;
+                for (k, v) in read.keywords().iter() {
+                    args.push(format!("{} = {}", k, Self::to_r_literal(v)?));
+                }
+                format!("{}({})", function, args.join(", "))
+            }
+            _ => {
+                return Err(PyValueError::new_err(format!(
+                    "AST node of type {} cannot be passed to an R task",
+                    ast.name()
+                )))
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aorist_ast::bash::ToBash;
+    use aorist_ast::{BigIntLiteral, Formatted, Subscript};
+    use aorist_primitives::{Bash, DuckDB, R};
+
+    fn string(value: &str) -> AST {
+        AST::StringLiteral(StringLiteral::new_wrapped(value.into(), false))
+    }
+    fn identifier(name: &str) -> AST {
+        AST::SimpleIdentifier(SimpleIdentifier::new_wrapped(name.into()))
+    }
+    fn r_chunk(kwargs: LinkedHashMap<AString, AST>) -> LiterateChunk {
+        LiterateChunk::new(
+            "task".into(),
+            AOption(ROption::RSome("download".into())),
+            AOption(ROption::RSome(ParameterTuple {
+                args: AVec::new(),
+                kwargs,
+            })),
+            AOption(ROption::RNone),
+            AOption(ROption::RSome(Dialect::R(R::new()))),
+        )
+    }
+
+    #[test]
+    fn test_r_source() {
+        let mut keywords = LinkedHashMap::new();
+        keywords.insert("name".into(), identifier("table_name"));
+        let mut kwargs = LinkedHashMap::new();
+        kwargs.insert("url".into(), string("http://example.com"));
+        kwargs.insert(
+            "n".into(),
+            AST::BigIntLiteral(BigIntLiteral::new_wrapped(3)),
+        );
+        kwargs.insert(
+            "file".into(),
+            AST::Formatted(Formatted::new_wrapped(string("{name}.csv"), keywords)),
+        );
+        assert_eq!(
+            r_chunk(kwargs).get_source().unwrap().as_str(),
+            "download(url = \"http://example.com\", n = 3L, file = glue::glue(\"{name}.csv\", name = table_name))\n"
+        );
+    }
+
+    #[test]
+    fn test_r_source_rejects_unsupported_nodes() {
+        let mut kwargs = LinkedHashMap::new();
+        kwargs.insert(
+            "x".into(),
+            AST::Subscript(Subscript::new_wrapped(
+                identifier("params"),
+                string("x"),
+                false,
+            )),
+        );
+        assert!(r_chunk(kwargs).get_source().is_err());
+    }
+
+    #[test]
+    fn test_from_compressed_resolves_loop_variable() {
+        let mut keywords = LinkedHashMap::new();
+        keywords.insert("t".into(), identifier("t"));
+        let mut compressed_kwargs = LinkedHashMap::new();
+        compressed_kwargs.insert("key".into(), identifier("t"));
+        // `t` is a keyword of the format string, not a placeholder in it
+        compressed_kwargs.insert(
+            "file".into(),
+            AST::Formatted(Formatted::new_wrapped(string("{{t}}/{t}.csv"), keywords)),
+        );
+        let chunk = LiterateChunk::from_compressed(
+            "task".into(),
+            &"trips".into(),
+            AOption(ROption::RSome("download".into())),
+            &compressed_kwargs,
+            AOption(ROption::RNone),
+            AOption(ROption::RNone),
+            AOption(ROption::RSome(Dialect::R(R::new()))),
+        );
+        assert_eq!(
+            chunk.get_source().unwrap().as_str(),
+            "download(key = \"trips\", file = \"{t}/trips.csv\")\n"
+        );
+    }
+
+    fn bash_chunk(command: &str, kwargs: LinkedHashMap<AString, AST>) -> LiterateChunk {
+        LiterateChunk::new(
+            "download-trips".into(),
+            AOption(ROption::RSome(command.into())),
+            AOption(ROption::RSome(ParameterTuple {
+                args: AVec::new(),
+                kwargs,
+            })),
+            AOption(ROption::RNone),
+            AOption(ROption::RSome(Dialect::Bash(Bash::new()))),
+        )
+    }
+
+    #[test]
+    fn test_bash_function() {
+        let mut kwargs = LinkedHashMap::new();
+        kwargs.insert("path".into(), string("/tmp/my data"));
+        kwargs.insert("unused".into(), identifier("x"));
+        let function = bash_chunk(
+            "cd {path} && echo '{path}: done' >> \"${PATH%%:*}\"",
+            kwargs,
+        )
+        .to_bash_function()
+        .unwrap();
+        assert_eq!(
+            function.to_bash().as_str(),
+            "download_trips() {\n  local arg_path='/tmp/my data'\n  cd \"${arg_path}\" && echo ''\"${arg_path}\"': done' >> \"${PATH%%:*}\"\n}"
+        );
+    }
+
+    #[test]
+    fn test_bash_function_rejects_unresolved_placeholders() {
+        let mut kwargs = LinkedHashMap::new();
+        kwargs.insert("path".into(), identifier("x"));
+        assert!(bash_chunk("cd {path}", kwargs).to_bash_function().is_err());
+    }
+
+    #[test]
+    fn test_bash_function_partition_date() {
+        let mut keywords = LinkedHashMap::new();
+        keywords.insert("partition_date".into(), identifier("partition_date"));
+        let mut kwargs = LinkedHashMap::new();
+        kwargs.insert("date".into(), identifier("partition_date"));
+        kwargs.insert(
+            "dir".into(),
+            AST::Formatted(Formatted::new_wrapped(
+                string("/tmp/dt={partition_date}"),
+                keywords,
+            )),
+        );
+        let function = bash_chunk("echo {date} {dir}", kwargs)
+            .to_bash_function()
+            .unwrap();
+        assert_eq!(
+            function.to_bash().as_str(),
+            "download_trips() {\n  local arg_date=\"${PARTITION_DATE}\"\n  local arg_dir=/tmp/dt=\"${PARTITION_DATE}\"\n  echo \"${arg_date}\" \"${arg_dir}\"\n}"
+        );
+    }
+
+    #[test]
+    fn test_sql_function_fills_in_partition_date() {
+        let chunk = LiterateChunk::new(
+            "delete-trips".into(),
+            AOption(ROption::RSome(
+                "DELETE FROM trips WHERE dt = '{partition_date}'".into(),
+            )),
+            AOption(ROption::RNone),
+            AOption(ROption::RNone),
+            AOption(ROption::RSome(Dialect::DuckDB(DuckDB::new()))),
+        );
+        assert_eq!(
+            chunk.to_bash_function().unwrap().to_bash().as_str(),
+            "delete_trips() {\n  sed 's/{partition_date}/'\"${PARTITION_DATE}\"/g <<'EOF' | duckdb \"${DUCKDB_DATABASE:-aorist.duckdb}\"\nDELETE FROM trips WHERE dt = '{partition_date}'\nEOF\n}"
+        );
+    }
+    #[test]
+    fn test_duckdb_function_uses_database() {
+        let mut kwargs = LinkedHashMap::new();
+        kwargs.insert("database".into(), string("/data/trips.duckdb"));
+        kwargs.insert("source_file".into(), string("trips.csv"));
+        let chunk = LiterateChunk::new(
+            "upload-trips".into(),
+            AOption(ROption::RSome(
+                "CREATE TABLE trips AS SELECT * FROM read_csv('{source_file}', delim=',')".into(),
+            )),
+            AOption(ROption::RSome(ParameterTuple {
+                args: AVec::new(),
+                kwargs,
+            })),
+            AOption(ROption::RNone),
+            AOption(ROption::RSome(Dialect::DuckDB(DuckDB::new()))),
+        );
+        assert_eq!(
+            chunk.to_bash_function().unwrap().to_bash().as_str(),
+            "upload_trips() {\n  duckdb /data/trips.duckdb <<'EOF'\nCREATE TABLE trips AS SELECT * FROM read_csv('trips.csv', delim=',')\nEOF\n}"
+        );
+    }
+}
@@ -2,9 +2,11 @@ use aorist_util::AOption;
 mod ast;
 mod code_block;
 mod constraint_block;
+mod literate_chunk;
 mod preamble;
 mod python_import;
 mod task;
+mod validation;
 
 use crate::flow::FlowBuilderInput;
 use aorist_util::{AString, AVec};
@@ -18,13 +20,16 @@ use aorist_ast::{
     SimpleIdentifier, StringLiteral, Subscript, Tuple, AST,
 };
 pub use ast::{
-    BashPythonTask, ConstantPythonTask, NativePythonTask, PrestoPythonTask, PythonTask, RPythonTask,
+    BashPythonTask, ConstantPythonTask, DuckDBPythonTask, NativePythonTask, PrestoPythonTask,
+    PythonTask, RPythonTask, SparkPythonTask,
 };
 pub use code_block::PythonBasedCodeBlock;
 pub use constraint_block::PythonBasedConstraintBlock;
+pub use literate_chunk::LiterateChunk;
 pub use preamble::*;
 pub use python_import::PythonImport;
 pub use task::{ForLoopPythonBasedTask, PythonBasedTask, StandalonePythonBasedTask};
+pub use validation::*;
 
 /// Wrapper type for stuff that gets passed around when building Python
 /// statements:
@@ -32,6 +37,7 @@ pub use task::{ForLoopPythonBasedTask, PythonBasedTask, StandalonePythonBasedTas
 /// - A set of PythonPreambles (which have their own imports attached)
 /// - A set of imports corresponding to the dialect used.
 /// - A comment string
+/// - The same tasks as literate chunks, each in its own dialect.
 pub struct PythonFlowBuilderInput {
     statements: AVec<AST>,
     preambles: LinkedHashSet<PythonPreamble>,
@@ -39,6 +45,7 @@ pub struct PythonFlowBuilderInput {
     constraint_name: AString,
     constraint_title: AOption<AString>,
     constraint_body: AOption<AString>,
+    literate_chunks: AVec<LiterateChunk>,
 }
 impl PythonFlowBuilderInput {
     pub fn has_statements(&self) -> bool {
@@ -59,6 +66,17 @@ impl PythonFlowBuilderInput {
             constraint_body,
         )
     }
+    pub fn with_literate_chunks(mut self, literate_chunks: AVec<LiterateChunk>) -> Self {
+        self.literate_chunks = literate_chunks;
+        self
+    }
+    pub fn with_statements(mut self, statements: AVec<AST>) -> Self {
+        self.statements = statements;
+        self
+    }
+    pub fn get_literate_chunks(&self) -> AVec<LiterateChunk> {
+        self.literate_chunks.clone()
+    }
     pub fn to_python_ast_nodes<'a>(
         &self,
         py: Python,
@@ -91,6 +109,7 @@ impl FlowBuilderInput for PythonFlowBuilderInput {
             constraint_name,
             constraint_title,
             constraint_body,
+            literate_chunks: AVec::new(),
         }
     }
     fn get_statements(&self) -> AVec<AST> {
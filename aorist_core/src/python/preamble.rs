@@ -1,9 +1,11 @@
 use crate::code::Preamble;
 use crate::python::PythonImport;
 use abi_stable::std_types::ROption;
-use aorist_ast::FunctionDef;
+use aorist_ast::{FunctionDef, AST};
 use aorist_util::AOption;
 use aorist_util::{AString, AVec};
+use linked_hash_map::LinkedHashMap;
+use linked_hash_set::LinkedHashSet;
 use pyo3::prelude::*;
 use pyo3::types::{PyList, PyModule, PyString, PyTuple};
 use std::hash::Hash;
@@ -118,6 +120,82 @@ impl Preamble for PythonPreamble {
     }
 }
 impl PythonPreamble {
+    /// Identifies the code the preamble defines, regardless of its imports.
+    fn get_definition_key(&self) -> AString {
+        match &self {
+            PythonPreamble::NativePythonPreamble(x) => format!("native:{}", x.body.as_str().trim()),
+            PythonPreamble::RPythonPreamble(x) => format!("r:{}", x.body.as_str().trim()),
+            PythonPreamble::PythonStatementsPreamble(x) => format!("statements:{:?}", x.function),
+        }
+        .as_str()
+        .into()
+    }
+    /// Merges preambles that define the same code (e.g. the same function,
+    /// required by different recipes with different imports) into the first
+    /// of them, which gets all of their imports.
+    pub fn merge_identical(preambles: LinkedHashSet<Self>) -> LinkedHashSet<Self> {
+        let mut merged: LinkedHashMap<AString, Self> = LinkedHashMap::new();
+        for preamble in preambles.into_iter() {
+            match merged.get_mut(&preamble.get_definition_key()) {
+                Some(PythonPreamble::NativePythonPreamble(ref mut x)) => {
+                    if let PythonPreamble::NativePythonPreamble(other) = preamble {
+                        merge_imports(&mut x.imports, other.imports);
+                        merge_imports(&mut x.from_imports, other.from_imports);
+                    }
+                }
+                Some(PythonPreamble::PythonStatementsPreamble(ref mut x)) => {
+                    if let PythonPreamble::PythonStatementsPreamble(other) = preamble {
+                        merge_imports(&mut x.imports, other.imports);
+                    }
+                }
+                Some(PythonPreamble::RPythonPreamble(_)) => {}
+                None => {
+                    merged.insert(preamble.get_definition_key(), preamble);
+                }
+            }
+        }
+        merged.into_iter().map(|(_, x)| x).collect()
+    }
+    /// Names read by the preamble's code, e.g. module-level constants its
+    /// functions use. R preambles cannot read Python names.
+    pub fn get_read_names(&self) -> PyResult<AVec<AString>> {
+        match &self {
+            PythonPreamble::NativePythonPreamble(x) => {
+                let gil = Python::acquire_gil();
+                let py = gil.python();
+                let helpers = PyModule::from_code(
+                    py,
+                    r#"
+import ast
+
+def get_read_names(body):
+    return [
+        node.id for node in ast.walk(ast.parse(body))
+        if isinstance(node, ast.Name) and isinstance(node.ctx, ast.Load)
+    ]
+        "#,
+                    "helpers.py",
+                    "helpers",
+                )?;
+                let names: Vec<String> = helpers
+                    .getattr("get_read_names")?
+                    .call1((x.body.as_str(),))?
+                    .extract()?;
+                Ok(names.into_iter().map(|x| x.as_str().into()).collect())
+            }
+            PythonPreamble::PythonStatementsPreamble(x) => Ok(x
+                .function
+                .get_direct_descendants()
+                .into_iter()
+                .flat_map(|x| x.get_descendants().into_iter())
+                .filter_map(|x| match x {
+                    AST::SimpleIdentifier(ref x) => Some(x.read().name()),
+                    _ => None,
+                })
+                .collect()),
+            PythonPreamble::RPythonPreamble(_) => Ok(AVec::new()),
+        }
+    }
     pub fn to_python_ast_nodes<'b>(
         &self,
         py: Python<'b>,
@@ -136,6 +214,13 @@ impl PythonPreamble {
         }
     }
 }
+fn merge_imports(imports: &mut AVec<PythonImport>, other: AVec<PythonImport>) {
+    for import in other.into_iter() {
+        if !imports.iter().any(|x| *x == import) {
+            imports.push(import);
+        }
+    }
+}
 impl RPythonPreamble {
     pub fn new(body: AString) -> PyResult<Self> {
         Ok(Self { body })
@@ -1,13 +1,16 @@
+use crate::code::{ParamsFileFormat, ParamsFiles};
 use crate::flow::{CompressionKey, ETLFlow, ForLoopCompressedTask, TaskBase, UncompressiblePart};
 use crate::python::task::key::PythonBasedTaskCompressionKey;
 use crate::python::task::uncompressible::PythonBasedTaskUncompressiblePart;
 use crate::python::{
-    Add, Assignment, Attribute, BigIntLiteral, BinOp, Call, Dict, ForLoop, List, PythonImport,
-    PythonPreamble, SimpleIdentifier, StringLiteral, Subscript, Tuple, AST,
+    Add, Assignment, Attribute, BigIntLiteral, BinOp, Call, Dict, ForLoop, List, LiterateChunk,
+    PythonImport, PythonPreamble, SimpleIdentifier, StringLiteral, Subscript, Tuple, AST,
 };
 use abi_stable::std_types::ROption;
+use anyhow::Result;
+use aorist_ast::{Comprehension, With, WithItem};
 use aorist_primitives::AoristUniverse;
-use aorist_util::{AOption, AVec};
+use aorist_util::{AOption, AString, AVec};
 use linked_hash_map::LinkedHashMap;
 use std::hash::Hash;
 use std::marker::PhantomData;
@@ -27,6 +30,11 @@ where
     insert_task_name: bool,
     _universe: PhantomData<U>,
     render_dependencies: bool,
+    /// set if the params dict is loaded from a sidecar file at runtime
+    params_files: AOption<ParamsFiles>,
+    /// use the flow's native fan-out primitive instead of a for loop, where
+    /// available
+    dynamic_mapping: bool,
 }
 impl<T, U> ForLoopCompressedTask<T, U> for ForLoopPythonBasedTask<T, U>
 where
@@ -64,6 +72,8 @@ where
             singleton_type: PhantomData,
             _universe: PhantomData,
             render_dependencies,
+            params_files: AOption(ROption::RNone),
+            dynamic_mapping: false,
         }
     }
 }
@@ -134,6 +144,57 @@ where
             has_params_dict,
         )
     }
+    fn get_params_dict_name(&self) -> AString {
+        match self.params_dict_name {
+            AST::SimpleIdentifier(ref x) => x.read().name(),
+            _ => panic!("params_dict_name must be a SimpleIdentifier"),
+        }
+    }
+    /// Writes the params dict to a sidecar file, which the flow loads
+    /// instead of embedding the dict. Dicts that reference other tasks
+    /// (e.g. via dependencies) stay in the flow.
+    pub fn externalize_params(&mut self, params_files: &ParamsFiles) -> Result<()> {
+        if let (AST::Assignment(assign), _) = self.get_dict_assign() {
+            let name = self.get_params_dict_name();
+            if let AOption(ROption::RSome(_)) =
+                params_files.write(name.as_str(), &assign.read().call())?
+            {
+                self.params_files = AOption(ROption::RSome(params_files.clone()));
+            }
+        }
+        Ok(())
+    }
+    /// Create the compressed tasks via dynamic task mapping (Airflow
+    /// `.expand()`, Prefect `.map()`), if the flow supports it. Tasks with
+    /// dependencies of their own are still created in a for loop, so that
+    /// these dependencies are preserved.
+    pub fn map_dynamically(&mut self) {
+        self.dynamic_mapping = true;
+    }
+    /// Task id shared by all mapped tasks, e.g. `download_data` for task ids
+    /// of the form `download_data__{t}`.
+    fn get_mapped_task_id(&self) -> AOption<AString> {
+        if self.insert_task_name {
+            return AOption(ROption::RNone);
+        }
+        if let AST::Formatted(ref rw) = self.task_id {
+            if let AST::StringLiteral(ref fmt) = rw.read().fmt() {
+                let template = fmt.read().value();
+                if template.as_str().contains("{t}") {
+                    let task_id = template.as_str().replace("{t}", "");
+                    let task_id = task_id.trim_matches('_');
+                    return AOption(ROption::RSome(
+                        match task_id.is_empty() {
+                            true => "mapped",
+                            false => task_id,
+                        }
+                        .into(),
+                    ));
+                }
+            }
+        }
+        AOption(ROption::RNone)
+    }
     fn get_for_loop_tuple(&self, ident: &AST, params: &AST) -> AST {
         AST::Tuple(Tuple::new_wrapped(
             vec![ident.clone(), params.clone()].into_iter().collect(),
@@ -147,6 +208,22 @@ where
             false,
         ))
     }
+    pub fn get_literate_chunks(&self) -> AVec<LiterateChunk> {
+        self.values
+            .iter()
+            .map(|x| {
+                LiterateChunk::from_compressed(
+                    x.task_id.clone(),
+                    &x.dict,
+                    self.key.get_call(),
+                    &self.key.kwargs,
+                    x.params.clone(),
+                    self.key.get_preamble(),
+                    self.key.get_dialect(),
+                )
+            })
+            .collect()
+    }
     pub fn get_statements(
         &self,
         endpoints: U::TEndpoints,
@@ -158,7 +235,19 @@ where
             .next()
             .is_some();
 
-        let (dict_assign, has_params_dict) = self.get_dict_assign();
+        let (mut dict_assign, has_params_dict) = self.get_dict_assign();
+        let mut imports = AVec::new();
+        if let AOption(ROption::RSome(ref params_files)) = self.params_files {
+            let (load, load_imports) = params_file_load(
+                params_files,
+                &self.params_dict_name,
+                self.get_params_dict_name().as_str(),
+            );
+            dict_assign = load;
+            for import in load_imports {
+                imports.push(import);
+            }
+        }
 
         let params = AST::SimpleIdentifier(SimpleIdentifier::new_wrapped("params".into()));
         let ident = AST::SimpleIdentifier(SimpleIdentifier::new_wrapped("t".into()));
@@ -229,18 +318,6 @@ where
             }
         }
 
-        let singleton = T::new(
-            self.task_id.clone(),
-            new_collector.clone(),
-            self.key.get_call(),
-            args,
-            kwargs,
-            dependencies,
-            self.key.get_preamble(),
-            self.key.get_dialect(),
-            endpoints.clone(),
-        );
-        let statements = singleton.get_statements();
         let items_call = match has_params_dict {
             true => AST::Call(Call::new_wrapped(
                 AST::Attribute(Attribute::new_wrapped(
@@ -253,6 +330,78 @@ where
             )),
             false => self.params_dict_name.clone(),
         };
+        let mapped_task_id =
+            match self.dynamic_mapping && !(self.render_dependencies && any_dependencies) {
+                true => self.get_mapped_task_id(),
+                false => AOption(ROption::RNone),
+            };
+        if let AOption(ROption::RSome(task_id)) = mapped_task_id {
+            let mapped_task = AST::SimpleIdentifier(SimpleIdentifier::new_wrapped(
+                format!(
+                    "mapped_{}",
+                    task_id
+                        .as_str()
+                        .replace(|c: char| !c.is_alphanumeric(), "_")
+                )
+                .as_str()
+                .into(),
+            ));
+            let singleton = T::new(
+                AST::StringLiteral(StringLiteral::new_wrapped(task_id, false)),
+                mapped_task.clone(),
+                self.key.get_call(),
+                args.clone(),
+                kwargs.clone(),
+                dependencies.clone(),
+                self.key.get_preamble(),
+                self.key.get_dialect(),
+                endpoints.clone(),
+                self.key.get_execution_policy(),
+            );
+            let generator = AST::Comprehension(Comprehension::new_wrapped(
+                tpl.clone(),
+                items_call.clone(),
+                AVec::new(),
+            ));
+            if let AOption(ROption::RSome((statements, mapped_imports))) =
+                singleton.get_mapped_statements(generator)
+            {
+                // downstream tasks still refer to individual tasks by key
+                let collect = AST::ForLoop(ForLoop::new_wrapped(
+                    ident.clone(),
+                    self.params_dict_name.clone(),
+                    vec![AST::Assignment(Assignment::new_wrapped(
+                        new_collector.clone(),
+                        mapped_task,
+                    ))]
+                    .into_iter()
+                    .collect(),
+                ));
+                return (
+                    vec![dict_assign]
+                        .into_iter()
+                        .chain(statements.into_iter())
+                        .chain(vec![collect].into_iter())
+                        .collect(),
+                    singleton.get_preamble().unwrap(),
+                    mapped_imports.into_iter().chain(imports).collect(),
+                );
+            }
+        }
+
+        let singleton = T::new(
+            self.task_id.clone(),
+            new_collector.clone(),
+            self.key.get_call(),
+            args,
+            kwargs,
+            dependencies,
+            self.key.get_preamble(),
+            self.key.get_dialect(),
+            endpoints.clone(),
+            self.key.get_execution_policy(),
+        );
+        let statements = singleton.get_statements();
         let for_loop = AST::ForLoop(ForLoop::new_wrapped(
             tpl.clone(),
             items_call,
@@ -262,7 +411,105 @@ where
             vec![dict_assign, for_loop].into_iter().collect(),
             // TODO: propagate erorr type here
             singleton.get_preamble().unwrap(),
-            singleton.get_imports(),
+            singleton.get_imports().into_iter().chain(imports).collect(),
         )
     }
 }
+/// with open("<path>") as params_file:
+///     params_<constraint> = json.load(params_file)
+///
+/// The path is absolute (see ParamsFiles), so that the flow can be saved
+/// and run from any directory, including as a notebook.
+fn params_file_load(
+    params_files: &ParamsFiles,
+    params_dict_name: &AST,
+    name: &str,
+) -> (AST, AVec<PythonImport>) {
+    let (module, function) = match params_files.get_format() {
+        ParamsFileFormat::JSON => ("json", "load"),
+        ParamsFileFormat::YAML => ("yaml", "safe_load"),
+    };
+    let file = AST::SimpleIdentifier(SimpleIdentifier::new_wrapped("params_file".into()));
+    let path = AST::StringLiteral(StringLiteral::new_wrapped(
+        params_files.get_path(name),
+        false,
+    ));
+    let open = AST::Call(Call::new_wrapped(
+        AST::SimpleIdentifier(SimpleIdentifier::new_wrapped("open".into())),
+        vec![path].into_iter().collect(),
+        LinkedHashMap::new(),
+    ));
+    let load = AST::Call(Call::new_wrapped(
+        AST::Attribute(Attribute::new_wrapped(
+            AST::SimpleIdentifier(SimpleIdentifier::new_wrapped(module.into())),
+            function.into(),
+            false,
+        )),
+        vec![file.clone()].into_iter().collect(),
+        LinkedHashMap::new(),
+    ));
+    (
+        AST::With(With::new_wrapped(
+            vec![AST::WithItem(WithItem::new_wrapped(
+                open,
+                AOption(ROption::RSome(file)),
+            ))]
+            .into_iter()
+            .collect(),
+            vec![AST::Assignment(Assignment::new_wrapped(
+                params_dict_name.clone(),
+                load,
+            ))]
+            .into_iter()
+            .collect(),
+        )),
+        vec![PythonImport::PythonModuleImport(
+            module.into(),
+            AOption(ROption::RNone),
+        )]
+        .into_iter()
+        .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    #[test]
+    fn test_params_file_load_uses_absolute_path() {
+        let params_files = ParamsFiles::new("params".into(), ParamsFileFormat::JSON).unwrap();
+        let name =
+            AST::SimpleIdentifier(SimpleIdentifier::new_wrapped("params_download_data".into()));
+        let (load, imports) = params_file_load(&params_files, &name, "params_download_data");
+        let expected = std::env::current_dir()
+            .unwrap()
+            .join("params")
+            .join("params_download_data.json");
+        let path = match load {
+            AST::With(ref with) => match with.read().items()[0] {
+                AST::WithItem(ref item) => match item.read().context_expr() {
+                    AST::Call(ref call) => match call.read().args()[0] {
+                        AST::StringLiteral(ref path) => path.read().value(),
+                        _ => panic!("params file path should be a string literal"),
+                    },
+                    _ => panic!("params file should be opened with a call"),
+                },
+                _ => panic!("with statement should have a with item"),
+            },
+            _ => panic!("params file should be loaded in a with statement"),
+        };
+        assert!(Path::new(path.as_str()).is_absolute());
+        assert_eq!(path.as_str(), expected.to_string_lossy());
+        assert!(
+            imports
+                == vec![PythonImport::PythonModuleImport(
+                    "json".into(),
+                    AOption(ROption::RNone)
+                )]
+                .into_iter()
+                .collect::<AVec<_>>()
+        );
+    }
+}
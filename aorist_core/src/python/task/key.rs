@@ -1,7 +1,7 @@
 use crate::flow::CompressionKey;
 use crate::parameter_tuple::ParameterTupleDedupKey;
 use crate::python::AST;
-use aorist_primitives::Dialect;
+use aorist_primitives::{Dialect, TaskPolicy};
 use aorist_util::AOption;
 use aorist_util::{AString, AVec};
 use linked_hash_map::LinkedHashMap;
@@ -15,6 +15,7 @@ use std::hash::Hash;
 ///   - names of kwargs
 /// - preamble
 /// - dialect
+/// - execution policy
 #[derive(Clone, Hash, PartialEq, Eq, Debug)]
 pub struct PythonBasedTaskCompressionKey {
     // dict name
@@ -27,6 +28,8 @@ pub struct PythonBasedTaskCompressionKey {
     preamble: AOption<AString>,
     // dialect
     dialect: AOption<Dialect>,
+    // execution policy
+    execution_policy: AOption<TaskPolicy>,
     // optional: dependencies
     pub deps: AVec<AST>,
     // optional: kwargs
@@ -39,6 +42,7 @@ impl CompressionKey for PythonBasedTaskCompressionKey {
         dedup_key: AOption<ParameterTupleDedupKey>,
         preamble: AOption<AString>,
         dialect: AOption<Dialect>,
+        execution_policy: AOption<TaskPolicy>,
     ) -> Self {
         Self {
             dict_name,
@@ -46,6 +50,7 @@ impl CompressionKey for PythonBasedTaskCompressionKey {
             dedup_key,
             preamble,
             dialect,
+            execution_policy,
             deps: AVec::new(),
             kwargs: LinkedHashMap::new(),
         }
@@ -65,4 +70,7 @@ impl CompressionKey for PythonBasedTaskCompressionKey {
     fn get_dialect(&self) -> AOption<Dialect> {
         self.dialect.clone()
     }
+    fn get_execution_policy(&self) -> AOption<TaskPolicy> {
+        self.execution_policy.clone()
+    }
 }
@@ -7,7 +7,7 @@ pub use compressed::*;
 pub use standalone::*;
 
 use crate::flow::{CompressibleETLTask, ETLFlow, ETLTask, TaskBase};
-use crate::python::{PythonImport, PythonPreamble, AST};
+use crate::python::{LiterateChunk, PythonImport, PythonPreamble, AST};
 use aorist_primitives::AoristUniverse;
 use aorist_util::AVec;
 
@@ -50,6 +50,12 @@ where
             PythonBasedTask::ForLoopPythonBasedTask(x) => x.get_statements(endpoints),
         }
     }
+    pub fn get_literate_chunks(&self) -> AVec<LiterateChunk> {
+        match &self {
+            PythonBasedTask::StandalonePythonBasedTask(x) => x.get_literate_chunks(),
+            PythonBasedTask::ForLoopPythonBasedTask(x) => x.get_literate_chunks(),
+        }
+    }
     #[allow(dead_code)]
     fn for_loop_task(task: ForLoopPythonBasedTask<T, U>) -> Self {
         Self::ForLoopPythonBasedTask(task)
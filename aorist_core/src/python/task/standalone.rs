@@ -4,10 +4,10 @@ use crate::flow::{
 use crate::parameter_tuple::ParameterTuple;
 use crate::python::task::key::PythonBasedTaskCompressionKey;
 use crate::python::task::uncompressible::PythonBasedTaskUncompressiblePart;
-use crate::python::{List, PythonImport, PythonPreamble, StringLiteral, AST};
+use crate::python::{List, LiterateChunk, PythonImport, PythonPreamble, StringLiteral, AST};
 use abi_stable::std_types::ROption;
 use aorist_primitives::AoristUniverse;
-use aorist_primitives::Dialect;
+use aorist_primitives::{Dialect, TaskPolicy};
 use aorist_util::AOption;
 use aorist_util::{AString, AVec};
 use linked_hash_map::LinkedHashMap;
@@ -37,6 +37,8 @@ where
     /// Dialect (e.g. Bash, Python, R, Presto, etc.), to be interpreted
     /// by render.
     dialect: AOption<Dialect>,
+    /// Retries, timeout and resources of the task, if set.
+    execution_policy: AOption<TaskPolicy>,
     singleton_type: PhantomData<T>,
     _universe: PhantomData<U>,
 }
@@ -60,6 +62,7 @@ where
         dependencies: AVec<AST>,
         preamble: AOption<AString>,
         dialect: AOption<Dialect>,
+        execution_policy: AOption<TaskPolicy>,
     ) -> Self {
         Self {
             task_id,
@@ -69,6 +72,7 @@ where
             dependencies,
             preamble,
             dialect,
+            execution_policy,
             singleton_type: PhantomData,
             _universe: PhantomData,
         }
@@ -98,6 +102,7 @@ where
             },
             self.preamble.clone(),
             self.dialect.clone(),
+            self.execution_policy.clone(),
         ))
     }
     fn get_left_of_task_val(&self) -> Result<AST, AString> {
@@ -147,6 +152,17 @@ where
             self.dependencies.clone(),
         ))
     }
+    pub fn get_literate_chunks(&self) -> AVec<LiterateChunk> {
+        vec![LiterateChunk::new(
+            self.task_id.clone(),
+            self.call.clone(),
+            self.params.clone(),
+            self.get_preamble(),
+            self.get_dialect(),
+        )]
+        .into_iter()
+        .collect()
+    }
     pub fn get_statements(
         &self,
         endpoints: U::TEndpoints,
@@ -176,6 +192,7 @@ where
             self.get_preamble(),
             self.get_dialect(),
             endpoints.clone(),
+            self.execution_policy.clone(),
         );
         (
             singleton.get_statements(),
@@ -0,0 +1,184 @@
+use crate::python::{NativePythonPreamble, PythonImport, PythonPreamble};
+use abi_stable::std_types::ROption;
+use aorist_ast::{
+    Assignment, Attribute, BigIntLiteral, BooleanLiteral, Call, Expression, Lambda, List,
+    SimpleIdentifier, StringLiteral, AST,
+};
+use aorist_primitives::ValidationCheck;
+use aorist_util::{AOption, AString, AVec};
+use linked_hash_map::LinkedHashMap;
+
+/// Defines one function per kind of ValidationCheck, each returning a
+/// description of the failure (or None), as well as run_checks and
+/// report_failures. Cloud clients are imported inside the checks using
+/// them, so that flows without such sources do not depend on them.
+pub fn get_validation_preamble() -> PythonPreamble {
+    PythonPreamble::NativePythonPreamble(NativePythonPreamble {
+        imports: vec![
+            "importlib",
+            "importlib.metadata",
+            "os",
+            "re",
+            "socket",
+            "sys",
+            "urllib.request",
+        ]
+        .into_iter()
+        .map(|x| PythonImport::PythonModuleImport(x.into(), AOption(ROption::RNone)))
+        .collect(),
+        from_imports: AVec::new(),
+        body: "
+def check_endpoint_reachable(name, host, port):
+    try:
+        socket.create_connection((host, port), timeout=10).close()
+    except OSError as e:
+        return 'cannot reach %s endpoint at %s:%s (%s)' % (name, host, port, e)
+
+def check_url_exists(url):
+    try:
+        urllib.request.urlopen(urllib.request.Request(url, method='HEAD'), timeout=30).close()
+    except Exception as e:
+        return 'source %s is not available (%s)' % (url, e)
+
+def check_gcs_blob_exists(bucket, blob):
+    from google.cloud import storage
+    if not storage.Client().bucket(bucket).blob(blob).exists():
+        return 'source gs://%s/%s does not exist' % (bucket, blob)
+
+def check_s3_object_exists(bucket, key, requester_pays):
+    import boto3
+    kwargs = {'RequestPayer': 'requester'} if requester_pays else {}
+    try:
+        boto3.client('s3').head_object(Bucket=bucket, Key=key, **kwargs)
+    except Exception as e:
+        return 'source s3://%s/%s is not available (%s)' % (bucket, key, e)
+
+def check_s3_bucket_exists(bucket):
+    import boto3
+    try:
+        boto3.client('s3').head_bucket(Bucket=bucket)
+    except Exception as e:
+        return 'target bucket s3://%s is not available (%s)' % (bucket, e)
+
+def check_database_directory_exists(database):
+    directory = os.path.dirname(os.path.abspath(database))
+    if database != ':memory:' and not os.path.isdir(directory):
+        return 'directory %s of target database %s does not exist' % (directory, database)
+
+def check_package_installed(requirement):
+    name = re.split(r'[<>=!~\\[;\\s]', requirement, 1)[0]
+    try:
+        distribution = importlib.metadata.distribution(name)
+    except importlib.metadata.PackageNotFoundError:
+        return 'required package %s is not installed' % requirement
+    for module in (distribution.read_text('top_level.txt') or '').split():
+        try:
+            importlib.import_module(module)
+        except Exception as e:
+            return 'required package %s cannot be imported (%s)' % (requirement, e)
+
+def run_checks(task, checks):
+    failures = []
+    for check in checks:
+        try:
+            failure = check()
+        except Exception as e:
+            failure = 'check could not be run (%s)' % e
+        if failure is not None:
+            failures.append('%s: %s' % (task, failure))
+    return failures
+
+def report_failures(failures):
+    for failure in failures:
+        print(failure)
+    if len(failures) > 0:
+        sys.exit('%d validation check(s) failed' % len(failures))
+    print('All validation checks passed')
+"
+        .into(),
+    })
+}
+fn string(value: &AString) -> AST {
+    AST::StringLiteral(StringLiteral::new_wrapped(value.clone(), false))
+}
+/// lambda: check_...(args), so that run_checks can catch whatever the
+/// check raises.
+fn get_check_lambda(check: &ValidationCheck) -> AST {
+    let (function, args) = match check {
+        ValidationCheck::EndpointReachable(name, host, port) => (
+            "check_endpoint_reachable",
+            vec![
+                string(name),
+                string(host),
+                AST::BigIntLiteral(BigIntLiteral::new_wrapped(*port as i64)),
+            ],
+        ),
+        ValidationCheck::UrlExists(url) => ("check_url_exists", vec![string(url)]),
+        ValidationCheck::GCSBlobExists(bucket, blob) => {
+            ("check_gcs_blob_exists", vec![string(bucket), string(blob)])
+        }
+        ValidationCheck::S3ObjectExists(bucket, key, requester_pays) => (
+            "check_s3_object_exists",
+            vec![
+                string(bucket),
+                string(key),
+                AST::BooleanLiteral(BooleanLiteral::new_wrapped(*requester_pays)),
+            ],
+        ),
+        ValidationCheck::S3BucketExists(bucket) => ("check_s3_bucket_exists", vec![string(bucket)]),
+        ValidationCheck::DatabaseDirectoryExists(database) => {
+            ("check_database_directory_exists", vec![string(database)])
+        }
+        ValidationCheck::PackageInstalled(requirement) => {
+            ("check_package_installed", vec![string(requirement)])
+        }
+    };
+    AST::Lambda(Lambda::new_wrapped(
+        AVec::new(),
+        AST::Call(Call::new_wrapped(
+            AST::SimpleIdentifier(SimpleIdentifier::new_wrapped(function.into())),
+            args.into_iter().collect(),
+            LinkedHashMap::new(),
+        )),
+    ))
+}
+fn failures() -> AST {
+    AST::SimpleIdentifier(SimpleIdentifier::new_wrapped("failures".into()))
+}
+/// failures = []
+pub fn get_failures_init() -> AST {
+    AST::Assignment(Assignment::new_wrapped(
+        failures(),
+        AST::List(List::new_wrapped(AVec::new(), false)),
+    ))
+}
+/// failures.extend(run_checks(task_name, [lambda: ..., ...]))
+pub fn get_run_checks(task_name: AString, checks: &AVec<ValidationCheck>) -> AST {
+    AST::Expression(Expression::new_wrapped(AST::Call(Call::new_wrapped(
+        AST::Attribute(Attribute::new_wrapped(failures(), "extend".into(), false)),
+        vec![AST::Call(Call::new_wrapped(
+            AST::SimpleIdentifier(SimpleIdentifier::new_wrapped("run_checks".into())),
+            vec![
+                string(&task_name),
+                AST::List(List::new_wrapped(
+                    checks.iter().map(get_check_lambda).collect(),
+                    false,
+                )),
+            ]
+            .into_iter()
+            .collect(),
+            LinkedHashMap::new(),
+        ))]
+        .into_iter()
+        .collect(),
+        LinkedHashMap::new(),
+    ))))
+}
+/// report_failures(failures)
+pub fn get_report_failures() -> AST {
+    AST::Expression(Expression::new_wrapped(AST::Call(Call::new_wrapped(
+        AST::SimpleIdentifier(SimpleIdentifier::new_wrapped("report_failures".into())),
+        vec![failures()].into_iter().collect(),
+        LinkedHashMap::new(),
+    ))))
+}
@@ -12,7 +12,7 @@ impl RImport {
     pub fn new(library: AString) -> Self {
         Self { library }
     }
-    pub fn to_r_ast_node(&self, depth: usize) -> Robj {
+    pub fn to_r_ast_node(&self, depth: usize) -> pyo3::PyResult<Robj> {
         AST::Call(Call::new_wrapped(
             AST::SimpleIdentifier(SimpleIdentifier::new_wrapped("library".into())),
             vec![AST::StringLiteral(StringLiteral::new_wrapped(
@@ -0,0 +1,282 @@
+use abi_stable::std_types::ROption;
+use aorist_ast::bash::{Command, Pipeline, Statement, ToBash, Word};
+use aorist_ast::AST;
+use aorist_primitives::Dialect;
+use aorist_util::AOption;
+use aorist_util::{AString, AVec};
+use linked_hash_map::LinkedHashMap;
+
+/// Environment variable holding the partition a scheduled run is
+/// responsible for, in flows running tasks as shell commands.
+pub(crate) const PARTITION_DATE_VARIABLE: &str = "PARTITION_DATE";
+
+/// Feeds SQL to `command` through a quoted here-document. Queries of
+/// partitioned tables refer to `{partition_date}`, which sed fills in from
+/// PARTITION_DATE on the way, since here-documents are not expanded.
+pub(crate) fn pipe_sql(mut command: Command, sql: &str) -> Statement {
+    if !sql.contains("{partition_date}") {
+        command.heredoc = AOption(ROption::RSome(sql.into()));
+        return Statement::Command(command);
+    }
+    let mut sed = Command::new(
+        "sed".into(),
+        vec![Word::Concat(
+            vec![
+                Word::Literal("s/{partition_date}/".into()),
+                Word::Variable(PARTITION_DATE_VARIABLE.into()),
+                Word::Literal("/g".into()),
+            ]
+            .into_iter()
+            .collect(),
+        )]
+        .into_iter()
+        .collect(),
+    );
+    sed.heredoc = AOption(ROption::RSome(sql.into()));
+    Statement::Pipeline(Pipeline::new(vec![sed, command].into_iter().collect()))
+}
+
+/// The query template, with its string parameters filled in. Lists of
+/// queries are run one after the other; schema changes among them (see
+/// SchemaChange) are left out, to be run on their own.
+pub(crate) fn fill_sql(template: &str, kwargs: &LinkedHashMap<AString, AST>) -> AString {
+    let mut sql = template.to_string();
+    for (k, v) in kwargs.iter() {
+        let value = match v {
+            AST::StringLiteral(ref x) => x.read().value().as_str().to_string(),
+            AST::List(ref x) => x
+                .read()
+                .elems()
+                .iter()
+                .filter_map(|e| match e {
+                    AST::StringLiteral(ref q) => Some(q.read().value().as_str().to_string()),
+                    _ => Option::None,
+                })
+                .collect::<AVec<String>>()
+                .join(";\n"),
+            _ => continue,
+        };
+        sql = sql.replace(&format!("{{{}}}", k), &value);
+    }
+    sql.as_str().into()
+}
+
+/// A change of a table's columns, passed by recipes as a dict among their
+/// queries, e.g. `{"query": "ALTER TABLE t ADD COLUMNS (c INT)", "table":
+/// "t", "column": "c", "added": True}`. Spark SQL has no ADD COLUMNS IF NOT
+/// EXISTS or DROP COLUMN IF EXISTS, so runners skip the change once the
+/// table has (or no longer has) the column.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct SchemaChange {
+    query: AString,
+    table: AString,
+    column: AString,
+    added: bool,
+}
+impl SchemaChange {
+    fn from_ast(ast: &AST) -> AOption<Self> {
+        let elems = match ast {
+            AST::Dict(ref x) => x.read().elems().clone(),
+            _ => return AOption(ROption::RNone),
+        };
+        let string = |key: &str| match elems.get(&key.into()) {
+            Some(AST::StringLiteral(ref x)) => Some(x.read().value()),
+            _ => Option::None,
+        };
+        match (
+            string("query"),
+            string("table"),
+            string("column"),
+            elems.get(&"added".into()),
+        ) {
+            (Some(query), Some(table), Some(column), Some(AST::BooleanLiteral(ref added))) => {
+                AOption(ROption::RSome(Self {
+                    query,
+                    table,
+                    column,
+                    added: added.read().val(),
+                }))
+            }
+            _ => AOption(ROption::RNone),
+        }
+    }
+    /// The schema changes among the (lists of) queries in `kwargs`.
+    pub(crate) fn from_kwargs(kwargs: &LinkedHashMap<AString, AST>) -> AVec<Self> {
+        kwargs
+            .values()
+            .flat_map(|v| match v {
+                AST::List(ref x) => x.read().elems().iter().cloned().collect::<Vec<_>>(),
+                _ => vec![v.clone()],
+            })
+            .filter_map(|x| match Self::from_ast(&x) {
+                AOption(ROption::RSome(change)) => Some(change),
+                AOption(ROption::RNone) => Option::None,
+            })
+            .collect()
+    }
+    /// if ! spark-sql ... -S -e 'SHOW COLUMNS IN t' | awk '{print $1}' | grep -ixF c >/dev/null; then
+    ///   spark-sql ... -e 'ALTER TABLE t ADD COLUMNS (c INT)'
+    /// fi
+    ///
+    /// `command` is the spark-sql command reading queries from stdin (see
+    /// sql_command). The columns are searched without `grep -q`, which
+    /// could fail the pipeline by closing it early.
+    pub(crate) fn to_statement(&self, command: &Command) -> Statement {
+        let spark_sql = |args: Vec<Word>| {
+            Command::new(
+                command.program.clone(),
+                command
+                    .args
+                    .iter()
+                    .take_while(|x| **x != Word::Literal("-f".into()))
+                    .cloned()
+                    .chain(args.into_iter())
+                    .collect(),
+            )
+        };
+        let columns = Pipeline::new(
+            vec![
+                spark_sql(vec![
+                    Word::Literal("-S".into()),
+                    Word::Literal("-e".into()),
+                    Word::Literal(format!("SHOW COLUMNS IN {}", self.table).as_str().into()),
+                ]),
+                Command::new(
+                    "awk".into(),
+                    vec![Word::Literal("{print $1}".into())]
+                        .into_iter()
+                        .collect(),
+                ),
+                Command::new(
+                    "grep".into(),
+                    vec![
+                        Word::Literal("-ixF".into()),
+                        Word::Literal(self.column.clone()),
+                    ]
+                    .into_iter()
+                    .collect(),
+                ),
+            ]
+            .into_iter()
+            .collect(),
+        );
+        let change = spark_sql(vec![
+            Word::Literal("-e".into()),
+            Word::Literal(self.query.clone()),
+        ]);
+        Statement::Raw(
+            format!(
+                "if {}{} >/dev/null; then
+  {}
+fi",
+                match self.added {
+                    true => "! ",
+                    false => "",
+                },
+                columns.to_bash(),
+                change.to_bash(),
+            )
+            .as_str()
+            .into(),
+        )
+    }
+}
+
+/// Command line client reading the queries of a SQL dialect from stdin,
+/// configured from the environment. DuckDB programs binding a `database`
+/// parameter (e.g. the file of a DuckDBLocation) run against that
+/// database, as DuckDBPythonTask does.
+pub(crate) fn sql_command(dialect: &Dialect, kwargs: &LinkedHashMap<AString, AST>) -> Command {
+    let (program, args): (&str, Vec<Word>) = match dialect {
+        Dialect::DuckDB(_) => (
+            "duckdb",
+            vec![match kwargs.get(&("database".into())) {
+                Some(AST::StringLiteral(ref x)) => Word::Literal(x.read().value()),
+                _ => Word::Raw("\"${DUCKDB_DATABASE:-aorist.duckdb}\"".into()),
+            }],
+        ),
+        Dialect::Spark(_) => (
+            "spark-sql",
+            vec![
+                Word::Literal("--master".into()),
+                Word::Raw("\"${SPARK_MASTER:-local[*]}\"".into()),
+                Word::Literal("-f".into()),
+                Word::Literal("/dev/stdin".into()),
+            ],
+        ),
+        _ => (
+            "presto",
+            vec![
+                Word::Literal("--server".into()),
+                Word::Raw("\"${PRESTO_SERVER:-localhost}:${PRESTO_HTTP_PORT:-8080}\"".into()),
+                Word::Literal("--user".into()),
+                Word::Raw("\"${PRESTO_USER:-aorist}\"".into()),
+                Word::Literal("--catalog".into()),
+                Word::Literal("hive".into()),
+                Word::Literal("--schema".into()),
+                Word::Literal("default".into()),
+            ],
+        ),
+    };
+    Command::new(program.into(), args.into_iter().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aorist_ast::{BooleanLiteral, Dict, List, StringLiteral};
+    use aorist_primitives::Spark;
+
+    fn string(value: &str) -> AST {
+        AST::StringLiteral(StringLiteral::new_wrapped(value.into(), false))
+    }
+    fn schema_change(query: &str, column: &str, added: bool) -> AST {
+        let mut elems = LinkedHashMap::new();
+        elems.insert("query".into(), string(query));
+        elems.insert("table".into(), string("db.t"));
+        elems.insert("column".into(), string(column));
+        elems.insert(
+            "added".into(),
+            AST::BooleanLiteral(BooleanLiteral::new_wrapped(added)),
+        );
+        AST::Dict(Dict::new_wrapped(elems))
+    }
+
+    #[test]
+    fn test_schema_changes_run_on_their_own() {
+        let mut kwargs = LinkedHashMap::new();
+        kwargs.insert(
+            "queries".into(),
+            AST::List(List::new_wrapped(
+                vec![
+                    string("CREATE TABLE IF NOT EXISTS db.t (a INT)"),
+                    schema_change("ALTER TABLE db.t ADD COLUMNS (b INT)", "b", true),
+                    schema_change("ALTER TABLE db.t DROP COLUMN c", "c", false),
+                ]
+                .into_iter()
+                .collect(),
+                false,
+            )),
+        );
+        assert_eq!(
+            fill_sql("{queries}", &kwargs).as_str(),
+            "CREATE TABLE IF NOT EXISTS db.t (a INT)"
+        );
+        let command = sql_command(&Dialect::Spark(Spark::new()), &kwargs);
+        let changes = SchemaChange::from_kwargs(&kwargs)
+            .iter()
+            .map(|x| x.to_statement(&command).to_bash().as_str().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            changes,
+            vec![
+                "if ! spark-sql --master \"${SPARK_MASTER:-local[*]}\" -S -e 'SHOW COLUMNS IN db.t' \
+                 | awk '{print $1}' | grep -ixF b >/dev/null; then\n  \
+                 spark-sql --master \"${SPARK_MASTER:-local[*]}\" -e 'ALTER TABLE db.t ADD COLUMNS (b INT)'\nfi",
+                "if spark-sql --master \"${SPARK_MASTER:-local[*]}\" -S -e 'SHOW COLUMNS IN db.t' \
+                 | awk '{print $1}' | grep -ixF c >/dev/null; then\n  \
+                 spark-sql --master \"${SPARK_MASTER:-local[*]}\" -e 'ALTER TABLE db.t DROP COLUMN c'\nfi",
+            ]
+        );
+    }
+}
@@ -203,6 +203,242 @@ pub fn derive_sqlite_text(input: TokenStream) -> TokenStream {
     gen.into()
 }
 
+#[proc_macro_derive(DuckDBVarchar)]
+pub fn derive_duckdb_varchar(input: TokenStream) -> TokenStream {
+    let ast: syn::DeriveInput = syn::parse(input).unwrap();
+    let name = &ast.ident;
+    let gen = quote! {
+        impl TDuckDBAttribute for #name {
+            fn get_duckdb_type(&self) -> AString {
+                "VARCHAR".into()
+            }
+        }
+    };
+    gen.into()
+}
+
+#[proc_macro_derive(DuckDBBigint)]
+pub fn derive_duckdb_bigint(input: TokenStream) -> TokenStream {
+    let ast: syn::DeriveInput = syn::parse(input).unwrap();
+    let name = &ast.ident;
+    let gen = quote! {
+        impl TDuckDBAttribute for #name {
+            fn get_duckdb_type(&self) -> AString {
+                "BIGINT".into()
+            }
+        }
+    };
+    gen.into()
+}
+
+#[proc_macro_derive(DuckDBDouble)]
+pub fn derive_duckdb_double(input: TokenStream) -> TokenStream {
+    let ast: syn::DeriveInput = syn::parse(input).unwrap();
+    let name = &ast.ident;
+    let gen = quote! {
+        impl TDuckDBAttribute for #name {
+            fn get_duckdb_type(&self) -> AString {
+                "DOUBLE".into()
+            }
+        }
+    };
+    gen.into()
+}
+
+#[proc_macro_derive(SparkString)]
+pub fn derive_spark_string(input: TokenStream) -> TokenStream {
+    let ast: syn::DeriveInput = syn::parse(input).unwrap();
+    let name = &ast.ident;
+    let gen = quote! {
+        impl TSparkAttribute for #name {
+            fn get_spark_type(&self) -> AString {
+                "STRING".into()
+            }
+        }
+    };
+    gen.into()
+}
+
+#[proc_macro_derive(SparkBigint)]
+pub fn derive_spark_bigint(input: TokenStream) -> TokenStream {
+    let ast: syn::DeriveInput = syn::parse(input).unwrap();
+    let name = &ast.ident;
+    let gen = quote! {
+        impl TSparkAttribute for #name {
+            fn get_spark_type(&self) -> AString {
+                "BIGINT".into()
+            }
+        }
+    };
+    gen.into()
+}
+
+#[proc_macro_derive(SparkDouble)]
+pub fn derive_spark_double(input: TokenStream) -> TokenStream {
+    let ast: syn::DeriveInput = syn::parse(input).unwrap();
+    let name = &ast.ident;
+    let gen = quote! {
+        impl TSparkAttribute for #name {
+            fn get_spark_type(&self) -> AString {
+                "DOUBLE".into()
+            }
+        }
+    };
+    gen.into()
+}
+
+#[proc_macro_derive(ParquetString)]
+pub fn derive_parquet_string(input: TokenStream) -> TokenStream {
+    let ast: syn::DeriveInput = syn::parse(input).unwrap();
+    let name = &ast.ident;
+    let gen = quote! {
+        impl TParquetAttribute for #name {
+            fn get_parquet_physical_type(&self) -> AString {
+                "BYTE_ARRAY".into()
+            }
+            fn get_parquet_logical_type(&self) -> AOption<AString> {
+                AOption(ROption::RSome("STRING".into()))
+            }
+        }
+    };
+    gen.into()
+}
+
+#[proc_macro_derive(ParquetJSON)]
+pub fn derive_parquet_json(input: TokenStream) -> TokenStream {
+    let ast: syn::DeriveInput = syn::parse(input).unwrap();
+    let name = &ast.ident;
+    let gen = quote! {
+        impl TParquetAttribute for #name {
+            fn get_parquet_physical_type(&self) -> AString {
+                "BYTE_ARRAY".into()
+            }
+            fn get_parquet_logical_type(&self) -> AOption<AString> {
+                AOption(ROption::RSome("JSON".into()))
+            }
+        }
+    };
+    gen.into()
+}
+
+#[proc_macro_derive(ParquetInt64)]
+pub fn derive_parquet_int64(input: TokenStream) -> TokenStream {
+    let ast: syn::DeriveInput = syn::parse(input).unwrap();
+    let name = &ast.ident;
+    let gen = quote! {
+        impl TParquetAttribute for #name {
+            fn get_parquet_physical_type(&self) -> AString {
+                "INT64".into()
+            }
+            fn get_parquet_logical_type(&self) -> AOption<AString> {
+                AOption(ROption::RNone)
+            }
+        }
+    };
+    gen.into()
+}
+
+#[proc_macro_derive(ParquetDouble)]
+pub fn derive_parquet_double(input: TokenStream) -> TokenStream {
+    let ast: syn::DeriveInput = syn::parse(input).unwrap();
+    let name = &ast.ident;
+    let gen = quote! {
+        impl TParquetAttribute for #name {
+            fn get_parquet_physical_type(&self) -> AString {
+                "DOUBLE".into()
+            }
+            fn get_parquet_logical_type(&self) -> AOption<AString> {
+                AOption(ROption::RNone)
+            }
+        }
+    };
+    gen.into()
+}
+
+#[proc_macro_derive(AvroString)]
+pub fn derive_avro_string(input: TokenStream) -> TokenStream {
+    let ast: syn::DeriveInput = syn::parse(input).unwrap();
+    let name = &ast.ident;
+    let gen = quote! {
+        impl TAvroAttribute for #name {
+            fn get_avro_type(&self) -> AString {
+                "string".into()
+            }
+        }
+    };
+    gen.into()
+}
+
+#[proc_macro_derive(AvroLong)]
+pub fn derive_avro_long(input: TokenStream) -> TokenStream {
+    let ast: syn::DeriveInput = syn::parse(input).unwrap();
+    let name = &ast.ident;
+    let gen = quote! {
+        impl TAvroAttribute for #name {
+            fn get_avro_type(&self) -> AString {
+                "long".into()
+            }
+        }
+    };
+    gen.into()
+}
+
+#[proc_macro_derive(AvroDouble)]
+pub fn derive_avro_double(input: TokenStream) -> TokenStream {
+    let ast: syn::DeriveInput = syn::parse(input).unwrap();
+    let name = &ast.ident;
+    let gen = quote! {
+        impl TAvroAttribute for #name {
+            fn get_avro_type(&self) -> AString {
+                "double".into()
+            }
+        }
+    };
+    gen.into()
+}
+
+#[proc_macro_derive(ArrowString)]
+pub fn derive_arrow_string(input: TokenStream) -> TokenStream {
+    let ast: syn::DeriveInput = syn::parse(input).unwrap();
+    let name = &ast.ident;
+    let gen = quote! {
+        impl TArrowAttribute for #name {
+            fn get_arrow_type(&self) -> AString {
+                "string".into()
+            }
+        }
+    };
+    gen.into()
+}
+
+#[proc_macro_derive(ArrowInt64)]
+pub fn derive_arrow_int64(input: TokenStream) -> TokenStream {
+    let ast: syn::DeriveInput = syn::parse(input).unwrap();
+    let name = &ast.ident;
+    let gen = quote! {
+        impl TArrowAttribute for #name {
+            fn get_arrow_type(&self) -> AString {
+                "int64".into()
+            }
+        }
+    };
+    gen.into()
+}
+
+#[proc_macro_derive(ArrowFloat64)]
+pub fn derive_arrow_float64(input: TokenStream) -> TokenStream {
+    let ast: syn::DeriveInput = syn::parse(input).unwrap();
+    let name = &ast.ident;
+    let gen = quote! {
+        impl TArrowAttribute for #name {
+            fn get_arrow_type(&self) -> AString {
+                "float64".into()
+            }
+        }
+    };
+    gen.into()
+}
+
 #[proc_macro_derive(PostgresSmallInt)]
 pub fn derive_postgres_smallint(input: TokenStream) -> TokenStream {
     let ast: syn::DeriveInput = syn::parse(input).unwrap();
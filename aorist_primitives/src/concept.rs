@@ -112,6 +112,9 @@ pub trait AoristUniverse {
 pub trait TPrestoEndpoints {
     fn presto_config(&self) -> PrestoConfig;
 }
+pub trait TSparkEndpoints {
+    fn spark_config(&self) -> SparkConfig;
+}
 pub trait Ancestry {
     type TConcept: ConceptEnum + Clone + ToplineConcept;
     fn new(parents: RArc<RRwLock<HashMap<ATaskId, Self::TConcept>>>) -> Self;
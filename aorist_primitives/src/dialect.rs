@@ -66,6 +66,32 @@ impl Presto {
     }
 }
 
+#[repr(C)]
+#[cfg_attr(feature = "python", pyclass)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash, StableAbi)]
+pub struct DuckDB {}
+#[cfg(feature = "python")]
+#[pymethods]
+impl DuckDB {
+    #[new]
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+#[repr(C)]
+#[cfg_attr(feature = "python", pyclass)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash, StableAbi)]
+pub struct Spark {}
+#[cfg(feature = "python")]
+#[pymethods]
+impl Spark {
+    #[new]
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
 #[repr(C)]
 #[cfg_attr(feature = "python", derive(FromPyObject))]
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash, StableAbi)]
@@ -74,6 +100,8 @@ pub enum Dialect {
     R(R),
     Bash(Bash),
     Presto(Presto),
+    DuckDB(DuckDB),
+    Spark(Spark),
 }
 
 #[cfg(feature = "python")]
@@ -84,6 +112,8 @@ pub fn dialects_module(
     m.add_class::<Python>()?;
     m.add_class::<Bash>()?;
     m.add_class::<Presto>()?;
+    m.add_class::<DuckDB>()?;
+    m.add_class::<Spark>()?;
     m.add_class::<R>()?;
     Ok(())
 }
@@ -0,0 +1,48 @@
+#[cfg(feature = "python")]
+use abi_stable::std_types::ROption;
+use aorist_util::{AOption, AString};
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Credentials for Azure Blob Storage, read from environment variables.
+/// The default Azure credential chain is used if neither is set.
+#[repr(C)]
+#[cfg_attr(feature = "python", pyclass)]
+#[derive(PartialEq, Deserialize, Serialize, Debug, Clone, Hash, abi_stable::StableAbi)]
+pub struct AzureConfig {
+    pub account_key_env_var: AOption<AString>,
+    pub sas_token_env_var: AOption<AString>,
+}
+#[cfg(feature = "python")]
+#[pymethods]
+impl AzureConfig {
+    #[new]
+    #[args(account_key_env_var = "None", sas_token_env_var = "None")]
+    fn new(account_key_env_var: Option<String>, sas_token_env_var: Option<String>) -> Self {
+        AzureConfig {
+            account_key_env_var: AOption(match account_key_env_var {
+                Some(x) => ROption::RSome(x.as_str().into()),
+                None => ROption::RNone,
+            }),
+            sas_token_env_var: AOption(match sas_token_env_var {
+                Some(x) => ROption::RSome(x.as_str().into()),
+                None => ROption::RNone,
+            }),
+        }
+    }
+    #[getter]
+    fn account_key_env_var(&self) -> Option<String> {
+        match self.account_key_env_var.0 {
+            ROption::RSome(ref x) => Some(x.to_string()),
+            ROption::RNone => None,
+        }
+    }
+    #[getter]
+    fn sas_token_env_var(&self) -> Option<String> {
+        match self.sas_token_env_var.0 {
+            ROption::RSome(ref x) => Some(x.to_string()),
+            ROption::RNone => None,
+        }
+    }
+}
@@ -0,0 +1,43 @@
+#[cfg(feature = "python")]
+use abi_stable::std_types::ROption;
+use aorist_util::{AOption, AString};
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Hadoop client used to read from and write to HDFS.
+#[repr(C)]
+#[cfg_attr(feature = "python", pyclass)]
+#[derive(PartialEq, Deserialize, Serialize, Debug, Clone, Hash, abi_stable::StableAbi)]
+pub struct HDFSConfig {
+    // directory containing the hdfs command
+    pub hadoop_path: AString,
+    // user the client acts as (HADOOP_USER_NAME)
+    pub user: AOption<AString>,
+}
+#[cfg(feature = "python")]
+#[pymethods]
+impl HDFSConfig {
+    #[new]
+    #[args(user = "None")]
+    fn new(hadoop_path: String, user: Option<String>) -> Self {
+        HDFSConfig {
+            hadoop_path: hadoop_path.as_str().into(),
+            user: AOption(match user {
+                Some(x) => ROption::RSome(x.as_str().into()),
+                None => ROption::RNone,
+            }),
+        }
+    }
+    #[getter]
+    pub fn hadoop_path(&self) -> String {
+        self.hadoop_path.to_string()
+    }
+    #[getter]
+    fn user(&self) -> Option<String> {
+        match self.user.0 {
+            ROption::RSome(ref x) => Some(x.to_string()),
+            ROption::RNone => None,
+        }
+    }
+}
@@ -1,28 +1,36 @@
 mod alluxio;
 mod aws;
+mod azure;
 mod dask;
 mod gcp;
 mod gdal;
 mod gitea;
+mod hdfs;
 mod linz;
 mod minio;
 mod pdal;
 mod postgres;
 mod presto;
 mod ranger;
+mod sftp;
+mod spark;
 
 pub use alluxio::*;
 pub use aws::*;
+pub use azure::*;
 pub use dask::*;
 pub use gcp::*;
 pub use gdal::*;
 pub use gitea::*;
+pub use hdfs::*;
 pub use linz::*;
 pub use minio::*;
 pub use pdal::*;
 pub use postgres::*;
 pub use presto::*;
 pub use ranger::*;
+pub use sftp::*;
+pub use spark::*;
 
 #[cfg(feature = "python")]
 pub fn endpoints_module(
@@ -41,5 +49,9 @@ pub fn endpoints_module(
     m.add_class::<MinioConfig>()?;
     m.add_class::<PDALConfig>()?;
     m.add_class::<GDALConfig>()?;
+    m.add_class::<SparkConfig>()?;
+    m.add_class::<AzureConfig>()?;
+    m.add_class::<HDFSConfig>()?;
+    m.add_class::<SFTPConfig>()?;
     Ok(())
 }
@@ -0,0 +1,49 @@
+#[cfg(feature = "python")]
+use abi_stable::std_types::ROption;
+use aorist_util::{AOption, AString};
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// SSH settings shared by all SFTP sources.
+#[repr(C)]
+#[cfg_attr(feature = "python", pyclass)]
+#[derive(PartialEq, Deserialize, Serialize, Debug, Clone, Hash, abi_stable::StableAbi)]
+pub struct SFTPConfig {
+    // key used for locations without a password
+    pub private_key_file: AOption<AString>,
+    // host keys are checked against this file, or ~/.ssh/known_hosts
+    pub known_hosts_file: AOption<AString>,
+}
+#[cfg(feature = "python")]
+#[pymethods]
+impl SFTPConfig {
+    #[new]
+    #[args(private_key_file = "None", known_hosts_file = "None")]
+    fn new(private_key_file: Option<String>, known_hosts_file: Option<String>) -> Self {
+        SFTPConfig {
+            private_key_file: AOption(match private_key_file {
+                Some(x) => ROption::RSome(x.as_str().into()),
+                None => ROption::RNone,
+            }),
+            known_hosts_file: AOption(match known_hosts_file {
+                Some(x) => ROption::RSome(x.as_str().into()),
+                None => ROption::RNone,
+            }),
+        }
+    }
+    #[getter]
+    fn private_key_file(&self) -> Option<String> {
+        match self.private_key_file.0 {
+            ROption::RSome(ref x) => Some(x.to_string()),
+            ROption::RNone => None,
+        }
+    }
+    #[getter]
+    fn known_hosts_file(&self) -> Option<String> {
+        match self.known_hosts_file.0 {
+            ROption::RSome(ref x) => Some(x.to_string()),
+            ROption::RNone => None,
+        }
+    }
+}
@@ -0,0 +1,39 @@
+#[cfg(feature = "python")]
+use abi_stable::std_types::ROption;
+use aorist_util::{AOption, AString};
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+use serde::{Deserialize, Serialize};
+#[repr(C)]
+#[cfg_attr(feature = "python", pyclass)]
+#[derive(PartialEq, Deserialize, Serialize, Debug, Clone, Hash, abi_stable::StableAbi)]
+pub struct SparkConfig {
+    pub master: AString,
+    pub metastore_uri: AOption<AString>,
+}
+#[cfg(feature = "python")]
+#[pymethods]
+impl SparkConfig {
+    #[new]
+    #[args(metastore_uri = "None")]
+    fn new(master: String, metastore_uri: Option<String>) -> Self {
+        SparkConfig {
+            master: master.as_str().into(),
+            metastore_uri: AOption(match metastore_uri {
+                Some(x) => ROption::RSome(x.as_str().into()),
+                None => ROption::RNone,
+            }),
+        }
+    }
+    #[getter]
+    fn master(&self) -> String {
+        self.master.to_string()
+    }
+    #[getter]
+    fn metastore_uri(&self) -> Option<String> {
+        match self.metastore_uri.0 {
+            ROption::RSome(ref x) => Some(x.to_string()),
+            ROption::RNone => None,
+        }
+    }
+}
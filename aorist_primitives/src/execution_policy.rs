@@ -0,0 +1,87 @@
+use abi_stable::std_types::ROption;
+use aorist_util::{AOption, AString, ATaskId};
+use std::collections::HashMap;
+
+/// How a single task is run: retries, timeout, resources and pool. Unset
+/// fields are left to the scheduler's defaults. Durations are in seconds;
+/// memory and cpu are Kubernetes quantities (e.g. "8Gi", "500m").
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TaskPolicy {
+    pub retries: AOption<i64>,
+    pub retry_delay: AOption<i64>,
+    pub exponential_backoff: AOption<bool>,
+    pub timeout: AOption<i64>,
+    pub memory: AOption<AString>,
+    pub cpu: AOption<AString>,
+    pub pool: AOption<AString>,
+}
+impl TaskPolicy {
+    /// This policy, with the fields set in `other` taking precedence.
+    pub fn override_with(&self, other: &TaskPolicy) -> TaskPolicy {
+        fn pick<T: Clone>(a: &AOption<T>, b: &AOption<T>) -> AOption<T> {
+            match b {
+                AOption(ROption::RSome(_)) => b.clone(),
+                AOption(ROption::RNone) => a.clone(),
+            }
+        }
+        TaskPolicy {
+            retries: pick(&self.retries, &other.retries),
+            retry_delay: pick(&self.retry_delay, &other.retry_delay),
+            exponential_backoff: pick(&self.exponential_backoff, &other.exponential_backoff),
+            timeout: pick(&self.timeout, &other.timeout),
+            memory: pick(&self.memory, &other.memory),
+            cpu: pick(&self.cpu, &other.cpu),
+            pool: pick(&self.pool, &other.pool),
+        }
+    }
+    pub fn get_retries(&self) -> i64 {
+        match self.retries {
+            AOption(ROption::RSome(x)) => x,
+            AOption(ROption::RNone) => 0,
+        }
+    }
+    pub fn get_exponential_backoff(&self) -> bool {
+        match self.exponential_backoff {
+            AOption(ROption::RSome(x)) => x,
+            AOption(ROption::RNone) => false,
+        }
+    }
+    pub fn has_resources(&self) -> bool {
+        self.memory.is_some() || self.cpu.is_some()
+    }
+}
+
+/// Execution policies attached to concepts (keyed by the concept they are
+/// attached to), and overrides by constraint name.
+#[derive(Clone, Debug, Default)]
+pub struct ExecutionPolicies {
+    by_concept: HashMap<ATaskId, TaskPolicy>,
+    by_constraint: HashMap<AString, TaskPolicy>,
+}
+impl ExecutionPolicies {
+    pub fn new(
+        by_concept: HashMap<ATaskId, TaskPolicy>,
+        by_constraint: HashMap<AString, TaskPolicy>,
+    ) -> Self {
+        Self {
+            by_concept,
+            by_constraint,
+        }
+    }
+    /// Policy of a constraint's task: that of the nearest concept, among
+    /// `ancestors` (nearest first), with a policy attached, overridden by
+    /// the one given for the constraint's name.
+    pub fn resolve<I: Iterator<Item = ATaskId>>(
+        &self,
+        constraint_name: &AString,
+        mut ancestors: I,
+    ) -> AOption<TaskPolicy> {
+        let attached = ancestors.find_map(|x| self.by_concept.get(&x).cloned());
+        match (attached, self.by_constraint.get(constraint_name)) {
+            (Some(a), Some(b)) => AOption(ROption::RSome(a.override_with(b))),
+            (Some(a), None) => AOption(ROption::RSome(a)),
+            (None, Some(b)) => AOption(ROption::RSome(b.clone())),
+            (None, None) => AOption(ROption::RNone),
+        }
+    }
+}
@@ -9,17 +9,24 @@ pub use context::*;
 mod dialect;
 #[cfg(feature = "python")]
 pub use dialect::dialects_module;
-pub use dialect::{Bash, Dialect, Presto, Python, R};
+pub use dialect::{Bash, Dialect, DuckDB, Presto, Python, Spark, R};
 mod program;
 pub use program::*;
+mod schedule;
+pub use schedule::*;
+mod execution_policy;
+pub use execution_policy::*;
+mod validation;
+pub use validation::*;
 
 #[macro_export]
 macro_rules! register_ast_nodes {
-    ($name:ident, $($variant: ident,)+) => {
+    ($name:ident, $($(#[$meta:meta])* $variant: ident,)+) => {
 
         #[derive(Clone)]
         pub enum $name {
             $(
+                $(#[$meta])*
                 $variant(RArc<RRwLock<$variant>>),
             )+
         }
@@ -27,6 +34,7 @@ macro_rules! register_ast_nodes {
             fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::result::Result<(), std::fmt::Error> {
                 match (&self) {
                     $(
+                        $(#[$meta])*
                         Self::$variant(v) => v.read().fmt(f),
                     )+
                 }
@@ -36,6 +44,7 @@ macro_rules! register_ast_nodes {
             fn eq(&self, other: &Self) -> bool {
                 match (&self, other) {
                     $(
+                        $(#[$meta])*
                         (Self::$variant(v1), Self::$variant(v2)) => {
                             v1.read().eq(&v2.read())
                         },
@@ -49,6 +58,7 @@ macro_rules! register_ast_nodes {
             pub fn clone_without_ancestors(&self) -> Self {
                 match &self {
                     $(
+                        $(#[$meta])*
                         Self::$variant(x) => Self::$variant(RArc::new(RRwLock::new(x.read().clone_without_ancestors()))),
                     )+
                 }
@@ -56,6 +66,7 @@ macro_rules! register_ast_nodes {
             pub fn set_ancestors(&self, ancestors: AVec<AncestorRecord>) {
                 match &self {
                     $(
+                        $(#[$meta])*
                         Self::$variant(x) => x.write().set_ancestors(ancestors),
                     )+
                 }
@@ -63,6 +74,7 @@ macro_rules! register_ast_nodes {
             pub fn get_ancestors(&self) -> AOption<AVec<AncestorRecord>> {
                 match &self {
                     $(
+                        $(#[$meta])*
                         Self::$variant(x) => x.read().get_ancestors(),
                     )+
                 }
@@ -75,6 +87,7 @@ macro_rules! register_ast_nodes {
                 while let Some(elem) = current {
                     let direct_descendants = match &elem {
                         $(
+                            $(#[$meta])*
                             Self::$variant(x) => {
                             let read = x.read();
                             read.get_direct_descendants()
@@ -92,14 +105,34 @@ macro_rules! register_ast_nodes {
             pub fn name(&self) -> String {
                 match &self {
                     $(
+                        $(#[$meta])*
                         Self::$variant(..) => stringify!($variant),
                     )+
                 }
                 .to_string()
             }
+            pub fn get_direct_descendants(&self) -> AVec<$name> {
+                match &self {
+                    $(
+                        $(#[$meta])*
+                        Self::$variant(x) => x.read().get_direct_descendants(),
+                    )+
+                }
+            }
+            /// Applies `f` to each direct descendant, returning a new node
+            /// (descendants that are not rewritten are shared).
+            pub fn rewrite_children(&self, f: &mut dyn FnMut(&$name) -> $name) -> Self {
+                match &self {
+                    $(
+                        $(#[$meta])*
+                        Self::$variant(x) => Self::$variant(RArc::new(RRwLock::new(x.read().rewrite_children(f)))),
+                    )+
+                }
+            }
             pub fn optimize_fields(&self) {
                 match &self {
                     $(
+                        $(#[$meta])*
                         Self::$variant(rw) => rw.write().optimize_fields(),
                     )+
                 }
@@ -112,6 +145,7 @@ macro_rules! register_ast_nodes {
             ) -> PyResult<&'a PyAny> {
                 match &self {
                     $(
+                        $(#[$meta])*
                         Self::$variant(x) => x.read().to_python_ast_node(
                             py,
                             ast_module,
@@ -123,9 +157,10 @@ macro_rules! register_ast_nodes {
             pub fn to_r_ast_node(
                 &self,
                 depth: usize,
-            ) -> Robj {
+            ) -> PyResult<Robj> {
                 match &self {
                     $(
+                        $(#[$meta])*
                         Self::$variant(x) => x.read().to_r_ast_node(
                             depth,
                         ),
@@ -137,6 +172,7 @@ macro_rules! register_ast_nodes {
             fn hash<H: Hasher>(&self, state: &mut H) {
                 match &self {
                     $(
+                        $(#[$meta])*
                         Self::$variant(x) => x.read().hash(state),
                     )+
                 }
@@ -271,7 +307,7 @@ macro_rules! define_ast_node {
             ) -> PyResult<&'a PyAny> {
                 ($py_ast_closure)(self, py, ast_module, depth)
             }
-            pub fn to_r_ast_node(&self, depth: usize) -> Robj {
+            pub fn to_r_ast_node(&self, depth: usize) -> PyResult<Robj> {
                 ($r_ast_closure)(self, depth)
             }
             pub fn new($(
@@ -303,6 +339,12 @@ macro_rules! define_ast_node {
             pub fn get_direct_descendants(&self) -> AVec<AST> {
                 $descendants(self)
             }
+            pub fn rewrite_children(&self, f: &mut dyn FnMut(&AST) -> AST) -> Self {
+                Self {
+                    $($field: self.$field.rewrite(f),)*
+                    ancestors: self.ancestors.clone(),
+                }
+            }
         }
     };
 }
@@ -357,6 +399,11 @@ macro_rules! define_attribute {
       $sqlite_type:ident,
       $postgres_type:ident,
       $bigquery_type:ident,
+      $duckdb_type:ident,
+      $spark_type:ident,
+      $parquet_type:ident,
+      $avro_type:ident,
+      $arrow_type:ident,
       $value:ident,
       $key:expr,
       $pyo3_type: ty
@@ -377,6 +424,11 @@ macro_rules! define_attribute {
                 $sqlite_type,
                 $postgres_type,
                 $bigquery_type,
+                $duckdb_type,
+                $spark_type,
+                $parquet_type,
+                $avro_type,
+                $arrow_type,
                 abi_stable::StableAbi
             )]
             #[cfg_attr(feature = "sql", derive($sql_type))]
@@ -593,6 +645,36 @@ macro_rules! define_constraint {
                     ))
                 }
                 #[staticmethod]
+                pub fn register_duckdb_program(
+                    code: &str,
+                    entrypoint: &str,
+                    arg_functions: Vec<(Vec<&str>, &str)>,
+                    kwarg_functions: HashMap<&str, (Vec<&str>, &str)>,
+                ) -> PyResult<[<$element Program>]> {
+                    Ok([<$element Program>]::new(
+                        code,
+                        entrypoint,
+                        arg_functions,
+                        kwarg_functions,
+                        Dialect::DuckDB(aorist_core::DuckDB::new()),
+                    ))
+                }
+                #[staticmethod]
+                pub fn register_spark_program(
+                    code: &str,
+                    entrypoint: &str,
+                    arg_functions: Vec<(Vec<&str>, &str)>,
+                    kwarg_functions: HashMap<&str, (Vec<&str>, &str)>,
+                ) -> PyResult<[<$element Program>]> {
+                    Ok([<$element Program>]::new(
+                        code,
+                        entrypoint,
+                        arg_functions,
+                        kwarg_functions,
+                        Dialect::Spark(aorist_core::Spark::new()),
+                    ))
+                }
+                #[staticmethod]
                 pub fn register_bash_program(
                     code: &str,
                     entrypoint: &str,
@@ -851,6 +933,55 @@ macro_rules! register_attribute_new {
                     )+
                 }
             }
+            pub fn get_duckdb_type(&self) -> AString {
+                match self {
+                    $(
+                        [<$name Enum>]::$element(x) => x.get_duckdb_type(),
+                    )+
+                }
+            }
+            pub fn get_spark_type(&self) -> AString {
+                match self {
+                    $(
+                        [<$name Enum>]::$element(x) => x.get_spark_type(),
+                    )+
+                }
+            }
+            pub fn get_parquet_physical_type(&self) -> AString {
+                match self {
+                    $(
+                        [<$name Enum>]::$element(x) => x.get_parquet_physical_type(),
+                    )+
+                }
+            }
+            pub fn get_parquet_logical_type(&self) -> AOption<AString> {
+                match self {
+                    $(
+                        [<$name Enum>]::$element(x) => x.get_parquet_logical_type(),
+                    )+
+                }
+            }
+            pub fn get_parquet_schema(&self) -> AString {
+                match self {
+                    $(
+                        [<$name Enum>]::$element(x) => x.get_parquet_schema(),
+                    )+
+                }
+            }
+            pub fn get_avro_type(&self) -> AString {
+                match self {
+                    $(
+                        [<$name Enum>]::$element(x) => x.get_avro_type(),
+                    )+
+                }
+            }
+            pub fn get_arrow_type(&self) -> AString {
+                match self {
+                    $(
+                        [<$name Enum>]::$element(x) => x.get_arrow_type(),
+                    )+
+                }
+            }
             pub fn get_postgres_type(&self) -> AString {
                 match self {
                     $(
@@ -928,6 +1059,27 @@ macro_rules! register_attribute_new {
             pub fn get_sqlite_type(&self) -> AString {
                 self.inner.get_sqlite_type()
             }
+            pub fn get_duckdb_type(&self) -> AString {
+                self.inner.get_duckdb_type()
+            }
+            pub fn get_spark_type(&self) -> AString {
+                self.inner.get_spark_type()
+            }
+            pub fn get_parquet_physical_type(&self) -> AString {
+                self.inner.get_parquet_physical_type()
+            }
+            pub fn get_parquet_logical_type(&self) -> AOption<AString> {
+                self.inner.get_parquet_logical_type()
+            }
+            pub fn get_parquet_schema(&self) -> AString {
+                self.inner.get_parquet_schema()
+            }
+            pub fn get_avro_type(&self) -> AString {
+                self.inner.get_avro_type()
+            }
+            pub fn get_arrow_type(&self) -> AString {
+                self.inner.get_arrow_type()
+            }
             pub fn get_postgres_type(&self) -> AString {
                 self.inner.get_postgres_type()
             }
@@ -988,6 +1140,37 @@ macro_rules! register_attribute_new {
                 Ok(self.inner.0.read().get_sqlite_type().as_str().into())
             }
             #[getter]
+            pub fn duckdb_type(&self) -> pyo3::prelude::PyResult<String> {
+                Ok(self.inner.0.read().get_duckdb_type().as_str().into())
+            }
+            #[getter]
+            pub fn spark_type(&self) -> pyo3::prelude::PyResult<String> {
+                Ok(self.inner.0.read().get_spark_type().as_str().into())
+            }
+            #[getter]
+            pub fn parquet_physical_type(&self) -> pyo3::prelude::PyResult<String> {
+                Ok(self.inner.0.read().get_parquet_physical_type().as_str().into())
+            }
+            #[getter]
+            pub fn parquet_logical_type(&self) -> pyo3::prelude::PyResult<Option<String>> {
+                Ok(match self.inner.0.read().get_parquet_logical_type() {
+                    AOption(ROption::RSome(x)) => Some(x.as_str().into()),
+                    AOption(ROption::RNone) => None,
+                })
+            }
+            #[getter]
+            pub fn parquet_schema(&self) -> pyo3::prelude::PyResult<String> {
+                Ok(self.inner.0.read().get_parquet_schema().as_str().into())
+            }
+            #[getter]
+            pub fn avro_type(&self) -> pyo3::prelude::PyResult<String> {
+                Ok(self.inner.0.read().get_avro_type().as_str().into())
+            }
+            #[getter]
+            pub fn arrow_type(&self) -> pyo3::prelude::PyResult<String> {
+                Ok(self.inner.0.read().get_arrow_type().as_str().into())
+            }
+            #[getter]
             pub fn is_nullable(&self) -> pyo3::prelude::PyResult<bool> {
                 Ok(self.inner.0.read().is_nullable().clone())
             }
@@ -1393,6 +1576,15 @@ macro_rules! register_constraint_new {
                         }
                     };
 
+                    // statements built by SQL recipes are rendered for the
+                    // recipe's engine
+                    let extracted = match extracted.render_sql(&self.inner.get_dialect()) {
+                        Ok(x) => x,
+                        Err(err) => {
+                            err.print(py);
+                            panic!("Problem when rendering SQL for key {}", key);
+                        }
+                    };
                     if key.as_str().as_bytes()[0] != '_' as u8 {
                         kwargs.insert(key.clone(), extracted);
                     }
@@ -1668,7 +1860,9 @@ macro_rules! define_dag_function {
             Dialect::R(R::new()),
             Dialect::Python(aorist_core::Python::new(vec![])), 
             Dialect::Bash(Bash::new()), 
-            Dialect::Presto(Presto::new())
+            Dialect::Presto(Presto::new()),
+            Dialect::DuckDB(DuckDB::new()),
+            Dialect::Spark(Spark::new())
         ]")]
         pub fn $name<'a>(
             mut universe: PyConcept,
@@ -1678,12 +1872,59 @@ macro_rules! define_dag_function {
             programs: BTreeMap<String, Vec<AoristConstraintProgram>>,
             dialect_preferences: Vec<Dialect>,
             dag_name: Option<String>,
+            params_dir: Option<String>,
+            params_format: Option<String>,
+            dynamic_mapping: Option<bool>,
+            execution_policies: Option<BTreeMap<String, PyExecutionPolicy>>,
+            dependencies_dir: Option<String>,
         ) -> PyResult<String> {
             // TODO: must call compute_uuids before 
             //universe.compute_uuids();
             let endpoints = endpoints_py.inner.0.read().clone();
+            let params_files = match params_dir {
+                Some(dir) => AOption(ROption::RSome(ParamsFiles::new(
+                    dir.as_str().into(),
+                    ParamsFileFormat::from_name(params_format.as_deref().unwrap_or("json"))
+                        .map_err(|e| pyo3::exceptions::PyException::new_err(e.to_string()))?,
+                ).map_err(|e| pyo3::exceptions::PyException::new_err(e.to_string()))?)),
+                None => AOption(ROption::RNone),
+            };
+            // only the assets of the requested constraints (and of the
+            // constraints they require) are scheduled
+            let root_types = <AoristConstraintBuilder as TBuilder>::get_relevant_builders(
+                &constraints.iter().map(|x| x.as_str().into()).collect(),
+            )
+            .into_iter()
+            .map(|x| x.get_root_type_name())
+            .collect::<anyhow::Result<std::collections::HashSet<aorist_util::AString>>>()
+            .map_err(|e| pyo3::exceptions::PyException::new_err(e.to_string()))?;
+            // fails if the assets' granularities are incompatible
+            let schedule = get_flow_schedule(&universe.inner, &root_types)
+                .map_err(|e| pyo3::exceptions::PyException::new_err(e.to_string()))?;
+            // policies attached to concepts, overridden by constraint name
+            let execution_policies = ExecutionPolicies::new(
+                get_execution_policies(&universe.inner),
+                execution_policies
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|(k, v)| (k.as_str().into(), v.inner.0.read().get_task_policy()))
+                    .collect(),
+            );
+            // only used when mode is "validate"
+            let validation_checks = ValidationChecks::new(
+                endpoints.get_validation_checks(),
+                get_validation_checks(&universe.inner),
+            );
+            let flow_name = dag_name.clone().unwrap_or("flow".to_string());
+            // for the flow's container image
+            let system_packages = endpoints
+                .get_system_packages()
+                .into_iter()
+                .chain(get_system_packages(&universe.inner).into_iter())
+                .collect::<Vec<_>>();
+            let avro_schemas = get_avro_schemas(&universe.inner);
             let programs_map = programs.into_iter().map(|(k, v)| (k.as_str().into(), v.into_iter().collect())).collect();
-            let (output, _requirements) = match mode {
+            let (output, dependencies) = match mode {
                 "airflow" => PythonBasedDriver::<
                     AoristConstraintBuilder,
                     AirflowFlowBuilder<AoristRef<Universe>>,
@@ -1698,6 +1939,10 @@ macro_rules! define_dag_function {
                     programs_map,
                     dialect_preferences.into_iter().collect(),
                     true,
+                    params_files.clone(),
+                    dynamic_mapping.unwrap_or(false),
+                    schedule.clone(),
+                    execution_policies.clone(),
                 )
                 .map_err(|e| pyo3::exceptions::PyException::new_err(e.to_string()))?
                 .run(match dag_name {
@@ -1718,6 +1963,10 @@ macro_rules! define_dag_function {
                     programs_map,
                     dialect_preferences.into_iter().collect(),
                     true,
+                    params_files.clone(),
+                    dynamic_mapping.unwrap_or(false),
+                    schedule.clone(),
+                    execution_policies.clone(),
                 )
                 .map_err(|e| pyo3::exceptions::PyException::new_err(e.to_string()))?
                 .run(match dag_name {
@@ -1738,6 +1987,10 @@ macro_rules! define_dag_function {
                     programs_map,
                     dialect_preferences.into_iter().collect(),
                     false,
+                    params_files.clone(),
+                    dynamic_mapping.unwrap_or(false),
+                    schedule.clone(),
+                    execution_policies.clone(),
                 )
                 .map_err(|e| pyo3::exceptions::PyException::new_err(e.to_string()))?
                 .run(match dag_name {
@@ -1758,18 +2011,139 @@ macro_rules! define_dag_function {
                     programs_map,
                     dialect_preferences.into_iter().collect(),
                     false,
+                    params_files.clone(),
+                    dynamic_mapping.unwrap_or(false),
+                    schedule.clone(),
+                    execution_policies.clone(),
+                )
+                .map_err(|e| pyo3::exceptions::PyException::new_err(e.to_string()))?
+                .run(match dag_name {
+                    Some(x) => AOption(ROption::RSome(x.as_str().into())),
+                    None => AOption(ROption::RNone),
+                }),
+                "quarto" => PythonBasedDriver::<
+                    AoristConstraintBuilder,
+                    QuartoFlowBuilder<AoristRef<Universe>>,
+                    AoristRef<Universe>,
+                    AoristRef<Concept>,
+                    ConceptAncestry,
+                    AoristConstraintProgram,
+                >::new(
+                    universe.inner.clone(),
+                    endpoints,
+                    constraints.into_iter().map(|x| x.as_str().into()).collect(),
+                    programs_map,
+                    dialect_preferences.into_iter().collect(),
+                    false,
+                    params_files.clone(),
+                    dynamic_mapping.unwrap_or(false),
+                    schedule.clone(),
+                    execution_policies.clone(),
+                )
+                .map_err(|e| pyo3::exceptions::PyException::new_err(e.to_string()))?
+                .run(match dag_name {
+                    Some(x) => AOption(ROption::RSome(x.as_str().into())),
+                    None => AOption(ROption::RNone),
+                }),
+                "rmarkdown" => PythonBasedDriver::<
+                    AoristConstraintBuilder,
+                    RMarkdownFlowBuilder<AoristRef<Universe>>,
+                    AoristRef<Universe>,
+                    AoristRef<Concept>,
+                    ConceptAncestry,
+                    AoristConstraintProgram,
+                >::new(
+                    universe.inner.clone(),
+                    endpoints,
+                    constraints.into_iter().map(|x| x.as_str().into()).collect(),
+                    programs_map,
+                    dialect_preferences.into_iter().collect(),
+                    false,
+                    params_files.clone(),
+                    dynamic_mapping.unwrap_or(false),
+                    schedule.clone(),
+                    execution_policies.clone(),
+                )
+                .map_err(|e| pyo3::exceptions::PyException::new_err(e.to_string()))?
+                .run(match dag_name {
+                    Some(x) => AOption(ROption::RSome(x.as_str().into())),
+                    None => AOption(ROption::RNone),
+                }),
+                "bash" => PythonBasedDriver::<
+                    AoristConstraintBuilder,
+                    BashFlowBuilder<AoristRef<Universe>>,
+                    AoristRef<Universe>,
+                    AoristRef<Concept>,
+                    ConceptAncestry,
+                    AoristConstraintProgram,
+                >::new(
+                    universe.inner.clone(),
+                    endpoints,
+                    constraints.into_iter().map(|x| x.as_str().into()).collect(),
+                    programs_map,
+                    dialect_preferences.into_iter().collect(),
+                    false,
+                    params_files.clone(),
+                    dynamic_mapping.unwrap_or(false),
+                    schedule.clone(),
+                    execution_policies.clone(),
                 )
                 .map_err(|e| pyo3::exceptions::PyException::new_err(e.to_string()))?
                 .run(match dag_name {
                     Some(x) => AOption(ROption::RSome(x.as_str().into())),
                     None => AOption(ROption::RNone),
                 }),
+                "validate" => PythonBasedDriver::<
+                    AoristConstraintBuilder,
+                    PythonFlowBuilder<AoristRef<Universe>>,
+                    AoristRef<Universe>,
+                    AoristRef<Concept>,
+                    ConceptAncestry,
+                    AoristConstraintProgram,
+                >::new(
+                    universe.inner.clone(),
+                    endpoints,
+                    constraints.into_iter().map(|x| x.as_str().into()).collect(),
+                    programs_map,
+                    dialect_preferences.into_iter().collect(),
+                    false,
+                    params_files.clone(),
+                    dynamic_mapping.unwrap_or(false),
+                    schedule.clone(),
+                    execution_policies.clone(),
+                )
+                .map_err(|e| pyo3::exceptions::PyException::new_err(e.to_string()))?
+                .validate(&validation_checks, match dag_name {
+                    Some(x) => AOption(ROption::RSome(x.as_str().into())),
+                    None => AOption(ROption::RNone),
+                }),
                 /*"r" => RBasedDriver::<ConstraintBuilder, RBasedFlowBuilder>::new(&universe, constraints.into_iter().collect())
                 .map_err(|e| pyo3::exceptions::PyException::new_err(e.to_string()))?
                 .run(dag_name),*/
                 _ => panic!("Unknown mode provided: {}", mode),
             }
             .map_err(|e| pyo3::exceptions::PyException::new_err(e.to_string()))?;
+            // requirements.txt, environment.yml, DESCRIPTION and Dockerfile for the flow's image,
+            // and schemas of Avro-encoded assets
+            if let Some(dir) = dependencies_dir {
+                let mut dependencies = dependencies;
+                for package in system_packages {
+                    dependencies.add_system_package(package);
+                }
+                dependencies
+                    .write(&dir, &flow_name)
+                    .map_err(|e| pyo3::exceptions::PyException::new_err(e.to_string()))?;
+                ContainerImage::new(dependencies)
+                    .write(&dir, &flow_name)
+                    .map_err(|e| pyo3::exceptions::PyException::new_err(e.to_string()))?;
+                for (name, schema) in avro_schemas {
+                    std::fs::write(
+                        std::path::Path::new(&dir).join(format!("{}.avsc", name)),
+                        schema.as_str(),
+                    )
+                    .map_err(|e| pyo3::exceptions::PyException::new_err(e.to_string()))?;
+                }
+            }
             Ok(output.as_str().to_string().replace("\\\\", "\\").as_str().into())
         }
     }
@@ -1817,6 +2191,7 @@ macro_rules! export_aorist_python_module {
             concept_module(py, m)?;
             endpoints_module(py, m)?;
             dialects_module(py, m)?;
+            sql_module(py, m)?;
             m.add_wrapped(wrap_pyfunction!($dag_function))?;
             //m.add_wrapped(wrap_pyfunction!(test))?;
             Ok(())
@@ -1857,6 +2232,8 @@ macro_rules! asset {
             pub schema: AoristRef<DataSchema>,
             #[constrainable]
             pub setup: AoristRef<StorageSetup>,
+            #[constrainable]
+            pub execution_policy: AOption<AoristRef<ExecutionPolicy>>,
         }
         impl TAsset for $name {
             fn get_name(&self) -> AString {
@@ -1891,6 +2268,7 @@ macro_rules! asset {
                                 .replicate_to_local(t, tmp_dir, tmp_encoding),
                         ))),
                         schema: self.schema.clone(),
+                        execution_policy: self.execution_policy.clone(),
                         tag: self.tag.clone(),
                         uuid: AOption(ROption::RNone),
                     });
@@ -0,0 +1,369 @@
+use abi_stable::std_types::ROption;
+use anyhow::{bail, Result};
+use aorist_util::{AOption, AString, AVec};
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ScheduleFrequency {
+    Hourly,
+    Daily,
+    Weekly,
+    Monthly,
+    Cron(AString),
+}
+impl ScheduleFrequency {
+    pub fn get_cron_expression(&self) -> AString {
+        match self {
+            Self::Hourly => "0 * * * *".into(),
+            Self::Daily => "0 0 * * *".into(),
+            Self::Weekly => "0 0 * * 0".into(),
+            Self::Monthly => "0 0 1 * *".into(),
+            Self::Cron(ref expression) => expression.clone(),
+        }
+    }
+    /// Airflow's preset name for the frequency, or the cron expression.
+    pub fn get_airflow_schedule_interval(&self) -> AString {
+        match self {
+            Self::Hourly => "@hourly".into(),
+            Self::Daily => "@daily".into(),
+            Self::Weekly => "@weekly".into(),
+            Self::Monthly => "@monthly".into(),
+            Self::Cron(ref expression) => expression.clone(),
+        }
+    }
+    /// strftime format of the partition a run is responsible for.
+    pub fn get_partition_format(&self) -> AString {
+        match self {
+            Self::Hourly | Self::Cron(_) => "%Y-%m-%dT%H".into(),
+            Self::Daily | Self::Weekly | Self::Monthly => "%Y-%m-%d".into(),
+        }
+    }
+    /// Hive partition columns of tables written at this frequency, each
+    /// with the (start, length) slice of the run's partition date (formatted
+    /// with `get_partition_format`) holding its value.
+    pub fn get_partition_slices(&self) -> AVec<(AString, usize, usize)> {
+        match self {
+            Self::Hourly | Self::Cron(_) => vec![("dt".into(), 0, 10), ("hour".into(), 11, 2)],
+            Self::Daily | Self::Weekly | Self::Monthly => vec![("dt".into(), 0, 10)],
+        }
+        .into_iter()
+        .collect()
+    }
+    /// The partition columns, each with the SQL expression deriving its
+    /// value from the run's `{partition_date}`.
+    pub fn get_partition_columns(&self) -> AVec<(AString, AString)> {
+        self.get_partition_slices()
+            .into_iter()
+            .map(|(name, start, length)| {
+                let value = match self {
+                    Self::Daily | Self::Weekly | Self::Monthly => "'{partition_date}'".into(),
+                    _ => format!("substr('{{partition_date}}', {}, {})", start + 1, length),
+                };
+                (name, value.as_str().into())
+            })
+            .collect()
+    }
+}
+
+/// When a flow runs: derived from the granularity of the dynamic layouts
+/// of the assets it processes. Dates are ISO 8601 (`YYYY-MM-DD`); a flow
+/// with a start date is backfilled from that date onwards.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Schedule {
+    frequency: ScheduleFrequency,
+    start_date: AOption<AString>,
+    end_date: AOption<AString>,
+}
+impl Schedule {
+    pub fn new(
+        frequency: ScheduleFrequency,
+        start_date: AOption<AString>,
+        end_date: AOption<AString>,
+    ) -> Result<Self> {
+        for date in [&start_date, &end_date] {
+            if let AOption(ROption::RSome(ref x)) = date {
+                Self::parse_date(x)?;
+            }
+        }
+        Ok(Self {
+            frequency,
+            start_date,
+            end_date,
+        })
+    }
+    pub fn get_frequency(&self) -> &ScheduleFrequency {
+        &self.frequency
+    }
+    /// (year, month, day) of the first partition to backfill.
+    pub fn get_start_date(&self) -> AOption<(i64, i64, i64)> {
+        AOption(
+            self.start_date
+                .0
+                .as_ref()
+                .map(|x| Self::parse_date(x).unwrap()),
+        )
+    }
+    /// (year, month, day) of the last partition to backfill.
+    pub fn get_end_date(&self) -> AOption<(i64, i64, i64)> {
+        AOption(
+            self.end_date
+                .0
+                .as_ref()
+                .map(|x| Self::parse_date(x).unwrap()),
+        )
+    }
+    /// (year, month, day) of an ISO 8601 date.
+    pub fn parse_date(date: &AString) -> Result<(i64, i64, i64)> {
+        let parts = date
+            .as_str()
+            .get(..10)
+            .unwrap_or("")
+            .split('-')
+            .map(|x| x.parse::<i64>())
+            .collect::<Vec<_>>();
+        match parts.as_slice() {
+            [Ok(y), Ok(m), Ok(d)]
+                if (1..=12).contains(m) && (1..=Self::get_days_in_month(*y, *m)).contains(d) =>
+            {
+                Ok((*y, *m, *d))
+            }
+            _ => bail!("Invalid date {}, expected YYYY-MM-DD", date),
+        }
+    }
+    fn get_days_in_month(year: i64, month: i64) -> i64 {
+        match month {
+            2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+            2 => 28,
+            4 | 6 | 9 | 11 => 30,
+            _ => 31,
+        }
+    }
+    // days since 1970-01-01, for (proleptic Gregorian) civil dates
+    fn days_from_civil((y, m, d): (i64, i64, i64)) -> i64 {
+        let y = if m <= 2 { y - 1 } else { y };
+        let era = (if y >= 0 { y } else { y - 399 }) / 400;
+        let yoe = y - era * 400;
+        let doy = (153 * (if m > 2 { m - 3 } else { m + 9 }) + 2) / 5 + d - 1;
+        let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+        era * 146097 + doe - 719468
+    }
+    fn civil_from_days(days: i64) -> (i64, i64, i64) {
+        let z = days + 719468;
+        let era = (if z >= 0 { z } else { z - 146096 }) / 146097;
+        let doe = z - era * 146097;
+        let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let d = doy - (153 * mp + 2) / 5 + 1;
+        let m = if mp < 10 { mp + 3 } else { mp - 9 };
+        let y = yoe + era * 400;
+        (if m <= 2 { y + 1 } else { y }, m, d)
+    }
+    /// (year, month, day) of the day before `date`.
+    pub fn get_day_before(date: (i64, i64, i64)) -> (i64, i64, i64) {
+        Self::civil_from_days(Self::days_from_civil(date) - 1)
+    }
+    /// Upper bound on the number of runs needed to backfill the schedule,
+    /// if it covers a closed date range (one run per minute at most).
+    pub fn get_backfill_limit(&self) -> AOption<i64> {
+        match (self.get_start_date(), self.get_end_date()) {
+            (AOption(ROption::RSome(start)), AOption(ROption::RSome(end))) => {
+                let days = Self::days_from_civil(end) - Self::days_from_civil(start);
+                AOption(ROption::RSome((days.max(0) + 1) * 24 * 60))
+            }
+            _ => AOption(ROption::RNone),
+        }
+    }
+    /// The single schedule shared by all assets in a flow. Backfills cover
+    /// the union of the assets' date ranges.
+    pub fn merge(schedules: AVec<Schedule>) -> Result<AOption<Schedule>> {
+        let mut iter = schedules.into_iter();
+        let mut merged = match iter.next() {
+            Some(x) => x,
+            None => return Ok(AOption(ROption::RNone)),
+        };
+        for schedule in iter {
+            if schedule.frequency != merged.frequency {
+                bail!(
+                    "Assets in the same flow have incompatible granularities: {:?} and {:?}",
+                    merged.frequency,
+                    schedule.frequency
+                );
+            }
+            merged.start_date = match (merged.start_date, schedule.start_date) {
+                (AOption(ROption::RSome(a)), AOption(ROption::RSome(b))) => AOption(
+                    ROption::RSome(if Self::parse_date(&b)? < Self::parse_date(&a)? {
+                        b
+                    } else {
+                        a
+                    }),
+                ),
+                (a, AOption(ROption::RNone)) => a,
+                (AOption(ROption::RNone), b) => b,
+            };
+            // an open-ended asset keeps the whole flow running
+            merged.end_date = match (merged.end_date, schedule.end_date) {
+                (AOption(ROption::RSome(a)), AOption(ROption::RSome(b))) => AOption(
+                    ROption::RSome(if Self::parse_date(&b)? > Self::parse_date(&a)? {
+                        b
+                    } else {
+                        a
+                    }),
+                ),
+                _ => AOption(ROption::RNone),
+            };
+        }
+        Ok(AOption(ROption::RSome(merged)))
+    }
+}
+
+#[cfg(test)]
+mod schedule_tests {
+    use crate::schedule::*;
+
+    fn date(x: &str) -> AOption<AString> {
+        AOption(ROption::RSome(x.into()))
+    }
+    fn daily(start_date: AOption<AString>, end_date: AOption<AString>) -> Schedule {
+        Schedule::new(ScheduleFrequency::Daily, start_date, end_date).unwrap()
+    }
+
+    #[test]
+    fn test_parse_date() {
+        assert_eq!(
+            Schedule::parse_date(&"2021-03-04".into()).unwrap(),
+            (2021, 3, 4)
+        );
+        assert_eq!(
+            Schedule::parse_date(&"2020-02-29".into()).unwrap(),
+            (2020, 2, 29)
+        );
+        assert_eq!(
+            Schedule::parse_date(&"2000-02-29".into()).unwrap(),
+            (2000, 2, 29)
+        );
+        for invalid in &[
+            "2021-02-29",
+            "1900-02-29",
+            "2021-02-31",
+            "2021-04-31",
+            "2021-13-01",
+            "2021-00-10",
+            "2021-1-1",
+            "yesterday",
+        ] {
+            assert!(
+                Schedule::parse_date(&(*invalid).into()).is_err(),
+                "{} should not parse",
+                invalid
+            );
+        }
+        assert!(Schedule::new(
+            ScheduleFrequency::Daily,
+            date("2021-02-31"),
+            date("2021-03-01")
+        )
+        .is_err());
+    }
+    #[test]
+    fn test_get_day_before() {
+        assert_eq!(Schedule::get_day_before((2021, 3, 1)), (2021, 2, 28));
+        assert_eq!(Schedule::get_day_before((2020, 3, 1)), (2020, 2, 29));
+        assert_eq!(Schedule::get_day_before((2021, 1, 1)), (2020, 12, 31));
+        assert_eq!(Schedule::get_day_before((2021, 6, 15)), (2021, 6, 14));
+    }
+    #[test]
+    fn test_get_backfill_limit() {
+        assert_eq!(
+            daily(date("2021-01-01"), date("2021-01-02")).get_backfill_limit(),
+            AOption(ROption::RSome(2 * 24 * 60))
+        );
+        assert_eq!(
+            daily(date("2021-01-01"), AOption(ROption::RNone)).get_backfill_limit(),
+            AOption(ROption::RNone)
+        );
+    }
+    #[test]
+    fn test_merge() {
+        let merged = Schedule::merge(
+            vec![
+                daily(date("2021-02-01"), date("2021-03-01")),
+                daily(date("2021-01-15"), date("2021-02-15")),
+            ]
+            .into_iter()
+            .collect(),
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(
+            merged.get_start_date(),
+            AOption(ROption::RSome((2021, 1, 15)))
+        );
+        assert_eq!(merged.get_end_date(), AOption(ROption::RSome((2021, 3, 1))));
+
+        // an open-ended asset keeps the whole flow running
+        let merged = Schedule::merge(
+            vec![
+                daily(date("2021-02-01"), date("2021-03-01")),
+                daily(AOption(ROption::RNone), AOption(ROption::RNone)),
+            ]
+            .into_iter()
+            .collect(),
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(
+            merged.get_start_date(),
+            AOption(ROption::RSome((2021, 2, 1)))
+        );
+        assert_eq!(merged.get_end_date(), AOption(ROption::RNone));
+
+        assert!(Schedule::merge(AVec::new()).unwrap().is_none());
+        assert!(Schedule::merge(
+            vec![
+                daily(AOption(ROption::RNone), AOption(ROption::RNone)),
+                Schedule::new(
+                    ScheduleFrequency::Hourly,
+                    AOption(ROption::RNone),
+                    AOption(ROption::RNone)
+                )
+                .unwrap(),
+            ]
+            .into_iter()
+            .collect()
+        )
+        .is_err());
+    }
+    #[test]
+    fn test_frequency() {
+        assert_eq!(
+            ScheduleFrequency::Weekly.get_cron_expression().as_str(),
+            "0 0 * * 0"
+        );
+        assert_eq!(
+            ScheduleFrequency::Cron("*/5 * * * *".into())
+                .get_airflow_schedule_interval()
+                .as_str(),
+            "*/5 * * * *"
+        );
+        assert_eq!(
+            ScheduleFrequency::Hourly
+                .get_partition_columns()
+                .iter()
+                .map(|(name, _)| name.as_str().to_string())
+                .collect::<Vec<_>>(),
+            vec!["dt", "hour"]
+        );
+        assert_eq!(
+            ScheduleFrequency::Hourly.get_partition_columns()[1]
+                .1
+                .as_str(),
+            "substr('{partition_date}', 12, 2)"
+        );
+        assert_eq!(
+            ScheduleFrequency::Daily.get_partition_columns()[0]
+                .1
+                .as_str(),
+            "'{partition_date}'"
+        );
+    }
+}
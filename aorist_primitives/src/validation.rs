@@ -0,0 +1,49 @@
+use aorist_util::{AString, ATaskId, AVec};
+use std::collections::HashMap;
+
+/// A precondition a flow relies on, checked by flows built in `validate`
+/// mode instead of running the tasks themselves.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ValidationCheck {
+    /// An endpoint (name, host, port) accepts TCP connections.
+    EndpointReachable(AString, AString, usize),
+    /// A source URL answers a HEAD request.
+    UrlExists(AString),
+    /// A source blob (bucket, blob) exists on GCS.
+    GCSBlobExists(AString, AString),
+    /// A source object (bucket, key, requester pays) exists on S3.
+    S3ObjectExists(AString, AString, bool),
+    /// A target bucket exists on S3.
+    S3BucketExists(AString),
+    /// The directory a target database file (SQLite, DuckDB) is created in
+    /// exists.
+    DatabaseDirectoryExists(AString),
+    /// A pip requirement is installed and its modules are importable.
+    PackageInstalled(AString),
+}
+
+/// All checks a validation flow performs. Endpoint checks apply to the
+/// whole flow; the others are keyed by the concept they were derived from,
+/// and are reported against the tasks rooted at the nearest ancestor of
+/// that concept.
+pub struct ValidationChecks {
+    endpoints: AVec<ValidationCheck>,
+    by_concept: HashMap<ATaskId, AVec<ValidationCheck>>,
+}
+impl ValidationChecks {
+    pub fn new(
+        endpoints: AVec<ValidationCheck>,
+        by_concept: HashMap<ATaskId, AVec<ValidationCheck>>,
+    ) -> Self {
+        Self {
+            endpoints,
+            by_concept,
+        }
+    }
+    pub fn get_endpoint_checks(&self) -> &AVec<ValidationCheck> {
+        &self.endpoints
+    }
+    pub fn get_concept_checks(&self) -> &HashMap<ATaskId, AVec<ValidationCheck>> {
+        &self.by_concept
+    }
+}
@@ -1,8 +1,13 @@
 from . import download_data_from_remote_gcs_location
 from . import download_data_from_remote_web_location
+from . import download_data_from_remote_azure_blob_location
+from . import download_data_from_remote_sftp_location
+from . import decompress_downloaded_data
 from . import download_data_from_remote_pushshift_api_location_to_newline_delimited_json
+from . import download_data_from_remote_paginated_api_location_to_newline_delimited_json
 from . import extract_named_entities_using_spacy
 from . import convert_json_to_csv
+from . import convert_geotiff_to_zarr
 from . import text_corpus_data_from_hive
 from . import text_corpus_data_from_sqlite
 from . import train_fasttext_model
@@ -56,10 +61,15 @@ path = pathlib.Path(__file__).parent.resolve()
 programs = register_recipes(
     py_modules=[
         download_data_from_remote_pushshift_api_location_to_newline_delimited_json,
+        download_data_from_remote_paginated_api_location_to_newline_delimited_json,
         download_data_from_remote_gcs_location,
         download_data_from_remote_web_location,
+        download_data_from_remote_azure_blob_location,
+        download_data_from_remote_sftp_location,
+        decompress_downloaded_data,
         upload_data_to_minio,
         convert_json_to_csv,
+        convert_geotiff_to_zarr,
         text_corpus_data_from_hive,
         text_corpus_data_from_sqlite,
         train_fasttext_model,
@@ -72,9 +82,31 @@ programs = register_recipes(
         "%s/json_table_schemas_created.presto.sql" % path,
         "%s/convert_json_table_to_orc_table.presto.sql" % path,
         "%s/orc_table_schemas_created.presto.sql" % path,
+        "%s/parquet_table_schemas_created.presto.sql" % path,
+        "%s/csv_table_schemas_created.presto.sql" % path,
+        "%s/convert_json_table_to_parquet_table.presto.sql" % path,
+        "%s/convert_csv_table_to_parquet_table.presto.sql" % path,
+        "%s/delta_table_created.presto.sql" % path,
+        "%s/iceberg_table_created.presto.sql" % path,
+        "%s/upload_data_to_duckdb.duckdb.sql" % path,
+        "%s/convert_json_to_csv.duckdb.sql" % path,
+        "%s/convert_json_table_to_parquet_table.duckdb.sql" % path,
+        "%s/convert_csv_table_to_parquet_table.duckdb.sql" % path,
+        "%s/hive_directories_created.spark.sql" % path,
+        "%s/json_table_schemas_created.spark.sql" % path,
+        "%s/orc_table_schemas_created.spark.sql" % path,
+        "%s/parquet_table_schemas_created.spark.sql" % path,
+        "%s/delta_table_created.spark.sql" % path,
+        "%s/iceberg_table_created.spark.sql" % path,
+        "%s/write_to_delta_table.spark.sql" % path,
+        "%s/write_to_iceberg_table.spark.sql" % path,
     ],
     bash_modules=[
         "%s/download_data_from_remote_web_location.sh" % path,
+        "%s/download_data_from_remote_hdfs_location.sh" % path,
+        "%s/upload_data_to_hdfs.sh" % path,
+        "%s/decompress_downloaded_data.sh" % path,
+        "%s/convert_polygons_to_geo_parquet.sh" % path,
     ],
     r_modules=[
         "%s/download_data_from_remote_web_location.R" % path,
@@ -0,0 +1,96 @@
+/***
+@aorist_duckdb(
+    programs,
+    ConvertCSVTableToParquetTable,
+    args={
+        # DuckDB reads the locally replicated file, and writes Parquet straight
+        # into the table's directory under the schema location used by
+        # HiveDirectoriesCreated. It has no HDFS, Alluxio or Hive metastore
+        # client: partitions written here are only visible once the table's
+        # partition metadata is synced.
+        "queries": lambda data_set, asset, static_data_table, hive_table_storage, replication_storage_setup, universe, context: ([
+            "INSTALL httpfs",
+            "LOAD httpfs",
+        ] + ([
+            "SET s3_endpoint = '%s:%d'" % (
+                universe.endpoints.minio.server, universe.endpoints.minio.port,
+            ),
+            "SET s3_access_key_id = '%s'" % universe.endpoints.minio.access_key,
+            "SET s3_secret_access_key = '%s'" % universe.endpoints.minio.secret_key,
+            "SET s3_url_style = 'path'",
+            "SET s3_use_ssl = false",
+        ] if hive_table_storage.location.minio_location is not None else [
+            "CREATE OR REPLACE SECRET (TYPE S3, PROVIDER CREDENTIAL_CHAIN)",
+        ] if hive_table_storage.location.s3_location is not None else (
+            panic("Only MinIO or S3 locations supported.")
+        )) + [
+            "COPY (\nSELECT {columns}\nFROM {reader}\n) TO '{target}' ({options})".format(
+                columns=", ".join([
+                    x.name for x in data_set.get_template(asset).attributes()
+                ] + [
+                    "%s AS %s" % (value, name)
+                    for (name, value) in hive_table_storage.layout.partition_columns
+                ]),
+                reader=(lambda types: "read_csv('%s', %s)" % (
+                    context.get("file_to_replicate"),
+                    ", ".join([
+                        "delim='%s'" % context.get("delimiter").replace("'", "''"),
+                    ] + ([
+                        # the last header line names the columns, which may
+                        # come in any order
+                        "skip=%d, header=true" % (context.get_int("header_num_lines") - 1),
+                        "types=" + types,
+                    ] if (
+                        replication_storage_setup.source.encoding.header is not None
+                        and replication_storage_setup.source.encoding.header.map_column_names
+                        and context.get_int("header_num_lines") > 0
+                    ) else [
+                        "skip=%d, header=false" % context.get_int("header_num_lines"),
+                        "columns=" + types,
+                    ]) + ([
+                        "%s='%s'" % (key, value.replace("'", "''"))
+                        for (key, value) in [
+                            ("quote", replication_storage_setup.source.encoding.dialect.quote_char),
+                            ("escape", replication_storage_setup.source.encoding.dialect.escape_char),
+                            ("comment", replication_storage_setup.source.encoding.dialect.comment_prefix),
+                            ("encoding", replication_storage_setup.source.encoding.dialect.text_encoding),
+                        ]
+                        if value is not None
+                    ] + ([
+                        "nullstr=[%s]" % ", ".join([
+                            "'%s'" % x.replace("'", "''")
+                            for x in replication_storage_setup.source.encoding.dialect.null_tokens
+                        ])
+                    ] if len(replication_storage_setup.source.encoding.dialect.null_tokens) > 0 else [])
+                    if replication_storage_setup.source.encoding.dialect is not None else [])),
+                ))("{%s}" % ", ".join([
+                    "'%s': '%s'" % (x.name, x.duckdb_type)
+                    for x in data_set.get_template(asset).attributes()
+                ])),
+                target="s3://{bucket}/{dataset}/{table}{file}".format(
+                    bucket=(
+                        universe.endpoints.minio.bucket
+                        if hive_table_storage.location.minio_location is not None
+                        else universe.endpoints.s3.bucket
+                    ),
+                    dataset=data_set.name,
+                    table=static_data_table.name,
+                    file=(
+                        "" if len(hive_table_storage.layout.partition_columns) > 0
+                        else "/data.parquet"
+                    ),
+                ),
+                options=", ".join(["FORMAT PARQUET"] + ([
+                    "COMPRESSION '%s'" % hive_table_storage.encoding.compression_codec.lower()
+                ] if hive_table_storage.encoding.compression_codec is not None else []) + ([
+                    "PARTITION_BY (%s)" % ", ".join([
+                        name for (name, _) in hive_table_storage.layout.partition_columns
+                    ]),
+                    "OVERWRITE_OR_IGNORE",
+                ] if len(hive_table_storage.layout.partition_columns) > 0 else [])),
+            ),
+        ], context),
+    },
+)
+***/
+{queries}
@@ -0,0 +1,57 @@
+from aorist import aorist, ConvertGeoTiffToZarr
+
+programs = {}
+
+
+def zarr_target(raster_asset):
+    return [
+        t for t in raster_asset.setup.replication_storage_setup.targets
+        if t.encoding is not None and t.encoding.zarr_encoding is not None
+    ][0]
+
+
+@aorist(
+    programs,
+    ConvertGeoTiffToZarr,
+    entrypoint="convert_geotiff_to_zarr",
+    args={
+        "src_file_name": lambda data_set, raster_asset: (
+            "{tmp_dir}/{dataset}/{name}/{name}.{extension}"
+        ).format(
+            tmp_dir=raster_asset.setup.replication_storage_setup.tmp_dir,
+            dataset=data_set.name,
+            name=raster_asset.name,
+            extension=raster_asset.setup.replication_storage_setup.decompressed_extension,
+        ),
+        "dest_path": lambda raster_asset: (
+            "{path}/{name}.zarr"
+        ).format(
+            path=zarr_target(raster_asset).local_file_storage.location.local_file_system_location.path,
+            name=raster_asset.name,
+        ),
+        "chunks": lambda raster_asset: list(
+            zarr_target(raster_asset).encoding.zarr_encoding.chunks
+        ),
+        "compressor": lambda raster_asset: (
+            zarr_target(raster_asset).encoding.zarr_encoding.compressor
+        ),
+    },
+)
+def recipe(src_file_name, dest_path, chunks, compressor):
+    import os
+    import numcodecs
+    import rioxarray
+
+    def convert_geotiff_to_zarr(src_file_name, dest_path, chunks, compressor):
+        raster = rioxarray.open_rasterio(src_file_name)
+        if len(chunks) > 0:
+            raster = raster.chunk(dict(zip(raster.dims, chunks)))
+        encoding = {}
+        if compressor is not None:
+            encoding["band_data"] = {
+                "compressor": numcodecs.get_codec({"id": compressor}),
+            }
+        os.makedirs(os.path.dirname(dest_path), exist_ok=True)
+        raster.to_dataset(name="band_data").to_zarr(
+            dest_path, mode="w", encoding=encoding,
+        )
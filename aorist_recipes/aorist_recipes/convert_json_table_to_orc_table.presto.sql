@@ -3,15 +3,51 @@
     programs,
     ConvertJSONTableToORCTable,
     args={
-        "presto_schema": lambda data_set: data_set.name,
-        "source_table": lambda static_data_table: "tmp_" + static_data_table.name,
-        "table_name": lambda static_data_table: static_data_table.name,
-        "columns": lambda data_set, asset: ", ".join([
-            x.name for x in data_set.get_template(asset).attributes()
-        ]),
+        # dynamic tables only rewrite the partition of the current run, after
+        # registering the staging partitions uploaded since the last sync
+        "queries": lambda data_set, asset, static_data_table, hive_table_storage: (
+            [
+                "CALL system.sync_partition_metadata('{schema}', '{table}', 'ADD')".format(
+                    schema=data_set.name,
+                    table="tmp_" + static_data_table.name,
+                ),
+                "DELETE FROM {schema}.{table} WHERE {partition}".format(
+                    schema=data_set.name,
+                    table=static_data_table.name,
+                    partition=" AND ".join([
+                        "%s = %s" % (name, value)
+                        for (name, value) in hive_table_storage.layout.partition_columns
+                    ]),
+                ),
+            ] if len(hive_table_storage.layout.partition_columns) > 0 else []
+        ) + [
+            InsertInto(
+                "{schema}.{table}".format(
+                    schema=data_set.name,
+                    table=static_data_table.name,
+                ),
+                [],
+                Select(
+                    [
+                        ColumnRef(x.name)
+                        for x in data_set.get_template(asset).attributes()
+                    ] + [
+                        ColumnRef(name)
+                        for (name, _, _) in hive_table_storage.layout.partition_slices
+                    ],
+                    "tmp_" + static_data_table.name,
+                    selection=[
+                        BinaryOp(
+                            ColumnRef(name),
+                            "=",
+                            FunctionCall("substr", ["{partition_date}", start + 1, length]),
+                        )
+                        for (name, start, length) in hive_table_storage.layout.partition_slices
+                    ],
+                ),
+            ),
+        ],
     },
 )
 ***/
-INSERT INTO {presto_schema}.{table_name}
-SELECT {columns}
-FROM {source_table}
+{queries}
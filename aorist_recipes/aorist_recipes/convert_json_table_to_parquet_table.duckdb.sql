@@ -0,0 +1,67 @@
+/***
+@aorist_duckdb(
+    programs,
+    ConvertJSONTableToParquetTable,
+    args={
+        # DuckDB reads the locally replicated file, and writes Parquet straight
+        # into the table's directory under the schema location used by
+        # HiveDirectoriesCreated. It has no HDFS, Alluxio or Hive metastore
+        # client: partitions written here are only visible once the table's
+        # partition metadata is synced.
+        "queries": lambda data_set, asset, static_data_table, hive_table_storage, universe, context: ([
+            "INSTALL httpfs",
+            "LOAD httpfs",
+        ] + ([
+            "SET s3_endpoint = '%s:%d'" % (
+                universe.endpoints.minio.server, universe.endpoints.minio.port,
+            ),
+            "SET s3_access_key_id = '%s'" % universe.endpoints.minio.access_key,
+            "SET s3_secret_access_key = '%s'" % universe.endpoints.minio.secret_key,
+            "SET s3_url_style = 'path'",
+            "SET s3_use_ssl = false",
+        ] if hive_table_storage.location.minio_location is not None else [
+            "CREATE OR REPLACE SECRET (TYPE S3, PROVIDER CREDENTIAL_CHAIN)",
+        ] if hive_table_storage.location.s3_location is not None else (
+            panic("Only MinIO or S3 locations supported.")
+        )) + [
+            "COPY (\nSELECT {columns}\nFROM {reader}\n) TO '{target}' ({options})".format(
+                columns=", ".join([
+                    x.name for x in data_set.get_template(asset).attributes()
+                ] + [
+                    "%s AS %s" % (value, name)
+                    for (name, value) in hive_table_storage.layout.partition_columns
+                ]),
+                reader="read_json('%s', format='newline_delimited', %s)" % (
+                    context.get("file_to_replicate"),
+                    "columns={%s}" % ", ".join([
+                        "'%s': '%s'" % (x.name, x.duckdb_type)
+                        for x in data_set.get_template(asset).attributes()
+                    ]),
+                ),
+                target="s3://{bucket}/{dataset}/{table}{file}".format(
+                    bucket=(
+                        universe.endpoints.minio.bucket
+                        if hive_table_storage.location.minio_location is not None
+                        else universe.endpoints.s3.bucket
+                    ),
+                    dataset=data_set.name,
+                    table=static_data_table.name,
+                    file=(
+                        "" if len(hive_table_storage.layout.partition_columns) > 0
+                        else "/data.parquet"
+                    ),
+                ),
+                options=", ".join(["FORMAT PARQUET"] + ([
+                    "COMPRESSION '%s'" % hive_table_storage.encoding.compression_codec.lower()
+                ] if hive_table_storage.encoding.compression_codec is not None else []) + ([
+                    "PARTITION_BY (%s)" % ", ".join([
+                        name for (name, _) in hive_table_storage.layout.partition_columns
+                    ]),
+                    "OVERWRITE_OR_IGNORE",
+                ] if len(hive_table_storage.layout.partition_columns) > 0 else [])),
+            ),
+        ], context),
+    },
+)
+***/
+{queries}
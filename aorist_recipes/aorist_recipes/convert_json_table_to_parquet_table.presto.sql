@@ -0,0 +1,50 @@
+/***
+@aorist_presto(
+    programs,
+    ConvertJSONTableToParquetTable,
+    args={
+        "queries": lambda data_set, asset, static_data_table, hive_table_storage: (
+            [
+                "SET SESSION hive.compression_codec = '%s'" % (
+                    hive_table_storage.encoding.compression_codec.upper()
+                )
+            ] if hive_table_storage.encoding.compression_codec is not None else []
+        ) + (
+            # dynamic tables only rewrite the partition of the current run, after
+            # registering the staging partitions uploaded since the last sync
+            [
+                "CALL system.sync_partition_metadata('{schema}', '{table}', 'ADD')".format(
+                    schema=data_set.name,
+                    table="tmp_" + static_data_table.name,
+                ),
+                "DELETE FROM {schema}.{table} WHERE {partition}".format(
+                    schema=data_set.name,
+                    table=static_data_table.name,
+                    partition=" AND ".join([
+                        "%s = %s" % (name, value)
+                        for (name, value) in hive_table_storage.layout.partition_columns
+                    ]),
+                ),
+            ] if len(hive_table_storage.layout.partition_columns) > 0 else []
+        ) + [
+            "INSERT INTO {schema}.{table}\nSELECT {columns}\nFROM {source}{where}".format(
+                schema=data_set.name,
+                table=static_data_table.name,
+                source="tmp_" + static_data_table.name,
+                columns=", ".join([
+                    x.name for x in data_set.get_template(asset).attributes()
+                ] + [
+                    name for (name, _) in hive_table_storage.layout.partition_columns
+                ]),
+                where="".join([
+                    "\nWHERE " + " AND ".join([
+                        "%s = %s" % (name, value)
+                        for (name, value) in hive_table_storage.layout.partition_columns
+                    ])
+                ] if len(hive_table_storage.layout.partition_columns) > 0 else []),
+            ),
+        ],
+    },
+)
+***/
+{queries}
@@ -0,0 +1,28 @@
+/***
+@aorist_duckdb(
+    programs,
+    ConvertJSONToCSV,
+    args={
+        "src_file_name": lambda context: (context.get("json_file"), context),
+        "dest_file_name": lambda context: (
+            context.capture(
+                "csv_file", context.get("json_file").replace(".json", ".csv"),
+            ),
+            context
+        ),
+        # later steps read the CSV file instead
+        "_file_to_replicate": lambda context: (
+            context.capture(
+                "file_to_replicate", context.get("json_file").replace(".json", ".csv"),
+            ),
+            context
+        ),
+        "_is_json": lambda context: (context.capture_bool("is_json", False), context),
+        "_delimiter": lambda context: (context.capture("delimiter", ","), context),
+        "_header_num_lines": lambda context: (context.capture_int("header_num_lines", 0), context),
+    },
+)
+***/
+COPY (
+    SELECT * FROM read_json('{src_file_name}', format='newline_delimited')
+) TO '{dest_file_name}' (FORMAT CSV, HEADER false, DELIMITER ',')
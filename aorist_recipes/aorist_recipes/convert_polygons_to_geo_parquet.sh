@@ -0,0 +1,42 @@
+###+
+# @aorist_bash(
+#     programs,
+#     [ConvertPolygonCollectionToGeoParquet, ConvertPolygonIntersectionToGeoParquet],
+#     args={
+#         "gdal_path": lambda universe: universe.endpoints.gdal.gdal_path,
+#         "src_file": lambda data_set, asset: (
+#             "{tmp_dir}/{dataset}/{name}/{name}.{extension}"
+#         ).format(
+#             tmp_dir=asset.storage_setup.replication_storage_setup.tmp_dir,
+#             dataset=data_set.name,
+#             name=asset.name,
+#             extension=asset.storage_setup.replication_storage_setup.decompressed_extension,
+#         ),
+#         "dest_file": lambda asset: "{path}/{name}.parquet".format(
+#             path=[
+#                 t for t in asset.storage_setup.replication_storage_setup.targets
+#                 if t.encoding is not None and t.encoding.geo_parquet_encoding is not None
+#             ][0].local_file_storage.location.local_file_system_location.path,
+#             name=asset.name,
+#         ),
+#         "options": lambda asset: " ".join(
+#             ["-t_srs %s" % e.crs for e in [
+#                 t.encoding for t in asset.storage_setup.replication_storage_setup.targets
+#                 if t.encoding is not None and t.encoding.geo_parquet_encoding is not None
+#             ][:1] if e.crs is not None] + [
+#                 "-lco COMPRESSION=%s" % e.compression_codec for e in [
+#                     t.encoding for t in asset.storage_setup.replication_storage_setup.targets
+#                     if t.encoding is not None and t.encoding.geo_parquet_encoding is not None
+#                 ][:1] if e.compression_codec is not None
+#             ]
+#         ),
+#     },
+# )
+###+
+# the Parquet driver needs GDAL 3.5 or newer, built with Arrow
+if ! {gdal_path}/ogr2ogr --formats | grep -q '^ *Parquet '; then
+  echo "ogr2ogr in {gdal_path} has no Parquet driver (GDAL >= 3.5 with Arrow)" >&2
+  exit 1
+fi
+mkdir -p $(dirname {dest_file}) && \
+  {gdal_path}/ogr2ogr -f Parquet {options} {dest_file} {src_file}
@@ -0,0 +1,41 @@
+/***
+@aorist_presto(
+    programs,
+    CSVTableSchemasCreated,
+    args={
+        "presto_schema": lambda data_set: data_set.name,
+        "table_name": lambda static_data_table: "tmp_" + static_data_table.name,
+        "columns": lambda data_set, asset, hive_table_storage: ",\n    ".join([
+            # Trino's CSV tables only support VARCHAR columns
+            "%s VARCHAR" % x.name
+            for x in data_set.get_template(asset).attributes()
+        ] + [
+            "%s VARCHAR" % name
+            for (name, _) in hive_table_storage.layout.partition_columns
+        ]),
+        "properties": lambda hive_table_storage, replication_storage_setup: ", ".join(
+            ["format='CSV'"] + [
+                "%s='%s'" % (key, value.replace("'", "''"))
+                for (key, value) in [
+                    ("csv_separator", replication_storage_setup.source.encoding.delimiter),
+                ] + ([
+                    ("csv_quote", replication_storage_setup.source.encoding.dialect.quote_char),
+                    ("csv_escape", replication_storage_setup.source.encoding.dialect.escape_char),
+                ] if replication_storage_setup.source.encoding.dialect is not None else [])
+                if value is not None
+            ] + ([
+                "skip_header_line_count=%d" % replication_storage_setup.source.encoding.header.num_lines
+            ] if replication_storage_setup.source.encoding.header is not None else []) + ([
+                "partitioned_by=ARRAY[%s]" % ", ".join([
+                    "'%s'" % name
+                    for (name, _) in hive_table_storage.layout.partition_columns
+                ])
+            ] if len(hive_table_storage.layout.partition_columns) > 0 else [])
+        ),
+    },
+)
+***/
+CREATE TABLE IF NOT EXISTS {presto_schema}.{table_name} (
+    {columns}
+)
+WITH ({properties})
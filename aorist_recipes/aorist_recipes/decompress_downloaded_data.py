@@ -0,0 +1,136 @@
+from aorist import aorist, DecompressDownloadedData
+
+programs = {}
+
+@aorist(
+    programs,
+    DecompressDownloadedData,
+    entrypoint="decompress_downloaded_data",
+    args={
+        "tmp_dir": lambda data_set, asset, replication_storage_setup: (
+            "{tmp_dir}/{dataset}/{name}".format(
+                tmp_dir=replication_storage_setup.tmp_dir,
+                dataset=data_set.name,
+                name=asset.name,
+            )
+        ),
+        "src_file_name": lambda asset, replication_storage_setup: "{name}.{extension}".format(
+            name=asset.name,
+            extension=replication_storage_setup.download_extension,
+        ),
+        "dest_file_name": lambda asset, replication_storage_setup: "{name}.{extension}".format(
+            name=asset.name,
+            extension=replication_storage_setup.decompressed_extension,
+        ),
+        # later steps read the decompressed file
+        "_file_to_replicate": lambda data_set, asset, replication_storage_setup, context: (
+            context.capture(
+                "file_to_replicate",
+                "{tmp_dir}/{dataset}/{name}/{name}.{extension}".format(
+                    tmp_dir=replication_storage_setup.tmp_dir,
+                    dataset=data_set.name,
+                    name=asset.name,
+                    extension=replication_storage_setup.decompressed_extension,
+                ),
+            ),
+            context,
+        ),
+        "codec": lambda remote_storage: (lambda compression: (
+            "gzip" if compression.gzip_compression is not None else
+            "bzip2" if compression.b_zip_2_compression is not None else
+            "zstd" if compression.zstd_compression is not None else
+            "xz" if compression.xz_compression is not None else
+            "lz4" if compression.lz4_compression is not None else
+            "snappy" if compression.snappy_compression is not None else
+            "zip" if compression.zip_compression is not None else
+            "tar"
+        ))(remote_storage.encoding.get_compression()),
+        "tar_codec": lambda remote_storage: (
+            remote_storage.encoding.get_compression().tar_compression.codec
+            if remote_storage.encoding.get_compression().tar_compression is not None
+            else None
+        ),
+        "members": lambda remote_storage: (
+            list(remote_storage.encoding.get_compression().tar_compression.members)
+            if remote_storage.encoding.get_compression().tar_compression is not None
+            else []
+        ),
+    },
+)
+def recipe(tmp_dir, src_file_name, dest_file_name, codec, tar_codec, members):
+    import bz2
+    import fnmatch
+    import gzip
+    import lzma
+    import os
+    import shutil
+    import tarfile
+    import zipfile
+    import lz4.frame
+    import snappy
+    import zstandard
+
+    def open_compressed(src, codec):
+        if codec == "gzip":
+            return gzip.open(src, "rb")
+        if codec == "bzip2":
+            return bz2.open(src, "rb")
+        if codec == "xz":
+            return lzma.open(src, "rb")
+        if codec == "zstd":
+            return zstandard.ZstdDecompressor().stream_reader(open(src, "rb"))
+        if codec == "lz4":
+            return lz4.frame.open(src, "rb")
+        return open(src, "rb")
+
+    def selected(names, members):
+        if len(members) == 0:
+            return names
+        return [
+            name for name in names
+            if any([fnmatch.fnmatch(name, pattern) for pattern in members])
+        ]
+
+    def check_member_path(name):
+        # members cannot be written outside of tmp_dir
+        if os.path.isabs(name) or ".." in name.replace("\\", "/").split("/"):
+            raise ValueError("Unsafe path in archive: %s" % name)
+
+    def decompress_downloaded_data(tmp_dir, src_file_name, dest_file_name, codec, tar_codec, members):
+        src = os.path.join(tmp_dir, src_file_name)
+        dest = os.path.join(tmp_dir, dest_file_name)
+        if codec in ("zip", "tar"):
+            if codec == "zip":
+                with zipfile.ZipFile(src) as archive:
+                    extracted = selected(
+                        [name for name in archive.namelist() if not name.endswith("/")],
+                        members,
+                    )
+                    for name in extracted:
+                        check_member_path(name)
+                    archive.extractall(tmp_dir, extracted)
+            else:
+                # tar streams are read sequentially, so that .tar.zst works too
+                with tarfile.open(fileobj=open_compressed(src, tar_codec), mode="r|") as archive:
+                    extracted = []
+                    for member in archive:
+                        if member.isfile() and len(selected([member.name], members)) > 0:
+                            check_member_path(member.name)
+                            if hasattr(tarfile, "data_filter"):
+                                archive.extract(member, tmp_dir, filter="data")
+                            else:
+                                archive.extract(member, tmp_dir)
+                            extracted.append(member.name)
+            # a single extracted file takes the name later steps expect
+            if len(extracted) != 1:
+                raise ValueError(
+                    "expected exactly one archive member, found %d" % len(extracted)
+                )
+            os.rename(os.path.join(tmp_dir, extracted[0]), dest)
+        elif codec == "snappy":
+            with open(src, "rb") as f, open(dest, "wb") as g:
+                snappy.stream_decompress(f, g)
+        else:
+            with open_compressed(src, codec) as f, open(dest, "wb") as g:
+                shutil.copyfileobj(f, g)
+        os.remove(src)
@@ -0,0 +1,80 @@
+###+
+# @aorist_bash(
+#     programs,
+#     DecompressDownloadedData,
+#     args={
+#         "tmp_dir": lambda data_set, asset, replication_storage_setup: (
+#             "{tmp_dir}/{dataset}/{name}".format(
+#                 tmp_dir=replication_storage_setup.tmp_dir,
+#                 dataset=data_set.name,
+#                 name=asset.name,
+#             )
+#         ),
+#         "src_file_name": lambda asset, replication_storage_setup: "{name}.{extension}".format(
+#             name=asset.name,
+#             extension=replication_storage_setup.download_extension,
+#         ),
+#         "dest_file_name": lambda asset, replication_storage_setup: "{name}.{extension}".format(
+#             name=asset.name,
+#             extension=replication_storage_setup.decompressed_extension,
+#         ),
+#         # later steps read the decompressed file
+#         "_file_to_replicate": lambda data_set, asset, replication_storage_setup, context: (
+#             context.capture(
+#                 "file_to_replicate",
+#                 "{tmp_dir}/{dataset}/{name}/{name}.{extension}".format(
+#                     tmp_dir=replication_storage_setup.tmp_dir,
+#                     dataset=data_set.name,
+#                     name=asset.name,
+#                     extension=replication_storage_setup.decompressed_extension,
+#                 ),
+#             ),
+#             context,
+#         ),
+#         "codec": lambda remote_storage: (lambda compression: (
+#             "gzip" if compression.gzip_compression is not None else
+#             "bzip2" if compression.b_zip_2_compression is not None else
+#             "zstd" if compression.zstd_compression is not None else
+#             "xz" if compression.xz_compression is not None else
+#             "lz4" if compression.lz4_compression is not None else
+#             "snappy" if compression.snappy_compression is not None else
+#             "zip" if compression.zip_compression is not None else
+#             "tar"
+#         ))(remote_storage.encoding.get_compression()),
+#         "tar_flags": lambda remote_storage: (lambda tar: (
+#             {"gzip": "-z", "bzip2": "-j", "xz": "-J", "zstd": "--zstd"}.get(tar.codec, "")
+#             if tar is not None else ""
+#         ))(remote_storage.encoding.get_compression().tar_compression),
+#         "members": lambda remote_storage: (lambda tar: (
+#             " ".join(["'%s'" % member for member in tar.members])
+#             if tar is not None else ""
+#         ))(remote_storage.encoding.get_compression().tar_compression),
+#     },
+# )
+###+
+cd {tmp_dir} && \
+  case {codec} in
+    gzip) gzip -dc {src_file_name} > {dest_file_name} ;;
+    bzip2) bzip2 -dc {src_file_name} > {dest_file_name} ;;
+    zstd) zstd -dc {src_file_name} > {dest_file_name} ;;
+    xz) xz -dc {src_file_name} > {dest_file_name} ;;
+    lz4) lz4 -dc {src_file_name} > {dest_file_name} ;;
+    snappy) python3 -m snappy -d {src_file_name} {dest_file_name} ;;
+    zip|tar)
+      # members are extracted on their own, then exactly one of them
+      # takes the name later steps expect
+      rm -rf {dest_file_name}.members && mkdir {dest_file_name}.members && \
+      if [ {codec} = zip ]; then
+        unzip -o -d {dest_file_name}.members {src_file_name} {members}
+      else
+        tar -x {tar_flags} -C {dest_file_name}.members -f {src_file_name} --wildcards {members}
+      fi && \
+      count=$(find {dest_file_name}.members -type f | wc -l) && \
+      if [ "$count" -ne 1 ]; then
+        echo "expected exactly one archive member, found $count" >&2
+        exit 1
+      fi && \
+      find {dest_file_name}.members -type f -exec mv {} {dest_file_name} \; && \
+      rm -r {dest_file_name}.members ;;
+  esac && \
+  rm {src_file_name}
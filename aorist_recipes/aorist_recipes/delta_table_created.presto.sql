@@ -0,0 +1,68 @@
+/***
+@aorist_presto(
+    programs,
+    DeltaTableCreated,
+    args={
+        "queries": lambda data_set, asset, delta_table_storage, universe: [
+            "CREATE SCHEMA IF NOT EXISTS {catalog}.{namespace}".format(
+                catalog=delta_table_storage.catalog,
+                namespace=delta_table_storage.namespace,
+            ),
+        ] + [
+            "CREATE TABLE IF NOT EXISTS {catalog}.{namespace}.{table} (\n    {columns}\n)\nWITH ({properties})".format(
+                catalog=delta_table_storage.catalog,
+                namespace=delta_table_storage.namespace,
+                table=delta_table_storage.table,
+                columns=",\n    ".join([
+                    "{name} {presto_type}{comment}".format(
+                        name=x.name,
+                        presto_type=x.presto_type,
+                        comment=(
+                            " COMMENT '%s'" % x.comment.replace("'", "`")
+                        ) if x.comment is not None else "",
+                    ) for x in data_set.get_template(asset).attributes()
+                ]),
+                properties=", ".join([
+                    "location = '%s'" % (
+                        "s3://{bucket}/{key}".format(
+                            bucket=delta_table_storage.location.s3_location.bucket,
+                            key=delta_table_storage.location.s3_location.key,
+                        ) if delta_table_storage.location.s3_location is not None else
+                        "s3://{bucket}/{name}".format(
+                            bucket=universe.endpoints.minio.bucket,
+                            name=delta_table_storage.location.minio_location.name,
+                        )
+                    )
+                ] + ([
+                    "partitioned_by = ARRAY[%s]" % ", ".join([
+                        "'%s'" % name for name in delta_table_storage.partition_by
+                    ])
+                ] if len(delta_table_storage.partition_by) > 0 else [])),
+            )
+        ] + ([
+            # a table deployed with the previous attributes is evolved in
+            # place; changes already applied (e.g. to a table just created
+            # with the current attributes) are skipped
+            "ALTER TABLE {catalog}.{namespace}.{table} ADD COLUMN IF NOT EXISTS {name} {presto_type}".format(
+                catalog=delta_table_storage.catalog,
+                namespace=delta_table_storage.namespace,
+                table=delta_table_storage.table,
+                name=x.name,
+                presto_type=x.presto_type,
+            ) for x in delta_table_storage.schema_evolution(
+                data_set.get_template(asset).attributes()
+            )[0]
+        ] + [
+            "ALTER TABLE {catalog}.{namespace}.{table} DROP COLUMN IF EXISTS {name}".format(
+                catalog=delta_table_storage.catalog,
+                namespace=delta_table_storage.namespace,
+                table=delta_table_storage.table,
+                name=name,
+            ) for name in delta_table_storage.schema_evolution(
+                data_set.get_template(asset).attributes()
+            )[1]
+        ] if len(delta_table_storage.previous_attributes) > 0 else []),
+    },
+)
+***/
+{queries}
@@ -0,0 +1,77 @@
+/***
+@aorist_spark(
+    programs,
+    DeltaTableCreated,
+    args={
+        # Delta tables live in Spark's session catalog
+        "queries": lambda data_set, asset, delta_table_storage, universe: [
+            "CREATE NAMESPACE IF NOT EXISTS %s" % delta_table_storage.namespace,
+        ] + [
+            "CREATE TABLE IF NOT EXISTS {namespace}.{table} (\n    {columns}\n)\nUSING DELTA{partitioning}\nLOCATION '{location}'".format(
+                namespace=delta_table_storage.namespace,
+                table=delta_table_storage.table,
+                columns=",\n    ".join([
+                    "{name} {spark_type}{comment}".format(
+                        name=x.name,
+                        spark_type=x.spark_type,
+                        comment=(
+                            " COMMENT '%s'" % x.comment.replace("'", "`")
+                        ) if x.comment is not None else "",
+                    ) for x in data_set.get_template(asset).attributes()
+                ]),
+                partitioning=(
+                    "\nPARTITIONED BY (%s)" % ", ".join(delta_table_storage.partition_by)
+                ) if len(delta_table_storage.partition_by) > 0 else "",
+                location=(
+                    "s3a://{bucket}/{key}".format(
+                        bucket=delta_table_storage.location.s3_location.bucket,
+                        key=delta_table_storage.location.s3_location.key,
+                    ) if delta_table_storage.location.s3_location is not None else
+                    "s3a://{bucket}/{name}".format(
+                        bucket=universe.endpoints.minio.bucket,
+                        name=delta_table_storage.location.minio_location.name,
+                    )
+                ),
+            )
+        ] + ([
+            # a table deployed with the previous attributes is evolved in
+            # place. Spark SQL cannot add or drop columns conditionally, so
+            # these changes name their column, and are skipped once applied
+            # (e.g. to a table just created with the current attributes).
+            {
+                "query": "ALTER TABLE {namespace}.{table} ADD COLUMNS ({name} {spark_type})".format(
+                    namespace=delta_table_storage.namespace,
+                    table=delta_table_storage.table,
+                    name=x.name,
+                    spark_type=x.spark_type,
+                ),
+                "table": "{namespace}.{table}".format(
+                    namespace=delta_table_storage.namespace,
+                    table=delta_table_storage.table,
+                ),
+                "column": x.name,
+                "added": True,
+            } for x in delta_table_storage.schema_evolution(
+                data_set.get_template(asset).attributes()
+            )[0]
+        ] + [
+            {
+                "query": "ALTER TABLE {namespace}.{table} DROP COLUMN {name}".format(
+                    namespace=delta_table_storage.namespace,
+                    table=delta_table_storage.table,
+                    name=name,
+                ),
+                "table": "{namespace}.{table}".format(
+                    namespace=delta_table_storage.namespace,
+                    table=delta_table_storage.table,
+                ),
+                "column": name,
+                "added": False,
+            } for name in delta_table_storage.schema_evolution(
+                data_set.get_template(asset).attributes()
+            )[1]
+        ] if len(delta_table_storage.previous_attributes) > 0 else []),
+    },
+)
+***/
+{queries}
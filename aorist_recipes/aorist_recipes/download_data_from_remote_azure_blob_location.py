@@ -0,0 +1,112 @@
+from aorist import aorist
+from aorist import DownloadDataFromRemoteAzureBlobLocation
+from json import dumps
+
+programs = {}
+
+@aorist(
+    programs,
+    DownloadDataFromRemoteAzureBlobLocation,
+    entrypoint="download_azure_blob_to_file",
+    args={
+        "account": lambda azure_blob_location: azure_blob_location.account,
+        "container": lambda azure_blob_location: azure_blob_location.container,
+        "blob_name": lambda azure_blob_location: azure_blob_location.blob,
+        "account_key_env_var": lambda universe: (
+            universe.endpoints.azure.account_key_env_var
+            if universe.endpoints.azure is not None else None
+        ),
+        "sas_token_env_var": lambda universe: (
+            universe.endpoints.azure.sas_token_env_var
+            if universe.endpoints.azure is not None else None
+        ),
+        "tmp_dir": lambda replication_storage_setup, context: (
+            context.capture(
+                "downloaded_tmp_dir",
+                replication_storage_setup.tmp_dir,
+            ),
+            context,
+        ),
+        "_is_json": lambda replication_storage_setup, context: (
+            context.capture_bool(
+                "is_json",
+                replication_storage_setup.source.encoding.newline_delimited_json_encoding is not None,
+            ),
+            context,
+        ),
+        "_delimiter": lambda replication_storage_setup, context: (
+            context.capture(
+                "delimiter",
+                replication_storage_setup.source.encoding.delimiter,
+            ),
+            context,
+        ),
+        "_csv_dialect": lambda replication_storage_setup, context: (
+            context.capture(
+                "csv_dialect",
+                dumps({
+                    key: getattr(replication_storage_setup.source.encoding.dialect, key)
+                    for key in [
+                        "quote_char", "escape_char", "line_terminator",
+                        "text_encoding", "null_tokens", "comment_prefix",
+                    ]
+                } if replication_storage_setup.source.encoding.dialect is not None else {}),
+            ),
+            context,
+        ),
+        "_header_num_lines": lambda replication_storage_setup, context: (
+            context.capture_int(
+                "header_num_lines",
+                replication_storage_setup.source.encoding.header.num_lines
+                if replication_storage_setup.source.encoding.header is not None
+                else 0,
+            ),
+            context,
+        ),
+        "_map_column_names": lambda replication_storage_setup, context: (
+            context.capture_bool(
+                "map_column_names",
+                replication_storage_setup.source.encoding.header.map_column_names
+                if replication_storage_setup.source.encoding.header is not None
+                else False,
+            ),
+            context,
+        ),
+        "dest": lambda replication_storage_setup, data_set, static_data_table, context: (context.capture(
+            "file_to_replicate",
+            "{tmp_dir}/{dataset}/{table}/{table}.{extension}".format(
+                tmp_dir=replication_storage_setup.tmp_dir,
+                dataset=data_set.name,
+                table=static_data_table.name,
+                extension=replication_storage_setup.download_extension,
+            )
+        ), context),
+    },
+)
+def recipe(
+    account, container, blob_name, account_key_env_var, sas_token_env_var, tmp_dir, dest,
+):
+    from azure.storage.blob import BlobServiceClient
+    import os
+
+    def download_azure_blob_to_file(
+        account, container, blob_name, account_key_env_var, sas_token_env_var, tmp_dir, dest,
+    ):
+        if account_key_env_var is not None:
+            credential = os.environ[account_key_env_var]
+        elif sas_token_env_var is not None:
+            credential = os.environ[sas_token_env_var]
+        else:
+            from azure.identity import DefaultAzureCredential
+            credential = DefaultAzureCredential()
+        client = BlobServiceClient(
+            account_url="https://%s.blob.core.windows.net" % account,
+            credential=credential,
+        )
+        blob = client.get_blob_client(container=container, blob=blob_name)
+        os.makedirs(os.path.dirname(dest), exist_ok=True)
+        if not os.path.exists(dest):
+            with open(dest + ".part", "wb") as f:
+                blob.download_blob().readinto(f)
+            os.replace(dest + ".part", dest)
+        print("Downloaded file: %s" % dest)
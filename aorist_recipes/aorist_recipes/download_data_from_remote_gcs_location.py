@@ -29,9 +29,20 @@ programs = {}
         "_delimiter": lambda replication_storage_setup, context: (
             context.capture(
                 "delimiter",
-                "\t" if replication_storage_setup.source.encoding.tsv_encoding is not None
-                else "," if replication_storage_setup.source.encoding.csv_encoding is not None 
-                else None
+                replication_storage_setup.source.encoding.delimiter,
+            ),
+            context,
+        ),
+        "_csv_dialect": lambda replication_storage_setup, context: (
+            context.capture(
+                "csv_dialect",
+                dumps({
+                    key: getattr(replication_storage_setup.source.encoding.dialect, key)
+                    for key in [
+                        "quote_char", "escape_char", "line_terminator",
+                        "text_encoding", "null_tokens", "comment_prefix",
+                    ]
+                } if replication_storage_setup.source.encoding.dialect is not None else {}),
             ),
             context,
         ),
@@ -44,6 +55,15 @@ programs = {}
             ),
             context,
         ),
+        "_map_column_names": lambda replication_storage_setup, context: (
+            context.capture_bool(
+                "map_column_names",
+                replication_storage_setup.source.encoding.header.map_column_names
+                if replication_storage_setup.source.encoding.header is not None
+                else False,
+            ),
+            context,
+        ),
         "dest": lambda replication_storage_setup, static_data_table, context: (context.capture(
             "file_to_replicate",
             ("{tmp_dir}/{file_name}.{extension}").format(
@@ -0,0 +1,37 @@
+###+
+# @aorist_bash(
+#     programs,
+#     DownloadDataFromRemoteHDFSLocation,
+#     args={
+#         "dataset_name": lambda data_set: data_set.name,
+#         "table_name": lambda static_data_table: static_data_table.name,
+#         "src_url": lambda hdfs_location: "'hdfs://%s/%s'" % (
+#             hdfs_location.namenode,
+#             hdfs_location.path.lstrip("/").replace("'", "'\\''"),
+#         ),
+#         "tmp_dir": lambda replication_storage_setup, context: (
+#             context.capture(
+#                 "downloaded_tmp_dir",
+#                 replication_storage_setup.tmp_dir,
+#             ),
+#             context,
+#         ),
+#         "dest_file_name": lambda static_data_table: (
+#             "{file_name}.{extension}"
+#         ).format(
+#             file_name=static_data_table.name,
+#             extension=static_data_table.setup.replication_storage_setup.download_extension,
+#         ),
+#         "hdfs": lambda universe: "{user}{hadoop_path}/hdfs".format(
+#             user=(
+#                 "HADOOP_USER_NAME=%s " % universe.endpoints.hdfs.user
+#                 if universe.endpoints.hdfs.user is not None else ""
+#             ),
+#             hadoop_path=universe.endpoints.hdfs.hadoop_path,
+#         ),
+#     },
+# )
+###+
+mkdir -p {tmp_dir}/{dataset_name}/{table_name} && \
+  cd {tmp_dir}/{dataset_name}/{table_name} && \
+  {hdfs} dfs -get -f {src_url} {dest_file_name}
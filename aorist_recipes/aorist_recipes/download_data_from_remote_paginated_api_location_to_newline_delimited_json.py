@@ -0,0 +1,144 @@
+from aorist import aorist
+from aorist import DownloadDataFromRemotePaginatedAPILocationToNewlineDelimitedJSON
+from json import dumps
+
+programs = {}
+
+@aorist(
+    programs,
+    DownloadDataFromRemotePaginatedAPILocationToNewlineDelimitedJSON,
+    entrypoint="download_paginated_api",
+    args={
+        "address": lambda paginated_api_location: paginated_api_location.address,
+        "pagination": lambda paginated_api_location: (lambda pagination: (
+            {
+                "type": "cursor",
+                "cursor_param": pagination.cursor_pagination.cursor_param,
+                "next_cursor_path": pagination.cursor_pagination.next_cursor_path,
+                "page_size_param": pagination.cursor_pagination.page_size_param,
+            } if pagination.cursor_pagination is not None else {
+                "type": "offset",
+                "offset_param": pagination.offset_pagination.offset_param,
+                "page_size_param": pagination.offset_pagination.limit_param,
+            }
+        ))(paginated_api_location.pagination),
+        "records_path": lambda paginated_api_location: paginated_api_location.records_path,
+        "page_size": lambda paginated_api_location: (
+            paginated_api_location.page_size
+            if paginated_api_location.page_size is not None else 100
+        ),
+        "auth": lambda paginated_api_location: (lambda auth: (
+            None if auth is None else
+            ["basic", auth.basic_authentication.username, auth.basic_authentication.password_env_var]
+            if auth.basic_authentication is not None else
+            ["bearer", "Authorization", auth.bearer_token_authentication.token_env_var]
+            if auth.bearer_token_authentication is not None else
+            ["api_key", auth.api_key_authentication.header, auth.api_key_authentication.key_env_var]
+        ))(paginated_api_location.auth),
+        "headers": lambda paginated_api_location: [
+            [header.name, header.value] for header in (paginated_api_location.headers or [])
+        ],
+        "max_retries": lambda paginated_api_location: paginated_api_location.max_retries or 0,
+        "retry_backoff_seconds": lambda paginated_api_location: (
+            paginated_api_location.retry_backoff_seconds
+            if paginated_api_location.retry_backoff_seconds is not None else 1
+        ),
+        "output_file": lambda data_set, static_data_table, replication_storage_setup, context: (
+            # also the input of ConvertJSONToCSV
+            context.capture("json_file", context.capture(
+                "file_to_replicate",
+                "{tmp_dir}/{dataset}/{table}/{table}.{extension}".format(
+                    tmp_dir=replication_storage_setup.tmp_dir,
+                    dataset=data_set.name,
+                    table=static_data_table.name,
+                    extension=replication_storage_setup.download_extension,
+                ),
+            )),
+            context
+        ),
+        "_is_json": lambda context: (context.capture_bool("is_json", True), context),
+        "_delimiter": lambda context: (context.capture("delimiter", dumps(None)), context),
+        "_header_num_lines": lambda context: (context.capture_int("header_num_lines", 0), context),
+    },
+)
+def recipe(
+    address, pagination, records_path, page_size,
+    auth, headers, max_retries, retry_backoff_seconds, output_file,
+):
+    import base64
+    import json
+    import os
+    import time
+    import urllib.error
+    import urllib.parse
+    import urllib.request
+
+    def download_paginated_api(
+        address, pagination, records_path, page_size,
+        auth, headers, max_retries, retry_backoff_seconds, output_file,
+    ):
+        request_headers = dict(headers)
+        request_headers.setdefault("Accept", "application/json")
+        if auth is not None:
+            kind, name, env_var = auth
+            secret = os.environ[env_var]
+            if kind == "basic":
+                request_headers["Authorization"] = "Basic %s" % base64.b64encode(
+                    ("%s:%s" % (name, secret)).encode()
+                ).decode()
+            elif kind == "bearer":
+                request_headers["Authorization"] = "Bearer %s" % secret
+            else:
+                request_headers[name] = secret
+
+        def get_page(params):
+            url = address + ("&" if "?" in address else "?") + urllib.parse.urlencode(params)
+            request = urllib.request.Request(url, headers=request_headers)
+            for attempt in range(max_retries + 1):
+                try:
+                    with urllib.request.urlopen(request, timeout=60) as response:
+                        return json.load(response)
+                except urllib.error.HTTPError as e:
+                    # only server errors and rate limiting are worth retrying
+                    if (e.code < 500 and e.code != 429) or attempt == max_retries:
+                        raise
+                except OSError:
+                    if attempt == max_retries:
+                        raise
+                time.sleep(retry_backoff_seconds * 2 ** attempt)
+
+        def lookup(obj, path):
+            for key in (path.split(".") if path is not None else []):
+                if obj is None:
+                    return None
+                obj = obj.get(key)
+            return obj
+
+        params = {}
+        # without a page size parameter the server picks the page size, so
+        # only an empty page marks the end
+        sends_page_size = pagination["page_size_param"] not in (None, "")
+        if sends_page_size:
+            params[pagination["page_size_param"]] = page_size
+        if pagination["type"] == "offset":
+            params[pagination["offset_param"]] = 0
+
+        os.makedirs(os.path.dirname(output_file), exist_ok=True)
+        num_records = 0
+        with open(output_file, 'w') as f:
+            while True:
+                page = get_page(params)
+                records = lookup(page, records_path) or []
+                for record in records:
+                    f.write(json.dumps(record) + chr(10))
+                num_records += len(records)
+                if pagination["type"] == "offset":
+                    if len(records) == 0 or (sends_page_size and len(records) < page_size):
+                        break
+                    params[pagination["offset_param"]] += len(records)
+                else:
+                    cursor = lookup(page, pagination["next_cursor_path"])
+                    if cursor is None or cursor == "" or len(records) == 0:
+                        break
+                    params[pagination["cursor_param"]] = cursor
+        print("Downloaded %d records from %s" % (num_records, address))
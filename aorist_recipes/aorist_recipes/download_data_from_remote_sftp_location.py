@@ -0,0 +1,131 @@
+from aorist import aorist
+from aorist import DownloadDataFromRemoteSFTPLocation
+from json import dumps
+
+programs = {}
+
+@aorist(
+    programs,
+    DownloadDataFromRemoteSFTPLocation,
+    entrypoint="download_sftp_to_file",
+    args={
+        "host": lambda sftp_location: sftp_location.host,
+        "port": lambda sftp_location: (
+            sftp_location.port if sftp_location.port is not None else 22
+        ),
+        "path": lambda sftp_location: sftp_location.path,
+        "username": lambda sftp_location: sftp_location.username,
+        "password_env_var": lambda sftp_location: sftp_location.password_env_var,
+        "private_key_file": lambda universe: (
+            universe.endpoints.sftp.private_key_file
+            if universe.endpoints.sftp is not None else None
+        ),
+        "known_hosts_file": lambda universe: (
+            universe.endpoints.sftp.known_hosts_file
+            if universe.endpoints.sftp is not None else None
+        ),
+        "tmp_dir": lambda replication_storage_setup, context: (
+            context.capture(
+                "downloaded_tmp_dir",
+                replication_storage_setup.tmp_dir,
+            ),
+            context,
+        ),
+        "_is_json": lambda replication_storage_setup, context: (
+            context.capture_bool(
+                "is_json",
+                replication_storage_setup.source.encoding.newline_delimited_json_encoding is not None,
+            ),
+            context,
+        ),
+        "_delimiter": lambda replication_storage_setup, context: (
+            context.capture(
+                "delimiter",
+                replication_storage_setup.source.encoding.delimiter,
+            ),
+            context,
+        ),
+        "_csv_dialect": lambda replication_storage_setup, context: (
+            context.capture(
+                "csv_dialect",
+                dumps({
+                    key: getattr(replication_storage_setup.source.encoding.dialect, key)
+                    for key in [
+                        "quote_char", "escape_char", "line_terminator",
+                        "text_encoding", "null_tokens", "comment_prefix",
+                    ]
+                } if replication_storage_setup.source.encoding.dialect is not None else {}),
+            ),
+            context,
+        ),
+        "_header_num_lines": lambda replication_storage_setup, context: (
+            context.capture_int(
+                "header_num_lines",
+                replication_storage_setup.source.encoding.header.num_lines
+                if replication_storage_setup.source.encoding.header is not None
+                else 0,
+            ),
+            context,
+        ),
+        "_map_column_names": lambda replication_storage_setup, context: (
+            context.capture_bool(
+                "map_column_names",
+                replication_storage_setup.source.encoding.header.map_column_names
+                if replication_storage_setup.source.encoding.header is not None
+                else False,
+            ),
+            context,
+        ),
+        "dest": lambda replication_storage_setup, data_set, static_data_table, context: (context.capture(
+            "file_to_replicate",
+            "{tmp_dir}/{dataset}/{table}/{table}.{extension}".format(
+                tmp_dir=replication_storage_setup.tmp_dir,
+                dataset=data_set.name,
+                table=static_data_table.name,
+                extension=replication_storage_setup.download_extension,
+            )
+        ), context),
+    },
+)
+def recipe(
+    host, port, path, username, password_env_var, private_key_file, known_hosts_file,
+    tmp_dir, dest,
+):
+    import os
+    import paramiko
+
+    def download_sftp_to_file(
+        host, port, path, username, password_env_var, private_key_file, known_hosts_file,
+        tmp_dir, dest,
+    ):
+        client = paramiko.SSHClient()
+        if known_hosts_file is not None:
+            client.load_host_keys(os.path.expanduser(known_hosts_file))
+        else:
+            client.load_system_host_keys()
+        # unknown hosts are rejected rather than trusted on first use
+        client.set_missing_host_key_policy(paramiko.RejectPolicy())
+        client.connect(
+            host,
+            port=port,
+            username=username,
+            password=(
+                os.environ[password_env_var] if password_env_var is not None else None
+            ),
+            key_filename=(
+                os.path.expanduser(private_key_file)
+                if password_env_var is None and private_key_file is not None else None
+            ),
+        )
+        try:
+            os.makedirs(os.path.dirname(dest), exist_ok=True)
+            if not os.path.exists(dest):
+                sftp = client.open_sftp()
+                try:
+                    sftp.get(path, dest + ".part")
+                finally:
+                    sftp.close()
+                os.replace(dest + ".part", dest)
+        finally:
+            client.close()
+        print("Downloaded file: %s" % dest)
@@ -22,15 +22,106 @@ programs = {}
         ).format(
             file_name=static_data_table.name,
             extension=static_data_table.setup.replication_storage_setup.download_extension,
-        )
+        ),
+        "auth": lambda web_location: (lambda auth: (
+            None if auth is None else
+            ["basic", auth.basic_authentication.username, auth.basic_authentication.password_env_var]
+            if auth.basic_authentication is not None else
+            ["bearer", "Authorization", auth.bearer_token_authentication.token_env_var]
+            if auth.bearer_token_authentication is not None else
+            ["api_key", auth.api_key_authentication.header, auth.api_key_authentication.key_env_var]
+        ))(web_location.auth),
+        "headers": lambda web_location: [
+            [header.name, header.value] for header in (web_location.headers or [])
+        ],
+        "max_retries": lambda web_location: web_location.max_retries or 0,
+        "retry_backoff_seconds": lambda web_location: (
+            web_location.retry_backoff_seconds
+            if web_location.retry_backoff_seconds is not None else 1
+        ),
+        "sha256": lambda web_location: web_location.sha256,
+        "conditional_requests": lambda web_location: web_location.conditional_requests or False,
     },
 )
-def recipe(dataset_name, table_name, src_url, tmp_dir, dest_file_name):
+def recipe(
+    dataset_name, table_name, src_url, tmp_dir, dest_file_name,
+    auth, headers, max_retries, retry_backoff_seconds, sha256, conditional_requests,
+):
+    import base64
+    import hashlib
+    import json
     import os
+    import shutil
+    import time
+    import urllib.error
     import urllib.request
-    
-    def download_data_from_remote_web_location(src_url, tmp_dir, dest_file_name, table_name, dataset_name):
+
+    def download_data_from_remote_web_location(
+        src_url, tmp_dir, dest_file_name, table_name, dataset_name,
+        auth, headers, max_retries, retry_backoff_seconds, sha256, conditional_requests,
+    ):
         os.makedirs(tmp_dir + '/' + dataset_name + '/' + table_name, exist_ok=True)
-        urllib.request.urlretrieve(src_url, os.path.join(tmp_dir, dataset_name, table_name, dest_file_name))
-    
-    
+        dest = os.path.join(tmp_dir, dataset_name, table_name, dest_file_name)
+
+        request_headers = dict(headers)
+        if auth is not None:
+            kind, name, env_var = auth
+            secret = os.environ[env_var]
+            if kind == "basic":
+                request_headers["Authorization"] = "Basic %s" % base64.b64encode(
+                    ("%s:%s" % (name, secret)).encode()
+                ).decode()
+            elif kind == "bearer":
+                request_headers["Authorization"] = "Bearer %s" % secret
+            else:
+                request_headers[name] = secret
+
+        # ETag and Last-Modified of the previous download are kept next to it
+        validators_file = dest + ".validators.json"
+        if conditional_requests and os.path.exists(dest) and os.path.exists(validators_file):
+            with open(validators_file) as f:
+                validators = json.load(f)
+            if validators.get("etag") is not None:
+                request_headers["If-None-Match"] = validators["etag"]
+            if validators.get("last_modified") is not None:
+                request_headers["If-Modified-Since"] = validators["last_modified"]
+
+        request = urllib.request.Request(src_url, headers=request_headers)
+        for attempt in range(max_retries + 1):
+            try:
+                with urllib.request.urlopen(request, timeout=60) as response, \
+                        open(dest + ".part", "wb") as f:
+                    shutil.copyfileobj(response, f)
+                    validators = {
+                        "etag": response.headers.get("ETag"),
+                        "last_modified": response.headers.get("Last-Modified"),
+                    }
+                os.replace(dest + ".part", dest)
+                if conditional_requests:
+                    with open(validators_file, "w") as f:
+                        json.dump(validators, f)
+                break
+            except urllib.error.HTTPError as e:
+                if e.code == 304:
+                    print("%s has not changed since it was last downloaded" % src_url)
+                    break
+                # only server errors and rate limiting are worth retrying
+                if (e.code < 500 and e.code != 429) or attempt == max_retries:
+                    raise
+            except OSError:
+                if attempt == max_retries:
+                    raise
+            time.sleep(retry_backoff_seconds * 2 ** attempt)
+
+        if sha256 is not None:
+            digest = hashlib.sha256()
+            with open(dest, "rb") as f:
+                for chunk in iter(lambda: f.read(1 << 20), b""):
+                    digest.update(chunk)
+            if digest.hexdigest() != sha256.lower():
+                os.remove(dest)
+                raise ValueError("Checksum mismatch for %s: expected %s, got %s" % (
+                    src_url, sha256, digest.hexdigest(),
+                ))
+
+
@@ -5,7 +5,7 @@
 #     args={
 #         "dataset_name": lambda data_set: data_set.name,
 #         "table_name": lambda static_data_table: static_data_table.name,
-#         "src_url": lambda web_location: web_location.address,
+#         "src_url": lambda web_location: "'%s'" % web_location.address.replace("'", "'\\''"),
 #         "tmp_dir": lambda replication_storage_setup, context: (
 #             context.capture(
 #                 "downloaded_tmp_dir",
@@ -18,9 +18,47 @@
 #         ).format(
 #             file_name=static_data_table.name,
 #             extension=static_data_table.setup.replication_storage_setup.download_extension,
-#         )
+#         ),
+#         "options": lambda web_location, static_data_table: " ".join([
+#             "-H '%s: %s'" % (header.name, header.value.replace("'", "'\\''"))
+#             for header in (web_location.headers or [])
+#         ] + (lambda auth: (
+#             [] if auth is None else
+#             ["--user '%s':\"${%s}\"" % (
+#                 auth.basic_authentication.username.replace("'", "'\\''"),
+#                 auth.basic_authentication.password_env_var,
+#             )] if auth.basic_authentication is not None else
+#             ["-H \"Authorization: Bearer ${%s}\"" % auth.bearer_token_authentication.token_env_var]
+#             if auth.bearer_token_authentication is not None else
+#             ["-H '%s'\": ${%s}\"" % (
+#                 auth.api_key_authentication.header,
+#                 auth.api_key_authentication.key_env_var,
+#             )]
+#         ))(web_location.auth) + (
+#             ["--retry %d" % web_location.max_retries]
+#             if web_location.max_retries is not None else []
+#         ) + (
+#             ["--retry-delay %d" % web_location.retry_backoff_seconds]
+#             if web_location.retry_backoff_seconds is not None else []
+#         ) + (
+#             ["--etag-compare {name}.etag --etag-save {name}.etag -z {name} -R".format(
+#                 name="{file_name}.{extension}".format(
+#                     file_name=static_data_table.name,
+#                     extension=static_data_table.setup.replication_storage_setup.download_extension,
+#                 ),
+#             )] if web_location.conditional_requests else []
+#         )),
+#         "checksum": lambda web_location, static_data_table: (
+#             "echo '{sha256}  {file_name}.{extension}' | sha256sum -c -".format(
+#                 sha256=web_location.sha256.lower(),
+#                 file_name=static_data_table.name,
+#                 extension=static_data_table.setup.replication_storage_setup.download_extension,
+#             ) if web_location.sha256 is not None else "true"
+#         ),
 #     },
 # )
 ###+
 mkdir -p {tmp_dir}/{dataset_name}/{table_name} && \
-  curl {address} -o {tmp_dir}/{dataset_name}/{table_name}/{file_name}
+  cd {tmp_dir}/{dataset_name}/{table_name} && \
+  curl --fail --location {options} {src_url} -o {dest_file_name} && \
+  {checksum}
@@ -26,7 +26,13 @@
                 dataset=data_set.name,
             )
         ) if hive_table_storage.location.s3_location is not None else (
-            panic("Only Alluxio, MinIO or S3 locations supported.")
+            "WITH (location='hdfs://{namenode}/{path}/{dataset}/')".format(
+                namenode=hive_table_storage.location.hdfs_location.namenode,
+                path=hive_table_storage.location.hdfs_location.path.strip("/"),
+                dataset=data_set.name,
+            )
+        ) if hive_table_storage.location.hdfs_location is not None else (
+            panic("Only Alluxio, MinIO, S3 or HDFS locations supported.")
         )), context)
     },
 )
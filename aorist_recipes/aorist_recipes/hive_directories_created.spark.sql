@@ -0,0 +1,40 @@
+/***
+@aorist_spark(
+    programs,
+    HiveDirectoriesCreated,
+    args={
+        "schema": lambda data_set, context: (
+            context.capture("schema", data_set.name),
+            context,
+        ),
+        "location": lambda hive_table_storage, universe, data_set, context: (
+        context.capture("location",
+            "LOCATION 'alluxio://{server}:{port}/{directory}/{dataset}/{path}'".format(
+                server=universe.endpoints.alluxio.server,
+                port=universe.endpoints.alluxio.rpc_port,
+                directory=universe.endpoints.alluxio.directory,
+                dataset=data_set.name,
+                path=hive_table_storage.location.alluxio_location,
+        ) if hive_table_storage.location.alluxio_location is not None else (
+            "LOCATION 's3a://{bucket}/{dataset}/'".format(
+                bucket=universe.endpoints.minio.bucket,
+                dataset=data_set.name,
+            )
+        ) if hive_table_storage.location.minio_location is not None else (
+            "LOCATION 's3a://{bucket}/{dataset}/'".format(
+                bucket=universe.endpoints.s3.bucket,
+                dataset=data_set.name,
+            )
+        ) if hive_table_storage.location.s3_location is not None else (
+            "LOCATION 'hdfs://{namenode}/{path}/{dataset}/'".format(
+                namenode=hive_table_storage.location.hdfs_location.namenode,
+                path=hive_table_storage.location.hdfs_location.path.strip("/"),
+                dataset=data_set.name,
+            )
+        ) if hive_table_storage.location.hdfs_location is not None else (
+            panic("Only Alluxio, MinIO, S3 or HDFS locations supported.")
+        )), context)
+    },
+)
+***/
+CREATE SCHEMA IF NOT EXISTS {schema} {location}
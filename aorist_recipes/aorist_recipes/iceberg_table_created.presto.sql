@@ -0,0 +1,83 @@
+/***
+@aorist_presto(
+    programs,
+    IcebergTableCreated,
+    args={
+        "queries": lambda data_set, asset, iceberg_table_storage, universe: [
+            "CREATE SCHEMA IF NOT EXISTS {catalog}.{namespace}".format(
+                catalog=iceberg_table_storage.catalog,
+                namespace=iceberg_table_storage.namespace,
+            ),
+        ] + [
+            "CREATE TABLE IF NOT EXISTS {catalog}.{namespace}.{table} (\n    {columns}\n)\nWITH ({properties})".format(
+                catalog=iceberg_table_storage.catalog,
+                namespace=iceberg_table_storage.namespace,
+                table=iceberg_table_storage.table,
+                columns=",\n    ".join([
+                    "{name} {presto_type}{comment}".format(
+                        name=x.name,
+                        presto_type=x.presto_type,
+                        comment=(
+                            " COMMENT '%s'" % x.comment.replace("'", "`")
+                        ) if x.comment is not None else "",
+                    ) for x in data_set.get_template(asset).attributes()
+                ]),
+                properties=", ".join([
+                    "format = '%s'" % (
+                        "ORC" if iceberg_table_storage.encoding.orc_encoding is not None else
+                        "AVRO" if iceberg_table_storage.encoding.avro_encoding is not None else
+                        "PARQUET"
+                    ),
+                    "location = '%s'" % (
+                        "s3://{bucket}/{key}".format(
+                            bucket=iceberg_table_storage.location.s3_location.bucket,
+                            key=iceberg_table_storage.location.s3_location.key,
+                        ) if iceberg_table_storage.location.s3_location is not None else
+                        "s3://{bucket}/{name}".format(
+                            bucket=universe.endpoints.minio.bucket,
+                            name=iceberg_table_storage.location.minio_location.name,
+                        )
+                    )
+                ] + ([
+                    "partitioning = ARRAY[%s]" % ", ".join([
+                        "'%s'" % field for field in iceberg_table_storage.partition_spec
+                    ])
+                ] if len(iceberg_table_storage.partition_spec) > 0 else [])),
+            )
+        ] + ([
+            # a table deployed with the previous attributes is evolved in
+            # place; changes already applied (e.g. to a table just created
+            # with the current attributes) are skipped
+            "ALTER TABLE {catalog}.{namespace}.{table} ADD COLUMN IF NOT EXISTS {name} {presto_type}".format(
+                catalog=iceberg_table_storage.catalog,
+                namespace=iceberg_table_storage.namespace,
+                table=iceberg_table_storage.table,
+                name=x.name,
+                presto_type=x.presto_type,
+            ) for x in iceberg_table_storage.schema_evolution(
+                data_set.get_template(asset).attributes()
+            )[0]
+        ] + [
+            "ALTER TABLE {catalog}.{namespace}.{table} DROP COLUMN IF EXISTS {name}".format(
+                catalog=iceberg_table_storage.catalog,
+                namespace=iceberg_table_storage.namespace,
+                table=iceberg_table_storage.table,
+                name=name,
+            ) for name in iceberg_table_storage.schema_evolution(
+                data_set.get_template(asset).attributes()
+            )[1]
+        ] + [
+            "ALTER TABLE {catalog}.{namespace}.{table} ALTER COLUMN {name} SET DATA TYPE {presto_type}".format(
+                catalog=iceberg_table_storage.catalog,
+                namespace=iceberg_table_storage.namespace,
+                table=iceberg_table_storage.table,
+                name=x.name,
+                presto_type=x.presto_type,
+            ) for x in iceberg_table_storage.schema_evolution(
+                data_set.get_template(asset).attributes()
+            )[2]
+        ] if len(iceberg_table_storage.previous_attributes) > 0 else []),
+    },
+)
+***/
+{queries}
@@ -0,0 +1,99 @@
+/***
+@aorist_spark(
+    programs,
+    IcebergTableCreated,
+    args={
+        "queries": lambda data_set, asset, iceberg_table_storage, universe: [
+            "CREATE NAMESPACE IF NOT EXISTS {catalog}.{namespace}".format(
+                catalog=iceberg_table_storage.catalog,
+                namespace=iceberg_table_storage.namespace,
+            ),
+        ] + [
+            "CREATE TABLE IF NOT EXISTS {catalog}.{namespace}.{table} (\n    {columns}\n)\nUSING iceberg{partitioning}\nLOCATION '{location}'\nTBLPROPERTIES ('write.format.default' = '{format}')".format(
+                catalog=iceberg_table_storage.catalog,
+                namespace=iceberg_table_storage.namespace,
+                table=iceberg_table_storage.table,
+                columns=",\n    ".join([
+                    "{name} {spark_type}{comment}".format(
+                        name=x.name,
+                        spark_type=x.spark_type,
+                        comment=(
+                            " COMMENT '%s'" % x.comment.replace("'", "`")
+                        ) if x.comment is not None else "",
+                    ) for x in data_set.get_template(asset).attributes()
+                ]),
+                partitioning=(
+                    "\nPARTITIONED BY (%s)" % ", ".join(iceberg_table_storage.spark_partition_spec)
+                ) if len(iceberg_table_storage.partition_spec) > 0 else "",
+                location=(
+                    "s3a://{bucket}/{key}".format(
+                        bucket=iceberg_table_storage.location.s3_location.bucket,
+                        key=iceberg_table_storage.location.s3_location.key,
+                    ) if iceberg_table_storage.location.s3_location is not None else
+                    "s3a://{bucket}/{name}".format(
+                        bucket=universe.endpoints.minio.bucket,
+                        name=iceberg_table_storage.location.minio_location.name,
+                    )
+                ),
+                format=(
+                    "orc" if iceberg_table_storage.encoding.orc_encoding is not None else
+                    "avro" if iceberg_table_storage.encoding.avro_encoding is not None else
+                    "parquet"
+                ),
+            )
+        ] + ([
+            # a table deployed with the previous attributes is evolved in
+            # place. Spark SQL cannot add or drop columns conditionally, so
+            # these changes name their column, and are skipped once applied
+            # (e.g. to a table just created with the current attributes).
+            {
+                "query": "ALTER TABLE {catalog}.{namespace}.{table} ADD COLUMNS ({name} {spark_type})".format(
+                    catalog=iceberg_table_storage.catalog,
+                    namespace=iceberg_table_storage.namespace,
+                    table=iceberg_table_storage.table,
+                    name=x.name,
+                    spark_type=x.spark_type,
+                ),
+                "table": "{catalog}.{namespace}.{table}".format(
+                    catalog=iceberg_table_storage.catalog,
+                    namespace=iceberg_table_storage.namespace,
+                    table=iceberg_table_storage.table,
+                ),
+                "column": x.name,
+                "added": True,
+            } for x in iceberg_table_storage.schema_evolution(
+                data_set.get_template(asset).attributes()
+            )[0]
+        ] + [
+            {
+                "query": "ALTER TABLE {catalog}.{namespace}.{table} DROP COLUMN {name}".format(
+                    catalog=iceberg_table_storage.catalog,
+                    namespace=iceberg_table_storage.namespace,
+                    table=iceberg_table_storage.table,
+                    name=name,
+                ),
+                "table": "{catalog}.{namespace}.{table}".format(
+                    catalog=iceberg_table_storage.catalog,
+                    namespace=iceberg_table_storage.namespace,
+                    table=iceberg_table_storage.table,
+                ),
+                "column": name,
+                "added": False,
+            } for name in iceberg_table_storage.schema_evolution(
+                data_set.get_template(asset).attributes()
+            )[1]
+        ] + [
+            "ALTER TABLE {catalog}.{namespace}.{table} ALTER COLUMN {name} TYPE {spark_type}".format(
+                catalog=iceberg_table_storage.catalog,
+                namespace=iceberg_table_storage.namespace,
+                table=iceberg_table_storage.table,
+                name=x.name,
+                spark_type=x.spark_type,
+            ) for x in iceberg_table_storage.schema_evolution(
+                data_set.get_template(asset).attributes()
+            )[2]
+        ] if len(iceberg_table_storage.previous_attributes) > 0 else []),
+    },
+)
+***/
+{queries}
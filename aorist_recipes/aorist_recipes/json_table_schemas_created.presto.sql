@@ -5,10 +5,24 @@
     args={
         "presto_schema": lambda data_set: data_set.name,
         "table_name": lambda asset: asset.name(),
+        "columns": lambda hive_table_storage: ",\n    ".join(
+            ["json_obj VARCHAR"] + [
+                "%s VARCHAR" % name
+                for (name, _) in hive_table_storage.layout.partition_columns
+            ]
+        ),
+        "properties": lambda hive_table_storage: ", ".join(
+            ["format='CSV'"] + ([
+                "partitioned_by=ARRAY[%s]" % ", ".join([
+                    "'%s'" % name
+                    for (name, _) in hive_table_storage.layout.partition_columns
+                ])
+            ] if len(hive_table_storage.layout.partition_columns) > 0 else [])
+        ),
     },
 )
 ***/
 CREATE TABLE IF NOT EXISTS {presto_schema}.{table_name} (
-    json_obj VARCHAR
+    {columns}
 )
-WITH (format='CSV')
+WITH ({properties})
@@ -0,0 +1,25 @@
+/***
+@aorist_spark(
+    programs,
+    JSONTableSchemasCreated,
+    args={
+        "schema": lambda data_set: data_set.name,
+        "table_name": lambda asset: asset.name(),
+        "columns": lambda hive_table_storage: ",\n    ".join(
+            ["json_obj STRING"] + [
+                "%s STRING" % name
+                for (name, _) in hive_table_storage.layout.partition_columns
+            ]
+        ),
+        "partitioning": lambda hive_table_storage: (
+            "\nPARTITIONED BY (%s)" % ", ".join([
+                name for (name, _) in hive_table_storage.layout.partition_columns
+            ])
+        ) if len(hive_table_storage.layout.partition_columns) > 0 else "",
+    },
+)
+***/
+CREATE TABLE IF NOT EXISTS {schema}.{table_name} (
+    {columns}
+)
+USING TEXT{partitioning}
@@ -5,7 +5,7 @@
     args={
         "presto_schema": lambda data_set: data_set.name,
         "table_name": lambda static_data_table: static_data_table.name,
-        "columns": lambda data_set, asset: ",\n".join([
+        "columns": lambda data_set, asset, hive_table_storage: ",\n".join([
             "{name} {presto_type}{comment}".format(
                 name=x.name,
                 presto_type=x.presto_type,
@@ -13,10 +13,23 @@
                     "COMMENT '%s'" % x.comment.replace("'", "`")
                 ) if x.comment is not None else "",
             ) for x in data_set.get_template(asset).attributes()
+        ] + [
+            # partition columns come last, as Hive requires
+            "%s VARCHAR" % name
+            for (name, _) in hive_table_storage.layout.partition_columns
         ]),
+        "properties": lambda hive_table_storage: ", ".join(
+            ["format='ORC'"] + ([
+                "partitioned_by=ARRAY[%s]" % ", ".join([
+                    "'%s'" % name
+                    for (name, _) in hive_table_storage.layout.partition_columns
+                ])
+            ] if len(hive_table_storage.layout.partition_columns) > 0 else [])
+        ),
     },
 )
 ***/
 CREATE TABLE {presto_schema}.{table_name} (
     {columns}
 )
+WITH ({properties})
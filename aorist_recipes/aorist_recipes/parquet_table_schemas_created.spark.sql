@@ -0,0 +1,37 @@
+/***
+@aorist_spark(
+    programs,
+    ParquetTableSchemasCreated,
+    args={
+        "schema": lambda data_set: data_set.name,
+        "table_name": lambda static_data_table: static_data_table.name,
+        "columns": lambda data_set, asset, hive_table_storage: ",\n".join([
+            "{name} {spark_type}{comment}".format(
+                name=x.name,
+                spark_type=x.spark_type,
+                comment=(
+                    " COMMENT '%s'" % x.comment.replace("'", "`")
+                ) if x.comment is not None else "",
+            ) for x in data_set.get_template(asset).attributes()
+        ] + [
+            # partition columns come last, as Hive requires
+            "%s STRING" % name
+            for (name, _) in hive_table_storage.layout.partition_columns
+        ]),
+        "options": lambda hive_table_storage: (
+            "\nOPTIONS ('compression' = '%s')" % (
+                hive_table_storage.encoding.compression_codec.lower()
+            )
+        ) if hive_table_storage.encoding.compression_codec is not None else "",
+        "partitioning": lambda hive_table_storage: (
+            "\nPARTITIONED BY (%s)" % ", ".join([
+                name for (name, _) in hive_table_storage.layout.partition_columns
+            ])
+        ) if len(hive_table_storage.layout.partition_columns) > 0 else "",
+    },
+)
+***/
+CREATE TABLE {schema}.{table_name} (
+    {columns}
+)
+USING PARQUET{options}{partitioning}
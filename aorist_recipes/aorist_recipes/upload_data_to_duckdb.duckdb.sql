@@ -0,0 +1,45 @@
+/***
+@aorist_duckdb(
+    programs,
+    UploadDataToDuckDB,
+    args={
+        "database": lambda duck_db_location: duck_db_location.database,
+        "table_name": lambda asset: asset.name,
+        "source_file": lambda context: (context.get("file_to_replicate"), context),
+        "columns": lambda asset: "{%s}" % ", ".join([
+            "'%s': '%s'" % (x.name, x.duckdb_type)
+            for x in asset.schema.datum_template.attributes()
+        ]),
+        "reader": lambda context: (
+            "read_json" if context.get_bool("is_json") else "read_csv",
+            context,
+        ),
+        "options": lambda replication_storage_setup, context: (
+            "format='newline_delimited'" if context.get_bool("is_json") else
+            ", ".join([
+                "delim='%s', skip=%d, header=false" % (
+                    context.get("delimiter"),
+                    context.get_int("header_num_lines"),
+                ),
+            ] + ([
+                "%s='%s'" % (key, value.replace("'", "''"))
+                for (key, value) in [
+                    ("quote", replication_storage_setup.source.encoding.dialect.quote_char),
+                    ("escape", replication_storage_setup.source.encoding.dialect.escape_char),
+                    ("comment", replication_storage_setup.source.encoding.dialect.comment_prefix),
+                    ("encoding", replication_storage_setup.source.encoding.dialect.text_encoding),
+                ]
+                if value is not None
+            ] + ([
+                "nullstr=[%s]" % ", ".join([
+                    "'%s'" % x.replace("'", "''") for x in replication_storage_setup.source.encoding.dialect.null_tokens
+                ])
+            ] if len(replication_storage_setup.source.encoding.dialect.null_tokens) > 0 else [])
+            if replication_storage_setup.source.encoding.dialect is not None else [])),
+            context,
+        ),
+    },
+)
+***/
+CREATE OR REPLACE TABLE {table_name} AS
+SELECT * FROM {reader}('{source_file}', columns={columns}, {options})
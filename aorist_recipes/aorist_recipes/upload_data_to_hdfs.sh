@@ -0,0 +1,40 @@
+###+
+# @aorist_bash(
+#     programs,
+#     UploadDataToHDFS,
+#     args={
+#         "dest_dir": lambda hdfs_location, static_data_table, context: (
+#             "'hdfs://{namenode}/{path}/{schema}/{table}_csv'".format(
+#                 namenode=hdfs_location.namenode,
+#                 path=hdfs_location.path.strip("/").replace("'", "'\\''"),
+#                 schema=context.get("schema"),
+#                 table=static_data_table.name,
+#             ),
+#             context,
+#         ),
+#         "source_file": lambda replication_storage_setup, static_data_table: "{tmp_dir}/{table}.csv".format(
+#             tmp_dir=replication_storage_setup.tmp_dir,
+#             table=static_data_table.name,
+#         ),
+#         "hdfs": lambda universe: "{user}{hadoop_path}/hdfs".format(
+#             user=(
+#                 "HADOOP_USER_NAME=%s " % universe.endpoints.hdfs.user
+#                 if universe.endpoints.hdfs.user is not None else ""
+#             ),
+#             hadoop_path=universe.endpoints.hdfs.hadoop_path,
+#         ),
+#         # dynamic tables are staged in the Hive partition of the run, e.g.
+#         # dt=2021-01-01/hour=05, each value a slice of the partition date
+#         "partition_date": lambda hive_table_storage: (
+#             "{partition_date}" if len(hive_table_storage.layout.partition_slices) > 0 else ""
+#         ),
+#         "partition_dir": lambda hive_table_storage: "".join([
+#             '/%s="${partition_date:%d:%d}"' % x
+#             for x in hive_table_storage.layout.partition_slices
+#         ]),
+#     },
+# )
+###+
+partition_date={partition_date} && \
+  {hdfs} dfs -mkdir -p {dest_dir}{partition_dir} && \
+  {hdfs} dfs -put -f {source_file} {dest_dir}{partition_dir}/data.csv
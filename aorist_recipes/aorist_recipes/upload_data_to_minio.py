@@ -16,11 +16,24 @@ programs = {}
         "tablename": lambda static_data_table: "%s_csv" % static_data_table.name,
         "tmp_dir": lambda replication_storage_setup: replication_storage_setup.tmp_dir,
         "source_file": lambda static_data_table: "%s.csv" % static_data_table.name,
+        # dynamic tables are staged in the Hive partition of the run
+        "partition_date": lambda hive_table_storage: (
+            "{partition_date}" if len(hive_table_storage.layout.partition_slices) > 0 else ""
+        ),
+        "partition_slices": lambda hive_table_storage: ",".join([
+            "%s:%d:%d" % x for x in hive_table_storage.layout.partition_slices
+        ]),
     }
 )
-def recipe(hostname, port, access_key, secret_key, bucket, schema, tablename, tmp_dir, source_file):
+def recipe(
+    hostname, port, access_key, secret_key, bucket, schema, tablename, tmp_dir, source_file,
+    partition_date, partition_slices,
+):
     from minio import Minio
-    def upload_to_minio(hostname, port, access_key, secret_key, bucket, schema, tablename, tmp_dir, source_file):
+    def upload_to_minio(
+        hostname, port, access_key, secret_key, bucket, schema, tablename, tmp_dir, source_file,
+        partition_date, partition_slices,
+    ):
         client = Minio(
             "%s:%s" % (hostname, port),
             access_key=access_key,
@@ -28,7 +41,14 @@ def recipe(hostname, port, access_key, secret_key, bucket, schema, tablename, tm
             secure=False,
         )
         assert client.bucket_exists(bucket)
-        dest_path = schema + '/' + tablename + '/data.csv'
+        # e.g. dt=2021-01-01/hour=05, each value a slice of the partition date
+        partition = [
+            "%s=%s" % (name, partition_date[int(start):int(start) + int(length)])
+            for (name, start, length) in [
+                x.split(":") for x in partition_slices.split(",") if x
+            ]
+        ]
+        dest_path = "/".join([schema, tablename] + partition + ["data.csv"])
         source_path = tmp_dir + "/" + source_file
         client.fput_object(bucket, dest_path, source_path)
         print("Successfully uploaded %s to %s" % (source_path, dest_path))
@@ -13,6 +13,11 @@ programs = {}
         "header_num_lines": lambda context: (context.get_int("header_num_lines"), context),
         "is_json": lambda context: (context.get_bool("is_json"), context),
         "delimiter": lambda context: (context.get("delimiter"), context),
+        "csv_dialect": lambda context: (context.get_optional("csv_dialect"), context),
+        "map_column_names": lambda context: (
+            context.get_optional_bool("map_column_names") or False,
+            context,
+        ),
         "source_file": lambda context: (context.get("file_to_replicate"), context),
         "columns": lambda asset: [
             (x.name, x.sqlite_type, x.is_nullable)
@@ -22,15 +27,16 @@ programs = {}
 )
 def recipe(
     db_filename, table_name, source_file, columns,
-    is_json, delimiter, header_num_lines,
+    is_json, delimiter, header_num_lines, csv_dialect, map_column_names,
 ):
     
+    import csv
     import sqlite3
     import json
     
     def upload_to_sqlite(
         db_filename, table_name, source_file, columns,
-        is_json, delimiter, header_num_lines,
+        is_json, delimiter, header_num_lines, csv_dialect, map_column_names,
     ):
         con = sqlite3.connect(db_filename)
         con.execute("DROP TABLE IF EXISTS {table_name}".format(
@@ -58,16 +64,55 @@ def recipe(
                 type_fn += [lambda _: None]
 
         attr_names = [x[0] for x in columns]
-        with open(source_file, 'r') as f:
-            for line in f.readlines()[header_num_lines:]:
-                if is_json:
-                    x = json.loads(line)
-                    obj = [x[name] if name in x else None for name in attr_names]
-                else:
-                    obj = line.split(delimiter)
-                    assert len(obj) == len(type_fn), (obj, type_fn)
-                tpl = tuple(fn(arg) for fn, arg in zip(type_fn, obj))
-                values += [tpl]
+        dialect = json.loads(csv_dialect) if csv_dialect is not None else {}
+        null_tokens = set(dialect.get("null_tokens") or [])
+        with open(
+            source_file, 'r', newline='',
+            encoding=dialect.get("text_encoding") or 'utf-8',
+        ) as f:
+            content = f.read()
+        if dialect.get("line_terminator") is not None:
+            content = content.replace(dialect["line_terminator"], "\n")
+        lines = content.splitlines(keepends=True)
+        if dialect.get("comment_prefix") is not None:
+            lines = [x for x in lines if not x.startswith(dialect["comment_prefix"])]
+
+        def read_csv(lines):
+            return csv.reader(
+                lines,
+                delimiter=delimiter,
+                quotechar=dialect.get("quote_char") or '"',
+                escapechar=dialect.get("escape_char"),
+                doublequote=dialect.get("escape_char") is None,
+            )
+
+        positions = list(range(len(attr_names)))
+        if not is_json and map_column_names and header_num_lines > 0:
+            header = next(read_csv(lines[header_num_lines - 1:header_num_lines]))
+            positions = [
+                header.index(name) if name in header else None
+                for name in attr_names
+            ]
+
+        if is_json:
+            rows = (json.loads(line) for line in lines[header_num_lines:])
+        else:
+            rows = read_csv(lines[header_num_lines:])
+        for row in rows:
+            if is_json:
+                obj = [row[name] if name in row else None for name in attr_names]
+            else:
+                if not map_column_names:
+                    assert len(row) == len(type_fn), (row, type_fn)
+                obj = [
+                    row[i] if i is not None and row[i] not in null_tokens else None
+                    for i in positions
+                ]
+            tpl = tuple(
+                fn(arg) if arg is not None else None
+                for fn, arg in zip(type_fn, obj)
+            )
+            values += [tpl]
 
         con.executemany(
             "INSERT INTO {table_name}({columns}) VALUES ({vals})".format(
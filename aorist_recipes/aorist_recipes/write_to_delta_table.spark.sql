@@ -0,0 +1,68 @@
+/***
+@aorist_spark(
+    programs,
+    WriteToDeltaTable,
+    args={
+        "queries": lambda data_set, asset, static_data_table, replication_storage_setup, delta_table_storage: [
+            # the downloaded file is read through a temporary view, with the
+            # columns of the table (the file is read as the source encodes it)
+            "CREATE OR REPLACE TEMPORARY VIEW {view} (\n    {columns}\n)\nUSING {format}\nOPTIONS (path '{path}'{options})".format(
+                view="tmp_" + static_data_table.name,
+                columns=",\n    ".join([
+                    "{name} {spark_type}".format(name=x.name, spark_type=x.spark_type)
+                    for x in data_set.get_template(asset).attributes()
+                ]),
+                format=(
+                    "csv" if replication_storage_setup.source.encoding.delimiter is not None else
+                    "json" if replication_storage_setup.source.encoding.newline_delimited_json_encoding is not None else
+                    "orc" if replication_storage_setup.source.encoding.orc_encoding is not None else
+                    "parquet"
+                ),
+                path="{tmp_dir}/{dataset}/{table}/{table}.{extension}".format(
+                    tmp_dir=replication_storage_setup.tmp_dir,
+                    dataset=data_set.name,
+                    table=static_data_table.name,
+                    extension=replication_storage_setup.decompressed_extension,
+                ),
+                options="".join([
+                    ", %s '%s'" % (key, value.replace("'", "\\'"))
+                    for (key, value) in [
+                        ("header", "true" if replication_storage_setup.source.encoding.header is not None else None),
+                        ("sep", replication_storage_setup.source.encoding.delimiter),
+                    ] + ([
+                        ("quote", replication_storage_setup.source.encoding.dialect.quote_char),
+                        ("escape", replication_storage_setup.source.encoding.dialect.escape_char),
+                        ("lineSep", replication_storage_setup.source.encoding.dialect.line_terminator),
+                        ("encoding", replication_storage_setup.source.encoding.dialect.text_encoding),
+                        ("comment", replication_storage_setup.source.encoding.dialect.comment_prefix),
+                        # Spark reads a single token as NULL
+                        ("nullValue", (replication_storage_setup.source.encoding.dialect.null_tokens + [None])[0]),
+                    ] if replication_storage_setup.source.encoding.dialect is not None else [])
+                    if value is not None
+                ]),
+            ),
+        ] + [
+            # tables with key attributes are upserted, others appended to
+            "MERGE INTO {namespace}.{table} AS t\nUSING {view} AS s\nON {condition}\nWHEN MATCHED THEN UPDATE SET *\nWHEN NOT MATCHED THEN INSERT *".format(
+                namespace=delta_table_storage.namespace,
+                table=delta_table_storage.table,
+                view="tmp_" + static_data_table.name,
+                condition=" AND ".join([
+                    "t.{name} = s.{name}".format(name=x.name)
+                    for x in data_set.get_template(asset).attributes() if x.is_key
+                ]),
+            ) if any([
+                x.is_key for x in data_set.get_template(asset).attributes()
+            ]) else "INSERT INTO {namespace}.{table}\nSELECT {columns}\nFROM {view}".format(
+                namespace=delta_table_storage.namespace,
+                table=delta_table_storage.table,
+                view="tmp_" + static_data_table.name,
+                columns=", ".join([
+                    x.name for x in data_set.get_template(asset).attributes()
+                ]),
+            ),
+        ],
+    },
+)
+***/
+{queries}
@@ -2,6 +2,7 @@ use abi_stable::std_types::ROption;
 use aorist_util::AOption;
 
 use crate::asset::*;
+use crate::execution_policy::*;
 
 use crate::encoding::*;
 use crate::schema::*;
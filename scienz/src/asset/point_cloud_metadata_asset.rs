@@ -1,4 +1,5 @@
 use crate::asset::*;
+use crate::execution_policy::*;
 
 use crate::encoding::*;
 use crate::schema::*;
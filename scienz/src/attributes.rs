@@ -5,8 +5,9 @@ use abi_stable::StableAbi;
 #[cfg(feature = "sql")]
 use aorist_attributes::TSQLAttribute;
 use aorist_attributes::{
-    AttributeValue, TAttribute, TBigQueryAttribute, TOrcAttribute, TPostgresAttribute,
-    TPrestoAttribute, TSQLiteAttribute,
+    AttributeValue, TArrowAttribute, TAttribute, TAvroAttribute, TBigQueryAttribute,
+    TDuckDBAttribute, TOrcAttribute, TParquetAttribute, TPostgresAttribute, TPrestoAttribute,
+    TSQLiteAttribute, TSparkAttribute,
 };
 use aorist_concept::{aorist, Constrainable};
 use aorist_paste::paste;
@@ -134,6 +135,27 @@ impl IdentityTransform {
     pub fn get_sqlite_type(&self) -> AString {
         self.attribute.get_sqlite_type()
     }
+    pub fn get_duckdb_type(&self) -> AString {
+        self.attribute.get_duckdb_type()
+    }
+    pub fn get_spark_type(&self) -> AString {
+        self.attribute.get_spark_type()
+    }
+    pub fn get_parquet_physical_type(&self) -> AString {
+        self.attribute.get_parquet_physical_type()
+    }
+    pub fn get_parquet_logical_type(&self) -> AOption<AString> {
+        self.attribute.get_parquet_logical_type()
+    }
+    pub fn get_parquet_schema(&self) -> AString {
+        self.attribute.get_parquet_schema()
+    }
+    pub fn get_avro_type(&self) -> AString {
+        self.attribute.get_avro_type()
+    }
+    pub fn get_arrow_type(&self) -> AString {
+        self.attribute.get_arrow_type()
+    }
     pub fn get_postgres_type(&self) -> AString {
         self.attribute.get_postgres_type()
     }
@@ -202,6 +224,41 @@ impl Transform {
             Transform::IdentityTransform(x) => x.get_sqlite_type(),
         }
     }
+    pub fn get_duckdb_type(&self) -> AString {
+        match &self {
+            Transform::IdentityTransform(x) => x.get_duckdb_type(),
+        }
+    }
+    pub fn get_spark_type(&self) -> AString {
+        match &self {
+            Transform::IdentityTransform(x) => x.get_spark_type(),
+        }
+    }
+    pub fn get_parquet_physical_type(&self) -> AString {
+        match &self {
+            Transform::IdentityTransform(x) => x.get_parquet_physical_type(),
+        }
+    }
+    pub fn get_parquet_logical_type(&self) -> AOption<AString> {
+        match &self {
+            Transform::IdentityTransform(x) => x.get_parquet_logical_type(),
+        }
+    }
+    pub fn get_parquet_schema(&self) -> AString {
+        match &self {
+            Transform::IdentityTransform(x) => x.get_parquet_schema(),
+        }
+    }
+    pub fn get_avro_type(&self) -> AString {
+        match &self {
+            Transform::IdentityTransform(x) => x.get_avro_type(),
+        }
+    }
+    pub fn get_arrow_type(&self) -> AString {
+        match &self {
+            Transform::IdentityTransform(x) => x.get_arrow_type(),
+        }
+    }
     pub fn get_postgres_type(&self) -> AString {
         match &self {
             Transform::IdentityTransform(x) => x.get_postgres_type(),
@@ -328,6 +385,48 @@ impl AttributeOrTransform {
             AttributeOrTransform::Transform(x) => x.0.read().get_sqlite_type(),
         }
     }
+    pub fn get_duckdb_type(&self) -> AString {
+        match &self {
+            AttributeOrTransform::Attribute(x) => x.0.read().get_duckdb_type(),
+            AttributeOrTransform::Transform(x) => x.0.read().get_duckdb_type(),
+        }
+    }
+    pub fn get_spark_type(&self) -> AString {
+        match &self {
+            AttributeOrTransform::Attribute(x) => x.0.read().get_spark_type(),
+            AttributeOrTransform::Transform(x) => x.0.read().get_spark_type(),
+        }
+    }
+    pub fn get_parquet_physical_type(&self) -> AString {
+        match &self {
+            AttributeOrTransform::Attribute(x) => x.0.read().get_parquet_physical_type(),
+            AttributeOrTransform::Transform(x) => x.0.read().get_parquet_physical_type(),
+        }
+    }
+    pub fn get_parquet_logical_type(&self) -> AOption<AString> {
+        match &self {
+            AttributeOrTransform::Attribute(x) => x.0.read().get_parquet_logical_type(),
+            AttributeOrTransform::Transform(x) => x.0.read().get_parquet_logical_type(),
+        }
+    }
+    pub fn get_parquet_schema(&self) -> AString {
+        match &self {
+            AttributeOrTransform::Attribute(x) => x.0.read().get_parquet_schema(),
+            AttributeOrTransform::Transform(x) => x.0.read().get_parquet_schema(),
+        }
+    }
+    pub fn get_avro_type(&self) -> AString {
+        match &self {
+            AttributeOrTransform::Attribute(x) => x.0.read().get_avro_type(),
+            AttributeOrTransform::Transform(x) => x.0.read().get_avro_type(),
+        }
+    }
+    pub fn get_arrow_type(&self) -> AString {
+        match &self {
+            AttributeOrTransform::Attribute(x) => x.0.read().get_arrow_type(),
+            AttributeOrTransform::Transform(x) => x.0.read().get_arrow_type(),
+        }
+    }
     pub fn get_postgres_type(&self) -> AString {
         match &self {
             AttributeOrTransform::Attribute(x) => x.0.read().get_postgres_type(),
@@ -1,7 +1,12 @@
 use crate::compression::bzip2_compression::*;
 use crate::compression::gzip_compression::*;
 use crate::compression::laz_compression::*;
+use crate::compression::lz4_compression::*;
+use crate::compression::snappy_compression::*;
+use crate::compression::tar_compression::*;
+use crate::compression::xz_compression::*;
 use crate::compression::zip_compression::*;
+use crate::compression::zstd_compression::*;
 
 use abi_stable::std_types::ROption;
 use aorist_concept::{aorist, Constrainable};
@@ -24,4 +29,61 @@ pub enum DataCompression {
     ZipCompression(AoristRef<ZipCompression>),
     #[constrainable]
     LAZCompression(AoristRef<LAZCompression>),
+    #[constrainable]
+    ZstdCompression(AoristRef<ZstdCompression>),
+    #[constrainable]
+    XZCompression(AoristRef<XZCompression>),
+    #[constrainable]
+    LZ4Compression(AoristRef<LZ4Compression>),
+    #[constrainable]
+    SnappyCompression(AoristRef<SnappyCompression>),
+    #[constrainable]
+    TarCompression(AoristRef<TarCompression>),
+}
+impl DataCompression {
+    /// Extension of a file holding data with the given extension once
+    /// compressed this way. Archives are named after their own format.
+    pub fn get_compressed_extension(&self, extension: &str) -> AString {
+        match self {
+            Self::BZip2Compression(_) => format!("{}.bz2", extension).as_str().into(),
+            Self::GzipCompression(_) => format!("{}.gz", extension).as_str().into(),
+            Self::ZstdCompression(_) => format!("{}.zst", extension).as_str().into(),
+            Self::XZCompression(_) => format!("{}.xz", extension).as_str().into(),
+            Self::LZ4Compression(_) => format!("{}.lz4", extension).as_str().into(),
+            // framing format of the snappy command line tools
+            Self::SnappyCompression(_) => format!("{}.sz", extension).as_str().into(),
+            Self::ZipCompression(_) => "zip".into(),
+            Self::LAZCompression(_) => "laz".into(),
+            Self::TarCompression(x) => x.0.read().get_extension(),
+        }
+    }
+    /// Debian packages providing the command line tools decompressing
+    /// this format.
+    pub fn get_system_packages(&self) -> AVec<AString> {
+        let codec = match self {
+            Self::TarCompression(x) => x.0.read().codec.clone().0.into_option(),
+            _ => None,
+        };
+        match (self, codec.as_ref().map(|x| x.as_str())) {
+            (Self::BZip2Compression(_), _) | (Self::TarCompression(_), Some("bzip2")) => {
+                vec!["bzip2".into()]
+            }
+            (Self::ZstdCompression(_), _) | (Self::TarCompression(_), Some("zstd")) => {
+                vec!["zstd".into()]
+            }
+            (Self::XZCompression(_), _) | (Self::TarCompression(_), Some("xz")) => {
+                vec!["xz-utils".into()]
+            }
+            (Self::LZ4Compression(_), _) => vec!["lz4".into()],
+            (Self::ZipCompression(_), _) => vec!["unzip".into()],
+            _ => vec![],
+        }
+        .into_iter()
+        .collect()
+    }
+    /// Whether data has to be decompressed before it is used. LAZ point
+    /// clouds are read compressed.
+    pub fn is_decompressed_before_use(&self) -> bool {
+        !matches!(self, Self::LAZCompression(_))
+    }
 }
@@ -2,10 +2,20 @@ mod bzip2_compression;
 mod data_compression;
 mod gzip_compression;
 mod laz_compression;
+mod lz4_compression;
+mod snappy_compression;
+mod tar_compression;
+mod xz_compression;
 mod zip_compression;
+mod zstd_compression;
 
 pub use bzip2_compression::*;
 pub use data_compression::*;
 pub use gzip_compression::*;
 pub use laz_compression::*;
+pub use lz4_compression::*;
+pub use snappy_compression::*;
+pub use tar_compression::*;
+pub use xz_compression::*;
 pub use zip_compression::*;
+pub use zstd_compression::*;
@@ -0,0 +1,31 @@
+use abi_stable::std_types::ROption;
+use aorist_concept::{aorist, Constrainable};
+use aorist_paste::paste;
+use aorist_primitives::{AoristConceptBase, ConceptEnum};
+use aorist_util::AOption;
+use aorist_util::AUuid;
+use aorist_util::AoristRef;
+use aorist_util::{AString, AVec};
+use derivative::Derivative;
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+
+/// A tar archive, possibly compressed as a whole (.tar.gz, .tar.zst).
+#[aorist]
+pub struct TarCompression {
+    // codec the archive is compressed with: "gzip", "bzip2", "xz" or "zstd"
+    pub codec: AOption<AString>,
+    // members to extract, as paths or glob patterns; all if empty
+    pub members: AVec<AString>,
+}
+impl TarCompression {
+    pub fn get_extension(&self) -> AString {
+        match self.codec.0.as_ref().map(|x| x.as_str()).into_option() {
+            Some("gzip") => "tar.gz".into(),
+            Some("bzip2") => "tar.bz2".into(),
+            Some("xz") => "tar.xz".into(),
+            Some("zstd") => "tar.zst".into(),
+            _ => "tar".into(),
+        }
+    }
+}
@@ -1,6 +1,7 @@
 #![allow(dead_code)]
 use crate::access_policy::*;
 use crate::asset::*;
+use crate::execution_policy::*;
 #[cfg(feature = "python")]
 use crate::encoding::*;
 #[cfg(feature = "python")]
@@ -38,6 +39,8 @@ pub struct DataSet {
     pub datum_templates: AVec<AoristRef<DatumTemplate>>,
     #[constrainable]
     pub assets: AVec<AoristRef<Asset>>,
+    #[constrainable]
+    pub execution_policy: AOption<AoristRef<ExecutionPolicy>>,
 }
 
 impl DataSet {
@@ -144,6 +147,7 @@ impl PyDataSet {
             access_policies: dt.access_policies.clone(),
             datum_templates: dt.datum_templates.clone(),
             assets: persisted_assets,
+            execution_policy: dt.execution_policy.clone(),
             tag: dt.tag.clone(),
             uuid: dt.uuid.clone(),
         })));
@@ -176,6 +180,7 @@ impl PyDataSet {
             access_policies: dt.access_policies.clone(),
             datum_templates: dt.datum_templates.clone(),
             assets: replicated_assets,
+            execution_policy: dt.execution_policy.clone(),
             tag: dt.tag.clone(),
             uuid: dt.uuid.clone(),
         })));
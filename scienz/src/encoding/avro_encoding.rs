@@ -0,0 +1,58 @@
+use crate::asset::TAsset;
+use crate::encoding::Encoding;
+use crate::Concept;
+use abi_stable::std_types::ROption;
+use aorist_concept::{aorist, Constrainable};
+use aorist_paste::paste;
+use aorist_primitives::{AoristConceptBase, ConceptEnum, ToplineConcept};
+use aorist_util::AOption;
+use aorist_util::AUuid;
+use aorist_util::AoristRef;
+use aorist_util::{AString, AVec};
+use derivative::Derivative;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Debug;
+
+#[aorist]
+pub struct AvroEncoding {}
+
+fn is_avro_encoded(concept: &AoristRef<Concept>) -> bool {
+    let mut concept_map = HashMap::new();
+    concept.populate_child_concept_map(&mut concept_map);
+    concept_map.into_values().any(|x| match &*x.0.read() {
+        Concept::Encoding(ref encoding) => matches!(
+            &*encoding.get_reference().0.read(),
+            Encoding::AvroEncoding(_)
+        ),
+        _ => false,
+    })
+}
+
+/// Avro schemas (.avsc contents) of the assets under `concept` stored
+/// with an AvroEncoding, keyed by asset name.
+pub fn get_avro_schemas(concept: &AoristRef<Concept>) -> BTreeMap<AString, AString> {
+    let mut concept_map = HashMap::new();
+    concept.populate_child_concept_map(&mut concept_map);
+    concept_map
+        .into_values()
+        .filter_map(|x| {
+            let asset = match &*x.0.read() {
+                Concept::Asset(ref asset) => asset.get_reference(),
+                _ => return None,
+            };
+            if !is_avro_encoded(&x) {
+                return None;
+            }
+            let asset = asset.0.read();
+            let schema = asset.get_schema().0.read().get_avro_schema().ok()?;
+            Some((
+                asset.get_name(),
+                serde_json::to_string_pretty(&schema)
+                    .unwrap()
+                    .as_str()
+                    .into(),
+            ))
+        })
+        .collect()
+}
@@ -0,0 +1,27 @@
+use abi_stable::std_types::ROption;
+use aorist_concept::{aorist, Constrainable};
+use aorist_paste::paste;
+use aorist_primitives::{AoristConceptBase, ConceptEnum};
+use aorist_util::AOption;
+use aorist_util::AUuid;
+use aorist_util::AoristRef;
+use aorist_util::{AString, AVec};
+use derivative::Derivative;
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+
+/// A GeoTIFF laid out for HTTP range requests: internally tiled, with
+/// overviews stored after the full-resolution image.
+#[aorist]
+pub struct CloudOptimizedGeoTiffEncoding {
+    // coordinate reference system, e.g. "EPSG:3857"
+    pub crs: AOption<AString>,
+    // tile width and height, in pixels (GDAL defaults to 512)
+    pub block_size: AOption<usize>,
+    // whether to generate overviews
+    pub overviews: bool,
+    // resampling used for overviews, e.g. "AVERAGE" or "NEAREST"
+    pub overview_resampling: AOption<AString>,
+    // "DEFLATE", "LZW", "ZSTD", "WEBP", etc.
+    pub compression_codec: AOption<AString>,
+}
@@ -0,0 +1,28 @@
+use abi_stable::std_types::ROption;
+use aorist_concept::{aorist, Constrainable};
+use aorist_paste::paste;
+use aorist_primitives::{AoristConceptBase, ConceptEnum};
+use aorist_util::AOption;
+use aorist_util::AUuid;
+use aorist_util::AoristRef;
+use aorist_util::{AString, AVec};
+use derivative::Derivative;
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+
+/// How a delimited text file is laid out. Unset fields fall back to the
+/// defaults of the enclosing CSV or TSV encoding.
+#[aorist]
+pub struct CSVDialect {
+    pub delimiter: AOption<AString>,
+    pub quote_char: AOption<AString>,
+    // character escaping delimiters and quotes; quotes are doubled if unset
+    pub escape_char: AOption<AString>,
+    pub line_terminator: AOption<AString>,
+    // text encoding of the file, e.g. "utf-8" or "latin-1"
+    pub text_encoding: AOption<AString>,
+    // values to be read as NULL, e.g. "NA" or ""
+    pub null_tokens: AVec<AString>,
+    // lines starting with this prefix are skipped
+    pub comment_prefix: AOption<AString>,
+}
@@ -1,4 +1,5 @@
 use crate::compression::*;
+use crate::encoding::csv_dialect::*;
 use crate::header::*;
 use abi_stable::std_types::ROption;
 use aorist_concept::{aorist, Constrainable};
@@ -18,4 +19,6 @@ pub struct CSVEncoding {
     pub compression: AOption<AoristRef<DataCompression>>,
     #[constrainable]
     pub header: AOption<AoristRef<FileHeader>>,
+    #[constrainable]
+    pub dialect: AOption<AoristRef<CSVDialect>>,
 }
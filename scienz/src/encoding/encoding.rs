@@ -1,27 +1,39 @@
 #![allow(unused_parens)]
 use crate::compression::*;
+use crate::encoding::arrow_ipc_encoding::*;
+use crate::encoding::avro_encoding::*;
+use crate::encoding::cloud_optimized_geotiff_encoding::*;
+use crate::encoding::csv_dialect::*;
 use crate::encoding::csv_encoding::*;
+use crate::encoding::feather_encoding::*;
+use crate::encoding::flatgeobuf_encoding::*;
 use crate::encoding::gdb_encoding::*;
+use crate::encoding::geoparquet_encoding::*;
 use crate::encoding::geotiff_encoding::*;
 use crate::encoding::gpkg_encoding::*;
+use crate::encoding::hdf5_encoding::*;
 use crate::encoding::json_encoding::*;
 use crate::encoding::kml_encoding::*;
 use crate::encoding::las_encoding::*;
+use crate::encoding::netcdf_encoding::*;
 use crate::encoding::onnx_encoding::*;
 use crate::encoding::orc_encoding::*;
+use crate::encoding::parquet_encoding::*;
 use crate::encoding::shapefile_encoding::*;
 use crate::encoding::sqlite_encoding::*;
 use crate::encoding::tiff_encoding::*;
 use crate::encoding::tsv_encoding::*;
 use crate::encoding::wkt_encoding::*;
 use crate::encoding::xml_encoding::*;
+use crate::encoding::zarr_encoding::*;
 #[cfg(feature = "python")]
 use crate::header::FileHeader;
 use crate::header::*;
+use crate::Concept;
 use abi_stable::std_types::ROption;
 use aorist_concept::{aorist, Constrainable};
 use aorist_paste::paste;
-use aorist_primitives::{AoristConceptBase, ConceptEnum};
+use aorist_primitives::{AoristConceptBase, ConceptEnum, ToplineConcept};
 use aorist_util::AOption;
 use aorist_util::AUuid;
 use aorist_util::AoristRef;
@@ -29,6 +41,7 @@ use aorist_util::{AString, AVec};
 #[cfg(feature = "python")]
 use pyo3::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
 use std::fmt::Debug;
 
 #[aorist]
@@ -37,6 +50,10 @@ pub enum Encoding {
     JSONEncoding(AoristRef<JSONEncoding>),
     NewlineDelimitedJSONEncoding(AoristRef<NewlineDelimitedJSONEncoding>),
     ORCEncoding(AoristRef<ORCEncoding>),
+    ParquetEncoding(AoristRef<ParquetEncoding>),
+    AvroEncoding(AoristRef<AvroEncoding>),
+    ArrowIPCEncoding(AoristRef<ArrowIPCEncoding>),
+    FeatherEncoding(AoristRef<FeatherEncoding>),
     TSVEncoding(AoristRef<TSVEncoding>),
     ONNXEncoding(AoristRef<ONNXEncoding>),
     GDBEncoding(AoristRef<GDBEncoding>),
@@ -49,6 +66,12 @@ pub enum Encoding {
     XMLEncoding(AoristRef<XMLEncoding>),
     KMLEncoding(AoristRef<KMLEncoding>),
     GPKGEncoding(AoristRef<GPKGEncoding>),
+    NetCDFEncoding(AoristRef<NetCDFEncoding>),
+    HDF5Encoding(AoristRef<HDF5Encoding>),
+    ZarrEncoding(AoristRef<ZarrEncoding>),
+    GeoParquetEncoding(AoristRef<GeoParquetEncoding>),
+    FlatGeobufEncoding(AoristRef<FlatGeobufEncoding>),
+    CloudOptimizedGeoTiffEncoding(AoristRef<CloudOptimizedGeoTiffEncoding>),
 }
 
 impl Encoding {
@@ -59,6 +82,10 @@ impl Encoding {
             Self::TSVEncoding(x) => x.0.read().header.clone(),
             Self::JSONEncoding(_) => AOption(ROption::RNone),
             Self::ORCEncoding(_) => AOption(ROption::RNone),
+            Self::ParquetEncoding(_) => AOption(ROption::RNone),
+            Self::AvroEncoding(_) => AOption(ROption::RNone),
+            Self::ArrowIPCEncoding(_) => AOption(ROption::RNone),
+            Self::FeatherEncoding(_) => AOption(ROption::RNone),
             Self::ONNXEncoding(_) => AOption(ROption::RNone),
             Self::GDBEncoding(_) => AOption(ROption::RNone),
             Self::LASEncoding(_) => AOption(ROption::RNone),
@@ -71,6 +98,35 @@ impl Encoding {
             Self::XMLEncoding(_) => AOption(ROption::RNone),
             Self::KMLEncoding(_) => AOption(ROption::RNone),
             Self::GPKGEncoding(_) => AOption(ROption::RNone),
+            Self::NetCDFEncoding(_) => AOption(ROption::RNone),
+            Self::HDF5Encoding(_) => AOption(ROption::RNone),
+            Self::ZarrEncoding(_) => AOption(ROption::RNone),
+            Self::GeoParquetEncoding(_) => AOption(ROption::RNone),
+            Self::FlatGeobufEncoding(_) => AOption(ROption::RNone),
+            Self::CloudOptimizedGeoTiffEncoding(_) => AOption(ROption::RNone),
+        }
+    }
+    pub fn get_dialect(&self) -> AOption<AoristRef<CSVDialect>> {
+        match &self {
+            Self::CSVEncoding(x) => x.0.read().dialect.clone(),
+            Self::TSVEncoding(x) => x.0.read().dialect.clone(),
+            _ => AOption(ROption::RNone),
+        }
+    }
+    /// Field delimiter of delimited text encodings, falling back to a
+    /// comma for CSV and a tab for TSV.
+    pub fn get_delimiter(&self) -> AOption<AString> {
+        let default: AString = match &self {
+            Self::CSVEncoding(_) => ",".into(),
+            Self::TSVEncoding(_) => "\t".into(),
+            _ => return AOption(ROption::RNone),
+        };
+        match self.get_dialect() {
+            AOption(ROption::RSome(x)) => match x.0.read().delimiter {
+                AOption(ROption::RSome(ref d)) => AOption(ROption::RSome(d.clone())),
+                AOption(ROption::RNone) => AOption(ROption::RSome(default)),
+            },
+            AOption(ROption::RNone) => AOption(ROption::RSome(default)),
         }
     }
     pub fn get_compression(&self) -> AOption<AoristRef<DataCompression>> {
@@ -88,10 +144,23 @@ impl Encoding {
             Self::GPKGEncoding(x) => x.0.read().compression.clone(),
             Self::JSONEncoding(_) => AOption(ROption::RNone),
             Self::ORCEncoding(_) => AOption(ROption::RNone),
+            // column chunks are compressed internally, see compression_codec
+            Self::ParquetEncoding(_) => AOption(ROption::RNone),
+            Self::ArrowIPCEncoding(_) => AOption(ROption::RNone),
+            Self::FeatherEncoding(_) => AOption(ROption::RNone),
+            Self::AvroEncoding(_) => AOption(ROption::RNone),
             Self::ONNXEncoding(_) => AOption(ROption::RNone),
             Self::SQLiteEncoding(_) => AOption(ROption::RNone),
             Self::NewlineDelimitedJSONEncoding(_) => AOption(ROption::RNone),
             Self::ShapefileEncoding(_) => AOption(ROption::RNone),
+            // compressed per chunk, see compressor
+            Self::NetCDFEncoding(_) => AOption(ROption::RNone),
+            Self::HDF5Encoding(_) => AOption(ROption::RNone),
+            Self::ZarrEncoding(_) => AOption(ROption::RNone),
+            // compressed internally, see compression_codec
+            Self::GeoParquetEncoding(_) => AOption(ROption::RNone),
+            Self::FlatGeobufEncoding(_) => AOption(ROption::RNone),
+            Self::CloudOptimizedGeoTiffEncoding(_) => AOption(ROption::RNone),
         }
     }
     pub fn get_default_file_extension(&self) -> AString {
@@ -110,13 +179,77 @@ impl Encoding {
             Self::ShapefileEncoding(_) => "shp".into(),
             Self::JSONEncoding(_) => "json".into(),
             Self::ORCEncoding(_) => "orc".into(),
+            Self::ParquetEncoding(_) => "parquet".into(),
+            Self::AvroEncoding(_) => "avro".into(),
+            Self::ArrowIPCEncoding(_) => "arrow".into(),
+            Self::FeatherEncoding(_) => "feather".into(),
             Self::ONNXEncoding(_) => "onnx".into(),
             Self::SQLiteEncoding(_) => "sqlite".into(),
             Self::NewlineDelimitedJSONEncoding(_) => "json".into(),
+            Self::NetCDFEncoding(_) => "nc".into(),
+            Self::HDF5Encoding(_) => "h5".into(),
+            Self::ZarrEncoding(_) => "zarr".into(),
+            Self::GeoParquetEncoding(_) => "parquet".into(),
+            Self::FlatGeobufEncoding(_) => "fgb".into(),
+            Self::CloudOptimizedGeoTiffEncoding(_) => "tif".into(),
+        }
+    }
+    /// Debian packages providing the tools that read and write this
+    /// encoding.
+    pub fn get_system_packages(&self) -> AVec<AString> {
+        match &self {
+            Self::GDBEncoding(_)
+            | Self::GeoTiffEncoding(_)
+            | Self::CloudOptimizedGeoTiffEncoding(_)
+            | Self::WKTEncoding(_)
+            | Self::KMLEncoding(_)
+            | Self::GPKGEncoding(_)
+            | Self::ShapefileEncoding(_)
+            | Self::GeoParquetEncoding(_)
+            | Self::FlatGeobufEncoding(_) => vec!["gdal-bin".into()],
+            Self::LASEncoding(_) => vec!["pdal".into()],
+            Self::NetCDFEncoding(_) => vec!["netcdf-bin".into()],
+            Self::HDF5Encoding(_) => vec!["hdf5-tools".into()],
+            Self::CSVEncoding(_)
+            | Self::TSVEncoding(_)
+            | Self::TiffEncoding(_)
+            | Self::XMLEncoding(_)
+            | Self::JSONEncoding(_)
+            | Self::ORCEncoding(_)
+            | Self::ParquetEncoding(_)
+            | Self::AvroEncoding(_)
+            | Self::ArrowIPCEncoding(_)
+            | Self::FeatherEncoding(_)
+            | Self::ONNXEncoding(_)
+            | Self::SQLiteEncoding(_)
+            | Self::NewlineDelimitedJSONEncoding(_)
+            | Self::ZarrEncoding(_) => vec![],
         }
+        .into_iter()
+        .collect()
     }
 }
 
+/// System packages needed for all encodings and compressions under
+/// `concept`.
+pub fn get_system_packages(concept: &AoristRef<Concept>) -> BTreeSet<AString> {
+    let mut concept_map = HashMap::new();
+    concept.populate_child_concept_map(&mut concept_map);
+    concept_map
+        .into_values()
+        .filter_map(|x| match &*x.0.read() {
+            Concept::Encoding(ref encoding) => {
+                Some(encoding.get_reference().0.read().get_system_packages())
+            }
+            Concept::DataCompression(ref compression) => {
+                Some(compression.get_reference().0.read().get_system_packages())
+            }
+            _ => None,
+        })
+        .flatten()
+        .collect()
+}
+
 #[cfg(feature = "python")]
 #[pymethods]
 impl PyEncoding {
@@ -164,10 +297,20 @@ impl PyEncoding {
             },
             Encoding::JSONEncoding(_) => None,
             Encoding::ORCEncoding(_) => None,
+            Encoding::ParquetEncoding(_) => None,
+            Encoding::AvroEncoding(_) => None,
+            Encoding::ArrowIPCEncoding(_) => None,
+            Encoding::FeatherEncoding(_) => None,
             Encoding::ONNXEncoding(_) => None,
             Encoding::ShapefileEncoding(_) => None,
             Encoding::SQLiteEncoding(_) => None,
             Encoding::NewlineDelimitedJSONEncoding(_) => None,
+            Encoding::NetCDFEncoding(_) => None,
+            Encoding::HDF5Encoding(_) => None,
+            Encoding::ZarrEncoding(_) => None,
+            Encoding::GeoParquetEncoding(_) => None,
+            Encoding::FlatGeobufEncoding(_) => None,
+            Encoding::CloudOptimizedGeoTiffEncoding(_) => None,
         })
     }
     #[getter]
@@ -177,4 +320,50 @@ impl PyEncoding {
             AOption(ROption::RNone) => None,
         }
     }
+    /// Dialect of CSV and TSV encodings, if one was given.
+    #[getter]
+    pub fn dialect(&self) -> Option<PyCSVDialect> {
+        match self.inner.0.read().get_dialect() {
+            AOption(ROption::RSome(x)) => Some(PyCSVDialect { inner: x.clone() }),
+            AOption(ROption::RNone) => None,
+        }
+    }
+    #[getter]
+    pub fn delimiter(&self) -> Option<String> {
+        match self.inner.0.read().get_delimiter() {
+            AOption(ROption::RSome(x)) => Some(x.as_str().into()),
+            AOption(ROption::RNone) => None,
+        }
+    }
+    /// Codec used internally by columnar and cloud-native encodings
+    /// (Parquet, Arrow IPC, Feather, GeoParquet and COG).
+    #[getter]
+    pub fn compression_codec(&self) -> Option<String> {
+        let codec = match &*self.inner.0.read() {
+            Encoding::ParquetEncoding(x) => x.0.read().compression_codec.clone(),
+            Encoding::ArrowIPCEncoding(x) => x.0.read().compression_codec.clone(),
+            Encoding::FeatherEncoding(x) => x.0.read().compression_codec.clone(),
+            Encoding::GeoParquetEncoding(x) => x.0.read().compression_codec.clone(),
+            Encoding::CloudOptimizedGeoTiffEncoding(x) => x.0.read().compression_codec.clone(),
+            _ => AOption(ROption::RNone),
+        };
+        match codec {
+            AOption(ROption::RSome(x)) => Some(x.as_str().into()),
+            AOption(ROption::RNone) => None,
+        }
+    }
+    /// Coordinate reference system of cloud-native geospatial encodings.
+    #[getter]
+    pub fn crs(&self) -> Option<String> {
+        let crs = match &*self.inner.0.read() {
+            Encoding::GeoParquetEncoding(x) => x.0.read().crs.clone(),
+            Encoding::FlatGeobufEncoding(x) => x.0.read().crs.clone(),
+            Encoding::CloudOptimizedGeoTiffEncoding(x) => x.0.read().crs.clone(),
+            _ => AOption(ROption::RNone),
+        };
+        match crs {
+            AOption(ROption::RSome(x)) => Some(x.as_str().into()),
+            AOption(ROption::RNone) => None,
+        }
+    }
 }
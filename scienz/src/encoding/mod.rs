@@ -1,33 +1,55 @@
+mod arrow_ipc_encoding;
+mod avro_encoding;
+mod cloud_optimized_geotiff_encoding;
+mod csv_dialect;
 mod csv_encoding;
 mod encoding;
+mod feather_encoding;
+mod flatgeobuf_encoding;
 mod gdb_encoding;
+mod geoparquet_encoding;
 mod geotiff_encoding;
 mod gpkg_encoding;
+mod hdf5_encoding;
 mod json_encoding;
 mod kml_encoding;
 mod las_encoding;
+mod netcdf_encoding;
 mod onnx_encoding;
 mod orc_encoding;
+mod parquet_encoding;
 mod shapefile_encoding;
 mod sqlite_encoding;
 mod tiff_encoding;
 mod tsv_encoding;
 mod wkt_encoding;
 mod xml_encoding;
+mod zarr_encoding;
 
+pub use arrow_ipc_encoding::*;
+pub use avro_encoding::*;
+pub use cloud_optimized_geotiff_encoding::*;
+pub use csv_dialect::*;
 pub use csv_encoding::*;
 pub use encoding::*;
+pub use feather_encoding::*;
+pub use flatgeobuf_encoding::*;
 pub use gdb_encoding::*;
+pub use geoparquet_encoding::*;
 pub use geotiff_encoding::*;
 pub use gpkg_encoding::*;
+pub use hdf5_encoding::*;
 pub use json_encoding::*;
 pub use kml_encoding::*;
 pub use las_encoding::*;
+pub use netcdf_encoding::*;
 pub use onnx_encoding::*;
 pub use orc_encoding::*;
+pub use parquet_encoding::*;
 pub use shapefile_encoding::*;
 pub use sqlite_encoding::*;
 pub use tiff_encoding::*;
 pub use tsv_encoding::*;
 pub use wkt_encoding::*;
 pub use xml_encoding::*;
+pub use zarr_encoding::*;
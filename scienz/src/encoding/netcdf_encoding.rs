@@ -0,0 +1,19 @@
+use abi_stable::std_types::ROption;
+use aorist_concept::{aorist, Constrainable};
+use aorist_paste::paste;
+use aorist_primitives::{AoristConceptBase, ConceptEnum};
+use aorist_util::AOption;
+use aorist_util::AUuid;
+use aorist_util::AoristRef;
+use aorist_util::{AString, AVec};
+use derivative::Derivative;
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+
+#[aorist]
+pub struct NetCDFEncoding {
+    // chunk shape, one entry per dimension; empty if not chunked
+    pub chunks: AVec<usize>,
+    // compression filter, e.g. "zlib"
+    pub compressor: AOption<AString>,
+}
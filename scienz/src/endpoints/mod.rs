@@ -2,9 +2,10 @@ use abi_stable::std_types::ROption;
 use aorist_concept::{aorist, Constrainable};
 use aorist_paste::paste;
 use aorist_primitives::{
-    AWSConfig, AlluxioConfig, AoristConceptBase, ConceptEnum, DaskConfig, GCPConfig,
-    GDALConfig, GiteaConfig, LINZAPIConfig, MinioConfig, PDALConfig, PostgresConfig, PrestoConfig,
-    RangerConfig, TPrestoEndpoints,
+    AWSConfig, AlluxioConfig, AoristConceptBase, AzureConfig, ConceptEnum, DaskConfig, GCPConfig,
+    GDALConfig, GiteaConfig, HDFSConfig, LINZAPIConfig, MinioConfig, PDALConfig, PostgresConfig,
+    PrestoConfig, RangerConfig, SFTPConfig, SparkConfig, TPrestoEndpoints, TSparkEndpoints,
+    ValidationCheck,
 };
 use aorist_util::AOption;
 use aorist_util::AUuid;
@@ -27,6 +28,10 @@ pub struct EndpointConfig {
     pub linz: AOption<LINZAPIConfig>,
     pub dask: AOption<DaskConfig>,
     pub gdal: AOption<GDALConfig>,
+    pub spark: AOption<SparkConfig>,
+    pub azure: AOption<AzureConfig>,
+    pub hdfs: AOption<HDFSConfig>,
+    pub sftp: AOption<SFTPConfig>,
 }
 
 impl TPrestoEndpoints for EndpointConfig {
@@ -34,3 +39,85 @@ impl TPrestoEndpoints for EndpointConfig {
         self.presto.as_ref().unwrap().clone()
     }
 }
+
+impl TSparkEndpoints for EndpointConfig {
+    fn spark_config(&self) -> SparkConfig {
+        self.spark.as_ref().unwrap().clone()
+    }
+}
+
+/// host and port of a spark:// or thrift:// URI
+fn get_host_and_port(uri: &str, scheme: &str) -> Option<(AString, usize)> {
+    let (host, port) = uri.strip_prefix(scheme)?.split('/').next()?.rsplit_once(':')?;
+    Some((host.into(), port.parse().ok()?))
+}
+
+impl EndpointConfig {
+    /// Checks that the configured endpoints accept connections. Gitea and
+    /// Ranger do not expose their addresses, and are not checked.
+    pub fn get_validation_checks(&self) -> AVec<ValidationCheck> {
+        let mut checks = AVec::new();
+        if let AOption(ROption::RSome(ref x)) = self.presto {
+            checks.push(ValidationCheck::EndpointReachable(
+                "presto".into(),
+                x.server.clone(),
+                x.http_port,
+            ));
+        }
+        if let AOption(ROption::RSome(ref x)) = self.alluxio {
+            checks.push(ValidationCheck::EndpointReachable(
+                "alluxio".into(),
+                x.server.clone(),
+                x.rpc_port,
+            ));
+        }
+        if let AOption(ROption::RSome(ref x)) = self.minio {
+            checks.push(ValidationCheck::EndpointReachable(
+                "minio".into(),
+                x.server.clone(),
+                x.port,
+            ));
+        }
+        if let AOption(ROption::RSome(ref x)) = self.postgres {
+            checks.push(ValidationCheck::EndpointReachable(
+                "postgres".into(),
+                x.server.clone(),
+                x.port,
+            ));
+        }
+        if let AOption(ROption::RSome(ref x)) = self.dask {
+            checks.push(ValidationCheck::EndpointReachable(
+                "dask".into(),
+                x.server.clone(),
+                x.port,
+            ));
+        }
+        // local and YARN masters have no address to check
+        if let AOption(ROption::RSome(ref x)) = self.spark {
+            if let Some((host, port)) = get_host_and_port(x.master.as_str(), "spark://") {
+                checks.push(ValidationCheck::EndpointReachable("spark".into(), host, port));
+            }
+            if let AOption(ROption::RSome(ref uri)) = x.metastore_uri {
+                if let Some((host, port)) = get_host_and_port(uri.as_str(), "thrift://") {
+                    checks.push(ValidationCheck::EndpointReachable(
+                        "hive-metastore".into(),
+                        host,
+                        port,
+                    ));
+                }
+            }
+        }
+        checks
+    }
+    /// Debian packages providing the command-line tools configured here.
+    pub fn get_system_packages(&self) -> AVec<AString> {
+        let mut packages = AVec::new();
+        if let AOption(ROption::RSome(_)) = self.gdal {
+            packages.push("gdal-bin".into());
+        }
+        if let AOption(ROption::RSome(_)) = self.pdal {
+            packages.push("pdal".into());
+        }
+        packages
+    }
+}
@@ -0,0 +1,56 @@
+use crate::Concept;
+use abi_stable::std_types::ROption;
+use aorist_concept::{aorist, Constrainable};
+use aorist_paste::paste;
+use aorist_primitives::{AoristConceptBase, ConceptEnum, TaskPolicy, ToplineConcept};
+use aorist_util::AOption;
+use aorist_util::AUuid;
+use aorist_util::AoristRef;
+use aorist_util::{AString, ATaskId, AVec};
+use derivative::Derivative;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt::Debug;
+
+#[aorist]
+pub struct ExecutionPolicy {
+    pub retries: AOption<usize>,
+    // seconds
+    pub retry_delay: AOption<usize>,
+    pub exponential_backoff: AOption<bool>,
+    // seconds
+    pub timeout: AOption<usize>,
+    // Kubernetes quantities, e.g. "8Gi" or "500m"
+    pub memory: AOption<AString>,
+    pub cpu: AOption<AString>,
+    pub pool: AOption<AString>,
+}
+impl ExecutionPolicy {
+    pub fn get_task_policy(&self) -> TaskPolicy {
+        TaskPolicy {
+            retries: AOption(self.retries.0.map(|x| x as i64)),
+            retry_delay: AOption(self.retry_delay.0.map(|x| x as i64)),
+            exponential_backoff: self.exponential_backoff.clone(),
+            timeout: AOption(self.timeout.0.map(|x| x as i64)),
+            memory: self.memory.clone(),
+            cpu: self.cpu.clone(),
+            pool: self.pool.clone(),
+        }
+    }
+}
+
+/// Policies attached to `concept` and its descendants, keyed by the
+/// concept each one is attached to.
+pub fn get_execution_policies(concept: &AoristRef<Concept>) -> HashMap<ATaskId, TaskPolicy> {
+    let mut concept_map = HashMap::new();
+    concept.populate_child_concept_map(&mut concept_map);
+    concept_map
+        .into_values()
+        .filter_map(|x| match (&*x.0.read(), x.get_parent_id()) {
+            (Concept::ExecutionPolicy(ref policy), AOption(ROption::RSome(parent))) => {
+                Some((parent, policy.get_reference().0.read().get_task_policy()))
+            }
+            _ => None,
+        })
+        .collect()
+}
@@ -13,4 +13,7 @@ use std::fmt::Debug;
 #[aorist]
 pub struct CSVHeader {
     pub num_lines: AOption<usize>,
+    // whether column names in the header are matched to attribute names;
+    // otherwise the header is skipped and columns follow attribute order
+    pub map_column_names: AOption<bool>,
 }
@@ -26,6 +26,14 @@ impl FileHeader {
             AOption(ROption::RSome(n)) => n,
         }
     }
+    pub fn get_map_column_names(&self) -> bool {
+        let FileHeader::CSVHeader(x) = self;
+        let read = x.0.read();
+        match read.map_column_names {
+            AOption(ROption::RNone) => false,
+            AOption(ROption::RSome(b)) => b,
+        }
+    }
 }
 #[cfg(feature = "python")]
 #[pymethods]
@@ -34,4 +42,8 @@ impl PyFileHeader {
     pub fn get_num_lines(&self) -> usize {
         self.inner.0.read().get_num_lines()
     }
+    #[getter]
+    pub fn get_map_column_names(&self) -> bool {
+        self.inner.0.read().get_map_column_names()
+    }
 }
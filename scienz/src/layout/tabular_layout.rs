@@ -1,31 +1,115 @@
+use crate::Concept;
 use abi_stable::std_types::ROption;
+use anyhow::Result;
 use aorist_concept::{aorist, Constrainable};
 use aorist_paste::paste;
-use aorist_primitives::{AoristConceptBase, ConceptEnum};
+use aorist_primitives::{
+    AoristConceptBase, ConceptEnum, Schedule, ScheduleFrequency, ToplineConcept,
+};
 use aorist_util::AOption;
 use aorist_util::AUuid;
 use aorist_util::AoristRef;
 use aorist_util::{AString, AVec};
 use derivative::Derivative;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use std::fmt::Debug;
 
 #[aorist]
 pub struct StaticTabularLayout {}
 
+#[aorist]
+pub struct HourlyGranularity {}
+
 #[aorist]
 pub struct DailyGranularity {}
 
+#[aorist]
+pub struct WeeklyGranularity {}
+
+#[aorist]
+pub struct MonthlyGranularity {}
+
+#[aorist]
+pub struct CronGranularity {
+    // standard 5-field cron expression, e.g. "30 6 * * 1-5"
+    pub expression: AString,
+}
+
 #[aorist]
 pub enum Granularity {
+    #[constrainable]
+    HourlyGranularity(AoristRef<HourlyGranularity>),
     #[constrainable]
     DailyGranularity(AoristRef<DailyGranularity>),
+    #[constrainable]
+    WeeklyGranularity(AoristRef<WeeklyGranularity>),
+    #[constrainable]
+    MonthlyGranularity(AoristRef<MonthlyGranularity>),
+    #[constrainable]
+    CronGranularity(AoristRef<CronGranularity>),
+}
+impl Granularity {
+    pub fn get_frequency(&self) -> ScheduleFrequency {
+        match self {
+            Self::HourlyGranularity(_) => ScheduleFrequency::Hourly,
+            Self::DailyGranularity(_) => ScheduleFrequency::Daily,
+            Self::WeeklyGranularity(_) => ScheduleFrequency::Weekly,
+            Self::MonthlyGranularity(_) => ScheduleFrequency::Monthly,
+            Self::CronGranularity(x) => ScheduleFrequency::Cron(x.0.read().expression.clone()),
+        }
+    }
 }
 
 #[aorist]
 pub struct DynamicTabularLayout {
     #[constrainable]
     granularity: AoristRef<Granularity>,
+    // first and last partition to backfill (YYYY-MM-DD)
+    pub start_date: AOption<AString>,
+    pub end_date: AOption<AString>,
+}
+impl DynamicTabularLayout {
+    pub fn get_schedule(&self) -> Result<Schedule> {
+        Schedule::new(
+            self.granularity.0.read().get_frequency(),
+            self.start_date.clone(),
+            self.end_date.clone(),
+        )
+    }
+}
+
+/// Schedule of a flow processing `concept` and its descendants, derived
+/// from the dynamic layouts the flow's constraints apply to: those found
+/// in (or at) concepts of one of the constraints' `root_types`. Fails if
+/// their granularities differ.
+pub fn get_flow_schedule(
+    concept: &AoristRef<Concept>,
+    root_types: &HashSet<AString>,
+) -> Result<AOption<Schedule>> {
+    // depth-first, so that error messages do not depend on hashing
+    fn collect_schedules(
+        concept: &AoristRef<Concept>,
+        root_types: &HashSet<AString>,
+        covered: bool,
+        schedules: &mut AVec<Schedule>,
+    ) -> Result<()> {
+        let covered = covered || root_types.contains(&concept.get_type());
+        if let Concept::DynamicTabularLayout(ref layout) = *concept.0.read() {
+            if covered {
+                schedules.push(layout.get_reference().0.read().get_schedule()?);
+            }
+        }
+        for child in concept.get_child_concepts() {
+            collect_schedules(&child, root_types, covered, schedules)?;
+        }
+        Ok(())
+    }
+    let mut schedules = AVec::new();
+    collect_schedules(concept, root_types, false, &mut schedules)?;
+    Schedule::merge(schedules)
 }
 
 #[aorist]
@@ -33,3 +117,60 @@ pub enum TabularLayout {
     StaticTabularLayout(AoristRef<StaticTabularLayout>),
     DynamicTabularLayout(AoristRef<DynamicTabularLayout>),
 }
+impl TabularLayout {
+    /// (name, SQL value expression) of the Hive partition columns of
+    /// tables with this layout; static tables are not partitioned.
+    pub fn get_partition_columns(&self) -> AVec<(AString, AString)> {
+        match self {
+            Self::StaticTabularLayout(_) => AVec::new(),
+            Self::DynamicTabularLayout(x) => {
+                x.0.read()
+                    .granularity
+                    .0
+                    .read()
+                    .get_frequency()
+                    .get_partition_columns()
+            }
+        }
+    }
+    /// (name, start, length) of the Hive partition columns, with the slice
+    /// of the run's partition date holding each value, for programs laying
+    /// out partition directories.
+    pub fn get_partition_slices(&self) -> AVec<(AString, usize, usize)> {
+        match self {
+            Self::StaticTabularLayout(_) => AVec::new(),
+            Self::DynamicTabularLayout(x) => {
+                x.0.read()
+                    .granularity
+                    .0
+                    .read()
+                    .get_frequency()
+                    .get_partition_slices()
+            }
+        }
+    }
+}
+#[cfg(feature = "python")]
+#[pymethods]
+impl PyTabularLayout {
+    #[getter]
+    pub fn partition_columns(&self) -> Vec<(String, String)> {
+        self.inner
+            .0
+            .read()
+            .get_partition_columns()
+            .into_iter()
+            .map(|(name, value)| (name.as_str().into(), value.as_str().into()))
+            .collect()
+    }
+    #[getter]
+    pub fn partition_slices(&self) -> Vec<(String, usize, usize)> {
+        self.inner
+            .0
+            .read()
+            .get_partition_slices()
+            .into_iter()
+            .map(|(name, start, length)| (name.as_str().into(), start, length))
+            .collect()
+    }
+}
@@ -8,6 +8,7 @@ mod dataset;
 mod encoding;
 mod endpoints;
 mod error;
+mod execution_policy;
 mod header;
 mod layout;
 mod location;
@@ -31,6 +32,7 @@ pub use dataset::*;
 pub use encoding::*;
 pub use endpoints::*;
 pub use error::*;
+pub use execution_policy::*;
 pub use header::*;
 pub use layout::*;
 pub use location::*;
@@ -77,13 +79,22 @@ register_concept!(
     DynamicTabularLayout,
     StaticTabularLayout,
     Granularity,
+    HourlyGranularity,
     DailyGranularity,
+    WeeklyGranularity,
+    MonthlyGranularity,
+    CronGranularity,
     DataSet,
     Role,
     GlobalPermissionsAdmin,
     GzipCompression,
     DataCompression,
     ZipCompression,
+    ZstdCompression,
+    XZCompression,
+    LZ4Compression,
+    SnappyCompression,
+    TarCompression,
     ComplianceConfig,
     CSVHeader,
     FileHeader,
@@ -96,18 +107,42 @@ register_concept!(
     OnPremiseLocation,
     MinioLocation,
     S3Location,
+    AzureBlobLocation,
+    HDFSLocation,
+    SFTPLocation,
     PostgresLocation,
     PushshiftAPILocation,
+    PaginatedAPILocation,
+    Pagination,
+    CursorPagination,
+    OffsetPagination,
     RemoteLocation,
     SQLiteLocation,
+    DuckDBLocation,
     WebLocation,
+    WebAuthentication,
+    BasicAuthentication,
+    BearerTokenAuthentication,
+    APIKeyAuthentication,
+    HTTPHeader,
     GDBEncoding,
+    CSVDialect,
     CSVEncoding,
     TSVEncoding,
     Encoding,
     JSONEncoding,
     NewlineDelimitedJSONEncoding,
     ORCEncoding,
+    ParquetEncoding,
+    AvroEncoding,
+    ArrowIPCEncoding,
+    FeatherEncoding,
+    NetCDFEncoding,
+    HDF5Encoding,
+    ZarrEncoding,
+    GeoParquetEncoding,
+    FlatGeobufEncoding,
+    CloudOptimizedGeoTiffEncoding,
     ONNXEncoding,
     UndefinedTabularSchema,
     TabularSchema,
@@ -121,6 +156,7 @@ register_concept!(
     Storage,
     BigQueryStorage,
     SQLiteStorage,
+    DuckDBStorage,
     HiveTableStorage,
     RemoteStorage,
     LocalFileStorage,
@@ -151,6 +187,7 @@ register_concept!(
     GeospatialAssetSchema,
     RasterSchema,
     Tensor,
+    NDArray,
     RasterAsset,
     PointCloud,
     GeospatialAsset,
@@ -200,6 +237,9 @@ register_concept!(
     TAOMarkerControlledWatershedSegmentationSchema,
     NAIPMetadataSchema,
     S3Storage,
+    ObjectStoreLocation,
+    DeltaTableStorage,
+    IcebergTableStorage,
     XMLEncoding,
     BZip2Compression,
     KMLEncoding,
@@ -236,5 +276,6 @@ register_concept!(
     NDVISchema,
     NBRSchema,
     PolygonCollectionStatsUnionSchema,
-    PolygonCollectionStatsFilterSchema
+    PolygonCollectionStatsFilterSchema,
+    ExecutionPolicy
 );
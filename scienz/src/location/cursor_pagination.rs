@@ -0,0 +1,23 @@
+use abi_stable::std_types::ROption;
+use aorist_concept::{aorist, Constrainable};
+use aorist_paste::paste;
+use aorist_primitives::{AoristConceptBase, ConceptEnum};
+use aorist_util::AOption;
+use aorist_util::AUuid;
+use aorist_util::AoristRef;
+use aorist_util::{AString, AVec};
+use derivative::Derivative;
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+
+/// Pages are requested with the cursor returned by the previous page,
+/// until no cursor is returned.
+#[aorist]
+pub struct CursorPagination {
+    // query parameter the cursor is passed in
+    pub cursor_param: AString,
+    // dotted path to the next cursor in a response, e.g. "meta.next_cursor"
+    pub next_cursor_path: AString,
+    // query parameter the page size is passed in, if any
+    pub page_size_param: AOption<AString>,
+}
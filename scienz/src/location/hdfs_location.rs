@@ -0,0 +1,30 @@
+use abi_stable::std_types::ROption;
+use aorist_concept::{aorist, Constrainable};
+use aorist_paste::paste;
+use aorist_primitives::{AoristConceptBase, ConceptEnum};
+use aorist_util::AOption;
+use aorist_util::AUuid;
+use aorist_util::AoristRef;
+use aorist_util::{AString, AVec};
+use derivative::Derivative;
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+
+#[aorist]
+pub struct HDFSLocation {
+    // host and RPC port of the namenode, e.g. "namenode:8020"
+    pub namenode: AString,
+    pub path: AString,
+}
+impl HDFSLocation {
+    /// Host and RPC port of the namenode, 8020 if no port is given.
+    pub fn get_namenode_host_and_port(&self) -> (AString, usize) {
+        match self.namenode.as_str().rsplit_once(':') {
+            Some((host, port)) => match port.parse() {
+                Ok(port) => (host.into(), port),
+                Err(_) => (self.namenode.clone(), 8020),
+            },
+            None => (self.namenode.clone(), 8020),
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use crate::location::alluxio_location::*;
+use crate::location::hdfs_location::*;
 use crate::location::minio_location::*;
 use crate::location::s3_location::*;
 use abi_stable::std_types::ROption;
@@ -20,4 +21,6 @@ pub enum HiveLocation {
     MinioLocation(AoristRef<MinioLocation>),
     #[constrainable]
     S3Location(AoristRef<S3Location>),
+    #[constrainable]
+    HDFSLocation(AoristRef<HDFSLocation>),
 }
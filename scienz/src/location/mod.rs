@@ -1,29 +1,57 @@
 mod alluxio_location;
+mod api_key_authentication;
+mod azure_blob_location;
+mod basic_authentication;
+mod bearer_token_authentication;
 mod bigquery_location;
+mod cursor_pagination;
+mod duckdb_location;
 mod gcs_location;
 mod github_location;
+mod hdfs_location;
 mod hive_location;
+mod http_header;
 mod local_file_system_location;
 mod minio_location;
+mod object_store_location;
+mod offset_pagination;
 mod on_premise_location;
+mod paginated_api_location;
+mod pagination;
 mod postgres_location;
 mod pushshift_api_location;
 mod remote_location;
 mod s3_location;
+mod sftp_location;
 mod sqlite_location;
+mod web_authentication;
 mod web_location;
 
 pub use alluxio_location::*;
+pub use api_key_authentication::*;
+pub use azure_blob_location::*;
+pub use basic_authentication::*;
+pub use bearer_token_authentication::*;
 pub use bigquery_location::*;
+pub use cursor_pagination::*;
+pub use duckdb_location::*;
 pub use gcs_location::*;
 pub use github_location::*;
+pub use hdfs_location::*;
 pub use hive_location::*;
+pub use http_header::*;
 pub use local_file_system_location::*;
 pub use minio_location::*;
+pub use object_store_location::*;
+pub use offset_pagination::*;
 pub use on_premise_location::*;
+pub use paginated_api_location::*;
+pub use pagination::*;
 pub use postgres_location::*;
 pub use pushshift_api_location::*;
 pub use remote_location::*;
 pub use s3_location::*;
+pub use sftp_location::*;
 pub use sqlite_location::*;
+pub use web_authentication::*;
 pub use web_location::*;
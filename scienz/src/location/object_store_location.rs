@@ -0,0 +1,22 @@
+use crate::location::minio_location::*;
+use crate::location::s3_location::*;
+use abi_stable::std_types::ROption;
+use aorist_concept::{aorist, Constrainable};
+use aorist_paste::paste;
+use aorist_primitives::{AoristConceptBase, ConceptEnum};
+use aorist_util::AOption;
+use aorist_util::AUuid;
+use aorist_util::AoristRef;
+use aorist_util::{AString, AVec};
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+
+/// Object stores holding the data and metadata files of Delta Lake and
+/// Iceberg tables.
+#[aorist]
+pub enum ObjectStoreLocation {
+    #[constrainable]
+    MinioLocation(AoristRef<MinioLocation>),
+    #[constrainable]
+    S3Location(AoristRef<S3Location>),
+}
@@ -1,4 +1,5 @@
 use crate::location::alluxio_location::*;
+use crate::location::duckdb_location::*;
 use crate::location::local_file_system_location::*;
 use crate::location::minio_location::*;
 use crate::location::postgres_location::*;
@@ -25,5 +26,7 @@ pub enum OnPremiseLocation {
     #[constrainable]
     SQLiteLocation(AoristRef<SQLiteLocation>),
     #[constrainable]
+    DuckDBLocation(AoristRef<DuckDBLocation>),
+    #[constrainable]
     PostgresLocation(AoristRef<PostgresLocation>),
 }
@@ -0,0 +1,34 @@
+use crate::location::http_header::*;
+use crate::location::pagination::*;
+use crate::location::web_authentication::*;
+use abi_stable::std_types::ROption;
+use aorist_concept::{aorist, Constrainable};
+use aorist_paste::paste;
+use aorist_primitives::{AoristConceptBase, ConceptEnum};
+use aorist_util::AOption;
+use aorist_util::AUuid;
+use aorist_util::AoristRef;
+use aorist_util::{AString, AVec};
+use derivative::Derivative;
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+
+/// A JSON API returning its records one page at a time. Records from all
+/// pages are downloaded as newline-delimited JSON.
+#[aorist]
+pub struct PaginatedAPILocation {
+    pub address: AString,
+    #[constrainable]
+    pub pagination: AoristRef<Pagination>,
+    // dotted path to the list of records in a response, e.g. "data.items";
+    // the response itself is the list if unset
+    pub records_path: AOption<AString>,
+    // records requested per page
+    pub page_size: AOption<usize>,
+    #[constrainable]
+    pub auth: AOption<AoristRef<WebAuthentication>>,
+    #[constrainable]
+    pub headers: AOption<AVec<AoristRef<HTTPHeader>>>,
+    pub max_retries: AOption<usize>,
+    pub retry_backoff_seconds: AOption<usize>,
+}
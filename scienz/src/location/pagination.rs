@@ -0,0 +1,20 @@
+use crate::location::cursor_pagination::*;
+use crate::location::offset_pagination::*;
+use abi_stable::std_types::ROption;
+use aorist_concept::{aorist, Constrainable};
+use aorist_paste::paste;
+use aorist_primitives::{AoristConceptBase, ConceptEnum};
+use aorist_util::AOption;
+use aorist_util::AUuid;
+use aorist_util::AoristRef;
+use aorist_util::{AString, AVec};
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+
+#[aorist]
+pub enum Pagination {
+    #[constrainable]
+    CursorPagination(AoristRef<CursorPagination>),
+    #[constrainable]
+    OffsetPagination(AoristRef<OffsetPagination>),
+}
@@ -1,8 +1,12 @@
+use crate::location::azure_blob_location::*;
 use crate::location::bigquery_location::*;
 use crate::location::gcs_location::*;
 use crate::location::github_location::*;
+use crate::location::hdfs_location::*;
+use crate::location::paginated_api_location::*;
 use crate::location::pushshift_api_location::*;
 use crate::location::s3_location::*;
+use crate::location::sftp_location::*;
 use crate::location::web_location::*;
 use abi_stable::std_types::ROption;
 use aorist_concept::{aorist, Constrainable};
@@ -24,9 +28,17 @@ pub enum RemoteLocation {
     #[constrainable]
     PushshiftAPILocation(AoristRef<PushshiftAPILocation>),
     #[constrainable]
+    PaginatedAPILocation(AoristRef<PaginatedAPILocation>),
+    #[constrainable]
     BigQueryLocation(AoristRef<BigQueryLocation>),
     #[constrainable]
     GithubLocation(AoristRef<GithubLocation>),
     #[constrainable]
     S3Location(AoristRef<S3Location>),
+    #[constrainable]
+    AzureBlobLocation(AoristRef<AzureBlobLocation>),
+    #[constrainable]
+    HDFSLocation(AoristRef<HDFSLocation>),
+    #[constrainable]
+    SFTPLocation(AoristRef<SFTPLocation>),
 }
@@ -0,0 +1,23 @@
+use abi_stable::std_types::ROption;
+use aorist_concept::{aorist, Constrainable};
+use aorist_paste::paste;
+use aorist_primitives::{AoristConceptBase, ConceptEnum};
+use aorist_util::AOption;
+use aorist_util::AUuid;
+use aorist_util::AoristRef;
+use aorist_util::{AString, AVec};
+use derivative::Derivative;
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+
+#[aorist]
+pub struct SFTPLocation {
+    pub host: AString,
+    // 22 if unset
+    pub port: AOption<usize>,
+    pub path: AString,
+    pub username: AString,
+    // environment variable holding the password; the private key of the
+    // SFTP endpoint is used if unset
+    pub password_env_var: AOption<AString>,
+}
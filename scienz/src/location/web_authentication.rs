@@ -0,0 +1,26 @@
+use crate::location::api_key_authentication::*;
+use crate::location::basic_authentication::*;
+use crate::location::bearer_token_authentication::*;
+use abi_stable::std_types::ROption;
+use aorist_concept::{aorist, Constrainable};
+use aorist_paste::paste;
+use aorist_primitives::{AoristConceptBase, ConceptEnum};
+use aorist_util::AOption;
+use aorist_util::AUuid;
+use aorist_util::AoristRef;
+use aorist_util::{AString, AVec};
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+
+/// How requests to a web source are authenticated. Secrets are never part
+/// of the universe: they are read from environment variables when the
+/// flow runs.
+#[aorist]
+pub enum WebAuthentication {
+    #[constrainable]
+    BasicAuthentication(AoristRef<BasicAuthentication>),
+    #[constrainable]
+    BearerTokenAuthentication(AoristRef<BearerTokenAuthentication>),
+    #[constrainable]
+    APIKeyAuthentication(AoristRef<APIKeyAuthentication>),
+}
@@ -1,3 +1,5 @@
+use crate::location::http_header::*;
+use crate::location::web_authentication::*;
 use abi_stable::std_types::ROption;
 use aorist_concept::{aorist, Constrainable};
 use aorist_paste::paste;
@@ -14,4 +16,18 @@ use std::fmt::Debug;
 pub struct WebLocation {
     // TODO: replace these with Getters and Setters
     pub address: AString,
+    #[constrainable]
+    pub auth: AOption<AoristRef<WebAuthentication>>,
+    #[constrainable]
+    pub headers: AOption<AVec<AoristRef<HTTPHeader>>>,
+    // failed requests are retried this many times, waiting
+    // retry_backoff_seconds before the first retry and twice as long
+    // before each following one
+    pub max_retries: AOption<usize>,
+    pub retry_backoff_seconds: AOption<usize>,
+    // hex digest the download is verified against
+    pub sha256: AOption<AString>,
+    // whether to send If-None-Match / If-Modified-Since with the ETag and
+    // Last-Modified of a previous download, keeping it if unchanged
+    pub conditional_requests: AOption<bool>,
 }
@@ -0,0 +1,22 @@
+use aorist_util::{AString, AVec};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// A field of an ArrowSchema. `data_type` is the name of the pyarrow / R
+/// arrow function constructing the type, e.g. `int64`.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct ArrowField {
+    pub name: AString,
+    #[serde(rename = "type")]
+    pub data_type: AString,
+    pub nullable: bool,
+    pub metadata: BTreeMap<AString, AString>,
+}
+
+/// Description of the Arrow schema of a tabular asset, serialized to JSON
+/// for consumers validating data frames against it.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct ArrowSchema {
+    pub fields: AVec<ArrowField>,
+    pub metadata: BTreeMap<AString, AString>,
+}
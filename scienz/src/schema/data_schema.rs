@@ -3,6 +3,7 @@ use aorist_util::AOption;
 
 use crate::attributes::*;
 use crate::error::AoristError;
+use crate::schema::arrow_schema::*;
 use crate::schema::geospatial_asset_schema::*;
 use crate::schema::graph_asset_schema::*;
 use crate::schema::language_asset_schema::*;
@@ -24,6 +25,8 @@ use pyo3::exceptions::PyValueError;
 #[cfg(feature = "python")]
 use pyo3::prelude::*;
 use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::BTreeMap;
 use std::fmt::Debug;
 
 #[aorist]
@@ -138,6 +141,126 @@ impl DataSchema {
             _ => self.get_datum_template().unwrap().0.read().get_attributes(),
         }
     }
+    /// Avro record schema for the schema's datum template. Nullable
+    /// attributes are unions with "null", and key attributes (and the key
+    /// columns of a LongTabularSchema) are named in the record's
+    /// documentation.
+    pub fn get_avro_schema(&self) -> Result<serde_json::Value, AoristError> {
+        let name = self.get_datum_template()?.0.read().get_name();
+        let attributes = self.get_attributes();
+        let fields = attributes
+            .iter()
+            .map(|x| {
+                let attribute = x.0.read();
+                let mut field = serde_json::Map::new();
+                field.insert("name".into(), json!(attribute.get_name().as_str()));
+                if attribute.is_nullable() {
+                    field.insert(
+                        "type".into(),
+                        json!(["null", attribute.get_avro_type().as_str()]),
+                    );
+                    field.insert("default".into(), serde_json::Value::Null);
+                } else {
+                    field.insert("type".into(), json!(attribute.get_avro_type().as_str()));
+                }
+                if let AOption(ROption::RSome(comment)) = attribute.get_comment() {
+                    field.insert("doc".into(), json!(comment.as_str()));
+                }
+                serde_json::Value::Object(field)
+            })
+            .collect::<Vec<_>>();
+        let key_columns = match self {
+            DataSchema::LongTabularSchema(x) => x.0.read().key_attributes.clone(),
+            _ => AVec::new(),
+        };
+        let keys = attributes
+            .iter()
+            .map(|x| x.0.read())
+            .filter(|x| x.is_key_type() || key_columns.iter().any(|y| *y == x.get_name()))
+            .map(|x| x.get_name().as_str().to_string())
+            .collect::<Vec<_>>();
+        let mut record = serde_json::Map::new();
+        record.insert("type".into(), json!("record"));
+        record.insert("name".into(), json!(name.as_str()));
+        if !keys.is_empty() {
+            record.insert(
+                "doc".into(),
+                json!(format!("Key attributes: {}", keys.join(", "))),
+            );
+        }
+        record.insert("fields".into(), json!(fields));
+        Ok(serde_json::Value::Object(record))
+    }
+    /// Arrow schema of a tabular schema's columns, in order. Key attributes
+    /// (and the key columns of a LongTabularSchema) are marked with
+    /// `key: true` metadata. Ordering attributes are never null.
+    pub fn get_arrow_schema(&self) -> Result<ArrowSchema, AoristError> {
+        let (names, keys, non_nullable) = match self {
+            DataSchema::TabularSchema(x) => {
+                (x.0.read().attributes.clone(), AVec::new(), AVec::new())
+            }
+            DataSchema::TimeOrderedTabularSchema(x) => {
+                let read = x.0.read();
+                (
+                    read.attributes.clone(),
+                    AVec::new(),
+                    read.ordering_attributes.clone(),
+                )
+            }
+            DataSchema::LongTabularSchema(x) => {
+                let read = x.0.read();
+                (
+                    read.get_attribute_names(),
+                    read.key_attributes.clone(),
+                    AVec::new(),
+                )
+            }
+            _ => {
+                return Err(AoristError::OtherError(AString::from(
+                    "Arrow schemas can only be derived from tabular schemas.",
+                )))
+            }
+        };
+        let attributes = self
+            .get_datum_template()?
+            .0
+            .read()
+            .get_attributes()
+            .into_iter()
+            .map(|x| (x.0.read().get_name(), x.clone()))
+            .collect::<BTreeMap<_, _>>();
+        let fields = names
+            .iter()
+            .map(|name| {
+                let attribute = match attributes.get(name) {
+                    Some(x) => x.0.read(),
+                    None => {
+                        return Err(AoristError::OtherError(
+                            format!("Attribute {} not found in datum template.", name)
+                                .as_str()
+                                .into(),
+                        ))
+                    }
+                };
+                let mut metadata = BTreeMap::new();
+                if attribute.is_key_type() || keys.iter().any(|x| x == name) {
+                    metadata.insert("key".into(), "true".into());
+                }
+                if let AOption(ROption::RSome(comment)) = attribute.get_comment() {
+                    metadata.insert("comment".into(), comment);
+                }
+                Ok(ArrowField {
+                    name: name.clone(),
+                    data_type: attribute.get_arrow_type(),
+                    nullable: attribute.is_nullable() && !non_nullable.iter().any(|x| x == name),
+                    metadata,
+                })
+            })
+            .collect::<Result<AVec<_>, _>>()?;
+        let mut metadata = BTreeMap::new();
+        metadata.insert("datum_template".into(), self.get_datum_template_name()?);
+        Ok(ArrowSchema { fields, metadata })
+    }
 }
 #[cfg(feature = "python")]
 #[pymethods]
@@ -156,6 +279,20 @@ impl PyDataSchema {
         }
     }
     #[getter]
+    pub fn avro_schema(&self) -> PyResult<String> {
+        match self.inner.0.read().get_avro_schema() {
+            Ok(s) => Ok(serde_json::to_string_pretty(&s).unwrap()),
+            Err(err) => Err(PyValueError::new_err(err.as_str().to_string())),
+        }
+    }
+    #[getter]
+    pub fn arrow_schema(&self) -> PyResult<String> {
+        match self.inner.0.read().get_arrow_schema() {
+            Ok(s) => Ok(serde_json::to_string_pretty(&s).unwrap()),
+            Err(err) => Err(PyValueError::new_err(err.as_str().to_string())),
+        }
+    }
+    #[getter]
     pub fn get_attributes(&self) -> Vec<PyAttribute> {
         self.inner
             .0
@@ -166,3 +303,141 @@ impl PyDataSchema {
             .collect()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use abi_stable::external_types::parking_lot::rw_lock::RRwLock;
+    use abi_stable::std_types::RArc;
+    use abi_stable::StableAbi;
+    use aorist_attributes::{FreeText, Int64, KeyInt64Identifier};
+    use aorist_primitives::attribute;
+
+    fn aorist_ref<T: PartialEq + Serialize + Debug + Clone + StableAbi>(x: T) -> AoristRef<T> {
+        AoristRef(RArc::new(RRwLock::new(x)))
+    }
+    fn template() -> AoristRef<DatumTemplate> {
+        aorist_ref(DatumTemplate::RowStruct(aorist_ref(RowStruct {
+            name: "row".into(),
+            attributes: vec![
+                aorist_ref(attribute! { KeyInt64Identifier(
+                    "id".into(), AOption(ROption::RNone), false
+                )}),
+                aorist_ref(attribute! { Int64(
+                    "count".into(), AOption(ROption::RNone), false
+                )}),
+                aorist_ref(attribute! { FreeText(
+                    "note".into(), AOption(ROption::RSome("A note".into())), true
+                )}),
+            ]
+            .into_iter()
+            .collect(),
+            tag: AOption(ROption::RNone),
+            uuid: AOption(ROption::RNone),
+        })))
+    }
+    fn names(names: &[&str]) -> AVec<AString> {
+        names.iter().map(|x| (*x).into()).collect()
+    }
+    fn tabular_schema() -> DataSchema {
+        DataSchema::TabularSchema(aorist_ref(TabularSchema {
+            datum_template: template(),
+            attributes: names(&["id", "count", "note"]),
+            tag: AOption(ROption::RNone),
+            uuid: AOption(ROption::RNone),
+        }))
+    }
+    fn long_tabular_schema() -> DataSchema {
+        DataSchema::LongTabularSchema(aorist_ref(LongTabularSchema {
+            datum_template: template(),
+            key_attributes: names(&["count"]),
+            value_attributes: names(&["id", "note"]),
+            tag: AOption(ROption::RNone),
+            uuid: AOption(ROption::RNone),
+        }))
+    }
+
+    #[test]
+    fn test_avro_schema_nullable_attributes_are_unions() {
+        let schema = tabular_schema().get_avro_schema().unwrap();
+        assert_eq!(
+            schema["fields"],
+            json!([
+                {"name": "id", "type": "long"},
+                {"name": "count", "type": "long"},
+                {"name": "note", "type": ["null", "string"], "default": null, "doc": "A note"},
+            ])
+        );
+    }
+    #[test]
+    fn test_avro_schema_documents_key_attributes() {
+        let schema = tabular_schema().get_avro_schema().unwrap();
+        assert_eq!(schema["name"], json!("row"));
+        assert_eq!(schema["doc"], json!("Key attributes: id"));
+    }
+    #[test]
+    fn test_avro_schema_documents_long_tabular_key_columns() {
+        let schema = long_tabular_schema().get_avro_schema().unwrap();
+        assert_eq!(schema["doc"], json!("Key attributes: id, count"));
+    }
+    #[test]
+    fn test_arrow_schema_nullability() {
+        let schema = tabular_schema().get_arrow_schema().unwrap();
+        let nullable = schema
+            .fields
+            .iter()
+            .map(|x| (x.name.as_str(), x.data_type.as_str(), x.nullable))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            nullable,
+            vec![
+                ("id", "int64", false),
+                ("count", "int64", false),
+                ("note", "string", true),
+            ]
+        );
+        let schema = DataSchema::TimeOrderedTabularSchema(aorist_ref(TimeOrderedTabularSchema {
+            datum_template: template(),
+            attributes: names(&["id", "note"]),
+            ordering_attributes: names(&["note"]),
+            tag: AOption(ROption::RNone),
+            uuid: AOption(ROption::RNone),
+        }))
+        .get_arrow_schema()
+        .unwrap();
+        assert!(!schema.fields[1].nullable);
+    }
+    #[test]
+    fn test_arrow_schema_key_metadata() {
+        let schema = tabular_schema().get_arrow_schema().unwrap();
+        let key: AString = "key".into();
+        assert_eq!(
+            schema.fields[0].metadata.get(&key),
+            Some(&AString::from("true"))
+        );
+        assert_eq!(schema.fields[1].metadata.get(&key), None);
+        assert_eq!(
+            schema.fields[2].metadata.get(&AString::from("comment")),
+            Some(&AString::from("A note"))
+        );
+        assert_eq!(
+            schema.metadata.get(&AString::from("datum_template")),
+            Some(&AString::from("row"))
+        );
+    }
+    #[test]
+    fn test_arrow_schema_long_tabular_key_columns() {
+        let schema = long_tabular_schema().get_arrow_schema().unwrap();
+        let keys = schema
+            .fields
+            .iter()
+            .map(|x| {
+                (
+                    x.name.as_str(),
+                    x.metadata.contains_key(&AString::from("key")),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(keys, vec![("count", true), ("id", true), ("note", false)]);
+    }
+}
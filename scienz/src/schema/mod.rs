@@ -1,4 +1,5 @@
 mod adjacent_polygons_schema;
+mod arrow_schema;
 mod data_schema;
 mod derived_asset_schema;
 mod edgelist_filter_schema;
@@ -61,6 +62,7 @@ mod undefined_tabular_schema;
 mod vision_asset_schema;
 
 pub use adjacent_polygons_schema::*;
+pub use arrow_schema::*;
 pub use data_schema::*;
 pub use derived_asset_schema::*;
 pub use edgelist_filter_schema::*;
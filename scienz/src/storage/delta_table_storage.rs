@@ -0,0 +1,71 @@
+use crate::attributes::*;
+use crate::location::*;
+use crate::storage::schema_evolution::*;
+use abi_stable::std_types::ROption;
+use aorist_concept::{aorist, Constrainable};
+use aorist_paste::paste;
+use aorist_primitives::{AoristConceptBase, ConceptEnum};
+use aorist_util::AOption;
+use aorist_util::AUuid;
+use aorist_util::AoristRef;
+use aorist_util::{AString, AVec};
+use derivative::Derivative;
+#[cfg(feature = "python")]
+use pyo3::exceptions::PyValueError;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+
+/// A Delta Lake table. Its data files are always Parquet.
+#[aorist]
+pub struct DeltaTableStorage {
+    // catalog the table is registered in, e.g. "delta" in Trino
+    pub catalog: AString,
+    pub namespace: AString,
+    pub table: AString,
+    #[constrainable]
+    pub location: AoristRef<ObjectStoreLocation>,
+    // partition columns, in order
+    pub partition_by: AVec<AString>,
+    // attributes the table was last deployed with; empty if it does not
+    // exist yet
+    #[constrainable]
+    pub previous_attributes: AVec<AoristRef<Attribute>>,
+}
+impl DeltaTableStorage {
+    pub fn get_schema_evolution(
+        &self,
+        attributes: &AVec<AoristRef<Attribute>>,
+    ) -> Result<SchemaEvolution, AString> {
+        let evolution = SchemaEvolution::new(&self.previous_attributes, attributes);
+        // neither Trino nor Spark can change the type of a Delta column in place
+        match evolution.retyped.iter().next() {
+            Some(x) => Err(format!(
+                "Cannot change the type of column {} of Delta table {}.{} without rewriting it.",
+                x.0.read().get_name(),
+                self.namespace,
+                self.table,
+            )
+            .as_str()
+            .into()),
+            None => Ok(evolution),
+        }
+    }
+}
+#[cfg(feature = "python")]
+#[pymethods]
+impl PyDeltaTableStorage {
+    /// (added, dropped, retyped) columns, comparing `attributes` with
+    /// the ones the table was last deployed with.
+    pub fn schema_evolution(
+        &self,
+        attributes: Vec<PyAttribute>,
+    ) -> PyResult<(Vec<PyAttribute>, Vec<String>, Vec<PyAttribute>)> {
+        let attributes = attributes.into_iter().map(|x| x.inner).collect();
+        match self.inner.0.read().get_schema_evolution(&attributes) {
+            Ok(evolution) => Ok(evolution.into_py()),
+            Err(err) => Err(PyValueError::new_err(err.as_str().to_string())),
+        }
+    }
+}
@@ -0,0 +1,140 @@
+use crate::attributes::*;
+use crate::encoding::*;
+use crate::location::*;
+use crate::storage::schema_evolution::*;
+use abi_stable::std_types::ROption;
+use aorist_concept::{aorist, Constrainable};
+use aorist_paste::paste;
+use aorist_primitives::{AoristConceptBase, ConceptEnum};
+use aorist_util::AOption;
+use aorist_util::AUuid;
+use aorist_util::AoristRef;
+use aorist_util::{AString, AVec};
+use derivative::Derivative;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+
+/// An Apache Iceberg table.
+#[aorist]
+pub struct IcebergTableStorage {
+    // catalog the table is registered in, e.g. "iceberg" in Trino
+    pub catalog: AString,
+    pub namespace: AString,
+    pub table: AString,
+    #[constrainable]
+    pub location: AoristRef<ObjectStoreLocation>,
+    // format of the data files: Parquet, ORC or Avro
+    #[constrainable]
+    pub encoding: AoristRef<Encoding>,
+    // partition transforms in Trino syntax, e.g. "day(ts)" or "bucket(id, 16)"
+    pub partition_spec: AVec<AString>,
+    // attributes the table was last deployed with; empty if it does not
+    // exist yet
+    #[constrainable]
+    pub previous_attributes: AVec<AoristRef<Attribute>>,
+}
+impl IcebergTableStorage {
+    pub fn get_schema_evolution(&self, attributes: &AVec<AoristRef<Attribute>>) -> SchemaEvolution {
+        SchemaEvolution::new(&self.previous_attributes, attributes)
+    }
+    /// The partition spec in Spark SQL syntax, where time transforms are
+    /// plural and the width of `bucket` and `truncate` comes first.
+    pub fn get_spark_partition_spec(&self) -> AVec<AString> {
+        self.partition_spec
+            .iter()
+            .map(|field| {
+                let (transform, args) = match field.as_str().split_once('(') {
+                    Some((transform, args)) => (transform.trim(), args.trim_end_matches(')')),
+                    None => return field.clone(),
+                };
+                match transform {
+                    "year" | "month" | "day" | "hour" => format!("{}s({})", transform, args),
+                    "bucket" | "truncate" => match args.split_once(',') {
+                        Some((column, width)) => {
+                            format!("{}({}, {})", transform, width.trim(), column.trim())
+                        }
+                        None => field.as_str().to_string(),
+                    },
+                    _ => field.as_str().to_string(),
+                }
+                .as_str()
+                .into()
+            })
+            .collect()
+    }
+}
+#[cfg(feature = "python")]
+#[pymethods]
+impl PyIcebergTableStorage {
+    /// (added, dropped, retyped) columns, comparing `attributes` with
+    /// the ones the table was last deployed with.
+    pub fn schema_evolution(
+        &self,
+        attributes: Vec<PyAttribute>,
+    ) -> (Vec<PyAttribute>, Vec<String>, Vec<PyAttribute>) {
+        let attributes = attributes.into_iter().map(|x| x.inner).collect();
+        self.inner
+            .0
+            .read()
+            .get_schema_evolution(&attributes)
+            .into_py()
+    }
+    #[getter]
+    pub fn spark_partition_spec(&self) -> Vec<String> {
+        self.inner
+            .0
+            .read()
+            .get_spark_partition_spec()
+            .into_iter()
+            .map(|x| x.as_str().into())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use abi_stable::external_types::parking_lot::rw_lock::RRwLock;
+    use abi_stable::std_types::RArc;
+
+    #[test]
+    fn test_spark_partition_spec() {
+        let storage = IcebergTableStorage {
+            catalog: "iceberg".into(),
+            namespace: "default".into(),
+            table: "events".into(),
+            location: AoristRef(RArc::new(RRwLock::new(ObjectStoreLocation::MinioLocation(
+                AoristRef(RArc::new(RRwLock::new(MinioLocation {
+                    name: "events".into(),
+                    tag: AOption(ROption::RNone),
+                    uuid: AOption(ROption::RNone),
+                }))),
+            )))),
+            encoding: AoristRef(RArc::new(RRwLock::new(Encoding::ParquetEncoding(
+                AoristRef(RArc::new(RRwLock::new(ParquetEncoding {
+                    compression_codec: AOption(ROption::RNone),
+                    row_group_size: AOption(ROption::RNone),
+                    tag: AOption(ROption::RNone),
+                    uuid: AOption(ROption::RNone),
+                }))),
+            )))),
+            partition_spec: vec!["region", "day(ts)", "bucket(id, 16)", "truncate(name, 4)"]
+                .into_iter()
+                .map(|x| x.into())
+                .collect(),
+            previous_attributes: AVec::new(),
+            tag: AOption(ROption::RNone),
+            uuid: AOption(ROption::RNone),
+        };
+        assert_eq!(
+            storage
+                .get_spark_partition_spec()
+                .iter()
+                .map(|x| x.as_str())
+                .collect::<Vec<_>>(),
+            vec!["region", "days(ts)", "bucket(16, id)", "truncate(4, name)"]
+        );
+    }
+}
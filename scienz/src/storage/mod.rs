@@ -1,21 +1,29 @@
 mod bigquery_storage;
+mod delta_table_storage;
+mod duckdb_storage;
 mod git_storage;
 mod hive_table_storage;
+mod iceberg_table_storage;
 mod inline_blob_storage;
 mod local_file_storage;
 mod postgres_storage;
 mod remote_storage;
 mod s3_storage;
+mod schema_evolution;
 mod sqlite_storage;
 mod storage;
 
 pub use bigquery_storage::*;
+pub use delta_table_storage::*;
+pub use duckdb_storage::*;
 pub use git_storage::*;
 pub use hive_table_storage::*;
+pub use iceberg_table_storage::*;
 pub use inline_blob_storage::*;
 pub use local_file_storage::*;
 pub use postgres_storage::*;
 pub use remote_storage::*;
 pub use s3_storage::*;
+pub use schema_evolution::*;
 pub use sqlite_storage::*;
 pub use storage::*;
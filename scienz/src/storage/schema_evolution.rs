@@ -0,0 +1,124 @@
+use crate::attributes::*;
+use aorist_util::AoristRef;
+use aorist_util::{AString, AVec};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Columns to add, drop or retype so that a table created with some
+/// previous attributes matches the attributes its asset has now.
+/// Attributes are matched by name.
+#[derive(Debug, Clone)]
+pub struct SchemaEvolution {
+    pub added: AVec<AoristRef<Attribute>>,
+    pub dropped: AVec<AString>,
+    pub retyped: AVec<AoristRef<Attribute>>,
+}
+impl SchemaEvolution {
+    pub fn new(
+        previous: &AVec<AoristRef<Attribute>>,
+        current: &AVec<AoristRef<Attribute>>,
+    ) -> Self {
+        let previous_types = previous
+            .iter()
+            .map(|x| (x.0.read().get_name(), x.0.read().get_type()))
+            .collect::<BTreeMap<_, _>>();
+        let current_names = current
+            .iter()
+            .map(|x| x.0.read().get_name())
+            .collect::<BTreeSet<_>>();
+        let mut added = AVec::new();
+        let mut retyped = AVec::new();
+        for attribute in current.iter() {
+            let read = attribute.0.read();
+            match previous_types.get(&read.get_name()) {
+                None => added.push(attribute.clone()),
+                Some(previous_type) if *previous_type != read.get_type() => {
+                    retyped.push(attribute.clone())
+                }
+                Some(_) => {}
+            }
+        }
+        // dropped columns are reported in their previous order
+        let dropped = previous
+            .iter()
+            .map(|x| x.0.read().get_name())
+            .filter(|x| !current_names.contains(x))
+            .collect();
+        Self {
+            added,
+            dropped,
+            retyped,
+        }
+    }
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.dropped.is_empty() && self.retyped.is_empty()
+    }
+    #[cfg(feature = "python")]
+    pub fn into_py(self) -> (Vec<PyAttribute>, Vec<String>, Vec<PyAttribute>) {
+        (
+            self.added
+                .into_iter()
+                .map(|x| PyAttribute { inner: x })
+                .collect(),
+            self.dropped
+                .into_iter()
+                .map(|x| x.as_str().into())
+                .collect(),
+            self.retyped
+                .into_iter()
+                .map(|x| PyAttribute { inner: x })
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use abi_stable::external_types::parking_lot::rw_lock::RRwLock;
+    use abi_stable::std_types::{RArc, ROption};
+    use aorist_attributes::{FreeText, Int64, KeyInt64Identifier};
+    use aorist_primitives::attribute;
+    use aorist_util::AOption;
+
+    fn attributes(attributes: Vec<Attribute>) -> AVec<AoristRef<Attribute>> {
+        attributes
+            .into_iter()
+            .map(|x| AoristRef(RArc::new(RRwLock::new(x))))
+            .collect()
+    }
+    fn names(attributes: &AVec<AoristRef<Attribute>>) -> Vec<String> {
+        attributes
+            .iter()
+            .map(|x| x.0.read().get_name().as_str().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_schema_evolution() {
+        let previous = attributes(vec![
+            attribute! { KeyInt64Identifier("id".into(), AOption(ROption::RNone), false) },
+            attribute! { Int64("count".into(), AOption(ROption::RNone), false) },
+            attribute! { FreeText("note".into(), AOption(ROption::RNone), true) },
+            attribute! { FreeText("old".into(), AOption(ROption::RNone), true) },
+        ]);
+        let current = attributes(vec![
+            attribute! { KeyInt64Identifier("id".into(), AOption(ROption::RNone), false) },
+            attribute! { FreeText("count".into(), AOption(ROption::RNone), true) },
+            attribute! { FreeText("new".into(), AOption(ROption::RNone), true) },
+            attribute! { FreeText("note".into(), AOption(ROption::RNone), true) },
+        ]);
+        let evolution = SchemaEvolution::new(&previous, &current);
+        assert_eq!(names(&evolution.added), vec!["new"]);
+        assert_eq!(
+            evolution
+                .dropped
+                .iter()
+                .map(|x| x.as_str())
+                .collect::<Vec<_>>(),
+            vec!["old"]
+        );
+        assert_eq!(names(&evolution.retyped), vec!["count"]);
+        assert!(!evolution.is_empty());
+        assert!(SchemaEvolution::new(&current, &current).is_empty());
+    }
+}
@@ -1,24 +1,30 @@
 use crate::encoding::*;
+use crate::location::*;
 use crate::storage::bigquery_storage::*;
+use crate::storage::delta_table_storage::*;
+use crate::storage::duckdb_storage::*;
 use crate::storage::git_storage::*;
 use crate::storage::hive_table_storage::*;
+use crate::storage::iceberg_table_storage::*;
 use crate::storage::inline_blob_storage::*;
 use crate::storage::local_file_storage::*;
 use crate::storage::postgres_storage::*;
 use crate::storage::remote_storage::*;
 use crate::storage::s3_storage::*;
 use crate::storage::sqlite_storage::*;
+use crate::Concept;
 use abi_stable::std_types::ROption;
 use aorist_concept::{aorist, Constrainable};
 use aorist_paste::paste;
-use aorist_primitives::{AoristConceptBase, ConceptEnum};
+use aorist_primitives::{AoristConceptBase, ConceptEnum, ToplineConcept, ValidationCheck};
 use aorist_util::AOption;
 use aorist_util::AUuid;
 use aorist_util::AoristRef;
-use aorist_util::{AString, AVec};
+use aorist_util::{AString, ATaskId, AVec};
 #[cfg(feature = "python")]
 use pyo3::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fmt::Debug;
 
 #[aorist]
@@ -34,6 +40,8 @@ pub enum Storage {
     #[constrainable]
     SQLiteStorage(AoristRef<SQLiteStorage>),
     #[constrainable]
+    DuckDBStorage(AoristRef<DuckDBStorage>),
+    #[constrainable]
     PostgresStorage(AoristRef<PostgresStorage>),
     #[constrainable]
     BigQueryStorage(AoristRef<BigQueryStorage>),
@@ -41,6 +49,10 @@ pub enum Storage {
     InlineBlobStorage(AoristRef<InlineBlobStorage>),
     #[constrainable]
     S3Storage(AoristRef<S3Storage>),
+    #[constrainable]
+    DeltaTableStorage(AoristRef<DeltaTableStorage>),
+    #[constrainable]
+    IcebergTableStorage(AoristRef<IcebergTableStorage>),
 }
 
 impl Storage {
@@ -52,13 +64,122 @@ impl Storage {
             Self::GitStorage(x) => AOption(ROption::RSome(x.0.read().encoding.clone())),
             Self::InlineBlobStorage(x) => AOption(ROption::RSome(x.0.read().encoding.clone())),
             Self::S3Storage(x) => AOption(ROption::RSome(x.0.read().encoding.clone())),
+            Self::IcebergTableStorage(x) => AOption(ROption::RSome(x.0.read().encoding.clone())),
             Self::SQLiteStorage(_) => AOption(ROption::RNone),
+            Self::DuckDBStorage(_) => AOption(ROption::RNone),
             Self::PostgresStorage(_) => AOption(ROption::RNone),
             Self::BigQueryStorage(_) => AOption(ROption::RNone),
+            Self::DeltaTableStorage(_) => AOption(ROption::RNone),
+        }
+    }
+    /// Sources that must exist, and target buckets and database directories
+    /// that must have been created, before tasks reading from or writing to
+    /// this storage run. Target tables are not checked: they are created by
+    /// the flow itself, so they legitimately do not exist before its first
+    /// run. The Postgres server and Hive metastore behind table storages
+    /// are checked with the endpoints instead.
+    pub fn get_validation_checks(&self) -> AVec<ValidationCheck> {
+        let mut checks = AVec::new();
+        match &self {
+            Self::RemoteStorage(x) => match &*x.0.read().location.0.read() {
+                RemoteLocation::WebLocation(l) => {
+                    let l = l.0.read();
+                    // credentials are only available when the flow runs
+                    if l.auth.is_none() {
+                        checks.push(ValidationCheck::UrlExists(l.address.clone()))
+                    }
+                }
+                RemoteLocation::GCSLocation(l) => {
+                    let l = l.0.read();
+                    checks.push(ValidationCheck::GCSBlobExists(
+                        l.bucket.clone(),
+                        l.blob.clone(),
+                    ))
+                }
+                RemoteLocation::S3Location(l) => {
+                    let l = l.0.read();
+                    checks.push(ValidationCheck::S3ObjectExists(
+                        l.bucket.clone(),
+                        l.key.clone(),
+                        l.requester_pays == AOption(ROption::RSome(true)),
+                    ))
+                }
+                RemoteLocation::SFTPLocation(l) => {
+                    let l = l.0.read();
+                    checks.push(ValidationCheck::EndpointReachable(
+                        "sftp".into(),
+                        l.host.clone(),
+                        l.port.0.unwrap_or(22),
+                    ))
+                }
+                RemoteLocation::HDFSLocation(l) => {
+                    let (host, port) = l.0.read().get_namenode_host_and_port();
+                    checks.push(ValidationCheck::EndpointReachable(
+                        "hdfs".into(),
+                        host,
+                        port,
+                    ))
+                }
+                _ => {}
+            },
+            Self::HiveTableStorage(x) => match &*x.0.read().location.0.read() {
+                HiveLocation::S3Location(l) => {
+                    checks.push(ValidationCheck::S3BucketExists(l.0.read().bucket.clone()))
+                }
+                HiveLocation::HDFSLocation(l) => {
+                    let (host, port) = l.0.read().get_namenode_host_and_port();
+                    checks.push(ValidationCheck::EndpointReachable(
+                        "hdfs".into(),
+                        host,
+                        port,
+                    ))
+                }
+                _ => {}
+            },
+            Self::SQLiteStorage(x) => checks.push(ValidationCheck::DatabaseDirectoryExists(
+                x.0.read().location.0.read().file_name.clone(),
+            )),
+            Self::DuckDBStorage(x) => checks.push(ValidationCheck::DatabaseDirectoryExists(
+                x.0.read().location.0.read().database.clone(),
+            )),
+            Self::S3Storage(x) => checks.push(ValidationCheck::S3BucketExists(
+                x.0.read().location.0.read().bucket.clone(),
+            )),
+            Self::DeltaTableStorage(x) => {
+                if let ObjectStoreLocation::S3Location(l) = &*x.0.read().location.0.read() {
+                    checks.push(ValidationCheck::S3BucketExists(l.0.read().bucket.clone()))
+                }
+            }
+            Self::IcebergTableStorage(x) => {
+                if let ObjectStoreLocation::S3Location(l) = &*x.0.read().location.0.read() {
+                    checks.push(ValidationCheck::S3BucketExists(l.0.read().bucket.clone()))
+                }
+            }
+            _ => {}
         }
+        checks
     }
 }
 
+/// Checks for all storages under `concept`, keyed by the storage they
+/// were derived from.
+pub fn get_validation_checks(
+    concept: &AoristRef<Concept>,
+) -> HashMap<ATaskId, AVec<ValidationCheck>> {
+    let mut concept_map = HashMap::new();
+    concept.populate_child_concept_map(&mut concept_map);
+    concept_map
+        .into_iter()
+        .filter_map(|(id, x)| match &*x.0.read() {
+            Concept::Storage(ref storage) => {
+                Some((id, storage.get_reference().0.read().get_validation_checks()))
+            }
+            _ => None,
+        })
+        .filter(|(_, checks)| !checks.is_empty())
+        .collect()
+}
+
 #[cfg(feature = "python")]
 #[pymethods]
 impl PyStorage {
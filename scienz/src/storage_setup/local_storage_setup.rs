@@ -1,3 +1,4 @@
+use crate::execution_policy::*;
 use crate::storage::*;
 use crate::storage_setup::two_tier_storage_setup::*;
 use abi_stable::std_types::ROption;
@@ -17,6 +18,8 @@ pub struct LocalStorageSetup {
     #[constrainable]
     pub local: AoristRef<Storage>,
     pub tmp_dir: AString,
+    #[constrainable]
+    pub execution_policy: AOption<AoristRef<ExecutionPolicy>>,
 }
 impl LocalStorageSetup {
     pub fn persist(&self, persistent: AoristRef<Storage>) -> TwoTierStorageSetup {
@@ -24,6 +27,7 @@ impl LocalStorageSetup {
             scratch: self.local.clone(),
             persistent,
             tmp_dir: self.tmp_dir.clone(),
+            execution_policy: self.execution_policy.clone(),
             tag: self.tag.clone(),
             uuid: AOption(ROption::RNone),
         }
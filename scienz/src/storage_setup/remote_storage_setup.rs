@@ -1,4 +1,5 @@
 use crate::encoding::Encoding;
+use crate::execution_policy::*;
 use crate::storage::*;
 use crate::storage_setup::replication_storage_setup::*;
 use abi_stable::std_types::ROption;
@@ -18,6 +19,8 @@ pub struct RemoteStorageSetup {
     #[constrainable]
     pub remote: AoristRef<Storage>,
     pub tmp_dir: AOption<AString>,
+    #[constrainable]
+    pub execution_policy: AOption<AoristRef<ExecutionPolicy>>,
 }
 impl RemoteStorageSetup {
     pub fn replicate_to_local(
@@ -32,6 +35,7 @@ impl RemoteStorageSetup {
             tag: self.tag.clone(),
             tmp_dir,
             tmp_encoding,
+            execution_policy: self.execution_policy.clone(),
             uuid: AOption(ROption::RNone),
         }
     }
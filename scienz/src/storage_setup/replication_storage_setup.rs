@@ -1,4 +1,5 @@
 use crate::encoding::*;
+use crate::execution_policy::*;
 use crate::storage::*;
 use abi_stable::std_types::ROption;
 use aorist_concept::{aorist, Constrainable};
@@ -23,6 +24,8 @@ pub struct ReplicationStorageSetup {
     pub tmp_dir: AString,
     #[constrainable]
     pub tmp_encoding: AoristRef<Encoding>,
+    #[constrainable]
+    pub execution_policy: AOption<AoristRef<ExecutionPolicy>>,
 }
 
 #[cfg(feature = "python")]
@@ -33,14 +36,28 @@ impl PyReplicationStorageSetup {
         let rss = &*self.inner.0.read();
         Ok(rss.get_download_extension().as_str().into())
     }
+    #[getter]
+    pub fn decompressed_extension(&self) -> PyResult<String> {
+        let rss = &*self.inner.0.read();
+        Ok(rss.get_decompressed_extension().as_str().into())
+    }
 }
 
 impl ReplicationStorageSetup {
+    /// Extension of the file the source is downloaded to, including that
+    /// of its compression, e.g. "csv.gz" or "tar.zst".
     pub fn get_download_extension(&self) -> AString {
         match self.source.0.read().get_encoding() {
             AOption(ROption::RSome(source_encoding_read)) => {
                 let source_encoding = source_encoding_read.0.read();
-                return source_encoding.get_default_file_extension();
+                let extension = source_encoding.get_default_file_extension();
+                return match source_encoding.get_compression() {
+                    AOption(ROption::RSome(compression)) => compression
+                        .0
+                        .read()
+                        .get_compressed_extension(extension.as_str()),
+                    AOption(ROption::RNone) => extension,
+                };
                 /*if source_encoding.is_same_variant_in_enum_as(&*self.tmp_encoding.0.read())
                 {
                     return source_encoding.get_default_file_extension();
@@ -53,4 +70,27 @@ impl ReplicationStorageSetup {
             }
         }
     }
+    /// Extension of the downloaded file once decompressed, as read by the
+    /// steps following the download.
+    pub fn get_decompressed_extension(&self) -> AString {
+        match self.source.0.read().get_encoding() {
+            AOption(ROption::RSome(source_encoding)) => {
+                let source_encoding = source_encoding.0.read();
+                let extension = source_encoding.get_default_file_extension();
+                match source_encoding.get_compression() {
+                    AOption(ROption::RSome(compression)) => {
+                        let compression = compression.0.read();
+                        match compression.is_decompressed_before_use() {
+                            true => extension,
+                            false => compression.get_compressed_extension(extension.as_str()),
+                        }
+                    }
+                    AOption(ROption::RNone) => extension,
+                }
+            }
+            AOption(ROption::RNone) => {
+                panic!("get_decompressed_extension called against source storage without encoding")
+            }
+        }
+    }
 }
@@ -1,3 +1,4 @@
+use crate::execution_policy::*;
 use crate::storage::*;
 use abi_stable::std_types::ROption;
 use aorist_concept::{aorist, Constrainable};
@@ -18,4 +19,6 @@ pub struct TwoTierStorageSetup {
     #[constrainable]
     pub persistent: AoristRef<Storage>,
     pub tmp_dir: AString,
+    #[constrainable]
+    pub execution_policy: AOption<AoristRef<ExecutionPolicy>>,
 }
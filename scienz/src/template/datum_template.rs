@@ -3,6 +3,7 @@ use crate::attributes::*;
 use crate::template::filter::*;
 use crate::template::identifier_tuple::*;
 use crate::template::measure::*;
+use crate::template::nd_array::*;
 use crate::template::point_cloud::*;
 use crate::template::point_cloud_info::*;
 use crate::template::polygon::*;
@@ -38,6 +39,7 @@ pub enum DatumTemplate {
     IntegerMeasure(AoristRef<IntegerMeasure>),
     Filter(AoristRef<Filter>),
     Tensor(AoristRef<Tensor>),
+    NDArray(AoristRef<NDArray>),
     PointCloud(AoristRef<PointCloud>),
     PointCloudInfo(AoristRef<PointCloudInfo>),
     Polygon(AoristRef<Polygon>),
@@ -57,6 +59,7 @@ impl DatumTemplate {
             }
             DatumTemplate::Filter(_) => "Filter",
             DatumTemplate::Tensor(_) => "Tensor",
+            DatumTemplate::NDArray(_) => "NDArray",
             DatumTemplate::PointCloud(_) => "PointCloud",
             DatumTemplate::PointCloudInfo(_) => "PointCloudInfo",
             DatumTemplate::Polygon(_) => "Polygon",
@@ -77,6 +80,7 @@ impl TDatumTemplate for DatumTemplate {
             DatumTemplate::PredictionsFromTrainedFloatMeasure(x) => x.0.read().get_name(),
             DatumTemplate::Filter(x) => x.0.read().get_name(),
             DatumTemplate::Tensor(x) => x.0.read().get_name(),
+            DatumTemplate::NDArray(x) => x.0.read().get_name(),
             DatumTemplate::PointCloud(x) => x.0.read().get_name(),
             DatumTemplate::PointCloudInfo(x) => x.0.read().get_name(),
             DatumTemplate::Polygon(x) => x.0.read().get_name(),
@@ -94,6 +98,7 @@ impl TDatumTemplate for DatumTemplate {
             DatumTemplate::PredictionsFromTrainedFloatMeasure(x) => x.0.read().get_attributes(),
             DatumTemplate::Filter(x) => x.0.read().get_attributes(),
             DatumTemplate::Tensor(x) => x.0.read().get_attributes(),
+            DatumTemplate::NDArray(x) => x.0.read().get_attributes(),
             DatumTemplate::PointCloud(x) => x.0.read().get_attributes(),
             DatumTemplate::PointCloudInfo(x) => x.0.read().get_attributes(),
             DatumTemplate::Polygon(x) => x.0.read().get_attributes(),
@@ -2,6 +2,7 @@ mod datum_template;
 mod filter;
 mod identifier_tuple;
 mod measure;
+mod nd_array;
 mod point_cloud;
 mod point_cloud_info;
 mod polygon;
@@ -15,6 +16,7 @@ pub use datum_template::*;
 pub use filter::*;
 pub use identifier_tuple::*;
 pub use measure::*;
+pub use nd_array::*;
 pub use point_cloud::*;
 pub use point_cloud_info::*;
 pub use polygon::*;
@@ -0,0 +1,40 @@
+use crate::attributes::*;
+
+use crate::template::datum_template::TDatumTemplate;
+use abi_stable::std_types::ROption;
+use aorist_concept::{aorist, Constrainable};
+use aorist_paste::paste;
+use aorist_primitives::{AoristConceptBase, ConceptEnum};
+use aorist_util::AOption;
+use aorist_util::AUuid;
+use aorist_util::AoristRef;
+use aorist_util::{AString, AVec};
+use derivative::Derivative;
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+
+/// A labelled n-dimensional array, as stored in NetCDF, HDF5 or
+/// Zarr. Every variable spans (a subset of) the named dimensions;
+/// coordinates are the 1-dimensional variables indexing them.
+#[aorist]
+pub struct NDArray {
+    pub name: AString,
+    // dimension names, outermost first, e.g. ["time", "y", "x"]
+    pub dimensions: AVec<AString>,
+    #[constrainable]
+    pub coordinates: AVec<AoristRef<Attribute>>,
+    #[constrainable]
+    pub variables: AVec<AoristRef<Attribute>>,
+}
+impl TDatumTemplate for NDArray {
+    fn get_attributes(&self) -> AVec<AoristRef<Attribute>> {
+        self.coordinates
+            .iter()
+            .chain(self.variables.iter())
+            .cloned()
+            .collect()
+    }
+    fn get_name(&self) -> AString {
+        self.name.clone()
+    }
+}